@@ -0,0 +1,79 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+
+use crate::json_patch::{deserialize_values, DeserializeValues};
+use crate::serde::{field_name, Direction};
+use crate::Context;
+
+/// Generate the `apply_json()` method.
+pub(crate) fn generate(ctx: &Context) -> TokenStream2 {
+    let Context {
+        ident,
+        vis: struct_vis,
+        generics,
+        where_clause,
+        tracker_ty,
+        ..
+    } = ctx;
+    let self_ty = ctx.self_ty();
+
+    let DeserializeValues {
+        value_decls,
+        match_arms,
+        known_keys,
+        apply_values,
+    } = deserialize_values(
+        ctx,
+        |field| field_name(ctx, field, Direction::Deserialize),
+        &quote! { ::tracker::ApplyJsonError::Deserialize },
+    );
+    let unknown_check = if known_keys.is_empty() {
+        quote! { true }
+    } else {
+        quote! { !::core::matches!(key.as_str(), #(#known_keys)|*) }
+    };
+
+    let eq_bounds = ctx.eq_bounds();
+
+    quote_spanned! { ident.span() =>
+        impl #generics #self_ty #where_clause {
+            /// Apply the fields of a JSON object and get a bit mask of
+            /// the fields that actually changed.
+            ///
+            /// Only the keys present in the object are assigned.
+            /// If the object contains unknown keys or a value can't be deserialized,
+            /// an error is returned and no field is modified.
+            /// Just like with `set_#field_name()`, fields are only marked
+            /// as changed if their new value is not equal to the previous value.
+            #[allow(dead_code, unused_variables)]
+            #struct_vis fn apply_json(
+                &mut self,
+                value: &::tracker::__private::serde_json::Value,
+            ) -> ::core::result::Result<#tracker_ty, ::tracker::ApplyJsonError>
+            where
+                #eq_bounds
+            {
+                let object = ::tracker::__private::apply_json_object(value)?;
+
+                let unknown: ::tracker::__private::Vec<::tracker::__private::String> = object
+                    .keys()
+                    .filter(|key| #unknown_check)
+                    .cloned()
+                    .collect();
+                if !unknown.is_empty() {
+                    return ::core::result::Result::Err(::tracker::ApplyJsonError::UnknownKeys(unknown));
+                }
+
+                #value_decls
+                for (key, value) in object {
+                    match key.as_str() {
+                        #match_arms
+                        _ => ::core::unreachable!(),
+                    }
+                }
+
+                ::core::result::Result::Ok({ #apply_values })
+            }
+        }
+    }
+}
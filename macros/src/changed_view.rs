@@ -0,0 +1,109 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote_spanned;
+use syn::parse_quote;
+
+use crate::serde::{field_name, Direction};
+use crate::{Context, TrackedField};
+
+/// Generate the changed view struct, its `Serialize` implementation
+/// and the `changed_view()` method.
+pub(crate) fn generate(ctx: &Context) -> TokenStream2 {
+    let Context {
+        ident,
+        vis: struct_vis,
+        generics,
+        generic_idents,
+        where_clause,
+        ..
+    } = ctx;
+    let self_ty = ctx.self_ty();
+    let view_ident = ctx.companion_ident("ChangedView");
+    let mut view_generics = ctx.generics.clone();
+    view_generics.params.insert(0, parse_quote! { '__tracker });
+    let where_predicates = ctx.where_predicates();
+
+    let mut serialize_bounds = TokenStream2::new();
+    let mut count_changed = TokenStream2::new();
+    let mut serialize_entries = TokenStream2::new();
+    for field in &ctx.fields {
+        let TrackedField {
+            ident: id,
+            ty,
+            cfg,
+            span: id_span,
+            ..
+        } = field;
+        let mask_id = field.mask_ident();
+        let name = field_name(ctx, field, Direction::Serialize);
+
+        let value = if ctx.redacts(id) {
+            quote_spanned! { *id_span => &::tracker::__private::Redacted }
+        } else {
+            quote_spanned! { *id_span => &self.inner.#id }
+        };
+        if cfg.is_empty() && !ctx.redacts(id) {
+            let bound_ty = ctx.bound_ty(ty, "'__tracker_serde");
+            serialize_bounds.extend(quote_spanned! { *id_span =>
+                for<'__tracker_serde> #bound_ty: ::tracker::__private::serde::Serialize,
+            });
+        }
+        count_changed.extend(quote_spanned! { *id_span =>
+            #(#cfg)*
+            if ::tracker::Track::changed(self.inner, <#self_ty>::#mask_id()) {
+                len += 1;
+            }
+        });
+        serialize_entries.extend(quote_spanned! { *id_span =>
+            #(#cfg)*
+            if ::tracker::Track::changed(self.inner, <#self_ty>::#mask_id()) {
+                map.serialize_entry(#name, #value)?;
+            }
+        });
+    }
+
+    let view_doc = format!(
+        "View created by [`{ident}::changed_view`] that only serializes the changed fields."
+    );
+    quote_spanned! { ident.span() =>
+        #[doc = #view_doc]
+        #[allow(dead_code)]
+        #struct_vis struct #view_ident #view_generics #where_clause {
+            inner: &'__tracker #self_ty,
+        }
+
+        impl #view_generics ::tracker::__private::serde::Serialize
+            for #view_ident < '__tracker, #generic_idents >
+        where
+            #where_predicates
+            #serialize_bounds
+        {
+            #[allow(unused_mut)]
+            fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
+            where
+                S: ::tracker::__private::serde::Serializer,
+            {
+                use ::tracker::__private::serde::ser::SerializeMap;
+
+                let mut len = 0;
+                #count_changed
+                let mut map = serializer.serialize_map(::core::option::Option::Some(len))?;
+                #serialize_entries
+                map.end()
+            }
+        }
+
+        impl #generics #self_ty #where_clause {
+            /// Get a view that serializes the changed fields as a map.
+            ///
+            /// The fields appear in the order of their declaration and
+            /// respect `#[serde(rename = "...")]` attributes, fields marked with
+            /// `#[tracker::redact]` are serialized as `"<redacted>"`.
+            /// The types of all tracked fields need to implement `serde::Serialize`.
+            #[allow(dead_code)]
+            #[must_use]
+            #struct_vis fn changed_view(&self) -> #view_ident <'_, #generic_idents > {
+                #view_ident { inner: self }
+            }
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::ext::IdentExt;
+use syn::Ident;
+
+use crate::serde::pascal_case;
+use crate::{Context, TrackedField};
+
+/// Generate the command enum with a variant that sets each tracked field,
+/// the `apply()` method and the `command_for_set_#field()` constructors.
+pub(crate) fn generate(ctx: &Context) -> syn::Result<TokenStream2> {
+    let Context {
+        ident,
+        vis: struct_vis,
+        generics,
+        generic_idents,
+        where_clause,
+        options,
+        ..
+    } = ctx;
+    let self_ty = ctx.self_ty();
+    let command_ident = ctx.companion_ident("Command");
+    let command_ty = quote! { #command_ident < #generic_idents > };
+
+    let mut variants = TokenStream2::new();
+    let mut apply_arms = TokenStream2::new();
+    let mut debug_arms = TokenStream2::new();
+    let mut clone_arms = TokenStream2::new();
+    let mut debug_bounds = TokenStream2::new();
+    let mut clone_bounds = TokenStream2::new();
+    let mut constructors = TokenStream2::new();
+    for field in &ctx.fields {
+        let TrackedField {
+            ident: id,
+            ty,
+            vis,
+            cfg,
+            method_name,
+            span: id_span,
+            ..
+        } = field;
+        let id_span = *id_span;
+        // The commands are applied with the setters, which only don't exist if they were skipped without a delta struct.
+        if ctx.skips_method(field, "set") && options.no_delta {
+            return Err(syn::Error::new(
+                id.span(),
+                format!(
+                    "`commands` needs the setter of `{}`, which is skipped",
+                    id.unraw()
+                ),
+            ));
+        }
+        let variant = Ident::new(&format!("Set{}", pascal_case(method_name)), id_span);
+        let variant_name = variant.to_string();
+        let set_id = field.method_ident("set_");
+        let constructor_id = field.method_ident("command_for_set_");
+        let variant_doc = format!("Set the {id} field.");
+        let constructor_doc = ctx.field_doc(format!(
+            "Create a command that sets the {id} field when it's applied."
+        ));
+        let debug_value = ctx.debug_value(id, quote! { value });
+
+        variants.extend(quote_spanned! { id_span =>
+            #(#cfg)*
+            #[doc = #variant_doc]
+            #variant(#ty),
+        });
+        for (no_eq, cfg) in field.no_eq_variants() {
+            let apply = if no_eq {
+                quote_spanned! { id_span =>
+                    self.#set_id(value);
+                    true
+                }
+            } else {
+                let ne = field.ne(quote! { self.#id }, quote! { value });
+                quote_spanned! { id_span =>
+                    let changed = #ne;
+                    self.#set_id(value);
+                    changed
+                }
+            };
+            apply_arms.extend(quote_spanned! { id_span =>
+                #(#cfg)*
+                #command_ident::#variant(value) => {
+                    #apply
+                }
+            });
+        }
+        debug_arms.extend(quote_spanned! { id_span =>
+            #(#cfg)*
+            Self::#variant(value) => f.debug_tuple(#variant_name).field(#debug_value).finish(),
+        });
+        clone_arms.extend(quote_spanned! { id_span =>
+            #(#cfg)*
+            Self::#variant(value) => Self::#variant(::core::clone::Clone::clone(value)),
+        });
+        if cfg.is_empty() {
+            let bound_ty = ctx.bound_ty(ty, "'__tracker");
+            if !ctx.redacts(id) {
+                debug_bounds.extend(quote_spanned! { id_span =>
+                    for<'__tracker> #bound_ty: ::core::fmt::Debug,
+                });
+            }
+            clone_bounds.extend(quote_spanned! { id_span =>
+                for<'__tracker> #bound_ty: ::core::clone::Clone,
+            });
+        }
+        constructors.extend(quote_spanned! { id_span =>
+            #(#cfg)*
+            #constructor_doc
+            #[allow(dead_code)]
+            #[must_use]
+            #vis fn #constructor_id(value: #ty) -> #command_ty {
+                #command_ident::#variant(value)
+            }
+        });
+    }
+
+    // Generic parameters that no tracked field uses need a variant that can't be created.
+    let (phantom_variant, phantom_arm) = match ctx.phantom_ty() {
+        Some(phantom_ty) => (
+            quote! {
+                #[doc(hidden)]
+                __Phantom(::core::convert::Infallible, #phantom_ty),
+            },
+            quote! { Self::__Phantom(never, _) => match *never {}, },
+        ),
+        None => Default::default(),
+    };
+    let apply_phantom_arm = ctx.phantom_ty().map(|_| {
+        quote! { #command_ident::__Phantom(never, _) => match never {}, }
+    });
+    let where_predicates = ctx.where_predicates();
+    let eq_bounds = ctx.eq_bounds();
+    let command_doc = format!(
+        "A change of a single field of [`{ident}`] that can be stored and applied later with [`{ident}::apply`]."
+    );
+
+    Ok(quote_spanned! { ident.span() =>
+        #[doc = #command_doc]
+        #struct_vis enum #command_ident #generics #where_clause {
+            #variants
+            #phantom_variant
+        }
+
+        impl #generics ::core::fmt::Debug for #command_ty
+        where
+            #where_predicates
+            #debug_bounds
+        {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                match self {
+                    #debug_arms
+                    #phantom_arm
+                }
+            }
+        }
+
+        impl #generics ::core::clone::Clone for #command_ty
+        where
+            #where_predicates
+            #clone_bounds
+        {
+            fn clone(&self) -> Self {
+                match self {
+                    #clone_arms
+                    #phantom_arm
+                }
+            }
+        }
+
+        impl #generics #self_ty #where_clause {
+            /// Apply a command with the setter of its field.
+            ///
+            /// Returns whether the field was marked as changed, which doesn't
+            /// happen if the value is equal to the current one.
+            #[allow(dead_code)]
+            #struct_vis fn apply(&mut self, command: #command_ty) -> bool
+            where
+                #where_predicates
+                #eq_bounds
+            {
+                match command {
+                    #apply_arms
+                    #apply_phantom_arm
+                }
+            }
+
+            #constructors
+        }
+    })
+}
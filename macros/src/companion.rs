@@ -0,0 +1,164 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::Ident;
+
+use crate::{Context, TrackedField};
+
+/// Generate the trait of the `trait = "..."` option with the accessors of all
+/// fields and the methods of the tracker, and implement it for the struct.
+pub(crate) fn generate(ctx: &Context, trait_ident: &Ident) -> TokenStream2 {
+    let Context {
+        ident,
+        vis: struct_vis,
+        generics,
+        generic_idents,
+        where_clause,
+        tracker_ty,
+        ..
+    } = ctx;
+    let self_ty = ctx.self_ty();
+
+    let mut declarations = TokenStream2::new();
+    let mut forwards = TokenStream2::new();
+    for field in &ctx.fields {
+        let (declaration, forward) = field_methods(ctx, field);
+        declarations.extend(declaration);
+        forwards.extend(forward);
+    }
+
+    let trait_doc = format!(
+        "The tracked API of [`{ident}`], implemented by forwarding to its methods \
+         so other implementations like mocks can be used in its place."
+    );
+    quote_spanned! { ident.span() =>
+        #[doc = #trait_doc]
+        #[allow(dead_code)]
+        #struct_vis trait #trait_ident #generics #where_clause {
+            #declarations
+
+            /// Check for changes with a bit mask.
+            #[must_use]
+            fn changed(&self, mask: #tracker_ty) -> bool;
+
+            /// Check whether any field changed.
+            #[must_use]
+            fn changed_any(&self) -> bool;
+
+            /// Mark all fields as unchanged.
+            fn reset(&mut self);
+
+            /// Mark all fields as changed.
+            fn mark_all_changed(&mut self);
+        }
+
+        impl #generics #trait_ident < #generic_idents > for #self_ty #where_clause {
+            #forwards
+
+            fn changed(&self, mask: #tracker_ty) -> bool {
+                ::tracker::Track::changed(self, mask)
+            }
+
+            fn changed_any(&self) -> bool {
+                ::tracker::Track::changed_any(self)
+            }
+
+            fn reset(&mut self) {
+                ::tracker::Track::reset(self);
+            }
+
+            fn mark_all_changed(&mut self) {
+                ::tracker::Track::mark_all_changed(self);
+            }
+        }
+    }
+}
+
+/// Generate the declarations of the accessors of `field` in the trait
+/// and their implementations that call the inherent methods.
+fn field_methods(ctx: &Context, field: &TrackedField) -> (TokenStream2, TokenStream2) {
+    let TrackedField {
+        ident: id,
+        ty,
+        cfg,
+        span: id_span,
+        ..
+    } = field;
+    let id_span = *id_span;
+    let mut declarations = TokenStream2::new();
+    let mut forwards = TokenStream2::new();
+    let mut method = |doc: String, signature: TokenStream2, call: TokenStream2, cfg: &[_]| {
+        let doc = ctx.accessor_doc(field, doc);
+        declarations.extend(quote_spanned! { id_span =>
+            #(#cfg)*
+            #doc
+            #signature;
+        });
+        forwards.extend(quote_spanned! { id_span =>
+            #(#cfg)*
+            #[inline]
+            #signature {
+                #call
+            }
+        });
+    };
+
+    if !ctx.skips_method(field, "get") {
+        let get_id = field.method_ident("get_");
+        method(
+            format!("Get an immutable reference to the {id} field."),
+            quote! { fn #get_id(&self) -> &#ty },
+            quote! { Self::#get_id(self) },
+            cfg,
+        );
+    }
+    if !ctx.skips_method(field, "get_mut") {
+        let get_mut_id = field.method_ident("get_mut_");
+        method(
+            format!("Get a mutable reference to the {id} field and mark the field as changed."),
+            quote! { fn #get_mut_id(&mut self) -> &mut #ty },
+            quote! { Self::#get_mut_id(self) },
+            cfg,
+        );
+    }
+    if !ctx.skips_method(field, "update") {
+        let update_id = field.method_ident("update_");
+        // Generic methods need `Self: Sized` so the trait can still be used as `dyn` trait.
+        method(
+            format!("Use a closure to update the {id} field and mark the field as changed."),
+            quote! {
+                fn #update_id<F: FnOnce(&mut #ty)>(&mut self, f: F)
+                where
+                    Self: Sized
+            },
+            quote! { Self::#update_id(self, f) },
+            cfg,
+        );
+    }
+    if !ctx.options.skips("changed_field") {
+        let changed_id = field.method_ident("changed_");
+        method(
+            format!("Check if value of {id} field has changed."),
+            quote! { fn #changed_id(&self) -> bool },
+            quote! { Self::#changed_id(self) },
+            cfg,
+        );
+    }
+    if !ctx.skips_method(field, "set") {
+        let set_id = field.method_ident("set_");
+        for (no_eq, cfg) in field.no_eq_variants() {
+            let set_bound = (!no_eq).then(|| field.eq_bound(ctx));
+            method(
+                format!("Set the value of field {id} and mark the field as changed."),
+                quote! {
+                    fn #set_id(&mut self, value: #ty)
+                    where
+                        #set_bound
+                },
+                quote! { Self::#set_id(self, value) },
+                &cfg,
+            );
+        }
+    }
+
+    (declarations, forwards)
+}
@@ -0,0 +1,89 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote, quote_spanned};
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
+
+use crate::{cfg_attrs, hash_eq, Context};
+
+/// Generate the `new()` constructor and the `with_#field()` methods of fields with a default.
+pub(crate) fn generate(ctx: &Context) -> TokenStream2 {
+    let Context {
+        ident,
+        vis: struct_vis,
+        generics,
+        where_clause,
+        tracker_field,
+        ..
+    } = ctx;
+    let self_ty = ctx.self_ty();
+    let tracker = ctx.tracker_init(ctx.empty_mask());
+
+    let mut params = TokenStream2::new();
+    let mut values = TokenStream2::new();
+    let mut with_methods = TokenStream2::new();
+    for field in &ctx.declared_fields {
+        let id = &field.ident;
+        let ty = &field.ty;
+        let cfg = cfg_attrs(&field.attrs);
+        let default = id.as_ref().and_then(|id| ctx.default_value(id));
+        let Some(default) = default else {
+            params.extend(quote_spanned! { field.span() => #(#cfg)* #id: #ty, });
+            values.extend(quote_spanned! { field.span() => #(#cfg)* #id, });
+            continue;
+        };
+        values.extend(quote_spanned! { field.span() => #(#cfg)* #id: #default, });
+
+        // Fields with a default aren't arguments of `new()`, but can be set without marking them.
+        let Some(id) = id else { continue };
+        let tracked = ctx.fields.iter().find(|tracked| tracked.ident == *id);
+        let with_id = tracked.map_or_else(
+            || format_ident!("with_{}", id.unraw()),
+            |tracked| tracked.method_ident("with_"),
+        );
+        let invalidate_hash =
+            tracked.map(|tracked| hash_eq::invalidate(ctx, &quote! { self }, tracked));
+        let with_doc = ctx.field_doc(format!(
+            "Replace the default value of the {id} field without marking it as changed."
+        ));
+        with_methods.extend(quote_spanned! { field.span() =>
+            #(#cfg)*
+            #with_doc
+            #[allow(dead_code)]
+            #[must_use]
+            #struct_vis fn #with_id(mut self, value: #ty) -> Self {
+                #invalidate_hash
+                self.#id = value;
+                self
+            }
+        });
+    }
+    for field in ctx.extra_fields() {
+        let id = &field.ident;
+        values.extend(quote! { #id: ::core::default::Default::default(), });
+    }
+
+    let defaults_doc = (!ctx.defaults.is_empty()).then(|| {
+        quote! {
+            ///
+            /// Fields with a default value aren't arguments, they can be
+            /// changed with the `with_#field_name()` methods.
+        }
+    });
+
+    quote_spanned! { ident.span() =>
+        impl #generics #self_ty #where_clause {
+            /// Create a new instance with all fields marked as unchanged.
+            #defaults_doc
+            #[allow(dead_code, clippy::too_many_arguments)]
+            #[must_use]
+            #struct_vis fn new(#params) -> Self {
+                Self {
+                    #values
+                    #tracker_field: #tracker,
+                }
+            }
+
+            #with_methods
+        }
+    }
+}
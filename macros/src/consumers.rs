@@ -0,0 +1,96 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::{parse_quote, Field};
+
+use crate::Context;
+
+/// The injected field that stores the masks of the consumers after the first one.
+pub(crate) fn field(count: usize) -> Field {
+    let len = count - 1;
+    parse_quote! {
+        tracker_consumers: ::tracker::Consumers<#len>
+    }
+}
+
+/// Add the bits of `mask` to the masks of all additional consumers of `this`.
+///
+/// All marked bits are added, not only the new ones, because the
+/// consumers are reset independently of the tracker.
+pub(crate) fn record(ctx: &Context, this: &TokenStream2, mask: TokenStream2) -> TokenStream2 {
+    let bits = ctx.mask_bits(mask);
+    quote! { #this.tracker_consumers.record(#bits); }
+}
+
+/// Generate the `changed_for()`, `changed_any_for()` and `reset_for()` methods.
+pub(crate) fn generate(ctx: &Context, count: usize) -> TokenStream2 {
+    let Context {
+        ident,
+        vis: struct_vis,
+        generics,
+        where_clause,
+        tracker_ty,
+        ..
+    } = ctx;
+    let self_ty = ctx.self_ty();
+    let bits = ctx.mask_bits(quote! { mask });
+    let all_bits = ctx.mask_bits(quote! { <Self as ::tracker::Track>::track_all() });
+    let panics_doc =
+        format!("Panics if `consumer` isn't smaller than {count}, the number of consumers.");
+
+    quote_spanned! { ident.span() =>
+        impl #generics #self_ty #where_clause {
+            /// Check for changes with a bit mask that `consumer` didn't reset yet.
+            ///
+            /// Consumer 0 uses the tracker of the struct, so this is the same as `changed()`
+            /// for it. The other consumers don't include changes that nested fields only
+            /// recorded in their own tracker.
+            ///
+            /// # Panics
+            ///
+            #[doc = #panics_doc]
+            #[allow(dead_code)]
+            #[must_use]
+            #[track_caller]
+            #struct_vis fn changed_for(&self, consumer: usize, mask: #tracker_ty) -> bool {
+                if consumer == 0 {
+                    ::tracker::Track::changed(self, mask)
+                } else {
+                    self.tracker_consumers.changed(consumer, #bits)
+                }
+            }
+
+            /// Check for any changes that `consumer` didn't reset yet.
+            ///
+            /// # Panics
+            ///
+            #[doc = #panics_doc]
+            #[allow(dead_code)]
+            #[must_use]
+            #[track_caller]
+            #struct_vis fn changed_any_for(&self, consumer: usize) -> bool {
+                if consumer == 0 {
+                    ::tracker::Track::changed_any(self)
+                } else {
+                    self.tracker_consumers.changed(consumer, #all_bits)
+                }
+            }
+
+            /// Mark all fields as unchanged for `consumer` without affecting the other consumers.
+            ///
+            /// Consumer 0 uses the tracker of the struct, so this is the same as `reset()` for it.
+            ///
+            /// # Panics
+            ///
+            #[doc = #panics_doc]
+            #[allow(dead_code)]
+            #[track_caller]
+            #struct_vis fn reset_for(&mut self, consumer: usize) {
+                if consumer == 0 {
+                    ::tracker::Track::reset(self);
+                } else {
+                    self.tracker_consumers.reset(consumer);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::{parse_quote, Field};
+
+use crate::{Context, TrackedField};
+
+/// The injected field that stores the change counters.
+pub(crate) fn field(ctx: &Context) -> Field {
+    let len = ctx.bit_count();
+    parse_quote! {
+        tracker_counters: ::tracker::Counters<#len>
+    }
+}
+
+/// Count the newly changed bits in `__tracker_new` in the counters of `this`.
+pub(crate) fn record(ctx: &Context, this: &TokenStream2) -> TokenStream2 {
+    let bits = ctx.mask_bits(quote! { __tracker_new });
+    quote! { #this.tracker_counters.record(#bits); }
+}
+
+/// Generate the `change_count_#field()` and `reset_counters()` methods.
+pub(crate) fn generate(ctx: &Context) -> TokenStream2 {
+    let Context {
+        ident,
+        vis: struct_vis,
+        generics,
+        where_clause,
+        ..
+    } = ctx;
+    let self_ty = ctx.self_ty();
+    let allow_missing_docs = ctx.allow_missing_docs();
+
+    let mut methods = TokenStream2::new();
+    for field in &ctx.fields {
+        let TrackedField {
+            ident: id,
+            vis,
+            cfg,
+            span: id_span,
+            ..
+        } = field;
+        let mask_id = field.mask_ident();
+        let count_id = field.method_ident("change_count_");
+        let bits = ctx.mask_bits(quote_spanned! { *id_span => Self::#mask_id() });
+        let doc = ctx.field_doc(format!(
+            "Get how often the {id} field was marked as changed while it was unchanged.\n\n\
+             The counter is not affected by `reset()`."
+        ));
+        methods.extend(quote_spanned! { *id_span =>
+            #(#cfg)*
+            #[allow(dead_code, non_snake_case)]
+            #[must_use]
+            #doc
+            #vis fn #count_id(&self) -> u32 {
+                self.tracker_counters.count(#bits)
+            }
+        });
+    }
+
+    quote_spanned! { ident.span() =>
+        #allow_missing_docs
+        impl #generics #self_ty #where_clause {
+            #methods
+
+            /// Set the change counters of all fields to zero.
+            #[allow(dead_code)]
+            #struct_vis fn reset_counters(&mut self) {
+                self.tracker_counters.reset();
+            }
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{Attribute, Ident, Path, Token};
+
+use crate::{call_site_at, cfg_attrs, Context};
+
+/// Generate the data struct with all declared fields, the conversions
+/// from and into it, `as_data()` and `from_data_changed()`.
+pub(crate) fn generate(ctx: &Context) -> syn::Result<TokenStream2> {
+    let Context {
+        ident,
+        vis: struct_vis,
+        generics,
+        generic_idents,
+        where_clause,
+        tracker_field,
+        ..
+    } = ctx;
+    let self_ty = ctx.self_ty();
+    let data_ident = data_ident(ctx);
+    let data_ty = quote! { #data_ident < #generic_idents > };
+    let attrs = data_attrs(ctx)?;
+    let where_predicates = ctx.where_predicates();
+    let tracker = ctx.tracker_init(ctx.empty_mask());
+
+    let mut data_fields = TokenStream2::new();
+    let mut names = TokenStream2::new();
+    let mut clone_values = TokenStream2::new();
+    let mut clone_bounds = TokenStream2::new();
+    for field in &ctx.declared_fields {
+        let id = &field.ident;
+        let ty = &field.ty;
+        let field_attrs = &field.attrs;
+        let cfg = cfg_attrs(&field.attrs);
+        let span = call_site_at(field.span());
+        // Fields of the struct are private with the `encapsulate` option,
+        // but the tracked field keeps the visibility of its accessors.
+        let vis = ctx
+            .fields
+            .iter()
+            .find(|tracked| Some(&tracked.ident) == id.as_ref())
+            .map_or(&field.vis, |tracked| &tracked.vis);
+
+        data_fields.extend(quote_spanned! { span =>
+            #(#field_attrs)*
+            #vis #id: #ty,
+        });
+        names.extend(quote_spanned! { span =>
+            #(#cfg)*
+            #id,
+        });
+        clone_values.extend(quote_spanned! { span =>
+            #(#cfg)*
+            #id: ::core::clone::Clone::clone(&self.#id),
+        });
+        if cfg.is_empty() {
+            let bound_ty = ctx.bound_ty(ty, "'__tracker");
+            clone_bounds.extend(quote_spanned! { span =>
+                for<'__tracker> #bound_ty: ::core::clone::Clone,
+            });
+        }
+    }
+    let mut extra_values = TokenStream2::new();
+    for field in ctx.extra_fields() {
+        let id = &field.ident;
+        extra_values.extend(quote! { #id: ::core::default::Default::default(), });
+    }
+
+    let data_doc = format!(
+        "The fields of [`{ident}`] without the tracker, created by [`{ident}::as_data`] or converted with `From`."
+    );
+    Ok(quote_spanned! { ident.span() =>
+        #[doc = #data_doc]
+        #(#attrs)*
+        #struct_vis struct #data_ident #generics #where_clause {
+            #data_fields
+        }
+
+        impl #generics ::core::convert::From<#self_ty> for #data_ty #where_clause {
+            fn from(value: #self_ty) -> Self {
+                let #ident { #names .. } = value;
+                Self { #names }
+            }
+        }
+
+        impl #generics ::core::convert::From<#data_ty> for #self_ty #where_clause {
+            /// Convert the data into the struct with all fields marked as unchanged.
+            fn from(data: #data_ty) -> Self {
+                let #data_ident { #names } = data;
+                Self {
+                    #names
+                    #extra_values
+                    #tracker_field: #tracker,
+                }
+            }
+        }
+
+        impl #generics #self_ty #where_clause {
+            /// Clone the values of all fields into the data struct without the tracker.
+            #[allow(dead_code)]
+            #[must_use]
+            #struct_vis fn as_data(&self) -> #data_ty
+            where
+                #where_predicates
+                #clone_bounds
+            {
+                #data_ident {
+                    #clone_values
+                }
+            }
+
+            /// Convert the data into the struct with all fields marked as changed.
+            #[allow(dead_code)]
+            #[must_use]
+            #struct_vis fn from_data_changed(data: #data_ty) -> Self {
+                let mut value = <Self as ::core::convert::From<#data_ty>>::from(data);
+                ::tracker::Track::mark_all_changed(&mut value);
+                value
+            }
+        }
+    })
+}
+
+/// The name of the data struct, `#structData` unless it's set with `data_struct = "..."`.
+fn data_ident(ctx: &Context) -> Ident {
+    match &ctx.options.data_struct_name {
+        Some(name) => name.clone(),
+        None => ctx.companion_ident("Data"),
+    }
+}
+
+/// The derives and `serde` attributes of the struct, which the data struct gets as well.
+///
+/// `Track` is left out of the derives because the data struct has no tracker.
+fn data_attrs(ctx: &Context) -> syn::Result<Vec<Attribute>> {
+    let mut attrs = Vec::new();
+    for attr in &ctx.attrs {
+        if attr.path().is_ident("derive") {
+            let paths = attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)?;
+            let paths = paths.into_iter().filter(|path| {
+                path.segments
+                    .last()
+                    .is_none_or(|segment| segment.ident != "Track")
+            });
+            attrs.push(syn::parse_quote! { #[derive(#(#paths),*)] });
+        } else if attr.path().is_ident("serde") {
+            attrs.push(attr.clone());
+        }
+    }
+    Ok(attrs)
+}
@@ -0,0 +1,79 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::ext::IdentExt;
+use syn::Meta;
+
+use crate::{Context, TrackedField};
+
+/// Generate the `changed_values()` method.
+pub(crate) fn generate(ctx: &Context) -> TokenStream2 {
+    let Context {
+        ident,
+        vis: struct_vis,
+        generics,
+        ..
+    } = ctx;
+    let self_ty = ctx.self_ty();
+    let where_predicates = ctx.where_predicates();
+    // Fields disabled by `#[cfg(...)]` are not part of the array.
+    let mut len = quote! { 0 };
+
+    let mut debug_bounds = TokenStream2::new();
+    let mut values = TokenStream2::new();
+    for field in &ctx.fields {
+        let TrackedField {
+            ident: id,
+            ty,
+            cfg,
+            span: id_span,
+            ..
+        } = field;
+        let mask_id = field.mask_ident();
+        let name = id.unraw().to_string();
+
+        if cfg.is_empty() {
+            len.extend(quote! { + 1 });
+        } else {
+            let enabled = cfg.iter().map(|attr| match &attr.meta {
+                Meta::List(list) => list.tokens.clone(),
+                _ => quote! { all() },
+            });
+            len.extend(quote! { + (#(::core::cfg!(#enabled))&&*) as usize });
+        }
+        if cfg.is_empty() && !ctx.redacts(id) {
+            let bound_ty = ctx.bound_ty(ty, "'__tracker");
+            debug_bounds.extend(quote_spanned! { *id_span =>
+                for<'__tracker> #bound_ty: ::core::fmt::Debug,
+            });
+        }
+        let value = ctx.debug_value(id, quote_spanned! { *id_span => &self.#id });
+        values.extend(quote_spanned! { *id_span =>
+            #(#cfg)*
+            (
+                ::tracker::Track::changed(self, Self::#mask_id()),
+                #name,
+                #value as &dyn ::core::fmt::Debug,
+            ),
+        });
+    }
+
+    quote_spanned! { ident.span() =>
+        impl #generics #self_ty where #where_predicates {
+            /// Iterate over the names and values of all changed fields
+            /// in the order of declaration.
+            #[allow(dead_code)]
+            #struct_vis fn changed_values(
+                &self,
+            ) -> impl ::core::iter::Iterator<Item = (&'static str, &dyn ::core::fmt::Debug)> + '_
+            where
+                #debug_bounds
+            {
+                let values: [(bool, &'static str, &dyn ::core::fmt::Debug); #len] = [#values];
+                values
+                    .into_iter()
+                    .filter(|(changed, _, _)| *changed)
+                    .map(|(_, name, value)| (name, value))
+            }
+        }
+    }
+}
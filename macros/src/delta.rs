@@ -0,0 +1,225 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote_spanned;
+use syn::ext::IdentExt;
+use syn::Ident;
+
+use crate::{Context, TrackedField};
+
+/// Generate a struct with one `Option` per tracked field and a [`Default`]
+/// implementation that sets all fields to `None`.
+///
+/// [`Clone`] and [`Debug`] are implemented if all field types implement them.
+pub(crate) fn option_struct(
+    ctx: &Context,
+    struct_ident: &Ident,
+    struct_doc: &str,
+    field_doc: fn(&Ident) -> String,
+) -> TokenStream2 {
+    let Context {
+        vis: struct_vis,
+        generics,
+        generic_idents,
+        where_clause,
+        ..
+    } = ctx;
+
+    let mut option_fields = TokenStream2::new();
+    let mut default_fields = TokenStream2::new();
+    let mut clone_fields = TokenStream2::new();
+    let mut clone_bounds = TokenStream2::new();
+    let mut debug_fields = TokenStream2::new();
+    let mut debug_bounds = TokenStream2::new();
+    for field in &ctx.fields {
+        let TrackedField {
+            ident: id,
+            ty,
+            vis,
+            cfg,
+            span: id_span,
+            ..
+        } = field;
+        let field_doc = ctx.field_doc(field_doc(id));
+
+        option_fields.extend(quote_spanned! { *id_span =>
+            #(#cfg)*
+            #field_doc
+            #vis #id: ::core::option::Option<#ty>,
+        });
+        default_fields.extend(quote_spanned! { *id_span =>
+            #(#cfg)*
+            #id: ::core::option::Option::None,
+        });
+        clone_fields.extend(quote_spanned! { *id_span =>
+            #(#cfg)*
+            #id: ::core::clone::Clone::clone(&self.#id),
+        });
+        if cfg.is_empty() {
+            let bound_ty = ctx.bound_ty(ty, "'__tracker");
+            clone_bounds.extend(quote_spanned! { *id_span =>
+                for<'__tracker> #bound_ty: ::core::clone::Clone,
+            });
+        }
+        let name = id.unraw().to_string();
+        let value = ctx.debug_value(id, quote_spanned! { *id_span => &self.#id });
+        let value = if ctx.redacts(id) {
+            quote_spanned! { *id_span => &self.#id.as_ref().map(|_| #value) }
+        } else {
+            value
+        };
+        debug_fields.extend(quote_spanned! { *id_span =>
+            #(#cfg)*
+            debug.field(#name, #value);
+        });
+        if cfg.is_empty() && !ctx.redacts(id) {
+            let bound_ty = ctx.bound_ty(ty, "'__tracker");
+            debug_bounds.extend(quote_spanned! { *id_span =>
+                for<'__tracker> #bound_ty: ::core::fmt::Debug,
+            });
+        }
+    }
+
+    let phantom_field = ctx.phantom_field();
+    let phantom_value = ctx.phantom_value();
+    let where_predicates = ctx.where_predicates();
+    let struct_name = struct_ident.to_string();
+    let allow_missing_docs = ctx.allow_missing_docs();
+
+    quote_spanned! { ctx.ident.span() =>
+        #[doc = #struct_doc]
+        #allow_missing_docs
+        #struct_vis struct #struct_ident #generics #where_clause {
+            #option_fields
+            #phantom_field
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl #generics ::core::default::Default for #struct_ident < #generic_idents > #where_clause {
+            fn default() -> Self {
+                Self {
+                    #default_fields
+                    #phantom_value
+                }
+            }
+        }
+
+        impl #generics ::core::clone::Clone for #struct_ident < #generic_idents >
+        where
+            #where_predicates
+            #clone_bounds
+        {
+            fn clone(&self) -> Self {
+                Self {
+                    #clone_fields
+                    #phantom_value
+                }
+            }
+        }
+
+        impl #generics ::core::fmt::Debug for #struct_ident < #generic_idents >
+        where
+            #where_predicates
+            #debug_bounds
+        {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut debug = f.debug_struct(#struct_name);
+                #debug_fields
+                debug.finish()
+            }
+        }
+    }
+}
+
+/// Generate the delta struct with one `Option` per tracked field
+/// as well as the `to_delta()` and `apply_delta()` methods.
+pub(crate) fn generate(ctx: &Context) -> TokenStream2 {
+    let Context {
+        ident,
+        vis: struct_vis,
+        generics,
+        generic_idents,
+        where_clause,
+        ..
+    } = ctx;
+    let self_ty = ctx.self_ty();
+    let delta_ident = ctx.companion_ident("Delta");
+
+    let mut clone_bounds = TokenStream2::new();
+    let mut to_delta_fields = TokenStream2::new();
+    let mut apply_fields = TokenStream2::new();
+    for field in &ctx.fields {
+        let TrackedField {
+            ident: id,
+            ty,
+            cfg,
+            span: id_span,
+            ..
+        } = field;
+        let mask_id = field.mask_ident();
+        let id_span = *id_span;
+        let set_id = field.method_ident("set_");
+
+        if cfg.is_empty() {
+            let bound_ty = ctx.bound_ty(ty, "'__tracker");
+            clone_bounds.extend(quote_spanned! { id_span =>
+                for<'__tracker> #bound_ty: ::core::clone::Clone,
+            });
+        }
+        to_delta_fields.extend(quote_spanned! { id_span =>
+            #(#cfg)*
+            #id: if ::tracker::Track::changed(self, Self::#mask_id()) {
+                ::core::option::Option::Some(::core::clone::Clone::clone(&self.#id))
+            } else {
+                ::core::option::Option::None
+            },
+        });
+        apply_fields.extend(quote_spanned! { id_span =>
+            #(#cfg)*
+            if let ::core::option::Option::Some(value) = delta.#id {
+                self.#set_id(value);
+            }
+        });
+    }
+
+    let phantom_value = ctx.phantom_value();
+
+    let delta_doc = format!(
+        "Changed fields of [`{ident}`] created by [`{ident}::to_delta`] \
+        and applied with [`{ident}::apply_delta`]."
+    );
+    let delta_struct = option_struct(ctx, &delta_ident, &delta_doc, |id| {
+        format!("New value of the {id} field if it was changed.")
+    });
+
+    let eq_bounds = ctx.eq_bounds();
+
+    quote_spanned! { ident.span() =>
+        #delta_struct
+
+        impl #generics #self_ty #where_clause {
+            /// Get a delta that contains the values of all changed fields.
+            ///
+            /// This method can only be used if the types
+            /// of all tracked fields implement [`Clone`].
+            #[allow(dead_code)]
+            #[must_use]
+            #struct_vis fn to_delta(&self) -> #delta_ident < #generic_idents > where #clone_bounds {
+                #delta_ident {
+                    #to_delta_fields
+                    #phantom_value
+                }
+            }
+
+            /// Assign all values provided by the delta.
+            ///
+            /// Just like with `set_#field_name()`, fields are only marked
+            /// as changed if their new value is not equal to the previous value.
+            #[allow(dead_code, unused_variables)]
+            #struct_vis fn apply_delta(&mut self, delta: #delta_ident < #generic_idents >)
+            where
+                #eq_bounds
+            {
+                #apply_fields
+            }
+        }
+    }
+}
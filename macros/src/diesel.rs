@@ -0,0 +1,104 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote_spanned;
+use syn::{parse_quote, Ident, Path};
+
+use crate::{sql, Context, TrackedField};
+
+/// Generate the changeset struct with a reference to every changed field,
+/// which derives Diesel's `AsChangeset`, and the `as_changeset()` method.
+pub(crate) fn generate(ctx: &Context, table: &Path) -> TokenStream2 {
+    let Context {
+        ident,
+        vis: struct_vis,
+        generics,
+        generic_idents,
+        where_clause,
+        ..
+    } = ctx;
+    let self_ty = ctx.self_ty();
+    let changeset_ident = ctx.companion_ident("Changeset");
+    let mut changeset_generics = ctx.generics.clone();
+    changeset_generics
+        .params
+        .insert(0, parse_quote! { '__tracker });
+
+    let mut changeset_fields = TokenStream2::new();
+    let mut values = TokenStream2::new();
+    for field in &ctx.fields {
+        let TrackedField {
+            ident: id,
+            ty,
+            vis,
+            cfg,
+            span: id_span,
+            ..
+        } = field;
+        let mask_id = field.mask_ident();
+        let column = match column_ident(field) {
+            Ok(column) => column,
+            Err(err) => return err.into_compile_error(),
+        };
+        let doc = ctx.field_doc(format!(
+            "Reference to the {id} field if it changed, `None` leaves the column unchanged."
+        ));
+        changeset_fields.extend(quote_spanned! { *id_span =>
+            #(#cfg)*
+            #doc
+            #[diesel(column_name = #column)]
+            #vis #id: ::core::option::Option<&'__tracker #ty>,
+        });
+        values.extend(quote_spanned! { *id_span =>
+            #(#cfg)*
+            #id: ::tracker::Track::changed(self, Self::#mask_id()).then_some(&self.#id),
+        });
+    }
+    let phantom = ctx.phantom_ty().map(|phantom_ty| {
+        quote_spanned! { ident.span() =>
+            #[diesel(skip_update)]
+            __phantom: #phantom_ty,
+        }
+    });
+    let phantom_value = ctx.phantom_value();
+
+    let changeset_doc = format!(
+        "Changeset created by [`{ident}::as_changeset`] that only updates the columns of the changed fields."
+    );
+    quote_spanned! { ident.span() =>
+        #[doc = #changeset_doc]
+        #[derive(::diesel::AsChangeset)]
+        #[diesel(table_name = #table)]
+        #[allow(dead_code)]
+        #struct_vis struct #changeset_ident #changeset_generics #where_clause {
+            #changeset_fields
+            #phantom
+        }
+
+        impl #generics #self_ty #where_clause {
+            /// Get a Diesel changeset that only updates the columns of the changed fields,
+            /// for example with `diesel::update(table).set(value.as_changeset())`.
+            ///
+            /// Diesel returns an error if no field changed.
+            #[allow(dead_code)]
+            #[must_use]
+            #struct_vis fn as_changeset(&self) -> #changeset_ident <'_, #generic_idents > {
+                #changeset_ident {
+                    #values
+                    #phantom_value
+                }
+            }
+        }
+    }
+}
+
+/// Get the column of a field as identifier, because Diesel refers to the columns of the table.
+fn column_ident(field: &TrackedField) -> syn::Result<Ident> {
+    let column = sql::column_name(field);
+    let mut ident = syn::parse_str::<Ident>(&column).map_err(|_| {
+        syn::Error::new(
+            field.span,
+            format!("the column `{column}` needs to be an identifier to be used with `diesel`"),
+        )
+    })?;
+    ident.set_span(field.span);
+    Ok(ident)
+}
@@ -0,0 +1,55 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::{parse_quote, Field};
+
+use crate::Context;
+
+/// The injected field that stores the changes before the last reset.
+pub(crate) fn field(ctx: &Context) -> Field {
+    let tracker_ty = &ctx.tracker_ty;
+    parse_quote! {
+        tracker_previous_changes: #tracker_ty
+    }
+}
+
+/// Move the changes of `self` into the previous slot before the tracker is cleared.
+pub(crate) fn store(ctx: &Context) -> TokenStream2 {
+    let current = ctx.tracker_value(&quote! { self });
+    quote! {
+        self.tracker_previous_changes = #current;
+    }
+}
+
+/// Generate the `previous_changes()` and `previously_changed()` methods.
+pub(crate) fn generate(ctx: &Context) -> TokenStream2 {
+    let Context {
+        ident,
+        vis: struct_vis,
+        generics,
+        where_clause,
+        tracker_ty,
+        ..
+    } = ctx;
+    let self_ty = ctx.self_ty();
+    let empty_mask = ctx.empty_mask();
+
+    quote_spanned! { ident.span() =>
+        impl #generics #self_ty #where_clause {
+            /// Get the mask of the fields that were changed when the tracker was last reset.
+            ///
+            /// This lets consumers that run after `reset()` see the changes of the previous cycle.
+            #[allow(dead_code)]
+            #[must_use]
+            #struct_vis fn previous_changes(&self) -> #tracker_ty {
+                self.tracker_previous_changes
+            }
+
+            /// Check for changes with a bit mask that were present when the tracker was last reset.
+            #[allow(dead_code)]
+            #[must_use]
+            #struct_vis fn previously_changed(&self, mask: #tracker_ty) -> bool {
+                self.tracker_previous_changes & mask != #empty_mask
+            }
+        }
+    }
+}
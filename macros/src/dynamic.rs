@@ -0,0 +1,179 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote_spanned;
+use syn::ext::IdentExt;
+use syn::Ident;
+
+use crate::serde::pascal_case;
+use crate::{Context, TrackedField};
+
+/// Generate the field enum as well as the `get_field()`, `set_field()`
+/// and `set_field_by_name()` methods.
+pub(crate) fn generate(ctx: &Context) -> syn::Result<TokenStream2> {
+    let Context {
+        ident,
+        vis: struct_vis,
+        generics,
+        where_clause,
+        options,
+        ..
+    } = ctx;
+    let self_ty = ctx.self_ty();
+    let field_enum = ctx.companion_ident("Field");
+
+    let mut variants = TokenStream2::new();
+    let mut name_arms = TokenStream2::new();
+    let mut from_name_arms = TokenStream2::new();
+    let mut get_arms = TokenStream2::new();
+    let mut set_arms = TokenStream2::new();
+    let mut static_bounds = TokenStream2::new();
+    for field in &ctx.fields {
+        let TrackedField {
+            ident: id,
+            ty,
+            cfg,
+            span: id_span,
+            ..
+        } = field;
+        let id_span = *id_span;
+        // The setter is needed to mark the field, it's only missing if it was skipped without a delta struct.
+        if ctx.skips_method(field, "set") && options.no_delta {
+            return Err(syn::Error::new(
+                id.span(),
+                format!(
+                    "`dynamic` needs the setter of `{}`, which is skipped",
+                    id.unraw()
+                ),
+            ));
+        }
+        let name = id.unraw().to_string();
+        let variant = Ident::new(&pascal_case(&name), id_span);
+        let set_id = field.method_ident("set_");
+        let variant_doc = format!("The `{name}` field.");
+
+        variants.extend(quote_spanned! { id_span =>
+            #(#cfg)*
+            #[doc = #variant_doc]
+            #variant,
+        });
+        name_arms.extend(quote_spanned! { id_span =>
+            #(#cfg)*
+            Self::#variant => #name,
+        });
+        from_name_arms.extend(quote_spanned! { id_span =>
+            #(#cfg)*
+            #name => ::core::option::Option::Some(Self::#variant),
+        });
+        get_arms.extend(quote_spanned! { id_span =>
+            #(#cfg)*
+            #field_enum::#variant => &self.#id,
+        });
+        set_arms.extend(quote_spanned! { id_span =>
+            #(#cfg)*
+            #field_enum::#variant => {
+                let value = value.downcast::<#ty>().map_err(|_| ::tracker::SetFieldError::WrongType {
+                    field: #name,
+                    expected: ::core::any::type_name::<#ty>(),
+                })?;
+                self.#set_id(*value);
+            }
+        });
+        if cfg.is_empty() {
+            let bound_ty = ctx.bound_ty(ty, "'__tracker");
+            static_bounds.extend(quote_spanned! { id_span =>
+                for<'__tracker> #bound_ty: 'static,
+            });
+        }
+    }
+
+    let where_predicates = ctx.where_predicates();
+    let eq_bounds = ctx.eq_bounds();
+    let enum_doc = format!(
+        "The tracked fields of [`{ident}`] to access them with [`{ident}::get_field`] and [`{ident}::set_field`]."
+    );
+
+    Ok(quote_spanned! { ident.span() =>
+        #[doc = #enum_doc]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #struct_vis enum #field_enum {
+            #variants
+        }
+
+        impl #field_enum {
+            /// Get the name of the field.
+            #[allow(dead_code)]
+            #[must_use]
+            #struct_vis fn name(self) -> &'static str {
+                match self {
+                    #name_arms
+                }
+            }
+
+            /// Get the field with the given name, or `None` if no tracked field has this name.
+            #[allow(dead_code)]
+            #[must_use]
+            #struct_vis fn from_name(name: &str) -> ::core::option::Option<Self> {
+                match name {
+                    #from_name_arms
+                    _ => ::core::option::Option::None,
+                }
+            }
+        }
+
+        impl #generics #self_ty #where_clause {
+            /// Get a reference to the value of a field chosen at runtime,
+            /// which can be downcast to the type of the field.
+            #[allow(dead_code)]
+            #[must_use]
+            #struct_vis fn get_field(&self, field: #field_enum) -> &dyn ::core::any::Any
+            where
+                #where_predicates
+                #static_bounds
+            {
+                match field {
+                    #get_arms
+                }
+            }
+
+            /// Set the value of a field chosen at runtime with its setter,
+            /// which marks the field as changed if the value isn't equal to the previous one.
+            ///
+            /// Returns an error if the value doesn't have the type of the field.
+            #[allow(dead_code, unreachable_code)]
+            #struct_vis fn set_field(
+                &mut self,
+                field: #field_enum,
+                value: ::tracker::__private::Box<dyn ::core::any::Any>,
+            ) -> ::core::result::Result<(), ::tracker::SetFieldError>
+            where
+                #where_predicates
+                #static_bounds
+                #eq_bounds
+            {
+                match field {
+                    #set_arms
+                }
+                ::core::result::Result::Ok(())
+            }
+
+            /// Set the value of the field with the given name, see [`Self::set_field`].
+            ///
+            /// Returns an error if no tracked field has this name
+            /// or the value doesn't have the type of the field.
+            #[allow(dead_code)]
+            #struct_vis fn set_field_by_name(
+                &mut self,
+                name: &str,
+                value: ::tracker::__private::Box<dyn ::core::any::Any>,
+            ) -> ::core::result::Result<(), ::tracker::SetFieldError>
+            where
+                #where_predicates
+                #static_bounds
+                #eq_bounds
+            {
+                let field = #field_enum::from_name(name)
+                    .ok_or_else(|| ::tracker::UnknownField::new(name))?;
+                self.set_field(field, value)
+            }
+        }
+    })
+}
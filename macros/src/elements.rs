@@ -0,0 +1,165 @@
+//! Support for fields marked with `#[tracker::elements]`.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{Expr, ExprLit, Lit, Type};
+
+use crate::{call_site_at, Context, TrackedField};
+
+/// Get the length of a `[T; N]` field, which needs to be an integer literal
+/// because the bits of the elements are allocated by the macro.
+pub(crate) fn array_len(ty: &Type) -> syn::Result<usize> {
+    let Type::Array(array) = ty else {
+        return Err(syn::Error::new_spanned(
+            ty,
+            "`elements` can only be used for fields of the type `[T; N]`",
+        ));
+    };
+    let Expr::Lit(ExprLit {
+        lit: Lit::Int(len), ..
+    }) = &array.len
+    else {
+        return Err(syn::Error::new_spanned(
+            &array.len,
+            "the length of an array marked with `elements` needs to be an integer literal",
+        ));
+    };
+    let len = len.base10_parse()?;
+    if len == 0 {
+        return Err(syn::Error::new_spanned(
+            &array.len,
+            "`elements` can't be used for empty arrays",
+        ));
+    }
+    Ok(len)
+}
+
+/// Generate `#field_all()`, `changed_#field_elem()` and `set_#field_elem()`
+/// for fields marked with `#[tracker::elements]`.
+pub(crate) fn methods(ctx: &Context) -> TokenStream2 {
+    let Context {
+        tracker_ty,
+        bits_ty,
+        ..
+    } = ctx;
+    let mut methods = TokenStream2::new();
+    for field in &ctx.fields {
+        let Some(len) = field.elements else {
+            continue;
+        };
+        let TrackedField {
+            ident: id,
+            ty,
+            vis,
+            cfg,
+            span,
+            bit,
+            ..
+        } = field;
+        let Type::Array(array) = ty else {
+            continue;
+        };
+        let element = &array.elem;
+        let mask_id = field.mask_ident();
+        let all_id = format_ident!("{}_all", mask_id);
+        let element_mask =
+            ctx.mask_from_bits(quote_spanned! { *span => (1 as #bits_ty) << (#bit + index) });
+
+        let all_doc = ctx.field_doc(format!(
+            "Get a bit mask with the bits of all elements of the {id} field, which is the same as `{mask_id}()`."
+        ));
+        methods.extend(quote_spanned! { *span =>
+            #(#cfg)*
+            #[must_use]
+            #[inline]
+            #all_doc
+            #vis fn #all_id() -> #tracker_ty {
+                Self::#mask_id()
+            }
+        });
+
+        if !ctx.options.skips("changed_field") {
+            let changed_vis = field.method_vis("changed_field");
+            let changed_id = format_ident!("{}_elem", field.method_ident("changed_"));
+            let doc = ctx.field_doc(format!(
+                "Check if the element of the {id} field at `index` has changed.\n\n\
+                 # Panics\n\n\
+                 Panics if `index` is out of bounds."
+            ));
+            methods.extend(quote_spanned! { *span =>
+                #(#cfg)*
+                #[must_use]
+                #[inline]
+                #doc
+                #changed_vis fn #changed_id(&self, index: usize) -> bool {
+                    ::core::assert!(
+                        index < #len,
+                        "index out of bounds: the len is {} but the index is {}",
+                        #len,
+                        index,
+                    );
+                    ::tracker::Track::changed(self, #element_mask)
+                }
+            });
+        }
+
+        if ctx.skips_method(field, "set") {
+            continue;
+        }
+        let set_vis = field.method_vis("set");
+        let set_id = format_ident!("{}_elem", field.method_ident("set_"));
+        let this = quote! { self };
+        let before_change = ctx.before_change(&this, field, None);
+        let trace_set = ctx.trace_change(&this, field, "set_elem");
+        let mark = ctx.mark(&this, element_mask);
+        for (no_eq, cfg) in field.no_eq_variants() {
+            let (summary, changed, bound) = if no_eq {
+                (
+                    "and mark it as changed",
+                    // Check the bounds before anything is recorded.
+                    quote_spanned! { *span => {
+                        let _ = &self.#id[index];
+                        true
+                    } },
+                    TokenStream2::new(),
+                )
+            } else {
+                let bound_span = call_site_at(element.span());
+                let element = ctx.bound_ty(element, "'__tracker_eq");
+                (
+                    "and mark it as changed if it's not equal to the previous element",
+                    quote_spanned! { *span => self.#id[index] != value },
+                    quote_spanned! { bound_span =>
+                        for<'__tracker_eq> #element: ::tracker::__private::FieldEq,
+                    },
+                )
+            };
+            let doc = ctx.field_doc(format!(
+                "Set the element of the {id} field at `index` {summary}.\n\n\
+                 # Panics\n\n\
+                 Panics if `index` is out of bounds."
+            ));
+            methods.extend(quote_spanned! { *span =>
+                #(#cfg)*
+                #[inline]
+                #doc
+                #set_vis fn #set_id(&mut self, index: usize, value: #element)
+                where
+                    #bound
+                {
+                    let changed = #changed;
+                    if changed {
+                        #before_change
+                        #trace_set
+                    }
+                    self.#id[index] = value;
+                    if changed {
+                        #mark
+                    }
+                }
+            });
+        }
+    }
+    methods
+}
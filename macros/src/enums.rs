@@ -0,0 +1,165 @@
+//! Support for enums, which are tracked as a whole by a generated wrapper struct.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::ItemEnum;
+
+use crate::{call_site_at, impl_generics};
+
+/// Generate the wrapper struct for an enum.
+pub(crate) fn generate(data: &ItemEnum) -> TokenStream2 {
+    let mut ident = data.ident.clone();
+    ident.set_span(call_site_at(ident.span()));
+    let ident = &ident;
+    let vis = &data.vis;
+    let (generics, generic_idents) = impl_generics(&data.generics);
+    let where_clause = &data.generics.where_clause;
+    let where_predicates = where_clause
+        .as_ref()
+        .map(|where_clause| {
+            let predicates = &where_clause.predicates;
+            if predicates.empty_or_trailing() {
+                quote! { #predicates }
+            } else {
+                quote! { #predicates, }
+            }
+        })
+        .unwrap_or_default();
+    let enum_ty = quote! { #ident < #generic_idents > };
+    let tracker_ident = syn::Ident::new(&format!("{ident}Tracker"), ident.span());
+    let tracker_ty = quote! { #tracker_ident < #generic_idents > };
+
+    let tracker_doc =
+        format!("Tracks changes of a [`{ident}`] value and whether its variant changed.");
+
+    quote_spanned! { ident.span() =>
+        #data
+
+        #[doc = #tracker_doc]
+        #vis struct #tracker_ident #generics #where_clause {
+            value: #enum_ty,
+            tracker: u8,
+        }
+
+        impl #generics #tracker_ty #where_clause {
+            const VALUE: u8 = 1;
+            const VARIANT: u8 = 1 << 1;
+
+            /// Wrap a value with all changes reset.
+            #[allow(dead_code)]
+            #[must_use]
+            #vis fn new(value: #enum_ty) -> Self {
+                Self { value, tracker: 0 }
+            }
+
+            /// Get an immutable reference to the value.
+            #[allow(dead_code)]
+            #[must_use]
+            #vis fn get(&self) -> &#enum_ty {
+                &self.value
+            }
+
+            /// Get a mutable reference to the value and mark it as changed.
+            ///
+            /// Since the new variant is not known, the variant is marked as changed as well.
+            #[allow(dead_code)]
+            #[must_use]
+            #vis fn get_mut(&mut self) -> &mut #enum_ty {
+                self.tracker |= Self::VALUE | Self::VARIANT;
+                &mut self.value
+            }
+
+            /// Use a closure to update the value and mark it as changed.
+            ///
+            /// The variant is only marked as changed if it differs after the update.
+            #[allow(dead_code)]
+            #vis fn update<F: FnOnce(&mut #enum_ty)>(&mut self, f: F) {
+                let discriminant = ::core::mem::discriminant(&self.value);
+                f(&mut self.value);
+                self.mark(discriminant);
+            }
+
+            /// Replace the value and mark it as changed.
+            ///
+            /// The variant is only marked as changed if the new value has another variant.
+            #[allow(dead_code)]
+            #vis fn replace(&mut self, value: #enum_ty) -> #enum_ty {
+                let discriminant = ::core::mem::discriminant(&self.value);
+                let old = ::core::mem::replace(&mut self.value, value);
+                self.mark(discriminant);
+                old
+            }
+
+            /// Set the value and mark it as changed if it's not equal to the previous value.
+            ///
+            /// The variant is only marked as changed if the new value has another variant.
+            #[allow(dead_code)]
+            #vis fn set(&mut self, value: #enum_ty)
+            where
+                for<'__tracker> #enum_ty: ::core::cmp::PartialEq,
+            {
+                if self.value != value {
+                    self.replace(value);
+                }
+            }
+
+            fn mark(&mut self, discriminant: ::core::mem::Discriminant<#enum_ty>) {
+                self.tracker |= Self::VALUE;
+                if discriminant != ::core::mem::discriminant(&self.value) {
+                    self.tracker |= Self::VARIANT;
+                }
+            }
+
+            /// Check whether the value changed.
+            #[allow(dead_code)]
+            #[must_use]
+            #vis fn changed(&self) -> bool {
+                self.tracker & Self::VALUE != 0
+            }
+
+            /// Check whether the variant of the value changed.
+            #[allow(dead_code)]
+            #[must_use]
+            #vis fn variant_changed(&self) -> bool {
+                self.tracker & Self::VARIANT != 0
+            }
+
+            /// Mark the value as unchanged again.
+            #[allow(dead_code)]
+            #vis fn reset(&mut self) {
+                self.tracker = 0;
+            }
+
+            /// Get the wrapped value.
+            #[allow(dead_code)]
+            #[must_use]
+            #vis fn into_inner(self) -> #enum_ty {
+                self.value
+            }
+        }
+
+        impl #generics ::core::ops::Deref for #tracker_ty #where_clause {
+            type Target = #enum_ty;
+
+            fn deref(&self) -> &Self::Target {
+                &self.value
+            }
+        }
+
+        impl #generics ::core::default::Default for #tracker_ty
+        where
+            #where_predicates
+            for<'__tracker> #enum_ty: ::core::default::Default,
+        {
+            fn default() -> Self {
+                Self::new(::core::default::Default::default())
+            }
+        }
+
+        impl #generics ::core::convert::From<#enum_ty> for #tracker_ty #where_clause {
+            fn from(value: #enum_ty) -> Self {
+                Self::new(value)
+            }
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote_spanned;
+use syn::ext::IdentExt;
+
+use crate::{Context, TrackedField};
+
+/// Generate the `field_mask_paths()` and `mask_from_paths()` methods.
+pub(crate) fn generate(ctx: &Context) -> TokenStream2 {
+    let Context {
+        ident,
+        vis: struct_vis,
+        generics,
+        where_clause,
+        tracker_ty,
+        ..
+    } = ctx;
+    let self_ty = ctx.self_ty();
+
+    let mut push_paths = TokenStream2::new();
+    let mut match_arms = TokenStream2::new();
+    for field in &ctx.fields {
+        let TrackedField {
+            ident: id,
+            cfg,
+            span: id_span,
+            ..
+        } = field;
+        let mask_id = field.mask_ident();
+        let name = id.unraw().to_string();
+
+        push_paths.extend(quote_spanned! { *id_span =>
+            #(#cfg)*
+            if ::tracker::Track::changed(self, Self::#mask_id()) {
+                paths.push(::tracker::__private::String::from(#name));
+            }
+        });
+        match_arms.extend(quote_spanned! { *id_span =>
+            #(#cfg)*
+            #name => ::core::result::Result::Ok(Self::#mask_id()),
+        });
+    }
+
+    let empty_mask = ctx.empty_mask();
+
+    let mut methods = TokenStream2::new();
+    if !ctx.options.skips("field_mask_paths") {
+        methods.extend(quote_spanned! { ident.span() =>
+            /// Get the names of all changed fields, for example
+            /// to fill the paths of a protobuf `FieldMask`.
+            #[allow(dead_code)]
+            #[must_use]
+            #struct_vis fn field_mask_paths(&self) -> ::tracker::__private::Vec<::tracker::__private::String> {
+                #[allow(unused_mut)]
+                let mut paths = ::tracker::__private::Vec::new();
+                #push_paths
+                paths
+            }
+        });
+    }
+    if !ctx.options.skips("mask_from_paths") {
+        methods.extend(quote_spanned! { ident.span() =>
+            /// Get a bit mask for a list of field names, for example
+            /// taken from the paths of a protobuf `FieldMask`.
+            ///
+            /// Returns an error with the first name that doesn't
+            /// belong to a tracked field.
+            #[allow(dead_code)]
+            #struct_vis fn mask_from_paths<S: ::core::convert::AsRef<str>>(
+                paths: &[S],
+            ) -> ::core::result::Result<#tracker_ty, ::tracker::UnknownField> {
+                paths.iter().try_fold(#empty_mask, |mask, path| {
+                    let field_mask: #tracker_ty = match path.as_ref() {
+                        #match_arms
+                        unknown => ::core::result::Result::Err(::tracker::UnknownField::new(unknown)),
+                    }?;
+                    ::core::result::Result::Ok(mask | field_mask)
+                })
+            }
+        });
+    }
+
+    quote_spanned! { ident.span() =>
+        impl #generics #self_ty #where_clause {
+            #methods
+        }
+    }
+}
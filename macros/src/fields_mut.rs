@@ -0,0 +1,143 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote, quote_spanned};
+use syn::ext::IdentExt;
+
+use crate::{cfg_attrs, Context, TrackedField};
+
+/// Generate the type that provides the field masks for `fields_mut!` and
+/// `#[tracker::track_impl]`, and the method that marks the fields as changed.
+///
+/// Only the masks for `fields_mut!` are left out with `skip_methods(fields_mut)`,
+/// `#[tracker::track_impl]` needs the rest.
+pub(crate) fn generate(ctx: &Context) -> TokenStream2 {
+    let Context {
+        ident,
+        vis: struct_vis,
+        declared_vis,
+        generics,
+        generic_idents,
+        where_clause,
+        tracker_ty,
+        ..
+    } = ctx;
+    let self_ty = ctx.self_ty();
+    let masks_ident = format_ident!("__{}FieldMasks", ident);
+
+    let mut mask_methods = TokenStream2::new();
+    let mut before_changes = TokenStream2::new();
+    let empty_mask = ctx.empty_mask();
+    let has_mask_methods = !ctx.options.skips("fields_mut");
+    for field in &ctx.fields {
+        let TrackedField {
+            ident: id,
+            vis,
+            cfg,
+            span: id_span,
+            ..
+        } = field;
+        let mask_id = field.mask_ident();
+        let before_change = ctx.before_change(&quote! { self }, field, None);
+
+        if has_mask_methods {
+            mask_methods.extend(quote_spanned! { *id_span =>
+                #(#cfg)*
+                #vis fn #id(&self) -> #tracker_ty {
+                    <#self_ty>::#mask_id()
+                }
+            });
+        }
+        if !before_change.is_empty() {
+            before_changes.extend(quote_spanned! { *id_span =>
+                #(#cfg)*
+                if mask & Self::#mask_id() != #empty_mask {
+                    #before_change
+                }
+            });
+        }
+    }
+
+    // `#[tracker::track_impl]` can't tell which fields are tracked, so
+    // every field has a mask for assignments, which is empty if untracked.
+    let mut assigned_methods = TokenStream2::new();
+    let mut all_fields: Vec<_> = ctx
+        .declared_fields
+        .iter()
+        .map(|field| {
+            (
+                field.ident.clone(),
+                field.vis.clone(),
+                cfg_attrs(&field.attrs),
+            )
+        })
+        .collect();
+    all_fields.push((
+        Some(ctx.tracker_field.clone()),
+        struct_vis.clone(),
+        Vec::new(),
+    ));
+    all_fields.extend(
+        ctx.extra_fields()
+            .into_iter()
+            .map(|field| (field.ident, struct_vis.clone(), Vec::new())),
+    );
+    for (id, vis, cfg) in all_fields {
+        let Some(id) = id else {
+            continue;
+        };
+        let assigned_id = format_ident!("__assigned_{}", id.unraw());
+        let mask = match ctx.fields.iter().find(|field| field.ident == id) {
+            Some(_) if has_mask_methods => quote! { self.#id() },
+            Some(field) => {
+                let mask_id = field.mask_ident();
+                quote! { <#self_ty>::#mask_id() }
+            }
+            None => empty_mask.clone(),
+        };
+        assigned_methods.extend(quote! {
+            #(#cfg)*
+            #vis fn #assigned_id(&self) -> #tracker_ty {
+                #mask
+            }
+        });
+    }
+    let mark = ctx.mark(&quote! { self }, quote! { mask });
+
+    quote_spanned! { ident.span() =>
+        #[doc(hidden)]
+        #[allow(non_camel_case_types)]
+        #declared_vis struct #masks_ident #generics #where_clause {
+            masks: ::core::marker::PhantomData<fn() -> #self_ty>,
+        }
+
+        #[allow(dead_code, non_snake_case)]
+        impl #generics #masks_ident < #generic_idents > #where_clause {
+            #mask_methods
+            #assigned_methods
+        }
+
+        impl #generics ::tracker::__private::FieldMasks for #self_ty #where_clause {
+            type Masks = #masks_ident < #generic_idents >;
+            type Mask = #tracker_ty;
+
+            fn field_masks(&self) -> Self::Masks {
+                #masks_ident { masks: ::core::marker::PhantomData }
+            }
+
+            fn empty_mask(&self) -> #tracker_ty {
+                #empty_mask
+            }
+        }
+
+        impl #generics #self_ty #where_clause {
+            #[doc(hidden)]
+            #[allow(dead_code)]
+            #struct_vis fn __tracker_mark_fields(&mut self, mask: #tracker_ty) {
+                if mask == #empty_mask {
+                    return;
+                }
+                #before_changes
+                #mark
+            }
+        }
+    }
+}
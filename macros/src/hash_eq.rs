@@ -0,0 +1,98 @@
+//! Support for fields marked with `#[tracker::hash_eq]`.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::{parse_quote, Field, LitStr};
+
+use crate::{Context, TrackedField};
+
+/// How the setter of a field marked with `#[tracker::hash_eq]` treats equal hashes.
+#[derive(Clone, Copy)]
+pub(crate) enum HashEq {
+    /// Compare the values if the hashes are equal, set with `#[tracker::hash_eq]`.
+    Compare,
+    /// Treat equal hashes as equal values, set with `#[tracker::hash_eq = "hash_only"]`.
+    HashOnly,
+}
+
+impl HashEq {
+    /// Parse the value of `#[tracker::hash_eq = "..."]`.
+    pub(crate) fn parse(value: &LitStr) -> syn::Result<Self> {
+        match value.value().as_str() {
+            "hash_only" => Ok(Self::HashOnly),
+            _ => Err(syn::Error::new_spanned(
+                value,
+                "expected `hash_only`, or no value to compare the values if the hashes are equal",
+            )),
+        }
+    }
+}
+
+/// The injected field with the cached hashes of all fields marked with `#[tracker::hash_eq]`.
+pub(crate) fn field(ctx: &Context) -> Option<Field> {
+    let len = ctx
+        .fields
+        .iter()
+        .filter(|field| field.hash_eq.is_some())
+        .count();
+    (len > 0).then(|| {
+        parse_quote! {
+            tracker_hashes: ::tracker::FieldHashes<#len>
+        }
+    })
+}
+
+/// The index of the cached hash of `field`, if it's marked with `#[tracker::hash_eq]`.
+fn index(ctx: &Context, field: &TrackedField) -> Option<usize> {
+    field.hash_eq?;
+    ctx.fields
+        .iter()
+        .filter(|other| other.hash_eq.is_some())
+        .position(|other| other.ident == field.ident)
+}
+
+/// Forget the cached hash of `field` of `this` before the field is modified.
+pub(crate) fn invalidate(ctx: &Context, this: &TokenStream2, field: &TrackedField) -> TokenStream2 {
+    match index(ctx, field) {
+        Some(index) => quote! { #this.tracker_hashes.invalidate(#index); },
+        None => TokenStream2::new(),
+    }
+}
+
+/// The body of the setter of a field marked with `#[tracker::hash_eq]`.
+///
+/// Only the hash of the new value is computed, the values are only
+/// compared if the hash is equal to the cached hash of the field.
+pub(crate) fn set_body(
+    ctx: &Context,
+    field: &TrackedField,
+    before_change: &TokenStream2,
+    trace_set: &TokenStream2,
+    mark: &TokenStream2,
+) -> TokenStream2 {
+    let id = &field.ident;
+    let index = index(ctx, field).unwrap();
+    let differs = quote_spanned! { field.span =>
+        self.tracker_hashes.differs(#index, &self.#id, hash)
+    };
+    let changed = match field.hash_eq {
+        Some(HashEq::HashOnly) => differs,
+        _ => {
+            let ne = field.ne(quote! { self.#id }, quote! { value });
+            quote! { #differs || #ne }
+        }
+    };
+    quote_spanned! { field.span =>
+        let hash = ::tracker::__private::field_hash(&value);
+        let changed = #changed;
+        if changed {
+            #before_change
+            #trace_set
+        }
+        self.#id = value;
+        self.tracker_hashes.store(#index, hash);
+        if changed {
+            #mark
+        }
+    }
+}
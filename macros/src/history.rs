@@ -0,0 +1,52 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::{parse_quote, Field};
+
+use crate::Context;
+
+/// The injected field that stores the last changes.
+pub(crate) fn field(ctx: &Context, capacity: usize) -> Field {
+    let tracker_ty = &ctx.tracker_ty;
+    parse_quote! {
+        tracker_history: ::tracker::History<#tracker_ty, #capacity>
+    }
+}
+
+/// Record the newly changed bits in `__tracker_new` in the history of `this`.
+pub(crate) fn record(this: &TokenStream2) -> TokenStream2 {
+    quote! {
+        #this.tracker_history.record(__tracker_new, ::core::panic::Location::caller());
+    }
+}
+
+/// Generate the `change_history()` and `clear_history()` methods.
+pub(crate) fn generate(ctx: &Context) -> TokenStream2 {
+    let Context {
+        ident,
+        vis: struct_vis,
+        generics,
+        where_clause,
+        tracker_ty,
+        ..
+    } = ctx;
+    let self_ty = ctx.self_ty();
+
+    quote_spanned! { ident.span() =>
+        impl #generics #self_ty #where_clause {
+            /// Iterate over the last changes from the oldest to the newest, each with
+            /// the bit mask of the fields that were marked as changed for the first time.
+            #[allow(dead_code)]
+            #struct_vis fn change_history(
+                &self,
+            ) -> impl ::core::iter::Iterator<Item = &::tracker::ChangeEvent<#tracker_ty>> + '_ {
+                self.tracker_history.iter()
+            }
+
+            /// Forget all recorded changes.
+            #[allow(dead_code)]
+            #struct_vis fn clear_history(&mut self) {
+                self.tracker_history.clear();
+            }
+        }
+    }
+}
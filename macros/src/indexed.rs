@@ -0,0 +1,129 @@
+//! Support for fields marked with `#[tracker::indexed]`.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{GenericArgument, Ident, PathArguments, Type};
+
+use crate::{call_site_at, Context, TrackedField};
+
+/// Get the element type of a `Vec<T>` or `[T; N]` field.
+pub(crate) fn element_type(ty: &Type) -> syn::Result<Type> {
+    let element = match ty {
+        Type::Array(array) => Some(&*array.elem),
+        Type::Path(path) => path.path.segments.last().and_then(|last| {
+            let PathArguments::AngleBracketed(args) = &last.arguments else {
+                return None;
+            };
+            match args.args.first() {
+                Some(GenericArgument::Type(element))
+                    if last.ident == "Vec" && args.args.len() == 1 =>
+                {
+                    Some(element)
+                }
+                _ => None,
+            }
+        }),
+        _ => None,
+    };
+    element.cloned().ok_or_else(|| {
+        syn::Error::new_spanned(
+            ty,
+            "`indexed` can only be used for fields of the types `Vec<T>` and `[T; N]`",
+        )
+    })
+}
+
+/// Generate `get_#field_at()` and `set_#field_at()` for indexed fields.
+pub(crate) fn methods(ctx: &Context) -> TokenStream2 {
+    let mut methods = TokenStream2::new();
+    for field in &ctx.fields {
+        let Some(element) = &field.indexed else {
+            continue;
+        };
+        let TrackedField { ident: id, cfg, .. } = field;
+
+        if !ctx.skips_method(field, "get") {
+            let vis = field.method_vis("get");
+            let get_id = at_ident(field, "get_");
+            let doc = ctx.field_doc(format!(
+                "Get the element of the {id} field at `index`, or `None` if it's out of bounds."
+            ));
+            methods.extend(quote_spanned! { field.span =>
+                #(#cfg)*
+                #[must_use]
+                #[inline]
+                #doc
+                #vis fn #get_id(&self, index: usize) -> ::core::option::Option<&#element> {
+                    self.#id.get(index)
+                }
+            });
+        }
+
+        if ctx.skips_method(field, "set") {
+            continue;
+        }
+        let vis = field.method_vis("set");
+        let set_id = at_ident(field, "set_");
+        let mask_id = field.mask_ident();
+        let this = quote! { self };
+        let before_change = ctx.before_change(&this, field, None);
+        let trace_set = ctx.trace_change(&this, field, "set_at");
+        let mark = ctx.mark(&this, quote_spanned! { field.span => Self::#mask_id() });
+        for (no_eq, cfg) in field.no_eq_variants() {
+            let (summary, changed, bound) = if no_eq {
+                (
+                    "and mark the field as changed",
+                    // Check the bounds before anything is recorded.
+                    quote_spanned! { field.span => {
+                        let _ = &self.#id[index];
+                        true
+                    } },
+                    TokenStream2::new(),
+                )
+            } else {
+                let bound_span = call_site_at(element.span());
+                let element = ctx.bound_ty(element, "'__tracker_eq");
+                (
+                    "and mark the field as changed if it's not equal to the previous element",
+                    quote_spanned! { field.span => self.#id[index] != value },
+                    quote_spanned! { bound_span =>
+                        for<'__tracker_eq> #element: ::tracker::__private::FieldEq,
+                    },
+                )
+            };
+            let doc = ctx.field_doc(format!(
+                "Set the element of the {id} field at `index` {summary}.\n\n\
+                 Returns whether the field was marked as changed.\n\n\
+                 # Panics\n\n\
+                 Panics if `index` is out of bounds."
+            ));
+            methods.extend(quote_spanned! { field.span =>
+                #(#cfg)*
+                #[inline]
+                #doc
+                #vis fn #set_id(&mut self, index: usize, value: #element) -> bool
+                where
+                    #bound
+                {
+                    let changed = #changed;
+                    if changed {
+                        #before_change
+                        #trace_set
+                    }
+                    self.#id[index] = value;
+                    if changed {
+                        #mark
+                    }
+                    changed
+                }
+            });
+        }
+    }
+    methods
+}
+
+/// Create the identifier of `get_#field_at()` or `set_#field_at()`.
+pub(crate) fn at_ident(field: &TrackedField, prefix: &str) -> Ident {
+    format_ident!("{}_at", field.method_ident(prefix))
+}
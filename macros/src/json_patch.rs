@@ -0,0 +1,195 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote, quote_spanned};
+
+use crate::serde::{field_name, Direction};
+use crate::{update, Context, TrackedField};
+
+/// Escape a field name for the use in a JSON Pointer (RFC 6901).
+fn json_pointer(name: &str) -> String {
+    format!("/{}", name.replace('~', "~0").replace('/', "~1"))
+}
+
+/// Code that deserializes JSON values into the tracked fields.
+pub(crate) struct DeserializeValues {
+    /// Declarations of an optional `value_#field` variable for each field.
+    pub(crate) value_decls: TokenStream2,
+    /// Match arms that deserialize `value` into `value_#field` for each key.
+    pub(crate) match_arms: TokenStream2,
+    /// The keys of all fields.
+    pub(crate) known_keys: Vec<String>,
+    /// Assigns all deserialized values and evaluates to the mask of changed fields.
+    pub(crate) apply_values: TokenStream2,
+}
+
+/// Generate the code that deserializes JSON values into the tracked fields.
+///
+/// `map_err` converts a `serde_json::Error` into the error
+/// type of the generated method.
+pub(crate) fn deserialize_values(
+    ctx: &Context,
+    key: impl Fn(&TrackedField) -> String,
+    map_err: &TokenStream2,
+) -> DeserializeValues {
+    let mut value_decls = TokenStream2::new();
+    let mut match_arms = TokenStream2::new();
+    let mut known_keys = Vec::new();
+    for field in &ctx.fields {
+        let TrackedField {
+            ident: id,
+            ty,
+            cfg,
+            span: id_span,
+            ..
+        } = field;
+        let key = key(field);
+        let value_id = format_ident!("value_{}", id);
+
+        value_decls.extend(quote_spanned! { *id_span =>
+            #(#cfg)*
+            let mut #value_id: ::core::option::Option<#ty> = ::core::option::Option::None;
+        });
+        match_arms.extend(quote_spanned! { *id_span =>
+            #(#cfg)*
+            #key => {
+                #value_id = ::core::option::Option::Some(
+                    ::tracker::__private::serde::Deserialize::deserialize(value)
+                        .map_err(#map_err)?,
+                );
+            }
+        });
+        known_keys.push(key);
+    }
+
+    let apply_values = update::apply_options(ctx, |id| {
+        let value_id = format_ident!("value_{}", id);
+        quote! { #value_id }
+    });
+
+    DeserializeValues {
+        value_decls,
+        match_arms,
+        known_keys,
+        apply_values,
+    }
+}
+
+/// Generate the `to_json_patch()` and `apply_json_patch()` methods.
+pub(crate) fn generate(ctx: &Context) -> syn::Result<TokenStream2> {
+    // A patch with placeholders can't be applied, so redacted values can't be part of it.
+    if let Some(field) = ctx.fields.iter().find(|field| ctx.redacts(&field.ident)) {
+        return Err(syn::Error::new_spanned(
+            &field.ident,
+            "`tracker::redact` can't be used for tracked fields with the `json_patch` option, which exports their values",
+        ));
+    }
+
+    let Context {
+        ident,
+        vis: struct_vis,
+        generics,
+        where_clause,
+        tracker_ty,
+        ..
+    } = ctx;
+    let self_ty = ctx.self_ty();
+
+    let mut replace_ops = TokenStream2::new();
+    for field in &ctx.fields {
+        let TrackedField {
+            ident: id,
+            cfg,
+            span: id_span,
+            ..
+        } = field;
+        let mask_id = field.mask_ident();
+        let serialize_path = json_pointer(&field_name(ctx, field, Direction::Serialize));
+
+        replace_ops.extend(quote_spanned! { *id_span =>
+            #(#cfg)*
+            if ::tracker::Track::changed(self, Self::#mask_id()) {
+                ops.push(::tracker::__private::json_patch_replace(
+                    #serialize_path,
+                    ::tracker::__private::serde_json::to_value(&self.#id)?,
+                ));
+            }
+        });
+    }
+
+    let DeserializeValues {
+        value_decls,
+        match_arms: deserialize_arms,
+        known_keys: known_paths,
+        apply_values,
+    } = deserialize_values(
+        ctx,
+        |field| json_pointer(&field_name(ctx, field, Direction::Deserialize)),
+        &quote! { ::tracker::JsonPatchError::Deserialize },
+    );
+    let unknown_check = if known_paths.is_empty() {
+        quote! { true }
+    } else {
+        quote! { !::core::matches!(*path, #(#known_paths)|*) }
+    };
+
+    let eq_bounds = ctx.eq_bounds();
+
+    Ok(quote_spanned! { ident.span() =>
+        impl #generics #self_ty #where_clause {
+            /// Get a JSON Patch (RFC 6902) with a `replace` operation
+            /// for every changed field.
+            ///
+            /// The types of all tracked fields need to implement `serde::Serialize`.
+            #[allow(dead_code, clippy::needless_borrows_for_generic_args)]
+            #struct_vis fn to_json_patch(
+                &self,
+            ) -> ::core::result::Result<
+                ::tracker::__private::serde_json::Value,
+                ::tracker::__private::serde_json::Error,
+            > {
+                #[allow(unused_mut)]
+                let mut ops = ::tracker::__private::Vec::new();
+                #replace_ops
+                ::core::result::Result::Ok(::tracker::__private::serde_json::Value::Array(ops))
+            }
+
+            /// Apply a JSON Patch (RFC 6902) and get a bit mask of
+            /// the fields that actually changed.
+            ///
+            /// Only `add` and `replace` operations on fields are supported.
+            /// If the patch contains unknown paths or a value can't be deserialized,
+            /// an error is returned and no field is modified.
+            /// Just like with `set_#field_name()`, fields are only marked
+            /// as changed if their new value is not equal to the previous value.
+            #[allow(dead_code, unused_variables)]
+            #struct_vis fn apply_json_patch(
+                &mut self,
+                patch: &::tracker::__private::serde_json::Value,
+            ) -> ::core::result::Result<#tracker_ty, ::tracker::JsonPatchError>
+            where
+                #eq_bounds
+            {
+                let ops = ::tracker::__private::json_patch_operations(patch)?;
+
+                let unknown: ::tracker::__private::Vec<::tracker::__private::String> = ops
+                    .iter()
+                    .map(|(path, _)| *path)
+                    .filter(|path| #unknown_check)
+                    .map(::tracker::__private::ToOwned::to_owned)
+                    .collect();
+                if !unknown.is_empty() {
+                    return ::core::result::Result::Err(::tracker::JsonPatchError::UnknownPaths(unknown));
+                }
+
+                #value_decls
+                for (path, value) in ops {
+                    match path {
+                        #deserialize_arms
+                        _ => ::core::unreachable!(),
+                    }
+                }
+
+                ::core::result::Result::Ok({ #apply_values })
+            }
+        }
+    })
+}
@@ -6,259 +6,2553 @@
     rust_2018_idioms,
     unreachable_pub,
     clippy::cargo,
-    clippy::must_use_candidate,
-    clippy::cargo
+    clippy::must_use_candidate
 )]
 
-use proc_macro::{self, Span, TokenStream};
+use proc_macro::{self, TokenStream};
 use proc_macro2::{Span as Span2, TokenStream as TokenStream2};
 use quote::{quote, quote_spanned, ToTokens};
+use syn::ext::IdentExt;
+use syn::meta::ParseNestedMeta;
+use syn::parse::{Parse, ParseStream, Parser};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::visit_mut::VisitMut;
 use syn::{
-    parse_macro_input, Attribute, Error, Field, Fields, GenericParam, Ident, ItemStruct, Type,
+    parse_macro_input, parse_quote, Attribute, Expr, Field, Fields, GenericParam, Generics, Ident,
+    Item, ItemStruct, Lifetime, LitStr, Meta, Path, Token, Type, Visibility, WhereClause,
 };
 
+mod apply_json;
+mod changed_view;
+mod commands;
+mod companion;
+mod constructor;
+mod consumers;
+mod counters;
+mod data_struct;
+mod debug_values;
+mod delta;
+mod diesel;
+mod double_buffer;
+mod dynamic;
+mod elements;
+mod enums;
+mod field_mask;
+mod fields_mut;
+mod hash_eq;
+mod history;
+mod indexed;
+mod json_patch;
+mod listeners;
+mod map_collection;
+mod merge;
+mod names;
+mod nested;
+mod options;
+mod ordered;
+mod persistent;
+mod previous;
+mod project;
+mod proxy;
+mod remote;
+mod serde;
+mod serde_state;
+mod set_collection;
+mod snapshot;
+mod sql;
+mod staged;
+mod stream;
+mod timestamps;
+mod track_impl;
+mod traits;
+mod transparent;
+mod typed_mask;
+mod unconsumed;
+mod update;
+mod versioned;
+mod visitor;
+
+use hash_eq::HashEq;
+use map_collection::MapCollection;
+use nested::Nested;
+use options::Options;
+use set_collection::SetCollection;
+
 const NO_EQ: &str = "no_eq";
+const EQ: &str = "eq";
 const DO_NOT_TRACK: &str = "do_not_track";
+const COLUMN: &str = "column";
+const COMPARE_WITH: &str = "compare_with";
+const RENAME: &str = "rename";
+const TRACKER_FIELD: &str = "tracker_field";
+const VIS: &str = "vis";
+const NESTED: &str = "nested";
+const INDEXED: &str = "indexed";
+const SET_COLLECTION: &str = "set_collection";
+const MAP_COLLECTION: &str = "map_collection";
+const HASH_EQ: &str = "hash_eq";
+const ELEMENTS: &str = "elements";
+const REDACT: &str = "redact";
+const DEFAULT: &str = "default";
+
+/// Field attributes like `#[tracker::no_set]` that skip a method, with the name of the method.
+const SKIP_ATTRS: &[(&str, &str)] = &[
+    ("no_get", "get"),
+    ("no_mut", "get_mut"),
+    ("no_update", "update"),
+    ("no_set", "set"),
+];
+
+/// Options of the structured field attribute `#[tracker(...)]`.
+const FIELD_OPTIONS: &[&str] = &[
+    NO_EQ,
+    EQ,
+    COMPARE_WITH,
+    DO_NOT_TRACK,
+    COLUMN,
+    RENAME,
+    TRACKER_FIELD,
+    VIS,
+    NESTED,
+    INDEXED,
+    SET_COLLECTION,
+    MAP_COLLECTION,
+    HASH_EQ,
+    ELEMENTS,
+    REDACT,
+    DEFAULT,
+    "no_get",
+    "no_mut",
+    "no_update",
+    "no_set",
+];
+
+/// Per-field methods whose visibility can be set with `#[tracker::vis(...)]`.
+const FIELD_METHODS: &[&str] = &["get", "get_mut", "update", "set", "changed_field"];
+
+/// The number of bits of the largest tracker type, one for each field
+/// and one for each element of fields marked with `#[tracker::elements]`.
+const MAX_FIELDS: usize = 128;
+
+/// A tracked field of the struct.
+pub(crate) struct TrackedField {
+    pub(crate) ident: Ident,
+    pub(crate) ty: Type,
+    /// Visibility of the generated methods of the field.
+    ///
+    /// This is the visibility of the field unless it's set with the `vis` option.
+    pub(crate) vis: Visibility,
+    /// Visibility of single methods set with `#[tracker::vis(get = "...", ...)]`.
+    pub(crate) method_vis: Vec<(String, Visibility)>,
+    /// Methods skipped with attributes like `#[tracker::no_set]`.
+    pub(crate) skipped_methods: Vec<String>,
+    /// Name used in the generated methods, set with `#[tracker::rename = "..."]`.
+    ///
+    /// This is the name of the field without the `r#` prefix by default.
+    pub(crate) method_name: String,
+    /// Prefix of all generated methods set with the `prefix` option.
+    pub(crate) method_prefix: String,
+    pub(crate) no_eq: bool,
+    /// Condition of a `no_eq` set with `#[cfg_attr(condition, tracker::no_eq)]`.
+    pub(crate) no_eq_cfg: Option<TokenStream2>,
+    /// Function used instead of `PartialEq`, set with `#[tracker::compare_with = "..."]`.
+    pub(crate) compare_with: Option<Path>,
+    /// Column name set with `#[tracker::column = "..."]`.
+    pub(crate) column: Option<String>,
+    /// The field contains tracked structs whose changes count as changes of the field,
+    /// set with `#[tracker::nested]`.
+    pub(crate) nested: Option<Nested>,
+    /// The element type of a `Vec` or array field marked with `#[tracker::indexed]`.
+    pub(crate) indexed: Option<Type>,
+    /// The set type of a field marked with `#[tracker::set_collection]`.
+    pub(crate) set_collection: Option<SetCollection>,
+    /// The map type of a field marked with `#[tracker::map_collection]`.
+    pub(crate) map_collection: Option<MapCollection>,
+    /// The setter compares a cached hash first, set with `#[tracker::hash_eq]`.
+    pub(crate) hash_eq: Option<HashEq>,
+    /// The length of an array field marked with `#[tracker::elements]`,
+    /// which uses one bit for each element.
+    pub(crate) elements: Option<usize>,
+    /// The index of the first bit of the field in the tracker.
+    pub(crate) bit: usize,
+    /// Attributes of the field without the ones consumed by tracker.
+    pub(crate) attrs: Vec<Attribute>,
+    /// The `#[cfg(...)]` attributes of the field.
+    ///
+    /// They are forwarded to all code generated for the field.
+    /// Attributes aren't allowed on where predicates, so trait bounds
+    /// for the type of the field are left out.
+    pub(crate) cfg: Vec<Attribute>,
+    /// Span used for the code generated for this field.
+    pub(crate) span: Span2,
+}
+
+impl TrackedField {
+    /// The number of bits the field uses in the tracker.
+    pub(crate) fn width(&self) -> usize {
+        self.elements.unwrap_or(1)
+    }
+
+    /// The bits of the field in the tracker as integer literal.
+    pub(crate) fn bits(&self) -> proc_macro2::Literal {
+        let bits = u128::MAX
+            .checked_shr(128 - self.width() as u32)
+            .unwrap_or(0)
+            << self.bit;
+        proc_macro2::Literal::u128_unsuffixed(bits)
+    }
+
+    /// Get the `no_eq` setting of the field with the attributes
+    /// that need to be added to the code that depends on it.
+    ///
+    /// If `no_eq` is set with `cfg_attr`, the code is generated
+    /// twice and guarded by the condition.
+    pub(crate) fn no_eq_variants(&self) -> Vec<(bool, Vec<Attribute>)> {
+        let cfg = &self.cfg;
+        match &self.no_eq_cfg {
+            Some(condition) if !self.no_eq => vec![
+                (true, parse_quote! { #(#cfg)* #[cfg(#condition)] }),
+                (false, parse_quote! { #(#cfg)* #[cfg(not(#condition))] }),
+            ],
+            _ => vec![(self.no_eq, cfg.clone())],
+        }
+    }
+
+    /// The `PartialEq` bound for comparing the field, used in where clauses of methods
+    /// so the struct itself doesn't need the bound.
+    ///
+    /// The bound is spanned at the type of the field, so a missing implementation
+    /// is reported there together with a hint to use `no_eq`.
+    pub(crate) fn eq_bound(&self, ctx: &Context) -> TokenStream2 {
+        if self.compare_with.is_some() {
+            return TokenStream2::new();
+        }
+        let ty = ctx.bound_ty(&self.ty, "'__tracker_eq");
+        quote_spanned! { call_site_at(self.ty.span()) =>
+            for<'__tracker_eq> #ty: ::tracker::__private::FieldEq,
+        }
+    }
+
+    /// Generate the condition that two values of the field are not equal,
+    /// using the function set with `compare_with` if there is one.
+    pub(crate) fn ne(&self, a: TokenStream2, b: TokenStream2) -> TokenStream2 {
+        match &self.compare_with {
+            Some(compare) => quote_spanned! { self.span => !#compare(&#a, &#b) },
+            None => quote_spanned! { self.span => #a != #b },
+        }
+    }
+
+    /// Get the visibility of a generated method like `set` (see [`FIELD_METHODS`]).
+    pub(crate) fn method_vis(&self, method: &str) -> &Visibility {
+        self.method_vis
+            .iter()
+            .find(|(name, _)| name == method)
+            .map_or(&self.vis, |(_, vis)| vis)
+    }
+
+    /// Create the identifier of the bit mask function of the field.
+    pub(crate) fn mask_ident(&self) -> Ident {
+        self.method_ident("")
+    }
+
+    /// Check whether a method like `set` was skipped with an attribute of the field.
+    pub(crate) fn skips(&self, method: &str) -> bool {
+        self.skipped_methods.iter().any(|skipped| skipped == method)
+    }
+
+    /// Create the identifier of a generated method like `get_#field`.
+    ///
+    /// The `r#` prefix of raw field names is stripped, unless the
+    /// resulting name is a keyword and has to stay raw.
+    pub(crate) fn method_ident(&self, prefix: &str) -> Ident {
+        let name = format!("{}{}{}", self.method_prefix, prefix, self.method_name);
+        if syn::parse_str::<Ident>(&name).is_ok() {
+            Ident::new(&name, self.span)
+        } else {
+            Ident::new_raw(&name, self.span)
+        }
+    }
+}
+
+/// Everything the code generation needs to know about the tracked struct.
+pub(crate) struct Context {
+    pub(crate) ident: Ident,
+    pub(crate) attrs: Vec<Attribute>,
+    /// Visibility of the generated items.
+    ///
+    /// This is the visibility of the struct unless it's set with the `vis` option.
+    pub(crate) vis: Visibility,
+    /// The visibility of the struct itself, needed for types used in trait impls.
+    pub(crate) declared_vis: Visibility,
+    /// Generics without default values, used for impl blocks.
+    pub(crate) generics: Generics,
+    /// Generic arguments of the struct (like `'a, T`).
+    pub(crate) generic_idents: TokenStream2,
+    pub(crate) where_clause: Option<WhereClause>,
+    /// The type of the tracker and all bit masks.
+    ///
+    /// This is either an integer or the mask type generated by the `typed_mask` option.
+    pub(crate) tracker_ty: TokenStream2,
+    /// The integer type that stores the bits of the tracker.
+    pub(crate) bits_ty: TokenStream2,
+    /// The atomic integer type of the tracker field with the `atomic` option.
+    pub(crate) atomic_ty: Option<TokenStream2>,
+    /// The atomic integer type of the changes marked through handles with the `remote` option.
+    pub(crate) remote_ty: Option<TokenStream2>,
+    /// Name of the injected tracker field.
+    pub(crate) tracker_field: Ident,
+    pub(crate) fields: Vec<TrackedField>,
+    /// All fields declared by the user, including untracked ones.
+    pub(crate) declared_fields: Vec<Field>,
+    /// Fields marked with `#[tracker::redact]`, including untracked ones.
+    pub(crate) redacted: Vec<Ident>,
+    /// Default values set with `#[tracker::default = ...]`, including untracked fields.
+    pub(crate) defaults: Vec<(Ident, Expr)>,
+    pub(crate) options: Options,
+}
+
+impl Context {
+    /// The type of the struct including its generic arguments.
+    pub(crate) fn self_ty(&self) -> TokenStream2 {
+        let ident = &self.ident;
+        let generic_idents = &self.generic_idents;
+        quote! { #ident < #generic_idents > }
+    }
+
+    /// Check whether the value of the field `id` is hidden with `#[tracker::redact]`.
+    ///
+    /// All generated code that formats values needs to check this.
+    pub(crate) fn redacts(&self, id: &Ident) -> bool {
+        self.redacted.contains(id)
+    }
+
+    /// The expression set with `#[tracker::default = ...]` for the field `id`, if any.
+    pub(crate) fn default_value(&self, id: &Ident) -> Option<&Expr> {
+        self.defaults
+            .iter()
+            .find(|(field, _)| field == id)
+            .map(|(_, value)| value)
+    }
+
+    /// The `&dyn Debug` compatible reference used to format the field `id`,
+    /// which is `value` unless the field is redacted.
+    pub(crate) fn debug_value(&self, id: &Ident, value: TokenStream2) -> TokenStream2 {
+        if self.redacts(id) {
+            quote! { &::tracker::__private::Redacted }
+        } else {
+            value
+        }
+    }
+
+    /// Create the identifier of a generated type like `#structDelta`.
+    pub(crate) fn companion_ident(&self, suffix: &str) -> Ident {
+        Ident::new(&format!("{}{}", self.ident, suffix), self.ident.span())
+    }
+
+    /// Type of a `PhantomData` field that uses all generic parameters of the struct.
+    ///
+    /// Generated types that don't contain all fields of the struct need this
+    /// field because unused generic parameters are not allowed.
+    /// Returns `None` if the struct has no type or lifetime parameters.
+    pub(crate) fn phantom_ty(&self) -> Option<TokenStream2> {
+        let mut params = TokenStream2::new();
+        for param in &self.generics.params {
+            match param {
+                GenericParam::Type(ty) => {
+                    let ident = &ty.ident;
+                    params.extend(quote! { fn() -> *const #ident, });
+                }
+                GenericParam::Lifetime(lifetime) => {
+                    let lifetime = &lifetime.lifetime;
+                    params.extend(quote! { &#lifetime (), });
+                }
+                GenericParam::Const(_) => {}
+            }
+        }
+
+        if params.is_empty() {
+            None
+        } else {
+            Some(quote! { ::core::marker::PhantomData<(#params)> })
+        }
+    }
+
+    /// Get `ty` for a higher-ranked trait bound like `for<'__tracker> #ty: Clone`,
+    /// with the lifetimes of the struct replaced by the lifetime of the binder.
+    ///
+    /// Bounds of types that only differ by their lifetimes, like `&'a u8` and `&'b u8`,
+    /// are ambiguous. With the lifetime of the binder, they hold for all lifetimes.
+    pub(crate) fn bound_ty(&self, ty: &Type, binder: &str) -> Type {
+        struct ReplaceLifetimes<'a>(&'a [&'a Lifetime], &'a str);
+
+        impl VisitMut for ReplaceLifetimes<'_> {
+            fn visit_lifetime_mut(&mut self, lifetime: &mut Lifetime) {
+                if self.0.contains(&&*lifetime) {
+                    *lifetime = Lifetime::new(self.1, lifetime.span());
+                }
+            }
+        }
+
+        let mut ty = ty.clone();
+        let lifetimes: Vec<&Lifetime> = self
+            .generics
+            .lifetimes()
+            .map(|param| &param.lifetime)
+            .collect();
+        ReplaceLifetimes(&lifetimes, binder).visit_type_mut(&mut ty);
+        ty
+    }
+
+    /// Declaration of the `PhantomData` field for generated types, see [`Self::phantom_ty`].
+    pub(crate) fn phantom_field(&self) -> Option<TokenStream2> {
+        let vis = &self.vis;
+        self.phantom_ty().map(|phantom_ty| {
+            quote! {
+                #[doc(hidden)]
+                #vis __phantom: #phantom_ty,
+            }
+        })
+    }
+
+    /// The predicates of the where clause of the struct
+    /// with a trailing comma, if any.
+    pub(crate) fn where_predicates(&self) -> TokenStream2 {
+        match &self.where_clause {
+            Some(where_clause) if !where_clause.predicates.is_empty() => {
+                let predicates = &where_clause.predicates;
+                if where_clause.predicates.trailing_punct() {
+                    quote! { #predicates }
+                } else {
+                    quote! { #predicates, }
+                }
+            }
+            _ => TokenStream2::new(),
+        }
+    }
+
+    /// Additional fields injected after the tracker field, with the visibility of the tracker field.
+    pub(crate) fn extra_fields(&self) -> Vec<Field> {
+        let mut fields = Vec::new();
+        if self.options.previous {
+            fields.push(previous::field(self));
+        }
+        if self.options.versioned {
+            fields.push(versioned::field(self));
+        }
+        if self.options.listeners {
+            fields.push(listeners::field(self));
+        }
+        if self.options.stream {
+            fields.push(stream::field(self));
+        }
+        if let Some(capacity) = self.options.history {
+            fields.push(history::field(self, capacity));
+        }
+        if self.options.counters {
+            fields.push(counters::field(self));
+        }
+        if let Some(clock) = &self.options.timestamps {
+            fields.push(timestamps::field(self, clock));
+        }
+        if self.options.ordered {
+            fields.push(ordered::field(self));
+        }
+        if self.options.persistent {
+            fields.push(persistent::field(self));
+        }
+        if self.options.double_buffer {
+            fields.push(double_buffer::field(self));
+        }
+        if let Some(count) = self.options.consumers {
+            fields.push(consumers::field(count));
+        }
+        if self.options.staged {
+            fields.push(staged::field(self));
+        }
+        if let Some(remote_ty) = &self.remote_ty {
+            fields.push(remote::field(remote_ty));
+        }
+        fields.extend(hash_eq::field(self));
+        fields
+    }
+
+    /// The type of the tracker field.
+    pub(crate) fn tracker_field_ty(&self) -> TokenStream2 {
+        match &self.atomic_ty {
+            Some(atomic_ty) => atomic_ty.clone(),
+            None => self.tracker_ty.clone(),
+        }
+    }
+
+    /// An expression of the mask type with the bits of the tracker of `this`.
+    pub(crate) fn tracker_value(&self, this: &TokenStream2) -> TokenStream2 {
+        let tracker_field = &self.tracker_field;
+        if self.atomic_ty.is_some() {
+            self.mask_from_bits(
+                quote! { #this.#tracker_field.load(::core::sync::atomic::Ordering::Relaxed) },
+            )
+        } else {
+            quote! { #this.#tracker_field }
+        }
+    }
+
+    /// An expression that initializes the tracker field with `mask`.
+    pub(crate) fn tracker_init(&self, mask: TokenStream2) -> TokenStream2 {
+        match &self.atomic_ty {
+            Some(atomic_ty) => {
+                let bits = self.mask_bits(mask);
+                quote! { #atomic_ty::new(#bits) }
+            }
+            None => mask,
+        }
+    }
+
+    /// Generate the code that marks all fields of `this` as unchanged.
+    pub(crate) fn clear_tracker(&self, this: &TokenStream2) -> TokenStream2 {
+        let tracker_field = &self.tracker_field;
+        if self.atomic_ty.is_some() {
+            quote! { #this.#tracker_field.store(0, ::core::sync::atomic::Ordering::Relaxed); }
+        } else {
+            let empty_mask = self.empty_mask();
+            quote! { #this.#tracker_field = #empty_mask; }
+        }
+    }
+
+    /// Generate the code that marks the bits of `mask` as unchanged on `this`.
+    ///
+    /// With the `ordered` option the bits are also removed from the order.
+    pub(crate) fn unmark(&self, this: &TokenStream2, mask: TokenStream2) -> TokenStream2 {
+        let tracker_field = &self.tracker_field;
+        let mut stream = if self.atomic_ty.is_some() {
+            let bits = self.mask_bits(mask.clone());
+            quote! { #this.#tracker_field.fetch_and(!#bits, ::core::sync::atomic::Ordering::Relaxed); }
+        } else {
+            quote! { #this.#tracker_field &= !#mask; }
+        };
+        if self.options.ordered {
+            stream.extend(ordered::remove(self, this, mask));
+        }
+        stream
+    }
+
+    /// Generate the code that marks the bits of `mask` as changed on `this`.
+    pub(crate) fn mark(&self, this: &TokenStream2, mask: TokenStream2) -> TokenStream2 {
+        let mut stream = self.mark_tracker(this, mask.clone());
+        if self.options.versioned {
+            stream.extend(versioned::record(this, &self.mask_bits(mask.clone())));
+        }
+        if self.options.persistent {
+            stream.extend(persistent::record(this, &mask));
+        }
+        if self.options.consumers.is_some() {
+            stream.extend(consumers::record(self, this, mask));
+        }
+        stream
+    }
+
+    /// Generate the code that only sets the bits of `mask` in the tracker of `this`,
+    /// which works through `&self` with the `atomic` option.
+    ///
+    /// With the `on_change` and `listeners` options the callbacks are called
+    /// with the bits that weren't set before, the `stream` option sends them,
+    /// the `history` option records them, the `counters` option counts them,
+    /// the `timestamps` option stores the time and the `ordered` option their order.
+    fn mark_tracker(&self, this: &TokenStream2, mask: TokenStream2) -> TokenStream2 {
+        let tracker_field = &self.tracker_field;
+        let mut notify = TokenStream2::new();
+        if let Some(on_change) = &self.options.on_change {
+            notify.extend(quote! { #on_change(__tracker_new); });
+        }
+        if self.options.listeners {
+            notify.extend(listeners::notify(this));
+        }
+        if self.options.stream {
+            notify.extend(stream::notify(this));
+        }
+        if self.options.history.is_some() {
+            notify.extend(history::record(this));
+        }
+        if self.options.counters {
+            notify.extend(counters::record(self, this));
+        }
+        if self.options.timestamps.is_some() {
+            notify.extend(timestamps::record(self, this));
+        }
+        if self.options.ordered {
+            notify.extend(ordered::record(self, this));
+        }
+        if notify.is_empty() {
+            return if self.atomic_ty.is_some() {
+                let bits = self.mask_bits(mask);
+                quote! { #this.#tracker_field.fetch_or(#bits, ::core::sync::atomic::Ordering::Relaxed); }
+            } else {
+                quote! { #this.#tracker_field |= #mask; }
+            };
+        }
+
+        let empty_mask = self.empty_mask();
+        if self.atomic_ty.is_some() {
+            // `listeners` can't be combined with `atomic`, the sender of `stream` works through `&self`.
+            let bits = self.mask_bits(mask);
+            let new_mask = self.mask_from_bits(quote! { __tracker_bits });
+            quote! {{
+                let __tracker_bits = #bits;
+                let __tracker_bits = __tracker_bits
+                    & !#this.#tracker_field.fetch_or(__tracker_bits, ::core::sync::atomic::Ordering::Relaxed);
+                if __tracker_bits != 0 {
+                    let __tracker_new = #new_mask;
+                    #notify
+                }
+            }}
+        } else {
+            quote! {{
+                let __tracker_new = (#mask) & !#this.#tracker_field;
+                #this.#tracker_field |= __tracker_new;
+                if __tracker_new != #empty_mask {
+                    #notify
+                }
+            }}
+        }
+    }
+
+    /// Convert an integer expression into an expression of the mask type.
+    pub(crate) fn mask_from_bits(&self, bits: TokenStream2) -> TokenStream2 {
+        if self.options.bitflags {
+            let tracker_ty = &self.tracker_ty;
+            quote! { #tracker_ty::from_bits_retain(#bits) }
+        } else if self.options.typed_mask {
+            let tracker_ty = &self.tracker_ty;
+            quote! { #tracker_ty::from_bits(#bits) }
+        } else {
+            bits
+        }
+    }
+
+    /// Convert an expression of the mask type into an integer expression.
+    pub(crate) fn mask_bits(&self, mask: TokenStream2) -> TokenStream2 {
+        if self.options.typed_mask {
+            quote! { (#mask).bits() }
+        } else {
+            mask
+        }
+    }
+
+    /// The number of bits used by the tracked fields.
+    pub(crate) fn bit_count(&self) -> usize {
+        self.fields
+            .last()
+            .map_or(0, |field| field.bit + field.width())
+    }
+
+    /// An expression of the mask type without any bits set.
+    pub(crate) fn empty_mask(&self) -> TokenStream2 {
+        self.mask_from_bits(quote! { 0 })
+    }
+
+    /// Generate the code that needs to run before `field` of `this` is modified.
+    ///
+    /// `pending` is a bit mask of changes that were recorded,
+    /// but not yet applied to the tracker of `this`.
+    pub(crate) fn before_change(
+        &self,
+        this: &TokenStream2,
+        field: &TrackedField,
+        pending: Option<&TokenStream2>,
+    ) -> TokenStream2 {
+        let mut stream = if self.options.previous {
+            previous::store(self, this, field, pending)
+        } else {
+            TokenStream2::new()
+        };
+        stream.extend(hash_eq::invalidate(self, this, field));
+        stream
+    }
+
+    /// Generate the code that needs to run when the tracker is reset.
+    fn on_reset(&self) -> TokenStream2 {
+        let mut on_reset = TokenStream2::new();
+        if self.options.previous {
+            on_reset.extend(previous::reset());
+        }
+        if self.options.ordered {
+            on_reset.extend(ordered::reset());
+        }
+        on_reset
+    }
+
+    /// The `PartialEq` bounds of all fields that are compared, see [`TrackedField::eq_bound`].
+    ///
+    /// Fields with `#[cfg(...)]` attributes are left out because
+    /// where predicates can't have attributes.
+    pub(crate) fn eq_bounds(&self) -> TokenStream2 {
+        self.fields
+            .iter()
+            .filter(|field| {
+                field
+                    .no_eq_variants()
+                    .iter()
+                    .any(|(no_eq, cfg)| !no_eq && cfg.is_empty())
+            })
+            .map(|field| field.eq_bound(self))
+            .collect()
+    }
+
+    /// Check whether a per-field method like `set` is skipped for `field`,
+    /// either with `skip_methods(...)` or with an attribute of the field.
+    pub(crate) fn skips_method(&self, field: &TrackedField, method: &str) -> bool {
+        self.options.skips(method) || field.skips(method)
+    }
+
+    /// Generate the `tracing` event of the `trace` option for a change of `field`
+    /// by `method`, which needs to run before the field is marked.
+    pub(crate) fn trace_change(
+        &self,
+        this: &TokenStream2,
+        field: &TrackedField,
+        method: &str,
+    ) -> TokenStream2 {
+        if !self.options.trace {
+            return TokenStream2::new();
+        }
+        let struct_name = self.ident.to_string();
+        let field_name = field.ident.unraw().to_string();
+        let mask_id = field.mask_ident();
+        let self_ty = self.self_ty();
+        let tracker_value = self.tracker_value(this);
+        let empty_mask = self.empty_mask();
+        quote! {
+            ::tracker::__private::tracing::trace!(
+                struct_name = #struct_name,
+                field = #field_name,
+                method = #method,
+                newly_changed = #tracker_value & <#self_ty>::#mask_id() == #empty_mask,
+                "field marked as changed"
+            );
+        }
+    }
+
+    /// Generate the `tracing` event of the `trace` option for a method that changes the whole tracker.
+    fn trace_tracker(&self, message: &str) -> TokenStream2 {
+        if !self.options.trace {
+            return TokenStream2::new();
+        }
+        let struct_name = self.ident.to_string();
+        quote! {
+            ::tracker::__private::tracing::trace!(struct_name = #struct_name, #message);
+        }
+    }
+
+    /// Check whether the setters of `field` can use `__private::set` instead of
+    /// comparing the values themselves, which is the case if nothing else
+    /// needs to happen when the field changes and the tracker isn't atomic.
+    pub(crate) fn uses_shared_setter(&self, field: &TrackedField) -> bool {
+        !self.options.previous
+            && !self.options.versioned
+            && !self.options.atomic
+            && self.options.on_change.is_none()
+            && !self.options.trace
+            && !self.options.listeners
+            && !self.options.stream
+            && self.options.history.is_none()
+            && !self.options.counters
+            && self.options.timestamps.is_none()
+            && !self.options.ordered
+            && !self.options.persistent
+            && self.options.consumers.is_none()
+            && field.compare_with.is_none()
+            && field.hash_eq.is_none()
+    }
+
+    /// The doc comment of an item generated for a single field, like a getter or
+    /// a field of the delta struct, left out with the `compact` option.
+    pub(crate) fn field_doc(&self, doc: String) -> TokenStream2 {
+        if self.options.compact {
+            TokenStream2::new()
+        } else {
+            quote! { #[doc = #doc] }
+        }
+    }
+
+    /// `#[track_caller]` for the methods that mark fields, so the `history`
+    /// option records the location of their callers.
+    pub(crate) fn track_caller(&self) -> Option<TokenStream2> {
+        self.options.history.map(|_| quote! { #[track_caller] })
+    }
+
+    /// Like [`Self::field_doc`], followed by the doc comments of `field` so the
+    /// documentation of the field is also shown on its getters and setters.
+    pub(crate) fn accessor_doc(&self, field: &TrackedField, doc: String) -> TokenStream2 {
+        let mut docs = self.field_doc(doc);
+        let field_docs: Vec<&Attribute> = field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("doc"))
+            .collect();
+        if !docs.is_empty() && !field_docs.is_empty() {
+            docs.extend(quote! {
+                #[doc = ""]
+                #(#field_docs)*
+            });
+        }
+        docs
+    }
+
+    /// Allow missing docs on the items that contain the ones documented with
+    /// [`Self::field_doc`] if their doc comments are left out.
+    pub(crate) fn allow_missing_docs(&self) -> Option<TokenStream2> {
+        self.options
+            .compact
+            .then(|| quote! { #[allow(missing_docs)] })
+    }
+
+    /// Initialization of the `PhantomData` field for generated types, see [`Self::phantom_ty`].
+    pub(crate) fn phantom_value(&self) -> Option<TokenStream2> {
+        self.phantom_ty()
+            .map(|_| quote! { __phantom: ::core::marker::PhantomData, })
+    }
+}
 
 /// Implements tracker methods for structs.
 #[proc_macro_attribute]
 pub fn track(attr: TokenStream, item: TokenStream) -> TokenStream {
-    if !attr.is_empty() {
-        return Error::new(
-            attr.into_iter().next().unwrap().span().into(),
-            "This macro doesn't handle attributes",
-        )
-        .into_compile_error()
-        .into();
+    let item: Item = parse_macro_input!(item);
+    match track_item(item, &[attr.into()]) {
+        Ok(output) => output.into(),
+        Err(err) => err.into_compile_error().into(),
     }
+}
 
-    let mut data: ItemStruct = parse_macro_input!(item);
-    let ident = data.ident.clone();
-    let tracker_ty;
-    let struct_vis = &data.vis;
-    let where_clause = &data.generics.where_clause;
+/// Marks the fields of `self` that are assigned in the methods of an impl block of a tracked struct.
+#[proc_macro_attribute]
+pub fn track_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let item: Item = parse_macro_input!(item);
+    match track_impl::expand(attr.into(), item) {
+        Ok(output) => output.into(),
+        Err(err) => err.into_compile_error().into(),
+    }
+}
 
-    // Remove default type parameters (like <Type=DefaultType>).
-    let mut generics = data.generics.clone();
-    for param in generics.params.iter_mut() {
-        if let GenericParam::Type(ty) = param {
-            ty.eq_token = None;
-            ty.default = None;
+/// Defines structs and implements tracker methods for them, like `#[tracker::track]`.
+///
+/// Options shared by all structs are passed with `#![track(...)]` at the start,
+/// options of single structs with `#[track(...)]` on the struct.
+#[proc_macro]
+pub fn tracked(input: TokenStream) -> TokenStream {
+    let TrackedItems { options, items } = parse_macro_input!(input);
+    let mut output = TokenStream2::new();
+    for mut item in items {
+        let mut item_options = options.clone();
+        let result = take_track_attrs(&mut item).and_then(|attrs| {
+            item_options.extend(attrs);
+            track_item(item, &item_options)
+        });
+        match result {
+            Ok(tokens) => output.extend(tokens),
+            Err(err) => output.extend(err.into_compile_error()),
         }
     }
+    output.into()
+}
 
-    let mut generics_iter = data.generics.params.iter();
-    let mut generic_idents = TokenStream2::new();
+/// The input of `tracked!`.
+struct TrackedItems {
+    /// Options of all items from `#![track(...)]`.
+    options: Vec<TokenStream2>,
+    items: Vec<Item>,
+}
 
-    if let Some(first) = generics_iter.next() {
-        impl_struct_generics(first, &mut generic_idents);
-        for generic_param in generics_iter {
-            generic_idents.extend(quote! {,});
-            impl_struct_generics(generic_param, &mut generic_idents);
+impl Parse for TrackedItems {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let options = input
+            .call(Attribute::parse_inner)?
+            .iter()
+            .map(track_options)
+            .collect::<syn::Result<_>>()?;
+        let mut items = Vec::new();
+        while !input.is_empty() {
+            items.push(input.parse()?);
+        }
+        Ok(Self { options, items })
+    }
+}
+
+/// Remove the `#[track(...)]` attributes of an item in `tracked!` and return their options.
+fn take_track_attrs(item: &mut Item) -> syn::Result<Vec<TokenStream2>> {
+    let attrs = match item {
+        Item::Struct(data) => &mut data.attrs,
+        Item::Enum(data) => &mut data.attrs,
+        _ => return Ok(Vec::new()),
+    };
+    let (track, other) = std::mem::take(attrs)
+        .into_iter()
+        .partition::<Vec<_>, _>(|attr| attr.path().is_ident("track"));
+    *attrs = other;
+    track.iter().map(track_options).collect()
+}
+
+/// Get the options of a `#[track(...)]` attribute in `tracked!`.
+fn track_options(attr: &Attribute) -> syn::Result<TokenStream2> {
+    if !attr.path().is_ident("track") {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "expected `#![track(...)]` with the options of all structs",
+        ));
+    }
+    match &attr.meta {
+        Meta::Path(_) => Ok(TokenStream2::new()),
+        Meta::List(list) => Ok(list.tokens.clone()),
+        Meta::NameValue(_) => Err(syn::Error::new_spanned(
+            attr,
+            "expected options in parentheses like `#[track(debug)]`",
+        )),
+    }
+}
+
+/// Generate the tracker code for an item of `#[track]` or `tracked!`,
+/// with the option lists that apply to it.
+fn track_item(item: Item, option_lists: &[TokenStream2]) -> syn::Result<TokenStream2> {
+    let mut data = match item {
+        Item::Struct(data) => data,
+        Item::Enum(data) => {
+            if let Some(options) = option_lists.iter().find(|options| !options.is_empty()) {
+                return Err(syn::Error::new(
+                    options.span(),
+                    "options are not supported on enums",
+                ));
+            }
+            return Ok(enums::generate(&data));
+        }
+        Item::Union(data) => {
+            return Err(syn::Error::new_spanned(
+                &data.ident,
+                format!(
+                    "`{}` is a union, only structs and enums can be tracked",
+                    data.ident
+                ),
+            ));
+        }
+        item => {
+            return Err(syn::Error::new_spanned(
+                item,
+                "expected a struct or an enum",
+            ));
+        }
+    };
+
+    let mut options = Options::default();
+    for tokens in option_lists {
+        syn::meta::parser(|meta| options.parse_meta(meta)).parse2(tokens.clone())?;
+    }
+    expand(&mut data, options, false)
+}
+
+/// Get a span at the location of `span` that resolves at the call site.
+///
+/// Generated code is spanned at the struct and its fields for error messages,
+/// but `self` and local variables must resolve the same for all tokens, even if
+/// the struct was passed in by a `macro_rules!` macro.
+fn call_site_at(span: Span2) -> Span2 {
+    Span2::call_site().located_at(span)
+}
+
+/// Generate the tracker code for a struct, shared by `#[track]` and `#[derive(Track)]`.
+///
+/// The attribute form emits the modified struct as well, the derive form
+/// can't modify the struct and only emits the generated items.
+fn expand(data: &mut ItemStruct, mut options: Options, derive: bool) -> syn::Result<TokenStream2> {
+    let (generics, generic_idents) = impl_generics(&data.generics);
+
+    // Unit structs become braced structs with a tracker field.
+    if let Fields::Unit = data.fields {
+        data.fields = Fields::Named(parse_quote! { {} });
+        data.semi_token = None;
+    }
+    let named_fields = match &mut data.fields {
+        Fields::Named(named_fields) => named_fields,
+        Fields::Unnamed(fields) => {
+            return Err(syn::Error::new_spanned(
+                fields,
+                format!(
+                    "`{}` is a tuple struct, only structs with named fields can be tracked",
+                    data.ident
+                ),
+            ));
+        }
+        Fields::Unit => unreachable!(),
+    };
+
+    let mut fields = Vec::new();
+    let mut redacted = Vec::new();
+    let mut defaults = Vec::new();
+    // The field declared with `#[tracker::tracker_field]`, if any.
+    let mut declared_tracker: Option<Field> = None;
+    // Errors in the attributes of different fields are reported together.
+    let mut attr_error: Option<syn::Error> = None;
+    for field in &mut named_fields.named {
+        let field_attrs = match parse_field_attrs(&mut field.attrs, options.strict_attrs) {
+            Ok(field_attrs) => field_attrs,
+            Err(err) => {
+                match &mut attr_error {
+                    Some(attr_error) => attr_error.combine(err),
+                    None => attr_error = Some(err),
+                }
+                continue;
+            }
+        };
+        let FieldAttrs {
+            do_not_track,
+            no_eq,
+            eq,
+            no_eq_cfg,
+            compare_with,
+            column,
+            tracker_field,
+            method_vis,
+            skipped_methods,
+            rename,
+            nested,
+            indexed,
+            set_collection,
+            map_collection,
+            hash_eq,
+            elements,
+            redact,
+            default,
+        } = field_attrs;
+        if eq && (no_eq || no_eq_cfg.is_some()) {
+            return Err(syn::Error::new_spanned(
+                &field.ident,
+                "a field can't be marked with both `eq` and `no_eq`",
+            ));
+        }
+        if let (Some(compare_with), true) = (&compare_with, no_eq || no_eq_cfg.is_some()) {
+            return Err(syn::Error::new_spanned(
+                compare_with,
+                "`compare_with` can't be combined with `no_eq` because the field isn't compared",
+            ));
+        }
+        if redact {
+            redacted.extend(field.ident.clone());
+        }
+        if let (Some(ident), Some(default)) = (&field.ident, default) {
+            defaults.push((ident.clone(), default));
+        }
+        if tracker_field {
+            if declared_tracker.is_some() {
+                return Err(syn::Error::new_spanned(
+                    &field.ident,
+                    "only one field can be marked with `#[tracker::tracker_field]`",
+                ));
+            }
+            declared_tracker = Some(field.clone());
+        } else if !do_not_track {
+            let Some(ident) = field.ident.clone() else {
+                return Err(syn::Error::new_spanned(field, "expected a named field"));
+            };
+            let span = call_site_at(ident.span());
+            let method_name = rename.unwrap_or_else(|| ident.unraw().to_string());
+            fields.push(TrackedField {
+                ident,
+                ty: field.ty.clone(),
+                vis: options.vis.clone().unwrap_or_else(|| field.vis.clone()),
+                method_vis,
+                skipped_methods,
+                method_name,
+                method_prefix: options.prefix.clone().unwrap_or_default(),
+                // Fields marked with `eq` are compared even if `no_eq` is the default.
+                no_eq: no_eq
+                    || (options.no_eq && !eq && compare_with.is_none() && hash_eq.is_none()),
+                no_eq_cfg,
+                compare_with,
+                column,
+                nested: nested.then(|| Nested::of(&field.ty)),
+                indexed: indexed
+                    .then(|| indexed::element_type(&field.ty))
+                    .transpose()?,
+                set_collection: set_collection
+                    .then(|| SetCollection::of(&field.ty))
+                    .transpose()?,
+                map_collection: map_collection
+                    .then(|| MapCollection::of(&field.ty))
+                    .transpose()?,
+                hash_eq,
+                elements: elements
+                    .then(|| elements::array_len(&field.ty))
+                    .transpose()?,
+                bit: 0,
+                attrs: field.attrs.clone(),
+                cfg: cfg_attrs(&field.attrs),
+                span,
+            });
+            if options.encapsulate {
+                field.vis = Visibility::Inherited;
+            }
+        }
+    }
+    if let Some(attr_error) = attr_error {
+        return Err(attr_error);
+    }
+
+    if derive {
+        check_derive_options(&data.ident, &options)?;
+        if let Some(field) = fields.iter().find(|field| field.hash_eq.is_some()) {
+            return Err(syn::Error::new_spanned(
+                &field.ident,
+                "`hash_eq` can't be used with `#[derive(Track)]` because it adds a field to the struct, use `#[tracker::track]` instead",
+            ));
+        }
+        if declared_tracker.is_none() {
+            // Derives can't add fields, so a field with the name of the tracker is used.
+            let name = options
+                .field
+                .take()
+                .unwrap_or_else(|| Ident::new("tracker", Span2::call_site()));
+            let Some(field) = named_fields
+                .named
+                .iter()
+                .find(|field| field.ident.as_ref() == Some(&name))
+            else {
+                return Err(syn::Error::new_spanned(
+                    &data.ident,
+                    format!(
+                        "`#[derive(Track)]` can't add a tracker field to `{}`, declare a field called `{name}` or mark one with `#[tracker_field]`",
+                        data.ident
+                    ),
+                ));
+            };
+            fields.retain(|tracked| tracked.ident != name);
+            declared_tracker = Some(field.clone());
+        }
+    }
+
+    let declared_fields = named_fields
+        .named
+        .iter()
+        .filter(|field| {
+            declared_tracker.as_ref().map(|tracker| &tracker.ident) != Some(&field.ident)
+        })
+        .cloned()
+        .collect();
+
+    let mut bit_count = 0;
+    for field in &mut fields {
+        field.bit = bit_count;
+        bit_count += field.width();
+        if bit_count <= MAX_FIELDS {
+            continue;
+        }
+        let message = if field.elements.is_some() {
+            format!(
+                "the elements of `{}` need {} bits, but `{}` can only track {MAX_FIELDS} bits and the fields before use {}",
+                field.ident.unraw(),
+                field.width(),
+                data.ident,
+                field.bit,
+            )
+        } else {
+            format!(
+                "`{}` can track up to {MAX_FIELDS} fields, mark the ones that don't need tracking with `#[tracker::do_not_track]`",
+                data.ident
+            )
+        };
+        return Err(syn::Error::new_spanned(&field.ident, message));
+    }
+
+    let tracker_field = match (&declared_tracker, &options.field) {
+        (Some(field), Some(_)) => {
+            return Err(syn::Error::new_spanned(
+                &field.ident,
+                "`#[tracker::tracker_field]` can't be combined with the `field` option",
+            ));
+        }
+        (Some(field), None) if options.atomic => {
+            return Err(syn::Error::new_spanned(
+                &field.ident,
+                "`#[tracker::tracker_field]` can't be combined with the `atomic` option, the injected tracker field is atomic",
+            ));
+        }
+        (Some(field), None) if !options.tracker_attrs.is_empty() => {
+            return Err(syn::Error::new_spanned(
+                &field.ident,
+                "`#[tracker::tracker_field]` can't be combined with the `tracker_attrs` option, add the attributes to the field instead",
+            ));
+        }
+        (Some(field), None) if options.tracker_vis.is_some() => {
+            return Err(syn::Error::new_spanned(
+                &field.ident,
+                "`#[tracker::tracker_field]` can't be combined with the `tracker_vis` option, the declared field keeps its visibility",
+            ));
+        }
+        (Some(field), None) => field.ident.clone().unwrap(),
+        (None, Some(field)) => field.clone(),
+        (None, None) => Ident::new("tracker", Span2::call_site()),
+    };
+    let collision = named_fields
+        .named
+        .iter()
+        .find(|field| field.ident.as_ref() == Some(&tracker_field));
+    if let (None, Some(field)) = (&declared_tracker, collision) {
+        let message = if options.field.is_some() {
+            format!("`{tracker_field}` is already a field of `{}`, choose another name for the tracker field", data.ident)
+        } else {
+            format!("`tracker` is already a field of `{}`, rename the tracker field with `#[tracker::track(field = \"...\")]`", data.ident)
+        };
+        return Err(syn::Error::new_spanned(&field.ident, message));
+    }
+
+    let bits_ty = match &declared_tracker {
+        Some(field) => declared_tracker_type(field, bit_count, &options)?,
+        None => match &options.ty {
+            Some(ty) => checked_tracker_type(ty, bit_count)?,
+            None => tracker_type(bit_count),
+        },
+    };
+    let atomic_ty = if options.atomic {
+        Some(atomic_type(&bits_ty).ok_or_else(|| {
+            syn::Error::new_spanned(
+                options
+                    .ty
+                    .as_ref()
+                    .map_or(data.ident.to_token_stream(), ToTokens::to_token_stream),
+                "`atomic` can track up to 64 fields because there's no atomic `u128`",
+            )
+        })?)
+    } else {
+        None
+    };
+    let remote_ty = if options.remote {
+        Some(atomic_type(&bits_ty).ok_or_else(|| {
+            syn::Error::new_spanned(
+                options
+                    .ty
+                    .as_ref()
+                    .map_or(data.ident.to_token_stream(), ToTokens::to_token_stream),
+                "`remote` can track up to 64 fields because there's no atomic `u128`",
+            )
+        })?)
+    } else {
+        None
+    };
+    let tracker_ty = if options.typed_mask {
+        typed_mask::ident(&data.ident, &options).to_token_stream()
+    } else {
+        bits_ty.clone()
+    };
+    let mut ident = data.ident.clone();
+    ident.set_span(call_site_at(ident.span()));
+    let ctx = Context {
+        ident,
+        attrs: data.attrs.clone(),
+        vis: options.vis.clone().unwrap_or_else(|| data.vis.clone()),
+        declared_vis: data.vis.clone(),
+        generics,
+        generic_idents,
+        where_clause: data.generics.where_clause.clone(),
+        tracker_ty,
+        bits_ty,
+        atomic_ty,
+        remote_ty,
+        tracker_field,
+        fields,
+        declared_fields,
+        redacted,
+        defaults,
+        options,
+    };
+    traits::check_derives(&ctx)?;
+    names::check_collisions(&ctx)?;
+
+    let mut tracker_attrs: Vec<Attribute> = Vec::new();
+    if ctx.options.serde_state {
+        tracker_attrs.push(serde_state::attribute(&ctx));
+    } else if ctx.options.serde {
+        tracker_attrs.push(parse_quote! { #[serde(skip)] });
+    }
+    for meta in &ctx.options.tracker_attrs {
+        tracker_attrs.push(parse_quote! { #[#meta] });
+    }
+    let tracker_vis = ctx
+        .options
+        .tracker_vis
+        .clone()
+        .unwrap_or(Visibility::Inherited);
+    let mut injected_fields = Vec::new();
+    if declared_tracker.is_some() {
+        // The declared field stays in place so the layout of the struct is kept.
+        let tracker = named_fields
+            .named
+            .iter_mut()
+            .find(|field| field.ident.as_ref() == Some(&ctx.tracker_field))
+            .unwrap();
+        tracker.attrs.extend(tracker_attrs);
+    } else {
+        injected_fields.push(Field {
+            attrs: tracker_attrs,
+            vis: tracker_vis.clone(),
+            mutability: syn::FieldMutability::None,
+            ident: Some(ctx.tracker_field.clone()),
+            colon_token: None,
+            ty: Type::Verbatim(ctx.tracker_field_ty()),
+        });
+    }
+    for mut field in ctx.extra_fields() {
+        field.vis = tracker_vis.clone();
+        if ctx.options.serde || ctx.options.serde_state {
+            field.attrs.push(parse_quote! { #[serde(skip)] });
+        }
+        injected_fields.push(field);
+    }
+    named_fields.named.extend(injected_fields);
+
+    let mut output = if derive {
+        TokenStream2::new()
+    } else {
+        data.to_token_stream()
+    };
+    output.extend(impl_methods(&ctx));
+    if !ctx.options.skips("update_all") {
+        output.extend(proxy::generate(&ctx));
+    }
+    output.extend(field_mask::generate(&ctx));
+    if !ctx.options.skips("visit_changed") {
+        output.extend(visitor::generate(&ctx));
+    }
+    output.extend(fields_mut::generate(&ctx));
+    if !ctx.options.no_delta {
+        output.extend(delta::generate(&ctx));
+    }
+    if !ctx.options.no_update {
+        output.extend(update::generate(&ctx));
+    }
+    if ctx.options.snapshot {
+        output.extend(snapshot::generate(&ctx));
+    }
+    if ctx.options.previous {
+        output.extend(previous::generate(&ctx));
+    }
+    if ctx.options.staged {
+        output.extend(staged::generate(&ctx));
+    }
+    if let Some(remote_ty) = &ctx.remote_ty {
+        output.extend(remote::generate(&ctx, remote_ty));
+    }
+    if ctx.options.versioned {
+        output.extend(versioned::generate(&ctx));
+    }
+    if ctx.options.listeners {
+        output.extend(listeners::generate(&ctx));
+    }
+    if ctx.options.stream {
+        output.extend(stream::generate(&ctx));
+    }
+    if ctx.options.history.is_some() {
+        output.extend(history::generate(&ctx));
+    }
+    if ctx.options.counters {
+        output.extend(counters::generate(&ctx));
+    }
+    if ctx.options.timestamps.is_some() {
+        output.extend(timestamps::generate(&ctx));
+    }
+    if ctx.options.ordered {
+        output.extend(ordered::generate(&ctx));
+    }
+    if ctx.options.persistent {
+        output.extend(persistent::generate(&ctx));
+    }
+    if ctx.options.double_buffer {
+        output.extend(double_buffer::generate(&ctx));
+    }
+    if let Some(count) = ctx.options.consumers {
+        output.extend(consumers::generate(&ctx, count));
+    }
+    if ctx.options.data_struct {
+        output.extend(data_struct::generate(&ctx)?);
+    }
+    if let Some(span) = ctx.options.transparent {
+        output.extend(transparent::generate(&ctx, span)?);
+    }
+    if ctx.options.dynamic {
+        output.extend(dynamic::generate(&ctx)?);
+    }
+    if ctx.options.commands {
+        output.extend(commands::generate(&ctx)?);
+    }
+    if ctx.options.merge {
+        output.extend(merge::generate(&ctx));
+    }
+    if let Some(span) = ctx.options.debug_unconsumed {
+        output.extend(unconsumed::generate(&ctx, span));
+    }
+    if ctx.options.json_patch {
+        output.extend(json_patch::generate(&ctx)?);
+    }
+    if ctx.options.sql {
+        output.extend(sql::generate(&ctx));
+    }
+    if ctx.options.changed_view {
+        output.extend(changed_view::generate(&ctx));
+    }
+    if let Some(table) = &ctx.options.diesel {
+        output.extend(diesel::generate(&ctx, table));
+    }
+    if ctx.options.apply_json {
+        output.extend(apply_json::generate(&ctx));
+    }
+    if ctx.options.serde_state {
+        output.extend(serde_state::generate(&ctx));
+    }
+    if ctx.options.project {
+        output.extend(project::generate(&ctx));
+    }
+    if ctx.options.typed_mask {
+        output.extend(typed_mask::generate(&ctx));
+    }
+    if ctx.options.debug_values {
+        output.extend(debug_values::generate(&ctx));
+    }
+    if let Some(trait_ident) = &ctx.options.companion_trait {
+        output.extend(companion::generate(&ctx, trait_ident));
+    }
+    if ctx.options.constructor {
+        output.extend(constructor::generate(&ctx));
+    }
+    output.extend(traits::generate(&ctx));
+    Ok(output)
+}
+
+/// Implements tracker methods for structs that declare their own tracker field.
+///
+/// Options are passed with `#[tracker(...)]` on the struct.
+#[proc_macro_derive(Track, attributes(tracker, no_eq, do_not_track, tracker_field))]
+pub fn derive_track(item: TokenStream) -> TokenStream {
+    let item: Item = parse_macro_input!(item);
+    let mut data = match item {
+        Item::Struct(data) => data,
+        Item::Enum(data) => {
+            return syn::Error::new_spanned(
+                &data.ident,
+                "`#[derive(Track)]` only supports structs, use `#[tracker::track]` for enums",
+            )
+            .into_compile_error()
+            .into();
+        }
+        Item::Union(data) => {
+            return syn::Error::new_spanned(
+                &data.ident,
+                format!(
+                    "`{}` is a union, only structs and enums can be tracked",
+                    data.ident
+                ),
+            )
+            .into_compile_error()
+            .into();
+        }
+        item => {
+            return syn::Error::new_spanned(item, "expected a struct")
+                .into_compile_error()
+                .into();
+        }
+    };
+
+    let mut options = Options::default();
+    let result = data
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("tracker"))
+        .try_for_each(|attr| attr.parse_nested_meta(|meta| options.parse_meta(meta)))
+        .and_then(|()| expand(&mut data, options, true));
+    match result {
+        Ok(output) => output.into(),
+        Err(err) => err.into_compile_error().into(),
+    }
+}
+
+/// Return an error for options that modify the struct, which `#[derive(Track)]` can't do.
+fn check_derive_options(ident: &Ident, options: &Options) -> syn::Result<()> {
+    for (name, enabled) in [
+        ("encapsulate", options.encapsulate),
+        ("previous", options.previous),
+        ("versioned", options.versioned),
+        ("serde", options.serde),
+        ("serde_state", options.serde_state),
+        ("atomic", options.atomic),
+        ("listeners", options.listeners),
+        ("stream", options.stream),
+        ("history", options.history.is_some()),
+        ("counters", options.counters),
+        ("timestamps", options.timestamps.is_some()),
+        ("ordered", options.ordered),
+        ("persistent", options.persistent),
+        ("double_buffer", options.double_buffer),
+        ("consumers", options.consumers.is_some()),
+        ("staged", options.staged),
+        ("remote", options.remote),
+        ("tracker_attrs", !options.tracker_attrs.is_empty()),
+        ("tracker_vis", options.tracker_vis.is_some()),
+    ] {
+        if enabled {
+            return Err(syn::Error::new_spanned(
+                ident,
+                format!("`{name}` can't be used with `#[derive(Track)]` because it modifies the struct, use `#[tracker::track]` instead"),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Generate the inherent impl block with all tracker methods.
+fn impl_methods(ctx: &Context) -> TokenStream2 {
+    let Context {
+        ident,
+        vis: struct_vis,
+        generics,
+        generic_idents,
+        where_clause,
+        tracker_ty,
+        bits_ty,
+        tracker_field,
+        options,
+        ..
+    } = ctx;
+
+    let mut methods = TokenStream2::new();
+    let mut diff_checks = TokenStream2::new();
+    let mut sync_fields = TokenStream2::new();
+    let mut apply_changes = TokenStream2::new();
+    for field in &ctx.fields {
+        let TrackedField {
+            ident: id,
+            ty,
+            vis,
+            cfg,
+            span: id_span,
+            ..
+        } = field;
+        let mask_id = field.mask_ident();
+        let id_span = *id_span;
+        let bits = field.bits();
+        let field_mask = ctx.mask_from_bits(quote! { #bits });
+
+        let get_id = field.method_ident("get_");
+        let get_mut_id = field.method_ident("get_mut_");
+        let update_id = field.method_ident("update_");
+        let changed_id = field.method_ident("changed_");
+        let set_id = field.method_ident("set_");
+        let get_vis = field.method_vis("get");
+        let get_mut_vis = field.method_vis("get_mut");
+        let update_vis = field.method_vis("update");
+        let changed_vis = field.method_vis("changed_field");
+
+        let get_doc = ctx.accessor_doc(
+            field,
+            format!("Get an immutable reference to the {id} field."),
+        );
+        let get_mut_doc = ctx.accessor_doc(
+            field,
+            format!("Get a mutable reference to the {id} field and mark the field as changed."),
+        );
+        let update_doc = ctx.accessor_doc(
+            field,
+            format!("Use a closure to update the {id} field and mark the field as changed."),
+        );
+        let changed_doc =
+            ctx.accessor_doc(field, format!("Check if value of {id} field has changed."));
+        let bit_mask_doc = ctx.field_doc(format!(
+            "Get a bit mask to look for changes on the {id} field."
+        ));
+
+        let this = quote! { self };
+        let before_change = ctx.before_change(&this, field, None);
+        let mark = ctx.mark(&this, quote_spanned! { id_span => Self::#mask_id() });
+        // Nested structs track changes made through references themselves.
+        let (mut_before_change, mut_mark, trace_get_mut, trace_update) =
+            if field.nested == Some(Nested::Direct) {
+                Default::default()
+            } else {
+                (
+                    before_change.clone(),
+                    mark.clone(),
+                    ctx.trace_change(&this, field, "get_mut"),
+                    ctx.trace_change(&this, field, "update"),
+                )
+            };
+        let trace_set = ctx.trace_change(&this, field, "set");
+        let track_caller = ctx.track_caller();
+        let trace_sync = ctx.trace_change(&this, field, "sync_from");
+
+        if options.apply_changes {
+            apply_changes.extend(quote_spanned! { id_span =>
+                #(#cfg)*
+                if ::tracker::Track::changed(other, Self::#mask_id()) {
+                    #before_change
+                    self.#id = ::core::clone::Clone::clone(&other.#id);
+                    #mark
+                }
+            });
+        }
+
+        if !ctx.skips_method(field, "get") {
+            methods.extend(quote_spanned! { id_span =>
+                #(#cfg)*
+                #[must_use]
+                #[inline]
+                #get_doc
+                #get_vis fn #get_id(&self) -> &#ty {
+                    &self.#id
+                }
+            });
+        }
+        if !ctx.skips_method(field, "get_mut") {
+            methods.extend(quote_spanned! { id_span =>
+                #(#cfg)*
+                #[must_use]
+                #[inline]
+                #track_caller
+                #get_mut_doc
+                #get_mut_vis fn #get_mut_id(&mut self) -> &mut #ty {
+                    #mut_before_change
+                    #trace_get_mut
+                    #mut_mark
+                    &mut self.#id
+                }
+            });
+        }
+        if !ctx.skips_method(field, "update") {
+            methods.extend(quote_spanned! { id_span =>
+                #(#cfg)*
+                #[inline]
+                #track_caller
+                #update_doc
+                #update_vis fn #update_id<F: FnOnce(&mut #ty)>(&mut self, f: F) {
+                    #mut_before_change
+                    #trace_update
+                    #mut_mark
+                    f(&mut self.#id);
+                }
+            });
+        }
+        if !options.skips("changed_field") {
+            methods.extend(quote_spanned! { id_span =>
+                #(#cfg)*
+                #[inline]
+                #changed_doc
+                #changed_vis fn #changed_id(&self) -> bool {
+                    ::tracker::Track::changed(self, Self::#mask_id())
+                }
+            });
+        }
+
+        methods.extend(quote_spanned! { id_span =>
+            #(#cfg)*
+            #[must_use]
+            #[inline]
+            #bit_mask_doc
+            #vis fn #mask_id() -> #tracker_ty {
+                #field_mask
+            }
+        });
+
+        // The delta struct is applied with the setters, so they are kept private if skipped.
+        let set_vis = if !ctx.skips_method(field, "set") {
+            let vis = field.method_vis("set");
+            Some(quote! { #vis })
+        } else if !options.no_delta {
+            Some(TokenStream2::new())
+        } else {
+            None
+        };
+        for (no_eq, cfg) in field.no_eq_variants() {
+            let mut set_bound = (!no_eq).then(|| field.eq_bound(ctx));
+            let ne = field.ne(quote! { self.#id }, quote! { value });
+            let (set_doc, set_body) = if no_eq {
+                (
+                    format!("Set the value of field {id} and mark the field as changed."),
+                    quote_spanned! { id_span =>
+                        #before_change
+                        #trace_set
+                        self.#id = value;
+                        #mark
+                    },
+                )
+            } else if let Some(mode) = field.hash_eq {
+                let bound_ty = ctx.bound_ty(ty, "'__tracker");
+                let hash_bound = quote_spanned! { call_site_at(ty.span()) =>
+                    for<'__tracker> #bound_ty: ::core::hash::Hash,
+                };
+                let (set_doc, bound) = match mode {
+                    HashEq::Compare => (
+                        format!("Set the value of field {id} and mark the field as changed if it's not equal to the previous value.\n\nThe values are only compared if their hashes are equal."),
+                        quote! { #hash_bound #set_bound },
+                    ),
+                    HashEq::HashOnly => (
+                        format!("Set the value of field {id} and mark the field as changed if its hash is not equal to the hash of the previous value."),
+                        hash_bound,
+                    ),
+                };
+                set_bound = Some(bound);
+                (
+                    set_doc,
+                    hash_eq::set_body(ctx, field, &before_change, &trace_set, &mark),
+                )
+            } else if ctx.uses_shared_setter(field) {
+                (
+                    format!("Set the value of field {id} and mark the field as changed if it's not equal to the previous value."),
+                    quote_spanned! { id_span =>
+                        ::tracker::__private::set(&mut self.#id, value, &mut self.#tracker_field, Self::#mask_id());
+                    },
+                )
+            } else {
+                (
+                    format!("Set the value of field {id} and mark the field as changed if it's not equal to the previous value."),
+                    quote_spanned! { id_span =>
+                        // The field is only marked after it was set,
+                        // in case a callback of the `on_change` or `listeners` options panics.
+                        let changed = #ne;
+                        if changed {
+                            #before_change
+                            #trace_set
+                        }
+                        self.#id = value;
+                        if changed {
+                            #mark
+                        }
+                    },
+                )
+            };
+            let set_doc = ctx.accessor_doc(field, set_doc);
+            if let Some(set_vis) = &set_vis {
+                methods.extend(quote_spanned! { id_span =>
+                    #(#cfg)*
+                    #[inline]
+                    #track_caller
+                    #set_doc
+                    #set_vis fn #set_id(&mut self, value: #ty)
+                    where
+                        #set_bound
+                    {
+                        #set_body
+                    }
+                });
+            }
+            let sync_ne = if no_eq {
+                quote! { true }
+            } else {
+                field.ne(quote! { self.#id }, quote! { new.#id })
+            };
+            sync_fields.extend(quote_spanned! { id_span =>
+                #(#cfg)*
+                if #sync_ne {
+                    #before_change
+                    #trace_sync
+                    ::core::mem::swap(&mut self.#id, &mut new.#id);
+                    mask |= Self::#mask_id();
+                }
+            });
+            if !no_eq {
+                let ne = field.ne(quote! { self.#id }, quote! { other.#id });
+                diff_checks.extend(quote_spanned! { id_span =>
+                    #(#cfg)*
+                    if #ne {
+                        mask |= Self::#mask_id();
+                    }
+                });
+            }
         }
     }
 
-    let mut field_list = Vec::new();
-    if let Fields::Named(named_fields) = &mut data.fields {
-        for field in &mut named_fields.named {
-            let (do_not_track, no_eq) = parse_field_attrs(&mut field.attrs);
-            if !do_not_track {
-                let ident = field.ident.clone().expect("Field has no identifier");
-                let ty: Type = field.ty.clone();
-                field_list.push((ident, ty, no_eq, field.vis.clone()));
+    let apply_changes_method = if options.apply_changes {
+        quote! {
+            /// Copy all fields that are marked as changed in `other`
+            /// and mark them as changed in `self` as well.
+            ///
+            /// Fields that are unchanged in `other` are left untouched.
+            #[allow(dead_code, unused_variables)]
+            #struct_vis fn apply_changes_from(&mut self, other: &Self) {
+                #apply_changes
             }
         }
-
-        tracker_ty = tracker_type(field_list.len());
-        let change_field = Field {
-            attrs: Vec::new(),
-            vis: syn::Visibility::Inherited,
-            mutability: syn::FieldMutability::None,
-            ident: Some(Ident::new("tracker", Span::call_site().into())),
-            colon_token: None,
-            ty: Type::Verbatim(tracker_ty.clone()),
-        };
-
-        named_fields.named.push(change_field);
     } else {
-        panic!("No named fields");
-    }
-
-    let mut output = data.to_token_stream();
+        TokenStream2::new()
+    };
 
-    let mut methods = proc_macro2::TokenStream::new();
-    for (num, (id, ty, no_eq, vis)) in field_list.iter().enumerate() {
-        let id_span: Span2 = id.span().unwrap().into();
-
-        let get_id = Ident::new(&format!("get_{}", id), id_span);
-        let get_mut_id = Ident::new(&format!("get_mut_{}", id), id_span);
-        let update_id = Ident::new(&format!("update_{}", id), id_span);
-        let changed_id = Ident::new(&format!("changed_{}", id), id_span);
-        let set_id = Ident::new(&format!("set_{}", id), id_span);
-
-        let get_doc = format!("Get an immutable reference to the {id} field.");
-        let get_mut_doc =
-            format!("Get a mutable reference to the {id} field and mark the field as changed.");
-        let update_doc =
-            format!("Use a closure to update the {id} field and mark the field as changed.");
-        let changed_doc =
-            format!("Check if value of {id} field has changed.");
-        let bit_mask_doc = format!("Get a bit mask to look for changes on the {id} field.");
+    let before_reset = ctx.options.double_buffer.then(|| double_buffer::store(ctx));
+    let on_reset = ctx.on_reset();
+    let clear_tracker = ctx.clear_tracker(&quote! { self });
+    let trace_reset = ctx.trace_tracker("tracker reset");
+    let trace_mark_all = ctx.trace_tracker("all fields marked as changed");
+    let track_caller = ctx.track_caller();
+    let eq_bounds = ctx.eq_bounds();
+    let all_bits = u128::MAX
+        .checked_shr(128 - ctx.bit_count() as u32)
+        .unwrap_or(0);
+    let all_bits = syn::LitInt::new(&format!("{all_bits}{bits_ty}"), ident.span());
+    let all_mask = ctx.mask_from_bits(quote! { #all_bits });
+    let field_names = ctx
+        .fields
+        .iter()
+        .map(|field| field.mask_ident().unraw().to_string());
+    let field_bits = ctx.fields.iter().map(TrackedField::bits);
+    let field_count = ctx.fields.len();
+    let index_mask = ctx.mask_from_bits(quote! { bits[index] });
+    let empty_mask = ctx.empty_mask();
+    // Structs without tracked fields never change.
+    let mark_all = if ctx.fields.is_empty() {
+        TokenStream2::new()
+    } else {
+        ctx.mark(&quote! { self }, all_mask.clone())
+    };
 
-        methods.extend(quote_spanned! { id_span =>
-            #[allow(dead_code, non_snake_case)]
+    let mut optional_methods = TokenStream2::new();
+    if !options.skips("update_all") {
+        optional_methods.extend(proxy::update_all_method(ctx));
+    }
+    optional_methods.extend(nested::indices_methods(ctx));
+    optional_methods.extend(indexed::methods(ctx));
+    optional_methods.extend(set_collection::methods(ctx));
+    optional_methods.extend(map_collection::methods(ctx));
+    optional_methods.extend(elements::methods(ctx));
+    if !options.skips("diff") {
+        optional_methods.extend(quote_spanned! { ident.span() =>
+            /// Compare all tracked fields with another instance and get
+            /// a bit mask of the fields that differ.
+            ///
+            /// Fields marked as `no_eq` can't be compared and are never
+            /// part of the returned mask.
+            #[allow(dead_code, unused_variables)]
             #[must_use]
-            #[doc = #get_doc]
-            #vis fn #get_id(&self) -> &#ty {
-                &self.#id
+            #struct_vis fn diff(&self, other: &Self) -> #tracker_ty
+            where
+                #eq_bounds
+            {
+                #[allow(unused_mut)]
+                let mut mask = #empty_mask;
+                #diff_checks
+                mask
             }
-
-            #[allow(dead_code, non_snake_case)]
-            #[must_use]
-            #[doc = #get_mut_doc]
-            #vis fn #get_mut_id(&mut self) -> &mut #ty {
-                self.tracker |= Self::#id();
-                &mut self.#id
+        });
+    }
+    if !options.skips("sync_from") {
+        for field in &ctx.declared_fields {
+            if ctx
+                .fields
+                .iter()
+                .all(|tracked| Some(&tracked.ident) != field.ident.as_ref())
+            {
+                let id = &field.ident;
+                let cfg = cfg_attrs(&field.attrs);
+                sync_fields.extend(quote_spanned! { field.span() =>
+                    #(#cfg)*
+                    ::core::mem::swap(&mut self.#id, &mut new.#id);
+                });
             }
-
-            #[allow(dead_code, non_snake_case)]
-            #[doc = #update_doc]
-            #vis fn #update_id<F: FnOnce(&mut #ty)>(&mut self, f: F) {
-                self.tracker |= Self::#id();
-                f(&mut self.#id);
+        }
+        let mark_synced = ctx.mark(&quote! { self }, quote! { mask });
+        optional_methods.extend(quote_spanned! { ident.span() =>
+            /// Move all fields from `new` into `self`, mark the tracked fields
+            /// whose values differ as changed and get a bit mask of them.
+            ///
+            /// Fields marked as `no_eq` are always marked, untracked fields
+            /// are moved without being marked.
+            #[allow(dead_code, unused_variables, unused_mut)]
+            #struct_vis fn sync_from(&mut self, mut new: Self) -> #tracker_ty
+            where
+                #eq_bounds
+            {
+                let mut mask = #empty_mask;
+                #sync_fields
+                if mask != #empty_mask {
+                    #mark_synced
+                }
+                mask
+            }
+        });
+    }
+    // The methods of the `Track` trait are forwarded by inherent methods,
+    // so they can be called without importing the trait.
+    if !options.trait_only {
+        optional_methods.extend(quote_spanned! { ident.span() =>
+            /// Check for changes made to this struct with a given bitmask.
+            ///
+            /// To receive the bitmask, simply call `Type::#field_name()`
+            /// or `Type::#track_all()`.
+            #[allow(dead_code)]
+            #[must_use]
+            #struct_vis fn changed(&self, mask: #tracker_ty) -> bool {
+                ::tracker::Track::changed(self, mask)
             }
 
-            #[allow(dead_code, non_snake_case)]
-            #[doc = #changed_doc]
-            #vis fn #changed_id(&self) -> bool {
-                self.changed(Self::#id())
+            /// Resets the tracker value of this struct to mark all fields
+            /// as unchanged again.
+            #[allow(dead_code)]
+            #struct_vis fn reset(&mut self) {
+                ::tracker::Track::reset(self);
             }
 
-            #[allow(dead_code, non_snake_case)]
-            #[must_use]
-            #[doc = #bit_mask_doc]
-            #vis fn #id() -> #tracker_ty {
-                1 << #num
+            /// Resets this struct and all nested structs, which `reset()` leaves untouched.
+            #[allow(dead_code)]
+            #struct_vis fn reset_all(&mut self) {
+                ::tracker::Track::reset_all(self);
             }
         });
-
-        if *no_eq {
-            let set_doc = format!("Set the value of field {id} and mark the field as changed.");
-            methods.extend(quote_spanned! { id_span =>
-                #[allow(dead_code, non_snake_case)]
-                #[doc = #set_doc]
-                #vis fn #set_id(&mut self, value: #ty) {
-                    self.tracker |= Self::#id();
-                    self.#id = value;
-                }
-            });
-        } else {
-            let set_doc = format!("Set the value of field {id} and mark the field as changed if it's not equal to the previous value.");
-            methods.extend(quote_spanned! { id_span =>
-                #[allow(dead_code, non_snake_case)]
-                #[doc = #set_doc]
-                #vis fn #set_id(&mut self, value: #ty) {
-                    if self.#id != value {
-                        self.tracker |= Self::#id();
-                    }
-                    self.#id = value;
-                }
-            });
-        }
     }
-
-    output.extend(quote_spanned! { ident.span() =>
-        impl #generics #ident < #generic_idents > #where_clause {
-            #methods
+    if !options.skips("track_all") && !options.trait_only {
+        optional_methods.extend(quote_spanned! { ident.span() =>
             #[allow(dead_code)]
             #[must_use]
             /// Get a bit mask to look for changes on all fields.
             #struct_vis fn track_all() -> #tracker_ty {
-                #tracker_ty::MAX
+                <Self as ::tracker::Track>::track_all()
             }
-
+        });
+    }
+    if options.atomic {
+        let mark = ctx.mark_tracker(&quote! { self }, quote! { mask });
+        optional_methods.extend(quote_spanned! { ident.span() =>
+            /// Mark the fields of the bit mask as changed through a shared reference.
+            #[allow(dead_code)]
+            #struct_vis fn mark_changed(&self, mask: #tracker_ty) {
+                #mark
+            }
+        });
+    }
+    if !options.skips("mark_all_changed") && !options.trait_only {
+        optional_methods.extend(quote_spanned! { ident.span() =>
             #[allow(dead_code)]
+            #track_caller
             /// Mark all fields of the struct as changed.
             #struct_vis fn mark_all_changed(&mut self) {
-                self.tracker = #tracker_ty::MAX;
+                ::tracker::Track::mark_all_changed(self);
             }
-
-            /// Check for changes made to this struct with a given bitmask.
-            ///
-            /// To receive the bitmask, simply call `Type::#field_name()`
-            /// or `Type::#track_all()`.
-            #[warn(dead_code)]
-            #[must_use]
-            #struct_vis fn changed(&self, mask: #tracker_ty) -> bool {
-                self.tracker & mask != 0
+        });
+    }
+    let nested_checks = nested::changed_checks(ctx);
+    let reset_all = nested::reset_all(ctx);
+    let tracker_value = ctx.tracker_value(&quote! { self });
+    let changed = if nested_checks.is_empty() {
+        quote! { #tracker_value & mask != #empty_mask }
+    } else {
+        quote! {
+            if #tracker_value & mask != #empty_mask {
+                return true;
             }
+            #nested_checks
+            false
+        }
+    };
 
+    let changed_any = if ctx.fields.is_empty() {
+        quote! { false }
+    } else {
+        quote! { ::tracker::Track::changed(self, #all_mask) }
+    };
+    if !options.skips("changed_any") && !options.trait_only {
+        optional_methods.extend(quote_spanned! { ident.span() =>
             /// Check for any changes made to this struct.
             #[allow(dead_code)]
             #[must_use]
             #struct_vis fn changed_any(&self) -> bool {
-                self.tracker != 0
+                ::tracker::Track::changed_any(self)
+            }
+        });
+    }
+    if !options.skips("on_changed") {
+        optional_methods.extend(quote_spanned! { ident.span() =>
+            /// Call `f` if any field of the bitmask has changed.
+            ///
+            /// Returns `self` so multiple calls can be chained like
+            /// `value.on_changed(Type::a(), |v| ...).on_changed(Type::b(), |v| ...)`.
+            #[allow(dead_code)]
+            #struct_vis fn on_changed<F: ::core::ops::FnOnce(&Self)>(&self, mask: #tracker_ty, f: F) -> &Self {
+                if ::tracker::Track::changed(self, mask) {
+                    f(self);
+                }
+                self
             }
 
-            /// Resets the tracker value of this struct to mark all fields
-            /// as unchanged again.
-            #[warn(dead_code)]
-            #struct_vis fn reset(&mut self) {
-                self.tracker = 0;
+            /// Call `f` with mutable access if any field of the bitmask has changed.
+            ///
+            /// Returns `self` so multiple calls can be chained, see [`Self::on_changed`].
+            #[allow(dead_code)]
+            #struct_vis fn on_changed_mut<F: ::core::ops::FnOnce(&mut Self)>(
+                &mut self,
+                mask: #tracker_ty,
+                f: F,
+            ) -> &mut Self {
+                if ::tracker::Track::changed(self, mask) {
+                    f(self);
+                }
+                self
+            }
+        });
+    }
+    if !options.skips("track_scope") {
+        optional_methods.extend(quote_spanned! { ident.span() =>
+            /// Get a guard that dereferences to this struct and
+            /// resets the tracker when it's dropped.
+            ///
+            /// The tracker is also reset on early returns and while unwinding.
+            #[allow(dead_code)]
+            #[must_use]
+            #struct_vis fn track_scope(&mut self) -> ::tracker::TrackGuard<'_, Self> {
+                ::tracker::TrackGuard::new(self, <Self as ::tracker::Track>::reset)
             }
+        });
+    }
+
+    let allow_missing_docs = ctx.allow_missing_docs();
+    quote_spanned! { ident.span() =>
+        #allow_missing_docs
+        // Allowed once for all per-field methods to keep the expansion short.
+        #[allow(dead_code, non_snake_case)]
+        impl #generics #ident < #generic_idents > #where_clause {
+            #methods
+            #optional_methods
+            #apply_changes_method
         }
-    });
 
-    output.into()
+        impl #generics ::tracker::Track for #ident < #generic_idents > #where_clause {
+            type Mask = #tracker_ty;
+
+            fn changed(&self, mask: #tracker_ty) -> bool {
+                #changed
+            }
+
+            fn changed_any(&self) -> bool {
+                #changed_any
+            }
+
+            fn reset(&mut self) {
+                #trace_reset
+                #before_reset
+                #clear_tracker
+                #on_reset
+            }
+
+            #reset_all
+
+            #track_caller
+            fn mark_all_changed(&mut self) {
+                #trace_mark_all
+                #mark_all
+            }
+
+            fn track_all() -> #tracker_ty {
+                #all_mask
+            }
+
+            fn field_names() -> &'static [&'static str] {
+                &[#(#field_names),*]
+            }
+
+            fn field_mask(name: &str) -> ::core::option::Option<#tracker_ty> {
+                let bits: [#bits_ty; #field_count] = [#(#field_bits),*];
+                let index = <Self as ::tracker::Track>::field_names()
+                    .iter()
+                    .position(|field| *field == name)?;
+                ::core::option::Option::Some(#index_mask)
+            }
+        }
+    }
+}
+
+/// Get the `#[cfg(...)]` attributes of a field.
+pub(crate) fn cfg_attrs(attrs: &[Attribute]) -> Vec<Attribute> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("cfg"))
+        .cloned()
+        .collect()
+}
+
+/// Get the generics for impl blocks without default values
+/// (like `<Type=DefaultType>`) and the generic arguments (like `'a, T`).
+pub(crate) fn impl_generics(generics: &Generics) -> (Generics, TokenStream2) {
+    let mut impl_generics = generics.clone();
+    for param in impl_generics.params.iter_mut() {
+        match param {
+            GenericParam::Type(ty) => {
+                ty.eq_token = None;
+                ty.default = None;
+            }
+            GenericParam::Const(cnst) => {
+                cnst.eq_token = None;
+                cnst.default = None;
+            }
+            GenericParam::Lifetime(_) => {}
+        }
+    }
+
+    let mut generics_iter = generics.params.iter();
+    let mut generic_idents = TokenStream2::new();
+
+    if let Some(first) = generics_iter.next() {
+        impl_struct_generics(first, &mut generic_idents);
+        for generic_param in generics_iter {
+            generic_idents.extend(quote! {,});
+            impl_struct_generics(generic_param, &mut generic_idents);
+        }
+    }
+
+    (impl_generics, generic_idents)
 }
 
 fn impl_struct_generics(param: &GenericParam, stream: &mut TokenStream2) {
     match param {
         GenericParam::Type(ty) => ty.ident.to_tokens(stream),
-        GenericParam::Const(cnst) => cnst.to_tokens(stream),
-        GenericParam::Lifetime(lifetime) => lifetime.to_tokens(stream),
-    }
-}
-
-/// Look for no_eq and do_not_track attributes and remove
-/// them from the tokens.
-fn parse_field_attrs(attrs: &mut Vec<Attribute>) -> (bool, bool) {
-    let mut do_not_track = false;
-    let mut no_eq = false;
-    let attrs_clone = attrs.clone();
-
-    for (index, attr) in attrs_clone.iter().enumerate() {
-        let segs = &attr.path().segments;
-        match segs.len() {
-            1 => {
-                let first = &segs.first().unwrap().ident;
-                if first == NO_EQ {
-                    attrs.remove(index);
-                    no_eq = true;
-                } else if first == DO_NOT_TRACK {
-                    attrs.remove(index);
-                    do_not_track = true;
-                }
+        GenericParam::Const(cnst) => cnst.ident.to_tokens(stream),
+        // Bounds like `'b: 'a` are only part of the impl generics.
+        GenericParam::Lifetime(lifetime) => lifetime.lifetime.to_tokens(stream),
+    }
+}
+
+/// Options of a field set with attributes like `#[tracker::no_eq]` or `#[tracker(no_eq)]`.
+#[derive(Default)]
+struct FieldAttrs {
+    do_not_track: bool,
+    no_eq: bool,
+    /// The field is compared even if `no_eq` is set for the struct, set with `#[tracker::eq]`.
+    eq: bool,
+    /// Condition of a `no_eq` set with `#[cfg_attr(condition, tracker::no_eq)]`.
+    no_eq_cfg: Option<TokenStream2>,
+    /// Function used instead of `PartialEq`, set with `#[tracker::compare_with = "..."]`.
+    compare_with: Option<Path>,
+    /// Column name set with `#[tracker::column = "..."]`.
+    column: Option<String>,
+    /// The field is used as tracker, set with `#[tracker::tracker_field]`.
+    tracker_field: bool,
+    /// Visibility of single methods set with `#[tracker::vis(...)]`.
+    method_vis: Vec<(String, Visibility)>,
+    /// Methods skipped with attributes like `#[tracker::no_set]`.
+    skipped_methods: Vec<String>,
+    /// Name used in the generated methods, set with `#[tracker::rename = "..."]`.
+    rename: Option<String>,
+    /// The field is a tracked struct, set with `#[tracker::nested]`.
+    nested: bool,
+    /// Generate setters and getters for single elements, set with `#[tracker::indexed]`.
+    indexed: bool,
+    /// Generate methods that insert and remove elements, set with `#[tracker::set_collection]`.
+    set_collection: bool,
+    /// Generate methods that insert and remove entries, set with `#[tracker::map_collection]`.
+    map_collection: bool,
+    /// Compare a cached hash in the setter, set with `#[tracker::hash_eq]`.
+    hash_eq: Option<HashEq>,
+    /// Track the elements of an array separately, set with `#[tracker::elements]`.
+    elements: bool,
+    /// Hide the value in generated output, set with `#[tracker::redact]`.
+    redact: bool,
+    /// Value used by `new()` and the `impl_default` option, set with `#[tracker::default = ...]`.
+    default: Option<Expr>,
+}
+
+impl FieldAttrs {
+    /// Return an error spanned at `last`, the attribute that was parsed last,
+    /// if it contradicts an attribute that was parsed before.
+    fn check_conflicts(&self, last: &impl ToTokens) -> syn::Result<()> {
+        if self.do_not_track && (self.no_eq || self.no_eq_cfg.is_some()) {
+            return Err(syn::Error::new_spanned(
+                last,
+                "`no_eq` and `do_not_track` can't be combined, fields that aren't tracked are never compared",
+            ));
+        }
+        if self.do_not_track && self.nested {
+            return Err(syn::Error::new_spanned(
+                last,
+                "`nested` and `do_not_track` can't be combined, changes of fields that aren't tracked are never checked",
+            ));
+        }
+        if self.do_not_track && self.elements {
+            return Err(syn::Error::new_spanned(
+                last,
+                "`elements` and `do_not_track` can't be combined, elements of fields that aren't tracked have no bits",
+            ));
+        }
+        if self.tracker_field && self.default.is_some() {
+            return Err(syn::Error::new_spanned(
+                last,
+                "`default` and `tracker_field` can't be combined, the tracker always starts without changes",
+            ));
+        }
+        if self.do_not_track && self.indexed {
+            return Err(syn::Error::new_spanned(
+                last,
+                "`indexed` and `do_not_track` can't be combined, no methods are generated for fields that aren't tracked",
+            ));
+        }
+        if self.do_not_track && self.set_collection {
+            return Err(syn::Error::new_spanned(
+                last,
+                "`set_collection` and `do_not_track` can't be combined, no methods are generated for fields that aren't tracked",
+            ));
+        }
+        if self.hash_eq.is_some() && (self.no_eq || self.no_eq_cfg.is_some()) {
+            return Err(syn::Error::new_spanned(
+                last,
+                "`hash_eq` and `no_eq` can't be combined, fields that aren't compared don't need a hash",
+            ));
+        }
+        if self.hash_eq.is_some() && (self.do_not_track || self.nested) {
+            return Err(syn::Error::new_spanned(
+                last,
+                "`hash_eq` can only be used for tracked fields that aren't `nested`, because the hash is only updated by the generated methods",
+            ));
+        }
+        if self.do_not_track && self.map_collection {
+            return Err(syn::Error::new_spanned(
+                last,
+                "`map_collection` and `do_not_track` can't be combined, no methods are generated for fields that aren't tracked",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Get the method skipped by an attribute like `no_set`, see [`SKIP_ATTRS`].
+fn skipped_method(attr: &str) -> Option<&'static str> {
+    SKIP_ATTRS
+        .iter()
+        .find(|(name, _)| *name == attr)
+        .map(|(_, method)| *method)
+}
+
+/// Get the name of a tracker attribute like `no_eq` or `tracker::no_eq`.
+///
+/// With `strict`, set by the `strict_attrs` option, only the namespaced forms are recognized.
+fn tracker_attr_name(path: &Path, strict: bool) -> Option<String> {
+    let segs = &path.segments;
+    match segs.len() {
+        1 if !strict => {
+            let first = &segs.first().unwrap().ident;
+            (first == NO_EQ || first == DO_NOT_TRACK || first == TRACKER_FIELD)
+                .then(|| first.to_string())
+        }
+        2 => {
+            let mut iter = segs.iter();
+            let first = &iter.next().unwrap().ident;
+            let second = &iter.next().unwrap().ident;
+            (first == "tracker"
+                && (second == NO_EQ
+                    || second == EQ
+                    || second == DO_NOT_TRACK
+                    || second == COLUMN
+                    || second == COMPARE_WITH
+                    || second == RENAME
+                    || second == TRACKER_FIELD
+                    || second == VIS
+                    || second == NESTED
+                    || second == INDEXED
+                    || second == SET_COLLECTION
+                    || second == MAP_COLLECTION
+                    || second == HASH_EQ
+                    || second == ELEMENTS
+                    || second == REDACT
+                    || second == DEFAULT
+                    || skipped_method(&second.to_string()).is_some()))
+            .then(|| second.to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Look for tracker attributes like no_eq and do_not_track
+/// and remove them from the tokens.
+///
+/// With `strict`, bare attributes like `#[no_eq]` are left for other macros.
+fn parse_field_attrs(attrs: &mut Vec<Attribute>, strict: bool) -> syn::Result<FieldAttrs> {
+    let mut field_attrs = FieldAttrs::default();
+    let mut remaining = Vec::with_capacity(attrs.len());
+
+    for attr in std::mem::take(attrs) {
+        if attr.path().is_ident("cfg_attr") {
+            remaining.extend(parse_cfg_attr(&attr, &mut field_attrs, strict)?);
+            field_attrs.check_conflicts(&attr)?;
+            continue;
+        }
+        if attr.path().is_ident("tracker") {
+            parse_tracker_attr(&attr, &mut field_attrs)?;
+            continue;
+        }
+        match tracker_attr_name(attr.path(), strict).as_deref() {
+            Some(NO_EQ) => field_attrs.no_eq = true,
+            Some(EQ) => field_attrs.eq = true,
+            Some(DO_NOT_TRACK) => field_attrs.do_not_track = true,
+            Some(COLUMN) => {
+                let value = &attr.meta.require_name_value()?.value;
+                let column: LitStr = syn::parse2(value.to_token_stream())?;
+                field_attrs.column = Some(column.value());
             }
-            2 => {
-                let mut iter = segs.iter();
-                let first = &iter.next().unwrap().ident;
-                if first == "tracker" {
-                    let second = &iter.next().unwrap().ident;
-                    if second == NO_EQ {
-                        attrs.remove(index);
-                        no_eq = true;
-                    } else if second == DO_NOT_TRACK {
-                        attrs.remove(index);
-                        do_not_track = true;
+            Some(COMPARE_WITH) => {
+                let value = &attr.meta.require_name_value()?.value;
+                let path: LitStr = syn::parse2(value.to_token_stream())?;
+                field_attrs.compare_with = Some(path.parse()?);
+            }
+            Some(RENAME) => {
+                let value = &attr.meta.require_name_value()?.value;
+                field_attrs.rename = Some(parse_rename(syn::parse2(value.to_token_stream())?)?);
+            }
+            Some(TRACKER_FIELD) => field_attrs.tracker_field = true,
+            Some(NESTED) => field_attrs.nested = true,
+            Some(INDEXED) => field_attrs.indexed = true,
+            Some(SET_COLLECTION) => field_attrs.set_collection = true,
+            Some(MAP_COLLECTION) => field_attrs.map_collection = true,
+            Some(HASH_EQ) => {
+                field_attrs.hash_eq = Some(match &attr.meta {
+                    Meta::NameValue(meta) => {
+                        HashEq::parse(&syn::parse2(meta.value.to_token_stream())?)?
                     }
+                    _ => {
+                        attr.meta.require_path_only()?;
+                        HashEq::Compare
+                    }
+                });
+            }
+            Some(ELEMENTS) => field_attrs.elements = true,
+            Some(REDACT) => field_attrs.redact = true,
+            Some(DEFAULT) => {
+                field_attrs.default = Some(attr.meta.require_name_value()?.value.clone());
+            }
+            Some(VIS) => {
+                attr.parse_nested_meta(|meta| parse_method_vis(meta, &mut field_attrs.method_vis))?
+            }
+            Some(name) if skipped_method(name).is_some() => {
+                attr.meta.require_path_only()?;
+                field_attrs
+                    .skipped_methods
+                    .extend(skipped_method(name).map(str::to_owned));
+            }
+            _ => {
+                remaining.push(attr);
+                continue;
+            }
+        }
+        field_attrs.check_conflicts(&attr)?;
+    }
+    *attrs = remaining;
+
+    Ok(field_attrs)
+}
+
+/// Parse the structured form `#[tracker(no_eq, column = "...")]`.
+fn parse_tracker_attr(attr: &Attribute, field_attrs: &mut FieldAttrs) -> syn::Result<()> {
+    let mut seen: Vec<String> = Vec::new();
+    attr.parse_nested_meta(|meta| {
+        let name = meta.path.require_ident()?.to_string();
+        if seen.contains(&name) {
+            return Err(meta.error(format!("duplicate field option `{name}`")));
+        }
+        match name.as_str() {
+            NO_EQ => field_attrs.no_eq = true,
+            EQ => field_attrs.eq = true,
+            DO_NOT_TRACK => field_attrs.do_not_track = true,
+            TRACKER_FIELD => field_attrs.tracker_field = true,
+            NESTED => field_attrs.nested = true,
+            INDEXED => field_attrs.indexed = true,
+            SET_COLLECTION => field_attrs.set_collection = true,
+            MAP_COLLECTION => field_attrs.map_collection = true,
+            HASH_EQ => {
+                field_attrs.hash_eq = Some(if meta.input.peek(Token![=]) {
+                    HashEq::parse(&meta.value()?.parse()?)?
+                } else {
+                    HashEq::Compare
+                });
+            }
+            ELEMENTS => field_attrs.elements = true,
+            REDACT => field_attrs.redact = true,
+            DEFAULT => field_attrs.default = Some(meta.value()?.parse()?),
+            COLUMN => {
+                let column: LitStr = meta.value()?.parse()?;
+                field_attrs.column = Some(column.value());
+            }
+            COMPARE_WITH => {
+                let path: LitStr = meta.value()?.parse()?;
+                field_attrs.compare_with = Some(path.parse()?);
+            }
+            RENAME => field_attrs.rename = Some(parse_rename(meta.value()?.parse()?)?),
+            VIS => {
+                meta.parse_nested_meta(|meta| parse_method_vis(meta, &mut field_attrs.method_vis))?
+            }
+            name if skipped_method(name).is_some() => {
+                field_attrs
+                    .skipped_methods
+                    .extend(skipped_method(name).map(str::to_owned));
+            }
+            _ => {
+                return Err(meta.error(format!(
+                    "unknown field option, expected one of {}",
+                    FIELD_OPTIONS.join(", ")
+                )));
+            }
+        }
+        seen.push(name);
+        field_attrs.check_conflicts(&meta.path)?;
+        options::require_end(&meta)
+    })
+}
+
+/// Parse the name of `#[tracker::rename = "..."]`, which needs to be a valid identifier.
+fn parse_rename(name: LitStr) -> syn::Result<String> {
+    match name.parse_with(Ident::parse_any) {
+        Ok(ident) => Ok(ident.unraw().to_string()),
+        Err(_) => Err(syn::Error::new_spanned(
+            &name,
+            format!("`{}` can't be used in method names", name.value()),
+        )),
+    }
+}
+
+/// Parse one entry of `vis(get = "pub", set = "pub(crate)")`.
+fn parse_method_vis(
+    meta: ParseNestedMeta<'_>,
+    method_vis: &mut Vec<(String, Visibility)>,
+) -> syn::Result<()> {
+    let name = meta.path.require_ident()?.to_string();
+    if !FIELD_METHODS.contains(&name.as_str()) {
+        return Err(meta.error(format!(
+            "unknown method, expected one of {}",
+            FIELD_METHODS.join(", ")
+        )));
+    }
+    if method_vis.iter().any(|(other, _)| *other == name) {
+        return Err(meta.error(format!("the visibility of `{name}` is already set")));
+    }
+    let vis = options::parse_vis(meta.value()?)?;
+    method_vis.push((name, vis));
+    Ok(())
+}
+
+/// Add the condition of `#[cfg_attr(condition, tracker::no_eq)]` to the field.
+fn add_no_eq_cfg(field_attrs: &mut FieldAttrs, condition: &Meta) {
+    let condition = condition.to_token_stream();
+    field_attrs.no_eq_cfg = Some(match field_attrs.no_eq_cfg.take() {
+        Some(previous) => quote! { any(#previous, #condition) },
+        None => condition,
+    });
+}
+
+/// Look for tracker attributes inside of `#[cfg_attr(condition, ...)]`.
+///
+/// Only `no_eq` can be configured this way, the code that depends on it
+/// is generated for both cases and guarded by the condition.
+/// Returns the attribute without the tracker attributes, if anything is left.
+fn parse_cfg_attr(
+    attr: &Attribute,
+    field_attrs: &mut FieldAttrs,
+    strict: bool,
+) -> syn::Result<Option<Attribute>> {
+    let (condition, metas) = attr.parse_args_with(|input: ParseStream<'_>| {
+        let condition: Meta = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let metas = Punctuated::<Meta, Token![,]>::parse_terminated(input)?;
+        Ok((condition, metas))
+    })?;
+
+    let mut remaining = Punctuated::<Meta, Token![,]>::new();
+    for meta in metas {
+        if meta.path().is_ident("tracker") {
+            meta.require_list()?.parse_nested_meta(|nested| {
+                if nested.path.is_ident(NO_EQ) {
+                    add_no_eq_cfg(field_attrs, &condition);
+                    Ok(())
+                } else {
+                    Err(nested.error(format!(
+                        "`{}` can't be used in `cfg_attr`, only `no_eq` can be configured",
+                        nested.path.to_token_stream()
+                    )))
                 }
+            })?;
+            continue;
+        }
+        match tracker_attr_name(meta.path(), strict).as_deref() {
+            Some(NO_EQ) => add_no_eq_cfg(field_attrs, &condition),
+            Some(name) => {
+                return Err(syn::Error::new_spanned(
+                    meta,
+                    format!("`{name}` can't be used in `cfg_attr`, only `no_eq` can be configured"),
+                ));
             }
-            _ => {}
+            None => remaining.push(meta),
         }
     }
 
-    (do_not_track, no_eq)
+    if remaining.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(parse_quote! { #[cfg_attr(#condition, #remaining)] }))
+    }
+}
+
+/// Get the atomic integer type with the bits of `bits_ty`, `u128` has none.
+fn atomic_type(bits_ty: &TokenStream2) -> Option<TokenStream2> {
+    let atomic = match bits_ty.to_string().as_str() {
+        "u8" => quote! { AtomicU8 },
+        "u16" => quote! { AtomicU16 },
+        "u32" => quote! { AtomicU32 },
+        "u64" => quote! { AtomicU64 },
+        _ => return None,
+    };
+    Some(quote! { ::core::sync::atomic::#atomic })
+}
+
+/// Get the type of a field declared with `#[tracker::tracker_field]`
+/// and check that it has enough bits for `len` tracked fields.
+fn declared_tracker_type(
+    field: &Field,
+    len: usize,
+    options: &Options,
+) -> syn::Result<TokenStream2> {
+    if options.typed_mask {
+        let option = if options.bitflags {
+            "bitflags"
+        } else {
+            "typed_mask"
+        };
+        return Err(syn::Error::new_spanned(
+            &field.ident,
+            format!("`#[tracker::tracker_field]` can't be combined with the `{option}` option"),
+        ));
+    }
+    if options.ty.is_some() {
+        return Err(syn::Error::new_spanned(
+            &field.ident,
+            "`#[tracker::tracker_field]` can't be combined with the `ty` option, the type of the field is used",
+        ));
+    }
+    checked_tracker_type(&field.ty, len)
+}
+
+/// Check that a tracker type chosen by the user is an unsigned integer
+/// with enough bits for `len` tracked fields.
+fn checked_tracker_type(ty: &Type, len: usize) -> syn::Result<TokenStream2> {
+    let bits = match ty {
+        Type::Path(path) if path.qself.is_none() => {
+            path.path
+                .get_ident()
+                .and_then(|ident| match ident.to_string().as_str() {
+                    "u8" => Some(8),
+                    "u16" => Some(16),
+                    "u32" => Some(32),
+                    "u64" => Some(64),
+                    "u128" => Some(128),
+                    _ => None,
+                })
+        }
+        _ => None,
+    };
+    match bits {
+        Some(bits) if bits < len => Err(syn::Error::new_spanned(
+            ty,
+            format!(
+                "the tracker type has only {bits} bits for {len} tracked fields, use `{}` instead",
+                tracker_type(len)
+            ),
+        )),
+        Some(_) => Ok(ty.to_token_stream()),
+        None => Err(syn::Error::new_spanned(
+            ty,
+            "the tracker type needs to be an unsigned integer like `u8`, `u16`, `u32`, `u64` or `u128`",
+        )),
+    }
 }
 
 fn tracker_type(len: usize) -> proc_macro2::TokenStream {
@@ -275,11 +2569,248 @@ fn tracker_type(len: usize) -> proc_macro2::TokenStream {
         33..=64 => {
             quote! {u64}
         }
-        65..=128 => {
+        // The number of fields is limited to `MAX_FIELDS` before.
+        _ => {
             quote! {u128}
         }
-        _ => {
-            panic!("You can only track up to 128 values")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proc_macro2::{TokenStream as TokenStream2, TokenTree};
+    use quote::{quote, ToTokens};
+    use syn::parse::Parser;
+    use syn::{parse_quote, File, ImplItem, Item, ItemStruct};
+
+    use crate::options::Options;
+    use crate::{expand, track_item, TrackedItems};
+
+    /// Count the tokens of a token stream including the ones in groups.
+    fn count_tokens(tokens: TokenStream2) -> usize {
+        tokens
+            .into_iter()
+            .map(|token| match token {
+                TokenTree::Group(group) => 1 + count_tokens(group.stream()),
+                _ => 1,
+            })
+            .sum()
+    }
+
+    /// Expand a struct with `fields` tracked fields and count the generated tokens.
+    fn expansion_size(options: &str, fields: usize) -> usize {
+        let fields: String = (0..fields)
+            .map(|num| format!("field_{num}: u32,"))
+            .collect();
+        let mut data: ItemStruct = syn::parse_str(&format!("struct Test {{ {fields} }}")).unwrap();
+        let mut parsed = Options::default();
+        syn::meta::parser(|meta| parsed.parse_meta(meta))
+            .parse_str(options)
+            .unwrap();
+        count_tokens(expand(&mut data, parsed, false).unwrap())
+    }
+
+    #[test]
+    fn compact_expansion_is_shorter() {
+        assert!(expansion_size("compact", 90) < expansion_size("", 90));
+        assert!(expansion_size("minimal, compact", 90) < expansion_size("minimal", 90));
+    }
+
+    #[test]
+    fn expansion_per_field() {
+        // Every field adds the same code, independent of the number of fields.
+        let per_field = expansion_size("", 91) - expansion_size("", 90);
+        assert_eq!(expansion_size("", 11) - expansion_size("", 10), per_field);
+        // Limits for the generated tokens per field, raise them deliberately if new code is needed.
+        assert!(per_field <= 1020, "{per_field} tokens per field");
+        let minimal_per_field = expansion_size("minimal", 91) - expansion_size("minimal", 90);
+        assert!(
+            minimal_per_field <= 115,
+            "{minimal_per_field} tokens per field"
+        );
+    }
+
+    // The code generation only uses `proc_macro2` spans, so it also works without
+    // the `proc_macro` runtime, like in rust-analyzer or in these tests.
+    #[test]
+    fn expands_without_proc_macro_runtime() {
+        let item: Item = parse_quote! {
+            pub struct Test<T: Clone> {
+                #[tracker::no_eq]
+                a: T,
+                #[cfg_attr(test, no_eq)]
+                b: u8,
+                #[tracker(column = "c_column", rename = "see", vis(set = "pub(crate)"))]
+                c: u8,
+                #[do_not_track]
+                d: u8,
+                #[tracker::nested]
+                e: Vec<Child>,
+                #[tracker::compare_with = "compare"]
+                f: f32,
+                #[tracker::hash_eq]
+                g: String,
+            }
+        };
+        for options in [
+            quote! { previous, snapshot, debug, impl_eq, impl_hash, clone_keeps, project, sql, data_struct, dynamic, commands, merge, staged, remote, listeners, history = 4, counters, timestamps, ordered, consumers = 3 },
+            quote! { versioned, encapsulate, typed_mask, debug_values, changed_view, serde, persistent, tracker_vis = "pub" },
+            quote! { serde_state, apply_changes, json_patch, apply_json, compact, prefix = "t_", impl_default },
+            quote! { minimal, ty = u32, vis = "pub(crate)", trait_only, strict_attrs, bitflags },
+            quote! { atomic, previous, typed_mask, clone_resets, encapsulate, on_change = redraw, stream, debug_unconsumed, double_buffer },
+        ] {
+            track_item(item.clone(), &[options]).unwrap();
+        }
+
+        let item: Item = parse_quote! {
+            enum Page {
+                Overview,
+                Details { id: u32 },
+            }
+        };
+        track_item(item, &[TokenStream2::new()]).unwrap();
+
+        let mut data: ItemStruct = parse_quote! {
+            #[tracker(apply_changes)]
+            struct Derived {
+                x: u8,
+                tracker: u8,
+            }
+        };
+        expand(&mut data, Options::default(), true).unwrap();
+
+        let items: TrackedItems = syn::parse2(quote! {
+            #![track(debug)]
+            struct A { x: u8 }
+            #[track(sql)]
+            struct B { y: u8 }
+        })
+        .unwrap();
+        assert_eq!(items.items.len(), 2);
+    }
+
+    /// Get the doc attributes of the generated method `name` as strings.
+    fn method_docs(output: TokenStream2, name: &str) -> Vec<String> {
+        let file: File = syn::parse2(output).unwrap();
+        file.items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Impl(item) => Some(&item.items),
+                _ => None,
+            })
+            .flatten()
+            .find_map(|item| match item {
+                ImplItem::Fn(method) if method.sig.ident == name => Some(&method.attrs),
+                _ => None,
+            })
+            .unwrap()
+            .iter()
+            .filter(|attr| attr.path().is_ident("doc"))
+            .map(|attr| attr.meta.to_token_stream().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn field_docs_are_copied_to_accessors() {
+        let item: Item = parse_quote! {
+            struct Test {
+                /// The first line.
+                ///
+                /// More details.
+                #[doc = include_str!("x.md")]
+                x: u8,
+                y: u8,
+            }
+        };
+        let output = track_item(item.clone(), &[TokenStream2::new()]).unwrap();
+        for method in ["get_x", "get_mut_x", "update_x", "changed_x", "set_x"] {
+            let docs = method_docs(output.clone(), method);
+            assert_eq!(docs.len(), 6, "{docs:?}");
+            assert_eq!(
+                docs[1..],
+                [
+                    r#"doc = """#,
+                    r#"doc = r" The first line.""#,
+                    r#"doc = r"""#,
+                    r#"doc = r" More details.""#,
+                    r#"doc = include_str ! ("x.md")"#,
+                ]
+            );
         }
+        assert_eq!(method_docs(output.clone(), "get_y").len(), 1);
+        assert_eq!(method_docs(output, "x").len(), 1);
+
+        let output = track_item(item, &[quote! { compact }]).unwrap();
+        assert!(method_docs(output, "get_x").is_empty());
+    }
+
+    #[cfg(feature = "diesel")]
+    #[test]
+    fn diesel_changeset_uses_the_columns() {
+        let item: Item = parse_quote! {
+            struct Test<T> {
+                x: u8,
+                #[tracker::column = "title"]
+                name: T,
+            }
+        };
+        let output = track_item(item, &[quote! { diesel(table = crate::schema::tests) }]).unwrap();
+        let file: File = syn::parse2(output).unwrap();
+        let changeset = file
+            .items
+            .iter()
+            .find_map(|item| match item {
+                Item::Struct(item) if item.ident == "TestChangeset" => Some(item),
+                _ => None,
+            })
+            .unwrap();
+        let attrs: Vec<String> = changeset
+            .attrs
+            .iter()
+            .filter(|attr| !attr.path().is_ident("doc"))
+            .map(|attr| attr.meta.to_token_stream().to_string())
+            .collect();
+        assert_eq!(
+            attrs[..2],
+            [
+                "derive (:: diesel :: AsChangeset)",
+                "diesel (table_name = crate :: schema :: tests)",
+            ]
+        );
+        let fields: Vec<String> = changeset
+            .fields
+            .iter()
+            .map(|field| {
+                let column = field
+                    .attrs
+                    .iter()
+                    .find(|attr| attr.path().is_ident("diesel"));
+                format!(
+                    "{} {}",
+                    column.unwrap().meta.to_token_stream(),
+                    field.ty.to_token_stream()
+                )
+            })
+            .collect();
+        assert_eq!(
+            fields,
+            [
+                "diesel (column_name = x) :: core :: option :: Option < & '__tracker u8 >",
+                "diesel (column_name = title) :: core :: option :: Option < & '__tracker T >",
+                "diesel (skip_update) :: core :: marker :: PhantomData < (fn () -> * const T ,) >",
+            ]
+        );
+
+        let item: Item = parse_quote! {
+            struct Test {
+                #[tracker::column = "Full Name"]
+                name: String,
+            }
+        };
+        let output = track_item(item, &[quote! { diesel(table = tests) }]).unwrap();
+        assert!(output
+            .to_string()
+            .contains("the column `Full Name` needs to be an identifier"));
+        assert!(track_item(parse_quote! { struct Test { x: u8 } }, &[quote! { diesel }]).is_err());
     }
 }
@@ -0,0 +1,51 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::{parse_quote, Field};
+
+use crate::Context;
+
+/// The injected field that stores the listeners.
+pub(crate) fn field(ctx: &Context) -> Field {
+    let tracker_ty = &ctx.tracker_ty;
+    parse_quote! {
+        tracker_listeners: ::tracker::Listeners<#tracker_ty>
+    }
+}
+
+/// Call all listeners of `this` with the newly changed bits in `__tracker_new`.
+pub(crate) fn notify(this: &TokenStream2) -> TokenStream2 {
+    quote! { #this.tracker_listeners.notify(__tracker_new); }
+}
+
+/// Generate the `add_change_listener()` and `remove_change_listener()` methods.
+pub(crate) fn generate(ctx: &Context) -> TokenStream2 {
+    let Context {
+        ident,
+        vis: struct_vis,
+        generics,
+        where_clause,
+        tracker_ty,
+        ..
+    } = ctx;
+    let self_ty = ctx.self_ty();
+
+    quote_spanned! { ident.span() =>
+        impl #generics #self_ty #where_clause {
+            /// Add a listener that is called with the bit mask of the fields
+            /// that were marked as changed since the last reset.
+            #[allow(dead_code)]
+            #struct_vis fn add_change_listener(
+                &mut self,
+                f: impl ::core::ops::FnMut(#tracker_ty) + 'static,
+            ) -> ::tracker::ListenerId {
+                self.tracker_listeners.add(f)
+            }
+
+            /// Remove a listener added with `add_change_listener()`.
+            #[allow(dead_code)]
+            #struct_vis fn remove_change_listener(&mut self, id: ::tracker::ListenerId) {
+                self.tracker_listeners.remove(id);
+            }
+        }
+    }
+}
@@ -0,0 +1,242 @@
+//! Support for fields marked with `#[tracker::map_collection]`.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{GenericArgument, PathArguments, Type};
+
+use crate::{call_site_at, Context, TrackedField};
+
+/// The map type of a field marked with `#[tracker::map_collection]`.
+pub(crate) struct MapCollection {
+    key: Type,
+    value: Type,
+    kind: MapKind,
+}
+
+enum MapKind {
+    /// `HashMap<K, V, S>` with the hasher, if there is one.
+    Hash(Option<Box<Type>>),
+    BTree,
+}
+
+impl MapCollection {
+    /// Detect the map type from the last segment of the path of `ty`.
+    pub(crate) fn of(ty: &Type) -> syn::Result<Self> {
+        let map = match ty {
+            Type::Path(path) => path.path.segments.last().and_then(|last| {
+                let PathArguments::AngleBracketed(args) = &last.arguments else {
+                    return None;
+                };
+                let mut types = args.args.iter().map(|arg| match arg {
+                    GenericArgument::Type(ty) => Some(ty.clone()),
+                    _ => None,
+                });
+                let (Some(key), Some(value)) = (types.next().flatten(), types.next().flatten())
+                else {
+                    return None;
+                };
+                let kind = match (types.next(), types.next()) {
+                    (None, None) if last.ident == "HashMap" => MapKind::Hash(None),
+                    (Some(Some(hasher)), None) if last.ident == "HashMap" => {
+                        MapKind::Hash(Some(Box::new(hasher)))
+                    }
+                    (None, None) if last.ident == "BTreeMap" => MapKind::BTree,
+                    _ => return None,
+                };
+                Some(Self { key, value, kind })
+            }),
+            _ => None,
+        };
+        map.ok_or_else(|| {
+            syn::Error::new_spanned(
+                ty,
+                "`map_collection` can only be used for fields of the types `HashMap<K, V>` and `BTreeMap<K, V>`",
+            )
+        })
+    }
+
+    /// The bounds the map needs for inserting and removing entries.
+    fn bounds(&self, ctx: &Context) -> TokenStream2 {
+        let key = ctx.bound_ty(&self.key, "'__tracker");
+        let span = call_site_at(self.key.span());
+        match &self.kind {
+            MapKind::Hash(hasher) => {
+                let hasher = hasher.as_ref().map(|hasher| {
+                    let hasher = ctx.bound_ty(hasher, "'__tracker");
+                    quote_spanned! { span =>
+                        for<'__tracker> #hasher: ::core::hash::BuildHasher,
+                    }
+                });
+                quote_spanned! { span =>
+                    for<'__tracker> #key: ::core::hash::Hash + ::core::cmp::Eq,
+                    #hasher
+                }
+            }
+            MapKind::BTree => quote_spanned! { span =>
+                for<'__tracker> #key: ::core::cmp::Ord,
+            },
+        }
+    }
+
+    /// The type of the entry of the map.
+    fn entry_ty(&self) -> TokenStream2 {
+        let Self { key, value, .. } = self;
+        match self.kind {
+            MapKind::Hash(_) => {
+                quote! { ::tracker::__private::hash_map::Entry<'_, #key, #value> }
+            }
+            MapKind::BTree => {
+                quote! { ::tracker::__private::btree_map::Entry<'_, #key, #value> }
+            }
+        }
+    }
+}
+
+/// Check whether `entry_#field()` is generated for a map field, which marks the
+/// tracker through the returned entry and can't run the code of options like `previous`.
+pub(crate) fn has_entry(ctx: &Context, field: &TrackedField) -> bool {
+    ctx.uses_shared_setter(field)
+}
+
+/// Generate `insert_#field()`, `remove_#field()`, `clear_#field()` and `entry_#field()` for map fields.
+pub(crate) fn methods(ctx: &Context) -> TokenStream2 {
+    let mut methods = TokenStream2::new();
+    for field in &ctx.fields {
+        let Some(map) = &field.map_collection else {
+            continue;
+        };
+        if ctx.skips_method(field, "set") {
+            continue;
+        }
+        let TrackedField {
+            ident: id,
+            cfg,
+            span,
+            ..
+        } = field;
+        let MapCollection { key, value, .. } = map;
+        let vis = field.method_vis("set");
+        let bounds = map.bounds(ctx);
+        let insert_id = field.method_ident("insert_");
+        let remove_id = field.method_ident("remove_");
+        let clear_id = field.method_ident("clear_");
+        let mask_id = field.mask_ident();
+        let this = quote! { self };
+        let before_change = ctx.before_change(&this, field, None);
+        let mark = ctx.mark(&this, quote_spanned! { *span => Self::#mask_id() });
+        let trace_insert = ctx.trace_change(&this, field, "insert");
+        let trace_remove = ctx.trace_change(&this, field, "remove");
+        let trace_clear = ctx.trace_change(&this, field, "clear");
+
+        for (no_eq, cfg) in field.no_eq_variants() {
+            let (summary, changed, value_bound) = if no_eq {
+                ("and mark the field as changed", quote! { true }, None)
+            } else {
+                let value_bound = ctx.bound_ty(value, "'__tracker_eq");
+                (
+                    "and mark the field as changed if the key had another value",
+                    quote_spanned! { *span =>
+                        self.#id.get(&key) != ::core::option::Option::Some(&value)
+                    },
+                    Some(quote_spanned! { call_site_at(value.span()) =>
+                        for<'__tracker_eq> #value_bound: ::tracker::__private::FieldEq,
+                    }),
+                )
+            };
+            let doc = ctx.field_doc(format!(
+                "Insert `value` for `key` into the {id} field {summary}.\n\n\
+                 Returns the previous value of the key, if any."
+            ));
+            methods.extend(quote_spanned! { *span =>
+                #(#cfg)*
+                #[inline]
+                #doc
+                #vis fn #insert_id(&mut self, key: #key, value: #value) -> ::core::option::Option<#value>
+                where
+                    #bounds
+                    #value_bound
+                {
+                    let changed = #changed;
+                    if changed {
+                        #before_change
+                        #trace_insert
+                    }
+                    let previous = self.#id.insert(key, value);
+                    if changed {
+                        #mark
+                    }
+                    previous
+                }
+            });
+        }
+
+        let remove_doc = ctx.field_doc(format!(
+            "Remove `key` from the {id} field and mark it as changed if the key was present.\n\n\
+             Returns the removed value, if any."
+        ));
+        let clear_doc = ctx.field_doc(format!(
+            "Remove all entries from the {id} field and mark it as changed if it wasn't empty.\n\n\
+             Returns whether the field was marked as changed."
+        ));
+        methods.extend(quote_spanned! { *span =>
+            #(#cfg)*
+            #[inline]
+            #remove_doc
+            #vis fn #remove_id(&mut self, key: &#key) -> ::core::option::Option<#value>
+            where
+                #bounds
+            {
+                if !self.#id.contains_key(key) {
+                    return ::core::option::Option::None;
+                }
+                #before_change
+                #trace_remove
+                let value = self.#id.remove(key);
+                #mark
+                value
+            }
+
+            #(#cfg)*
+            #[inline]
+            #clear_doc
+            #vis fn #clear_id(&mut self) -> bool {
+                if self.#id.is_empty() {
+                    return false;
+                }
+                #before_change
+                #trace_clear
+                self.#id.clear();
+                #mark
+                true
+            }
+        });
+
+        if has_entry(ctx, field) {
+            let entry_id = field.method_ident("entry_");
+            let entry_ty = map.entry_ty();
+            let tracker_field = &ctx.tracker_field;
+            let tracker_ty = &ctx.tracker_ty;
+            let doc = ctx.field_doc(format!(
+                "Get the entry of `key` in the {id} field for in-place modification.\n\n\
+                 The field is only marked as changed if the entry is modified."
+            ));
+            methods.extend(quote_spanned! { *span =>
+                #(#cfg)*
+                #[inline]
+                #doc
+                #vis fn #entry_id(&mut self, key: #key) -> ::tracker::FieldEntry<'_, #entry_ty, #tracker_ty>
+                where
+                    #bounds
+                {
+                    ::tracker::FieldEntry::new(
+                        self.#id.entry(key),
+                        &mut self.#tracker_field,
+                        Self::#mask_id(),
+                    )
+                }
+            });
+        }
+    }
+    methods
+}
@@ -0,0 +1,90 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+
+use crate::{Context, TrackedField};
+
+/// Generate the `merge_from()` method.
+pub(crate) fn generate(ctx: &Context) -> TokenStream2 {
+    let Context {
+        ident,
+        vis: struct_vis,
+        generics,
+        where_clause,
+        tracker_ty,
+        ..
+    } = ctx;
+    let self_ty = ctx.self_ty();
+    let this = quote! { self };
+
+    let mut merges = TokenStream2::new();
+    let mut clone_bounds = TokenStream2::new();
+    for field in &ctx.fields {
+        let TrackedField {
+            ident: id,
+            ty,
+            cfg,
+            span: id_span,
+            ..
+        } = field;
+        let id_span = *id_span;
+        let mask_id = field.mask_ident();
+        let before_change = ctx.before_change(&this, field, None);
+        let mark = ctx.mark(&this, quote_spanned! { id_span => Self::#mask_id() });
+
+        for (no_eq, cfg) in field.no_eq_variants() {
+            // Fields that can't be compared conflict whenever both values changed them.
+            let conflict = if no_eq {
+                quote! { true }
+            } else {
+                field.ne(quote! { self.#id }, quote! { other.#id })
+            };
+            merges.extend(quote_spanned! { id_span =>
+                #(#cfg)*
+                if ::tracker::Track::changed(other, Self::#mask_id()) {
+                    if !::tracker::Track::changed(self, Self::#mask_id()) {
+                        #before_change
+                        self.#id = ::core::clone::Clone::clone(&other.#id);
+                        #mark
+                        merged |= Self::#mask_id();
+                    } else if #conflict {
+                        conflicts |= Self::#mask_id();
+                    }
+                }
+            });
+        }
+        if cfg.is_empty() {
+            let bound_ty = ctx.bound_ty(ty, "'__tracker");
+            clone_bounds.extend(quote_spanned! { id_span =>
+                for<'__tracker> #bound_ty: ::core::clone::Clone,
+            });
+        }
+    }
+
+    let where_predicates = ctx.where_predicates();
+    let eq_bounds = ctx.eq_bounds();
+    let empty_mask = ctx.empty_mask();
+
+    quote_spanned! { ident.span() =>
+        impl #generics #self_ty #where_clause {
+            /// Merge the changes of `other`, a copy of the same value that was changed independently.
+            ///
+            /// Fields that only `other` changed are copied into `self` and marked as changed.
+            /// Fields that only `self` changed are kept. If both changed a field, `self` wins:
+            /// its value is kept as well, and if the values differ, the field is part of
+            /// [`MergeOutcome::conflicts`](::tracker::MergeOutcome::conflicts) so the caller can resolve it.
+            /// Fields marked as `no_eq` conflict whenever both changed them.
+            #[allow(dead_code, unused_mut)]
+            #struct_vis fn merge_from(&mut self, other: &Self) -> ::tracker::MergeOutcome<#tracker_ty>
+            where
+                #where_predicates
+                #clone_bounds
+                #eq_bounds
+            {
+                let mut merged = #empty_mask;
+                let mut conflicts = #empty_mask;
+                #merges
+                ::tracker::MergeOutcome::new(merged, conflicts)
+            }
+        }
+    }
+}
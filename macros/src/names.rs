@@ -0,0 +1,263 @@
+//! Detection of generated methods with the same name.
+
+use std::collections::HashMap;
+
+use quote::format_ident;
+use syn::ext::IdentExt;
+use syn::Ident;
+
+use crate::indexed;
+use crate::map_collection;
+use crate::nested::{self, Nested};
+use crate::{Context, TrackedField};
+
+/// A method that will be generated in the impl block of the struct.
+struct Method<'a> {
+    name: String,
+    /// The field the method is generated for, if any.
+    field: Option<&'a TrackedField>,
+    description: String,
+}
+
+/// Return an error if two generated methods of the struct have the same name,
+/// for example because a field is called `changed` or `get_x` next to `x`.
+pub(crate) fn check_collisions(ctx: &Context) -> syn::Result<()> {
+    let mut seen: HashMap<String, Method<'_>> = HashMap::new();
+    // Fields whose methods collide are only reported once.
+    let mut reported: Vec<(Option<&Ident>, Option<&Ident>)> = Vec::new();
+    let mut error: Option<syn::Error> = None;
+    for method in methods(ctx) {
+        let Some(other) = seen.get(&method.name) else {
+            seen.insert(method.name.clone(), method);
+            continue;
+        };
+        let Some(field) = method.field.or(other.field) else {
+            continue;
+        };
+        let pair = (
+            other.field.map(|field| &field.ident),
+            method.field.map(|field| &field.ident),
+        );
+        if reported.contains(&pair) {
+            continue;
+        }
+        reported.push(pair);
+        let new_error = syn::Error::new(
+            field.ident.span(),
+            format!(
+                "{} and {} are both called `{}`, rename the methods with `#[tracker::rename = \"...\"]` or mark the field with `#[tracker::do_not_track]`",
+                other.description, method.description, method.name
+            ),
+        );
+        match &mut error {
+            Some(error) => error.combine(new_error),
+            None => error = Some(new_error),
+        }
+    }
+
+    match error {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+/// Get all methods that are generated in the impl block of the struct.
+fn methods(ctx: &Context) -> Vec<Method<'_>> {
+    let options = &ctx.options;
+    let mut methods = Vec::new();
+
+    let mut struct_method = |name: &str, enabled: bool| {
+        if enabled {
+            methods.push(Method {
+                name: name.to_owned(),
+                field: None,
+                description: format!("the generated `{name}()` method"),
+            });
+        }
+    };
+    struct_method("changed", !options.trait_only);
+    struct_method("reset", !options.trait_only);
+    struct_method("reset_all", !options.trait_only);
+    for name in ["track_all", "mark_all_changed", "changed_any"] {
+        struct_method(name, !options.skips(name) && !options.trait_only);
+    }
+    for name in [
+        "update_all",
+        "diff",
+        "sync_from",
+        "on_changed",
+        "track_scope",
+        "visit_changed",
+        "field_mask_paths",
+        "mask_from_paths",
+    ] {
+        struct_method(name, !options.skips(name));
+    }
+    struct_method("on_changed_mut", !options.skips("on_changed"));
+    struct_method("__tracker_mark_fields", true);
+    struct_method("apply_changes_from", options.apply_changes);
+    struct_method("to_delta", !options.no_delta);
+    struct_method("apply_delta", !options.no_delta);
+    struct_method("apply_update", !options.no_update);
+    struct_method("snapshot", options.snapshot);
+    struct_method("changed_since", options.snapshot || options.versioned);
+    struct_method("version", options.versioned);
+    struct_method("to_json_patch", options.json_patch);
+    struct_method("apply_json_patch", options.json_patch);
+    struct_method("changed_columns", options.sql);
+    struct_method("set_clause_with_placeholders", options.sql);
+    struct_method("changed_view", options.changed_view);
+    struct_method("as_changeset", options.diesel.is_some());
+    struct_method("apply_json", options.apply_json);
+    struct_method("project", options.project);
+    struct_method("changed_values", options.debug_values);
+    struct_method("new", options.constructor);
+    struct_method("mark_changed", options.atomic);
+    struct_method("add_change_listener", options.listeners);
+    struct_method("remove_change_listener", options.listeners);
+    struct_method("changes_stream", options.stream);
+    struct_method("change_history", options.history.is_some());
+    struct_method("clear_history", options.history.is_some());
+    struct_method("reset_counters", options.counters);
+    struct_method("changes_in_order", options.ordered);
+    struct_method("ever_changed", options.persistent);
+    struct_method("ever_changed_any", options.persistent);
+    struct_method("clear_persistent", options.persistent);
+    struct_method("previous_changes", options.double_buffer);
+    struct_method("previously_changed", options.double_buffer);
+    struct_method("changed_for", options.consumers.is_some());
+    struct_method("changed_any_for", options.consumers.is_some());
+    struct_method("reset_for", options.consumers.is_some());
+    struct_method("as_data", options.data_struct);
+    struct_method("from_data_changed", options.data_struct);
+    struct_method("set", options.transparent.is_some());
+    struct_method("get_field", options.dynamic);
+    struct_method("set_field", options.dynamic);
+    struct_method("set_field_by_name", options.dynamic);
+    struct_method("apply", options.commands);
+    struct_method("merge_from", options.merge);
+    struct_method("begin", options.staged);
+    struct_method("is_staging", options.staged);
+    struct_method("commit", options.staged);
+    struct_method("abort", options.staged);
+    struct_method("change_handle", options.remote);
+    struct_method("absorb_remote", options.remote);
+
+    for field in &ctx.fields {
+        let id = field.ident.unraw();
+        let mut field_method = |prefix: &str, kind: &str, enabled: bool| {
+            if enabled {
+                methods.push(Method {
+                    name: field.method_ident(prefix).unraw().to_string(),
+                    field: Some(field),
+                    description: format!("the {kind} of the `{id}` field"),
+                });
+            }
+        };
+        field_method("", "bit mask function", true);
+        field_method("get_", "getter", !ctx.skips_method(field, "get"));
+        field_method(
+            "get_mut_",
+            "mutable getter",
+            !ctx.skips_method(field, "get_mut"),
+        );
+        field_method(
+            "update_",
+            "update method",
+            !ctx.skips_method(field, "update"),
+        );
+        field_method("changed_", "change check", !options.skips("changed_field"));
+        field_method(
+            "set_",
+            "setter",
+            !ctx.skips_method(field, "set") || !options.no_delta,
+        );
+        field_method("command_for_set_", "command constructor", options.commands);
+        field_method(
+            "with_",
+            "default replacement",
+            options.constructor && ctx.default_value(&field.ident).is_some(),
+        );
+        field_method("previous_", "previous value getter", options.previous);
+        field_method("revert_", "revert method", options.previous);
+        let stages = options.staged && !ctx.skips_method(field, "set");
+        field_method("stage_", "staging method", stages);
+        field_method("staged_", "staged value getter", stages);
+        field_method("change_count_", "change counter", options.counters);
+        field_method(
+            "last_changed_",
+            "timestamp getter",
+            options.timestamps.is_some(),
+        );
+        field_method(
+            "elapsed_since_change_",
+            "elapsed time getter",
+            options.timestamps.is_some(),
+        );
+        if field.set_collection.is_some() {
+            let enabled = !ctx.skips_method(field, "set");
+            field_method("insert_", "insertion method", enabled);
+            field_method("remove_", "removal method", enabled);
+            field_method("clear_", "clearing method", enabled);
+        }
+        if field.map_collection.is_some() {
+            let enabled = !ctx.skips_method(field, "set");
+            field_method("insert_", "insertion method", enabled);
+            field_method("remove_", "removal method", enabled);
+            field_method("clear_", "clearing method", enabled);
+            field_method(
+                "entry_",
+                "entry method",
+                enabled && map_collection::has_entry(ctx, field),
+            );
+        }
+        if field.elements.is_some() {
+            let mut element_method = |name: Ident, kind: &str, enabled: bool| {
+                if enabled {
+                    methods.push(Method {
+                        name: name.unraw().to_string(),
+                        field: Some(field),
+                        description: format!("the {kind} of the `{id}` field"),
+                    });
+                }
+            };
+            element_method(
+                format_ident!("{}_all", field.mask_ident()),
+                "bit mask function of all elements",
+                true,
+            );
+            element_method(
+                format_ident!("{}_elem", field.method_ident("changed_")),
+                "element change check",
+                !options.skips("changed_field"),
+            );
+            element_method(
+                format_ident!("{}_elem", field.method_ident("set_")),
+                "element setter",
+                !ctx.skips_method(field, "set"),
+            );
+        }
+        if field.indexed.is_some() {
+            let mut indexed_method = |prefix: &str, kind: &str, enabled: bool| {
+                if enabled {
+                    methods.push(Method {
+                        name: indexed::at_ident(field, prefix).unraw().to_string(),
+                        field: Some(field),
+                        description: format!("the {kind} of the `{id}` field"),
+                    });
+                }
+            };
+            indexed_method("get_", "element getter", !ctx.skips_method(field, "get"));
+            indexed_method("set_", "element setter", !ctx.skips_method(field, "set"));
+        }
+        if field.nested == Some(Nested::Vec) && !options.skips("changed_field") {
+            methods.push(Method {
+                name: nested::indices_ident(field).unraw().to_string(),
+                field: Some(field),
+                description: format!("the changed indices of the `{id}` field"),
+            });
+        }
+    }
+
+    methods
+}
@@ -0,0 +1,155 @@
+//! Support for fields marked with `#[tracker::nested]`.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote, quote_spanned};
+use syn::{GenericArgument, PathArguments, Type};
+
+use crate::{Context, TrackedField};
+
+/// How a nested tracked struct is stored in the field.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Nested {
+    /// The field is the tracked struct.
+    Direct,
+    /// The field is an `Option` of the tracked struct.
+    Option,
+    /// The field is a `Vec` of tracked structs.
+    Vec,
+}
+
+impl Nested {
+    /// Detect whether the tracked struct is wrapped in an `Option` or a `Vec`.
+    pub(crate) fn of(ty: &Type) -> Self {
+        let Type::Path(path) = ty else {
+            return Self::Direct;
+        };
+        let Some(last) = path.path.segments.last() else {
+            return Self::Direct;
+        };
+        let PathArguments::AngleBracketed(args) = &last.arguments else {
+            return Self::Direct;
+        };
+        if args.args.len() != 1 || !matches!(args.args[0], GenericArgument::Type(_)) {
+            return Self::Direct;
+        }
+        if last.ident == "Option" {
+            Self::Option
+        } else if last.ident == "Vec" {
+            Self::Vec
+        } else {
+            Self::Direct
+        }
+    }
+}
+
+/// Generate the condition that the nested struct of `field` has changed.
+fn changed_any(field: &TrackedField, nested: Nested) -> TokenStream2 {
+    let id = &field.ident;
+    match nested {
+        Nested::Direct => quote_spanned! { field.span => ::tracker::Track::changed_any(&self.#id) },
+        Nested::Option => quote_spanned! { field.span =>
+            self.#id.as_ref().is_some_and(::tracker::Track::changed_any)
+        },
+        Nested::Vec => quote_spanned! { field.span =>
+            self.#id.iter().any(::tracker::Track::changed_any)
+        },
+    }
+}
+
+/// Generate the checks of `Track::changed()` for the nested fields, which return
+/// `true` if a field in `mask` is nested and has changed.
+///
+/// Nested structs are only checked if the mask contains their fields.
+pub(crate) fn changed_checks(ctx: &Context) -> TokenStream2 {
+    let empty_mask = ctx.empty_mask();
+    let mut checks = TokenStream2::new();
+    for field in &ctx.fields {
+        let Some(nested) = field.nested else {
+            continue;
+        };
+        let cfg = &field.cfg;
+        let mask_id = field.mask_ident();
+        let changed_any = changed_any(field, nested);
+        checks.extend(quote_spanned! { field.span =>
+            #(#cfg)*
+            if mask & Self::#mask_id() != #empty_mask && #changed_any {
+                return true;
+            }
+        });
+    }
+    checks
+}
+
+/// Generate `Track::reset_all()` if there are nested fields, which resets their structs as well.
+pub(crate) fn reset_all(ctx: &Context) -> Option<TokenStream2> {
+    let mut resets = TokenStream2::new();
+    for field in &ctx.fields {
+        let Some(nested) = field.nested else {
+            continue;
+        };
+        let TrackedField { ident: id, cfg, .. } = field;
+        resets.extend(match nested {
+            Nested::Direct => quote_spanned! { field.span =>
+                #(#cfg)*
+                ::tracker::Track::reset_all(&mut self.#id);
+            },
+            Nested::Option => quote_spanned! { field.span =>
+                #(#cfg)*
+                if let ::core::option::Option::Some(child) = &mut self.#id {
+                    ::tracker::Track::reset_all(child);
+                }
+            },
+            Nested::Vec => quote_spanned! { field.span =>
+                #(#cfg)*
+                for child in &mut self.#id {
+                    ::tracker::Track::reset_all(child);
+                }
+            },
+        });
+    }
+    (!resets.is_empty()).then(|| {
+        quote! {
+            fn reset_all(&mut self) {
+                ::tracker::Track::reset(self);
+                #resets
+            }
+        }
+    })
+}
+
+/// Generate `changed_#field_indices()` for nested `Vec` fields.
+pub(crate) fn indices_methods(ctx: &Context) -> TokenStream2 {
+    let mut methods = TokenStream2::new();
+    if ctx.options.skips("changed_field") {
+        return methods;
+    }
+    for field in &ctx.fields {
+        if field.nested != Some(Nested::Vec) {
+            continue;
+        }
+        let TrackedField { ident: id, cfg, .. } = field;
+        let vis = field.method_vis("changed_field");
+        let indices_id = indices_ident(field);
+        let doc = ctx.field_doc(format!(
+            "Get the indices of the elements of the {id} field that have changed."
+        ));
+        methods.extend(quote_spanned! { field.span =>
+            #(#cfg)*
+            #[must_use]
+            #doc
+            #vis fn #indices_id(&self) -> impl ::core::iter::Iterator<Item = usize> + '_ {
+                self.#id
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, element)| ::tracker::Track::changed_any(*element))
+                    .map(|(index, _)| index)
+            }
+        });
+    }
+    methods
+}
+
+/// Create the identifier of `changed_#field_indices()`.
+pub(crate) fn indices_ident(field: &TrackedField) -> syn::Ident {
+    format_ident!("{}_indices", field.method_ident("changed_"))
+}
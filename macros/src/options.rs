@@ -0,0 +1,538 @@
+use proc_macro2::Span;
+use quote::ToTokens;
+use syn::meta::ParseNestedMeta;
+use syn::parse::{Parse, ParseStream};
+use syn::spanned::Spanned;
+use syn::{
+    parenthesized, parse_quote, Ident, LitInt, LitStr, Meta, Path, Result, Token, Type, Visibility,
+};
+
+/// Methods that can be skipped with `skip_methods(...)`.
+///
+/// The per-field methods are named after their prefix, `changed_field`
+/// stands for the `changed_#field_name()` methods.
+const SKIPPABLE_METHODS: &[&str] = &[
+    "get",
+    "get_mut",
+    "update",
+    "set",
+    "changed_field",
+    "diff",
+    "sync_from",
+    "track_all",
+    "mark_all_changed",
+    "changed_any",
+    "update_all",
+    "on_changed",
+    "track_scope",
+    "visit_changed",
+    "field_mask_paths",
+    "mask_from_paths",
+    "fields_mut",
+];
+
+/// Methods that are always generated because other methods depend on them.
+const REQUIRED_METHODS: &[&str] = &["changed", "reset"];
+
+/// Options whose generated code needs the `alloc` feature of tracker.
+const ALLOC_OPTIONS: &[&str] = &[
+    "apply_json",
+    "dynamic",
+    "json_patch",
+    "listeners",
+    "remote",
+    "serde_state",
+    "sql",
+    "stream",
+];
+
+/// Methods that are skipped without the `alloc` feature because they return allocated values.
+const ALLOC_METHODS: &[&str] = &["field_mask_paths", "mask_from_paths"];
+
+/// Struct level options passed to the `track` attribute
+/// like `#[tracker::track(apply_changes)]`.
+#[derive(Default)]
+pub(crate) struct Options {
+    /// Generate `apply_changes_from()`.
+    pub(crate) apply_changes: bool,
+    /// Don't generate the delta struct and its methods.
+    pub(crate) no_delta: bool,
+    /// Don't generate the update struct and its method.
+    pub(crate) no_update: bool,
+    /// Generate the snapshot struct, `snapshot()` and `changed_since()`.
+    pub(crate) snapshot: bool,
+    /// Store the previous values of changed fields.
+    pub(crate) previous: bool,
+    /// Maintain version counters for all fields.
+    pub(crate) versioned: bool,
+    /// Store the tracker in an atomic integer so changes can be marked through `&self`.
+    pub(crate) atomic: bool,
+    /// Store listeners that are called when fields are marked as changed.
+    pub(crate) listeners: bool,
+    /// Send the masks of newly changed fields to the streams of `changes_stream()`.
+    pub(crate) stream: bool,
+    /// Record the last `N` changes for `change_history()`.
+    pub(crate) history: Option<usize>,
+    /// Count how often each field is newly marked as changed.
+    pub(crate) counters: bool,
+    /// Store the time at which each field was newly marked as changed, using the given clock.
+    pub(crate) timestamps: Option<Type>,
+    /// Store the order in which the fields were marked as changed for `changes_in_order()`.
+    pub(crate) ordered: bool,
+    /// Keep a second mask of all changes that isn't cleared by `reset()`.
+    pub(crate) persistent: bool,
+    /// Keep the changes of the last cycle for `previous_changes()` when the tracker is reset.
+    pub(crate) double_buffer: bool,
+    /// Store a mask for each of the given number of consumers that can be reset independently.
+    pub(crate) consumers: Option<usize>,
+    /// Generate a struct with the declared fields but without the tracker, and conversions.
+    pub(crate) data_struct: bool,
+    /// Name of the data struct set with `data_struct = "..."`.
+    pub(crate) data_struct_name: Option<Ident>,
+    /// Implement `Deref`, `DerefMut` and `set()` for the only tracked field, with the span of the option.
+    pub(crate) transparent: Option<Span>,
+    /// Generate the field enum, `get_field()`, `set_field()` and `set_field_by_name()`.
+    pub(crate) dynamic: bool,
+    /// Generate the command enum, `apply()` and the `command_for_set_#field()` constructors.
+    pub(crate) commands: bool,
+    /// Generate `merge_from()` which merges the changes of another copy.
+    pub(crate) merge: bool,
+    /// Generate `begin()`, `commit()`, `abort()` and the `stage_#field()` methods.
+    pub(crate) staged: bool,
+    /// Generate `change_handle()` and `absorb_remote()` for marking fields from other threads.
+    pub(crate) remote: bool,
+    /// Panic in debug builds if the struct is dropped with changes, with the span of the option.
+    pub(crate) debug_unconsumed: Option<Span>,
+    /// Generate `to_json_patch()` and `apply_json_patch()`.
+    pub(crate) json_patch: bool,
+    /// Generate `changed_columns()` and `set_clause_with_placeholders()`.
+    pub(crate) sql: bool,
+    /// Generate the Diesel changeset struct for the table set with `diesel(table = ...)`.
+    pub(crate) diesel: Option<Path>,
+    /// Skip the injected fields during (de)serialization with serde.
+    pub(crate) serde: bool,
+    /// Generate `changed_view()` for serializing the changed fields.
+    pub(crate) changed_view: bool,
+    /// Generate `apply_json()`.
+    pub(crate) apply_json: bool,
+    /// Serialize the tracker as a list of changed field names.
+    pub(crate) serde_state: bool,
+    /// Implement `PartialEq` without comparing the tracker.
+    pub(crate) impl_partial_eq: bool,
+    /// Implement `Eq` and `PartialEq` without comparing the tracker.
+    pub(crate) impl_eq: bool,
+    /// Implement `Hash` without hashing the tracker.
+    pub(crate) impl_hash: bool,
+    /// Implement `Default` with the values of `#[tracker::default = ...]`.
+    pub(crate) impl_default: bool,
+    /// Implement `Debug` with markers for changed fields.
+    pub(crate) debug: bool,
+    /// Implement `Clone` and reset the tracker of the clone.
+    pub(crate) clone_resets: bool,
+    /// Implement `Clone` and keep the tracker of the clone.
+    pub(crate) clone_keeps: bool,
+    /// Generate the view struct and `project()`.
+    pub(crate) project: bool,
+    /// Use a generated mask type instead of integers for bit masks.
+    pub(crate) typed_mask: bool,
+    /// Generate the mask type with `bitflags!`, implies `typed_mask`.
+    pub(crate) bitflags: bool,
+    /// Generate `changed_values()`.
+    pub(crate) debug_values: bool,
+    /// Methods that are not generated.
+    pub(crate) skipped_methods: Vec<String>,
+    /// Make the tracked fields private.
+    pub(crate) encapsulate: bool,
+    /// Generate `new()`.
+    pub(crate) constructor: bool,
+    /// Name of the injected tracker field set with `field = "..."`.
+    pub(crate) field: Option<Ident>,
+    /// Name of the trait with the tracked API set with `trait = "..."`.
+    pub(crate) companion_trait: Option<Ident>,
+    /// Type of the tracker set with `ty = ...`.
+    pub(crate) ty: Option<Type>,
+    /// Function called with the newly changed bits, set with `on_change = ...`.
+    pub(crate) on_change: Option<Path>,
+    /// Don't compare any field unless it's marked with `#[tracker::eq]`.
+    pub(crate) no_eq: bool,
+    /// Only recognize namespaced field attributes like `#[tracker::no_eq]`.
+    pub(crate) strict_attrs: bool,
+    /// Leave out the doc comments of the generated per-field methods.
+    pub(crate) compact: bool,
+    /// Emit `tracing` events when fields are marked as changed or the tracker is reset.
+    pub(crate) trace: bool,
+    /// Only implement the `Track` trait without forwarding inherent methods.
+    pub(crate) trait_only: bool,
+    /// Prefix of all generated per-field methods set with `prefix = "..."`.
+    pub(crate) prefix: Option<String>,
+    /// Visibility of all generated items set with `vis = "..."`.
+    pub(crate) vis: Option<Visibility>,
+    /// Attributes of the injected tracker field set with `tracker_attrs(...)`.
+    pub(crate) tracker_attrs: Vec<Meta>,
+    /// Visibility of the injected fields set with `tracker_vis = "..."`.
+    pub(crate) tracker_vis: Option<Visibility>,
+    /// Names of the options that were already parsed, used to report duplicates.
+    parsed: Vec<String>,
+}
+
+impl Options {
+    /// Check whether a method was skipped with `skip_methods(...)` or `minimal`.
+    pub(crate) fn skips(&self, method: &str) -> bool {
+        self.skipped_methods.iter().any(|skipped| skipped == method)
+            || (!cfg!(feature = "alloc") && ALLOC_METHODS.contains(&method))
+    }
+
+    pub(crate) fn parse_meta(&mut self, meta: ParseNestedMeta<'_>) -> Result<()> {
+        if let Some(name) = meta.path.get_ident().map(ToString::to_string) {
+            if self.parsed.contains(&name) {
+                return Err(meta.error(format!("duplicate tracker option `{name}`")));
+            }
+            if !cfg!(feature = "alloc") && ALLOC_OPTIONS.contains(&name.as_str()) {
+                return Err(meta.error(format!("`{name}` requires the `alloc` feature of tracker")));
+            }
+            self.parsed.push(name);
+        }
+
+        if meta.path.is_ident("apply_changes") {
+            self.apply_changes = true;
+        } else if meta.path.is_ident("no_delta") {
+            self.no_delta = true;
+        } else if meta.path.is_ident("no_update") {
+            self.no_update = true;
+        } else if meta.path.is_ident("snapshot") {
+            if self.versioned {
+                return Err(meta.error(
+                    "`snapshot` can't be combined with `versioned` because both generate `changed_since()`",
+                ));
+            }
+            self.snapshot = true;
+        } else if meta.path.is_ident("previous") {
+            self.previous = true;
+        } else if meta.path.is_ident("versioned") {
+            if self.snapshot {
+                return Err(meta.error(
+                    "`versioned` can't be combined with `snapshot` because both generate `changed_since()`",
+                ));
+            }
+            if self.atomic {
+                return Err(meta.error(
+                    "`versioned` can't be combined with `atomic` because versions can't be recorded through `&self`",
+                ));
+            }
+            self.versioned = true;
+        } else if meta.path.is_ident("atomic") {
+            if self.versioned {
+                return Err(meta.error(
+                    "`atomic` can't be combined with `versioned` because versions can't be recorded through `&self`",
+                ));
+            }
+            if self.serde_state {
+                return Err(meta.error(
+                    "`atomic` can't be combined with `serde_state`, skip the tracker with the `serde` option instead",
+                ));
+            }
+            if self.listeners {
+                return Err(meta.error(
+                    "`atomic` can't be combined with `listeners` because listeners can't be called through `&self`",
+                ));
+            }
+            if self.history.is_some() {
+                return Err(meta.error(
+                    "`atomic` can't be combined with `history` because changes can't be recorded through `&self`",
+                ));
+            }
+            if self.counters {
+                return Err(meta.error(
+                    "`atomic` can't be combined with `counters` because changes can't be counted through `&self`",
+                ));
+            }
+            if self.timestamps.is_some() {
+                return Err(meta.error(
+                    "`atomic` can't be combined with `timestamps` because changes can't be recorded through `&self`",
+                ));
+            }
+            if self.ordered {
+                return Err(meta.error(
+                    "`atomic` can't be combined with `ordered` because changes can't be recorded through `&self`",
+                ));
+            }
+            if self.persistent {
+                return Err(meta.error(
+                    "`atomic` can't be combined with `persistent` because changes can't be recorded through `&self`",
+                ));
+            }
+            if self.consumers.is_some() {
+                return Err(meta.error(
+                    "`atomic` can't be combined with `consumers` because changes can't be recorded through `&self`",
+                ));
+            }
+            self.atomic = true;
+        } else if meta.path.is_ident("listeners") {
+            if self.atomic {
+                return Err(meta.error(
+                    "`listeners` can't be combined with `atomic` because listeners can't be called through `&self`",
+                ));
+            }
+            self.listeners = true;
+        } else if meta.path.is_ident("stream") {
+            self.stream = true;
+        } else if meta.path.is_ident("history") {
+            if self.atomic {
+                return Err(meta.error(
+                    "`history` can't be combined with `atomic` because changes can't be recorded through `&self`",
+                ));
+            }
+            let capacity: LitInt = meta.value()?.parse()?;
+            let capacity = capacity.base10_parse()?;
+            if capacity == 0 {
+                return Err(meta.error("`history` needs to record at least one change"));
+            }
+            self.history = Some(capacity);
+        } else if meta.path.is_ident("counters") {
+            if self.atomic {
+                return Err(meta.error(
+                    "`counters` can't be combined with `atomic` because changes can't be counted through `&self`",
+                ));
+            }
+            self.counters = true;
+        } else if meta.path.is_ident("timestamps") {
+            if self.atomic {
+                return Err(meta.error(
+                    "`timestamps` can't be combined with `atomic` because changes can't be recorded through `&self`",
+                ));
+            }
+            self.timestamps = Some(if meta.input.peek(Token![=]) {
+                meta.value()?.parse()?
+            } else {
+                parse_quote! { ::tracker::SystemClock }
+            });
+        } else if meta.path.is_ident("ordered") {
+            if self.atomic {
+                return Err(meta.error(
+                    "`ordered` can't be combined with `atomic` because changes can't be recorded through `&self`",
+                ));
+            }
+            self.ordered = true;
+        } else if meta.path.is_ident("persistent") {
+            if self.atomic {
+                return Err(meta.error(
+                    "`persistent` can't be combined with `atomic` because changes can't be recorded through `&self`",
+                ));
+            }
+            self.persistent = true;
+        } else if meta.path.is_ident("double_buffer") {
+            self.double_buffer = true;
+        } else if meta.path.is_ident("consumers") {
+            if self.atomic {
+                return Err(meta.error(
+                    "`consumers` can't be combined with `atomic` because changes can't be recorded through `&self`",
+                ));
+            }
+            let count: LitInt = meta.value()?.parse()?;
+            let count = count.base10_parse()?;
+            if count == 0 {
+                return Err(meta.error("`consumers` needs at least one consumer"));
+            }
+            self.consumers = Some(count);
+        } else if meta.path.is_ident("debug_unconsumed") {
+            if self.data_struct {
+                return Err(meta.error(
+                    "`debug_unconsumed` can't be combined with `data_struct` because fields can't be moved out of a struct that implements `Drop`",
+                ));
+            }
+            self.debug_unconsumed = Some(meta.path.span());
+        } else if meta.path.is_ident("data_struct") {
+            if self.debug_unconsumed.is_some() {
+                return Err(meta.error(
+                    "`data_struct` can't be combined with `debug_unconsumed` because fields can't be moved out of a struct that implements `Drop`",
+                ));
+            }
+            if meta.input.peek(Token![=]) {
+                let name: LitStr = meta.value()?.parse()?;
+                self.data_struct_name = Some(name.parse()?);
+            }
+            self.data_struct = true;
+        } else if meta.path.is_ident("transparent") {
+            self.transparent = Some(meta.path.span());
+        } else if meta.path.is_ident("dynamic") {
+            self.dynamic = true;
+        } else if meta.path.is_ident("commands") {
+            self.commands = true;
+        } else if meta.path.is_ident("merge") {
+            self.merge = true;
+        } else if meta.path.is_ident("staged") {
+            self.staged = true;
+        } else if meta.path.is_ident("remote") {
+            self.remote = true;
+        } else if meta.path.is_ident("json_patch") {
+            self.json_patch = true;
+        } else if meta.path.is_ident("sql") {
+            self.sql = true;
+        } else if meta.path.is_ident("diesel") {
+            if !cfg!(feature = "diesel") {
+                return Err(meta.error("`diesel` requires the `diesel` feature of tracker"));
+            }
+            meta.parse_nested_meta(|meta| {
+                if meta.path.is_ident("table") {
+                    self.diesel = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown `diesel` option, expected `table`"))
+                }
+            })?;
+            if self.diesel.is_none() {
+                return Err(meta.error(
+                    "`diesel` needs the table of the changeset, like `diesel(table = crate::schema::users)`",
+                ));
+            }
+        } else if meta.path.is_ident("serde") {
+            self.serde = true;
+        } else if meta.path.is_ident("changed_view") {
+            self.changed_view = true;
+        } else if meta.path.is_ident("apply_json") {
+            self.apply_json = true;
+        } else if meta.path.is_ident("serde_state") {
+            if self.atomic {
+                return Err(meta.error(
+                    "`serde_state` can't be combined with `atomic`, skip the tracker with the `serde` option instead",
+                ));
+            }
+            self.serde_state = true;
+        } else if meta.path.is_ident("impl_partial_eq") {
+            self.impl_partial_eq = true;
+        } else if meta.path.is_ident("impl_eq") {
+            self.impl_partial_eq = true;
+            self.impl_eq = true;
+        } else if meta.path.is_ident("impl_hash") {
+            self.impl_hash = true;
+        } else if meta.path.is_ident("impl_default") {
+            self.impl_default = true;
+        } else if meta.path.is_ident("debug") {
+            self.debug = true;
+        } else if meta.path.is_ident("clone_resets") {
+            if self.clone_keeps {
+                return Err(meta.error("`clone_resets` can't be combined with `clone_keeps`"));
+            }
+            self.clone_resets = true;
+        } else if meta.path.is_ident("clone_keeps") {
+            if self.clone_resets {
+                return Err(meta.error("`clone_keeps` can't be combined with `clone_resets`"));
+            }
+            self.clone_keeps = true;
+        } else if meta.path.is_ident("project") {
+            self.project = true;
+        } else if meta.path.is_ident("typed_mask") {
+            if self.bitflags {
+                return Err(meta.error(
+                    "`typed_mask` can't be combined with `bitflags`, which already generates a mask type",
+                ));
+            }
+            self.typed_mask = true;
+        } else if meta.path.is_ident("bitflags") {
+            if self.typed_mask {
+                return Err(meta.error(
+                    "`bitflags` can't be combined with `typed_mask`, which already generates a mask type",
+                ));
+            }
+            self.typed_mask = true;
+            self.bitflags = true;
+        } else if meta.path.is_ident("debug_values") {
+            self.debug_values = true;
+        } else if meta.path.is_ident("skip_methods") {
+            meta.parse_nested_meta(|meta| {
+                let name = meta.path.require_ident()?.to_string();
+                if REQUIRED_METHODS.contains(&name.as_str()) {
+                    return Err(meta.error(format!(
+                        "`{name}` can't be skipped because other methods depend on it"
+                    )));
+                }
+                if !SKIPPABLE_METHODS.contains(&name.as_str()) {
+                    return Err(meta.error(format!(
+                        "unknown method, expected one of {}",
+                        SKIPPABLE_METHODS.join(", ")
+                    )));
+                }
+                self.skipped_methods.push(name);
+                Ok(())
+            })?;
+        } else if meta.path.is_ident("encapsulate") {
+            self.encapsulate = true;
+            self.constructor = true;
+        } else if meta.path.is_ident("field") {
+            let name: LitStr = meta.value()?.parse()?;
+            self.field = Some(name.parse()?);
+        } else if meta.path.is_ident("trait") {
+            let name: LitStr = meta.value()?.parse()?;
+            self.companion_trait = Some(name.parse()?);
+        } else if meta.path.is_ident("ty") {
+            let value = meta.value()?;
+            self.ty = Some(if value.peek(LitStr) {
+                value.parse::<LitStr>()?.parse()?
+            } else {
+                value.parse()?
+            });
+        } else if meta.path.is_ident("on_change") {
+            let value = meta.value()?;
+            self.on_change = Some(if value.peek(LitStr) {
+                value.parse::<LitStr>()?.parse()?
+            } else {
+                value.parse()?
+            });
+        } else if meta.path.is_ident("vis") {
+            self.vis = Some(parse_vis(meta.value()?)?);
+        } else if meta.path.is_ident("tracker_vis") {
+            self.tracker_vis = Some(parse_vis(meta.value()?)?);
+        } else if meta.path.is_ident("tracker_attrs") {
+            let content;
+            parenthesized!(content in meta.input);
+            self.tracker_attrs
+                .extend(content.parse_terminated(Meta::parse, Token![,])?);
+        } else if meta.path.is_ident("strict_attrs") {
+            self.strict_attrs = true;
+        } else if meta.path.is_ident("compact") {
+            self.compact = true;
+        } else if meta.path.is_ident("trace") {
+            self.trace = true;
+        } else if meta.path.is_ident("trait_only") {
+            self.trait_only = true;
+        } else if meta.path.is_ident("prefix") {
+            let prefix: LitStr = meta.value()?.parse()?;
+            self.prefix = Some(prefix.value());
+        } else if meta.path.is_ident("no_eq") {
+            self.no_eq = true;
+        } else if meta.path.is_ident("minimal") {
+            self.no_delta = true;
+            self.no_update = true;
+            self.skipped_methods.extend(
+                SKIPPABLE_METHODS
+                    .iter()
+                    .filter(|name| **name != "set")
+                    .map(|name| (*name).to_owned()),
+            );
+        } else {
+            return Err(meta.error("unknown tracker option"));
+        }
+        require_end(&meta)
+    }
+}
+
+/// Parse a visibility like `pub(crate)` or `"pub(crate)"`.
+///
+/// Private visibility can only be written as an empty string.
+pub(crate) fn parse_vis(value: ParseStream<'_>) -> Result<Visibility> {
+    if value.peek(LitStr) {
+        value.parse::<LitStr>()?.parse()
+    } else {
+        value.parse()
+    }
+}
+
+/// Return an error if a value was passed to an option that doesn't take one, like `debug = true`.
+pub(crate) fn require_end(meta: &ParseNestedMeta<'_>) -> Result<()> {
+    if meta.input.is_empty() || meta.input.peek(Token![,]) {
+        Ok(())
+    } else {
+        let name = meta.path.to_token_stream();
+        Err(meta.error(format!("`{name}` doesn't take a value")))
+    }
+}
@@ -0,0 +1,59 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::{parse_quote, Field};
+
+use crate::Context;
+
+/// The injected field that stores the order of the changes.
+pub(crate) fn field(ctx: &Context) -> Field {
+    let len = ctx.bit_count();
+    parse_quote! {
+        tracker_order: ::tracker::ChangeOrder<#len>
+    }
+}
+
+/// Append the newly changed bits in `__tracker_new` to the order of `this`.
+pub(crate) fn record(ctx: &Context, this: &TokenStream2) -> TokenStream2 {
+    let bits = ctx.mask_bits(quote! { __tracker_new });
+    quote! { #this.tracker_order.record(#bits); }
+}
+
+/// Forget the order of the bits of `mask` in `this` when they're marked as unchanged.
+pub(crate) fn remove(ctx: &Context, this: &TokenStream2, mask: TokenStream2) -> TokenStream2 {
+    let bits = ctx.mask_bits(mask);
+    quote! { #this.tracker_order.remove(#bits); }
+}
+
+/// Forget the order of the changes of `self` when it's reset.
+pub(crate) fn reset() -> TokenStream2 {
+    quote! {
+        self.tracker_order.clear();
+    }
+}
+
+/// Generate the `changes_in_order()` method.
+pub(crate) fn generate(ctx: &Context) -> TokenStream2 {
+    let Context {
+        ident,
+        vis: struct_vis,
+        generics,
+        where_clause,
+        tracker_ty,
+        bits_ty,
+        ..
+    } = ctx;
+    let self_ty = ctx.self_ty();
+    let current = ctx.mask_bits(ctx.tracker_value(&quote! { self }));
+    let mask = ctx.mask_from_bits(quote! { bit as #bits_ty });
+
+    quote_spanned! { ident.span() =>
+        impl #generics #self_ty #where_clause {
+            /// Iterate over the bit masks of the changed fields in the order
+            /// in which they were first marked as changed since the last reset.
+            #[allow(dead_code)]
+            #struct_vis fn changes_in_order(&self) -> impl ::core::iter::Iterator<Item = #tracker_ty> + '_ {
+                self.tracker_order.iter(#current).map(|bit| #mask)
+            }
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::{parse_quote, Field};
+
+use crate::Context;
+
+/// The injected field that stores all changes since it was cleared.
+pub(crate) fn field(ctx: &Context) -> Field {
+    let tracker_ty = &ctx.tracker_ty;
+    parse_quote! {
+        tracker_persistent: #tracker_ty
+    }
+}
+
+/// Add the bits of `mask` to the persistent mask of `this`.
+///
+/// All marked bits are added, not only the new ones, because the persistent
+/// mask can be cleared while the bits are still set in the tracker.
+pub(crate) fn record(this: &TokenStream2, mask: &TokenStream2) -> TokenStream2 {
+    quote! { #this.tracker_persistent |= #mask; }
+}
+
+/// Generate the `ever_changed()`, `ever_changed_any()` and `clear_persistent()` methods.
+pub(crate) fn generate(ctx: &Context) -> TokenStream2 {
+    let Context {
+        ident,
+        vis: struct_vis,
+        generics,
+        where_clause,
+        tracker_ty,
+        ..
+    } = ctx;
+    let self_ty = ctx.self_ty();
+    let empty_mask = ctx.empty_mask();
+
+    quote_spanned! { ident.span() =>
+        impl #generics #self_ty #where_clause {
+            /// Check for changes with a bit mask since the persistent changes were cleared.
+            ///
+            /// Unlike `changed()`, this is not affected by `reset()`.
+            #[allow(dead_code)]
+            #[must_use]
+            #struct_vis fn ever_changed(&self, mask: #tracker_ty) -> bool {
+                self.tracker_persistent & mask != #empty_mask
+            }
+
+            /// Check whether any field changed since the persistent changes were cleared.
+            #[allow(dead_code)]
+            #[must_use]
+            #struct_vis fn ever_changed_any(&self) -> bool {
+                self.tracker_persistent != #empty_mask
+            }
+
+            /// Forget all persistent changes, for example after the data was saved.
+            #[allow(dead_code)]
+            #struct_vis fn clear_persistent(&mut self) {
+                self.tracker_persistent = #empty_mask;
+            }
+        }
+    }
+}
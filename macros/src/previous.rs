@@ -0,0 +1,116 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::{parse_quote, Field};
+
+use crate::{delta, hash_eq, Context, TrackedField};
+
+/// The injected field that stores the previous values.
+pub(crate) fn field(ctx: &Context) -> Field {
+    let previous_ident = ctx.companion_ident("Previous");
+    let generic_idents = &ctx.generic_idents;
+    parse_quote! {
+        tracker_previous: #previous_ident < #generic_idents >
+    }
+}
+
+/// Store the value of `field` unless it was already changed.
+pub(crate) fn store(
+    ctx: &Context,
+    this: &TokenStream2,
+    field: &TrackedField,
+    pending: Option<&TokenStream2>,
+) -> TokenStream2 {
+    let id = &field.ident;
+    let mask_id = field.mask_ident();
+    let self_ty = ctx.self_ty();
+    let pending = pending.map(|pending| quote! { | #pending });
+    let empty_mask = ctx.empty_mask();
+    let tracker_value = ctx.tracker_value(this);
+    quote_spanned! { field.span =>
+        if (#tracker_value #pending) & <#self_ty>::#mask_id() == #empty_mask {
+            #this.tracker_previous.#id =
+                ::core::option::Option::Some(::core::clone::Clone::clone(&#this.#id));
+        }
+    }
+}
+
+/// Forget all previous values.
+pub(crate) fn reset() -> TokenStream2 {
+    quote! {
+        self.tracker_previous = ::core::default::Default::default();
+    }
+}
+
+/// Generate the struct that stores the previous values as well as
+/// the `previous_#field()` and `revert_#field()` methods.
+pub(crate) fn generate(ctx: &Context) -> TokenStream2 {
+    let Context {
+        ident,
+        generics,
+        where_clause,
+        ..
+    } = ctx;
+    let self_ty = ctx.self_ty();
+    let previous_ident = ctx.companion_ident("Previous");
+
+    let mut methods = TokenStream2::new();
+    for field in &ctx.fields {
+        let TrackedField {
+            ident: id,
+            ty,
+            vis,
+            cfg,
+            span: id_span,
+            ..
+        } = field;
+        let mask_id = field.mask_ident();
+        let previous_id = field.method_ident("previous_");
+        let revert_id = field.method_ident("revert_");
+        let unmark = ctx.unmark(&quote! { self }, quote! { Self::#mask_id() });
+        let invalidate_hash = hash_eq::invalidate(ctx, &quote! { self }, field);
+
+        let previous_doc = ctx.field_doc(format!(
+            "Get the value the {id} field had before it was changed for the first time since the last reset."
+        ));
+        let revert_doc = ctx.field_doc(format!(
+            "Restore the value the {id} field had before it was changed and mark the field as unchanged."
+        ));
+
+        methods.extend(quote_spanned! { *id_span =>
+            #(#cfg)*
+            #[allow(dead_code, non_snake_case)]
+            #[must_use]
+            #previous_doc
+            #vis fn #previous_id(&self) -> ::core::option::Option<&#ty> {
+                self.tracker_previous.#id.as_ref()
+            }
+
+            #(#cfg)*
+            #[allow(dead_code, non_snake_case)]
+            #revert_doc
+            #vis fn #revert_id(&mut self) {
+                if let ::core::option::Option::Some(previous) = self.tracker_previous.#id.take() {
+                    #invalidate_hash
+                    self.#id = previous;
+                }
+                #unmark
+            }
+        });
+    }
+
+    let previous_doc = format!("Previous values of the changed fields of [`{ident}`].");
+    let previous_struct = delta::option_struct(ctx, &previous_ident, &previous_doc, |id| {
+        format!("Value of the {id} field before it was changed.")
+    });
+
+    let allow_missing_docs = ctx.allow_missing_docs();
+
+    quote_spanned! { ident.span() =>
+        #previous_struct
+
+        #allow_missing_docs
+        impl #generics #self_ty #where_clause {
+            #methods
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote_spanned;
+use syn::parse_quote;
+
+use crate::{Context, TrackedField};
+
+/// Generate the view struct and the `project()` method.
+pub(crate) fn generate(ctx: &Context) -> TokenStream2 {
+    let Context {
+        ident,
+        vis: struct_vis,
+        generics,
+        generic_idents,
+        where_clause,
+        ..
+    } = ctx;
+    let self_ty = ctx.self_ty();
+    let view_ident = ctx.companion_ident("View");
+    let mut view_generics = ctx.generics.clone();
+    view_generics.params.insert(0, parse_quote! { '__tracker });
+
+    let mut view_fields = TokenStream2::new();
+    let mut view_values = TokenStream2::new();
+    for field in &ctx.fields {
+        let TrackedField {
+            ident: id,
+            ty,
+            vis,
+            cfg,
+            span: id_span,
+            ..
+        } = field;
+        let mask_id = field.mask_ident();
+        let field_doc = ctx.field_doc(format!("The {id} field and whether it changed."));
+
+        view_fields.extend(quote_spanned! { *id_span =>
+            #(#cfg)*
+            #field_doc
+            #vis #id: ::tracker::FieldView<'__tracker, #ty>,
+        });
+        view_values.extend(quote_spanned! { *id_span =>
+            #(#cfg)*
+            #id: ::tracker::FieldView::new(&self.#id, ::tracker::Track::changed(self, Self::#mask_id())),
+        });
+    }
+
+    let phantom_field = ctx.phantom_field();
+    let phantom_value = ctx.phantom_value();
+
+    let view_doc = format!(
+        "Tracked fields of [`{ident}`] paired with their changed flag, created by [`{ident}::project`]."
+    );
+    let allow_missing_docs = ctx.allow_missing_docs();
+    quote_spanned! { ident.span() =>
+        #[doc = #view_doc]
+        #allow_missing_docs
+        #struct_vis struct #view_ident #view_generics #where_clause {
+            #view_fields
+            #phantom_field
+        }
+
+        impl #generics #self_ty #where_clause {
+            /// Get a view that pairs every tracked field with
+            /// the information whether it changed.
+            ///
+            /// Fields marked as `do_not_track` are not part of the view.
+            #[allow(dead_code)]
+            #[must_use]
+            #struct_vis fn project(&self) -> #view_ident <'_, #generic_idents > {
+                #view_ident {
+                    #view_values
+                    #phantom_value
+                }
+            }
+        }
+    }
+}
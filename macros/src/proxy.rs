@@ -0,0 +1,182 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::parse_quote;
+
+use crate::{Context, Nested, TrackedField};
+
+/// Generate the `update_all()` method.
+pub(crate) fn update_all_method(ctx: &Context) -> TokenStream2 {
+    let struct_vis = &ctx.vis;
+    let generic_idents = &ctx.generic_idents;
+    let proxy_ident = ctx.companion_ident("Proxy");
+    let mark = ctx.mark(&quote! { self }, quote! { changes });
+    let empty_mask = ctx.empty_mask();
+
+    quote_spanned! { ctx.ident.span() =>
+        /// Update several fields at once through a proxy.
+        ///
+        /// All fields modified through the proxy are marked as changed
+        /// after the closure returns, untouched fields stay unchanged.
+        #[allow(dead_code)]
+        #struct_vis fn update_all<F: FnOnce(&mut #proxy_ident < '_, #generic_idents >)>(&mut self, f: F) {
+            let mut proxy = #proxy_ident {
+                inner: self,
+                changes: #empty_mask,
+            };
+            f(&mut proxy);
+            let changes = proxy.changes;
+            #mark
+        }
+    }
+}
+
+/// Generate the proxy struct used by `update_all()`.
+pub(crate) fn generate(ctx: &Context) -> TokenStream2 {
+    let Context {
+        ident,
+        vis: struct_vis,
+        generic_idents,
+        where_clause,
+        tracker_ty,
+        ..
+    } = ctx;
+    let self_ty = ctx.self_ty();
+    let proxy_ident = ctx.companion_ident("Proxy");
+    let mut proxy_generics = ctx.generics.clone();
+    proxy_generics.params.insert(0, parse_quote! { '__tracker });
+
+    let mut proxy_methods = TokenStream2::new();
+    for field in &ctx.fields {
+        let TrackedField {
+            ident: id,
+            ty,
+            cfg,
+            span: id_span,
+            ..
+        } = field;
+        let mask_id = field.mask_ident();
+        let id_span = *id_span;
+
+        let get_id = field.method_ident("get_");
+        let get_mut_id = field.method_ident("get_mut_");
+        let set_id = field.method_ident("set_");
+        let get_vis = field.method_vis("get");
+        let get_mut_vis = field.method_vis("get_mut");
+        let set_vis = field.method_vis("set");
+        let before_change = ctx.before_change(
+            &quote! { self.inner },
+            field,
+            Some(&quote! { self.changes }),
+        );
+
+        let get_doc = ctx.field_doc(format!("Get an immutable reference to the {id} field."));
+        let get_mut_doc = ctx.field_doc(format!(
+            "Get a mutable reference to the {id} field and record the field as changed."
+        ));
+
+        // Nested structs track changes made through references themselves.
+        let record = if field.nested == Some(Nested::Direct) {
+            TokenStream2::new()
+        } else {
+            quote_spanned! { id_span =>
+                #before_change
+                self.changes |= <#self_ty>::#mask_id();
+            }
+        };
+
+        // Methods skipped with field attributes are left out of the proxy as well.
+        if !field.skips("get") {
+            proxy_methods.extend(quote_spanned! { id_span =>
+                #(#cfg)*
+                #[must_use]
+                #[inline]
+                #get_doc
+                #get_vis fn #get_id(&self) -> &#ty {
+                    &self.inner.#id
+                }
+            });
+        }
+        if !field.skips("get_mut") {
+            proxy_methods.extend(quote_spanned! { id_span =>
+                #(#cfg)*
+                #[must_use]
+                #[inline]
+                #get_mut_doc
+                #get_mut_vis fn #get_mut_id(&mut self) -> &mut #ty {
+                    #record
+                    &mut self.inner.#id
+                }
+            });
+        }
+
+        let set_variants = if field.skips("set") {
+            Vec::new()
+        } else {
+            field.no_eq_variants()
+        };
+        for (no_eq, cfg) in set_variants {
+            if no_eq {
+                let set_doc = ctx.field_doc(format!(
+                    "Set the value of field {id} and record the field as changed."
+                ));
+                proxy_methods.extend(quote_spanned! { id_span =>
+                    #(#cfg)*
+                    #[inline]
+                    #set_doc
+                    #set_vis fn #set_id(&mut self, value: #ty) {
+                        #before_change
+                        self.changes |= <#self_ty>::#mask_id();
+                        self.inner.#id = value;
+                    }
+                });
+            } else {
+                let set_doc = ctx.field_doc(format!("Set the value of field {id} and record the field as changed if it's not equal to the previous value."));
+                let eq_bound = field.eq_bound(ctx);
+                let set_body = if ctx.uses_shared_setter(field) {
+                    quote_spanned! { id_span =>
+                        ::tracker::__private::set(&mut self.inner.#id, value, &mut self.changes, <#self_ty>::#mask_id());
+                    }
+                } else {
+                    let ne = field.ne(quote! { self.inner.#id }, quote! { value });
+                    quote_spanned! { id_span =>
+                        if #ne {
+                            #before_change
+                            self.changes |= <#self_ty>::#mask_id();
+                        }
+                        self.inner.#id = value;
+                    }
+                };
+                proxy_methods.extend(quote_spanned! { id_span =>
+                    #(#cfg)*
+                    #[inline]
+                    #set_doc
+                    #set_vis fn #set_id(&mut self, value: #ty)
+                    where
+                        #eq_bound
+                    {
+                        #set_body
+                    }
+                });
+            }
+        }
+    }
+
+    let proxy_doc = format!(
+        "Proxy passed to [`{ident}::update_all`] that records every field modified through it."
+    );
+    let allow_missing_docs = ctx.allow_missing_docs();
+    quote_spanned! { ident.span() =>
+        #[doc = #proxy_doc]
+        #[allow(dead_code)]
+        #struct_vis struct #proxy_ident #proxy_generics #where_clause {
+            inner: &'__tracker mut #self_ty,
+            changes: #tracker_ty,
+        }
+
+        #allow_missing_docs
+        #[allow(dead_code, non_snake_case)]
+        impl #proxy_generics #proxy_ident < '__tracker, #generic_idents > #where_clause {
+            #proxy_methods
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::{parse_quote, Field};
+
+use crate::{Context, TrackedField};
+
+/// The injected field that collects the bits marked through the change handles.
+pub(crate) fn field(remote_ty: &TokenStream2) -> Field {
+    parse_quote! {
+        tracker_remote: ::tracker::RemoteChanges<#remote_ty>
+    }
+}
+
+/// Generate the change handle type as well as the `change_handle()` and `absorb_remote()` methods.
+pub(crate) fn generate(ctx: &Context, remote_ty: &TokenStream2) -> TokenStream2 {
+    let Context {
+        ident,
+        vis: struct_vis,
+        generics,
+        where_clause,
+        tracker_ty,
+        ..
+    } = ctx;
+    let self_ty = ctx.self_ty();
+    let handle_ident = ctx.companion_ident("ChangeHandle");
+
+    let mut mark_methods = TokenStream2::new();
+    let mut before_changes = TokenStream2::new();
+    let empty_mask = ctx.empty_mask();
+    for field in &ctx.fields {
+        let TrackedField {
+            ident: id,
+            vis,
+            cfg,
+            span: id_span,
+            ..
+        } = field;
+        let mask_id = field.mask_ident();
+        let mark_id = field.method_ident("mark_");
+        let bits = field.bits();
+        let doc = ctx.field_doc(format!(
+            "Mark the {id} field as changed, which is added to the tracker by `absorb_remote()`."
+        ));
+        mark_methods.extend(quote_spanned! { *id_span =>
+            #(#cfg)*
+            #[allow(dead_code)]
+            #[inline]
+            #doc
+            #vis fn #mark_id(&self) {
+                self.handle.mark(#bits);
+            }
+        });
+        let before_change = ctx.before_change(&quote! { self }, field, None);
+        if !before_change.is_empty() {
+            before_changes.extend(quote_spanned! { *id_span =>
+                #(#cfg)*
+                if mask & Self::#mask_id() != #empty_mask {
+                    #before_change
+                }
+            });
+        }
+    }
+    let mask_bits = ctx.mask_bits(quote! { mask });
+    let remote_mask = ctx.mask_from_bits(quote! { self.tracker_remote.take() });
+    let mark = ctx.mark(&quote! { self }, quote! { mask });
+    let handle_doc = format!(
+        "A handle that marks fields of [`{ident}`] as changed from other threads, created with [`{ident}::change_handle`].\n\n\
+         The fields are added to the tracker when the owner calls [`{ident}::absorb_remote`]."
+    );
+    let allow_missing_docs = ctx.allow_missing_docs();
+
+    quote_spanned! { ident.span() =>
+        #[doc = #handle_doc]
+        #[derive(Debug, Clone)]
+        #struct_vis struct #handle_ident {
+            handle: ::tracker::ChangeHandle<#remote_ty>,
+        }
+
+        #allow_missing_docs
+        impl #handle_ident {
+            /// Mark the fields of `mask` as changed, which are added to the tracker by `absorb_remote()`.
+            #[allow(dead_code)]
+            #[inline]
+            #struct_vis fn mark(&self, mask: #tracker_ty) {
+                self.handle.mark(#mask_bits);
+            }
+
+            #mark_methods
+        }
+
+        impl #generics #self_ty #where_clause {
+            /// Create a handle that marks fields as changed from anywhere, including other threads.
+            ///
+            /// Clones of the struct have their own handles.
+            #[allow(dead_code)]
+            #[must_use]
+            #struct_vis fn change_handle(&self) -> #handle_ident {
+                #handle_ident {
+                    handle: self.tracker_remote.handle(),
+                }
+            }
+
+            /// Mark the fields that were marked through the handles
+            /// since the last call as changed in the tracker.
+            #[allow(dead_code)]
+            #struct_vis fn absorb_remote(&mut self) {
+                let mask = #remote_mask;
+                if mask == #empty_mask {
+                    return;
+                }
+                #before_changes
+                #mark
+            }
+        }
+    }
+}
@@ -0,0 +1,109 @@
+//! Support for the attributes of `serde`.
+
+use syn::ext::IdentExt;
+use syn::meta::ParseNestedMeta;
+use syn::{Attribute, Expr, LitStr, Result, Token};
+
+use crate::{Context, TrackedField};
+
+/// Whether a name is used for serialization or deserialization.
+#[derive(Clone, Copy)]
+pub(crate) enum Direction {
+    Serialize,
+    Deserialize,
+}
+
+/// Get the name `serde` uses for a field, respecting
+/// `#[serde(rename = "...")]` and `#[serde(rename_all = "...")]`.
+pub(crate) fn field_name(ctx: &Context, field: &TrackedField, direction: Direction) -> String {
+    if let Some(name) = rename(&field.attrs, "rename", direction) {
+        return name;
+    }
+
+    let name = field.ident.unraw().to_string();
+    match rename(&ctx.attrs, "rename_all", direction) {
+        Some(rule) => apply_rename_rule(&rule, &name),
+        None => name,
+    }
+}
+
+/// Look for `key = "..."` or `key(serialize = "...", deserialize = "...")`
+/// in all `#[serde(...)]` attributes.
+fn rename(attrs: &[Attribute], key: &str, direction: Direction) -> Option<String> {
+    let mut name = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+        // Invalid serde attributes are reported by serde itself.
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident(key) {
+                if meta.input.peek(Token![=]) {
+                    name = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else {
+                    meta.parse_nested_meta(|meta| {
+                        let selected = match direction {
+                            Direction::Serialize => meta.path.is_ident("serialize"),
+                            Direction::Deserialize => meta.path.is_ident("deserialize"),
+                        };
+                        if selected {
+                            name = Some(meta.value()?.parse::<LitStr>()?.value());
+                        } else {
+                            skip(meta)?;
+                        }
+                        Ok(())
+                    })?;
+                }
+                Ok(())
+            } else {
+                skip(meta)
+            }
+        });
+    }
+    name
+}
+
+/// Skip an unrelated nested meta item.
+fn skip(meta: ParseNestedMeta<'_>) -> Result<()> {
+    if meta.input.peek(Token![=]) {
+        meta.value()?.parse::<Expr>()?;
+        Ok(())
+    } else if meta.input.peek(syn::token::Paren) {
+        meta.parse_nested_meta(skip)
+    } else {
+        Ok(())
+    }
+}
+
+/// Apply a `rename_all` rule to a snake case field name like serde does.
+fn apply_rename_rule(rule: &str, name: &str) -> String {
+    match rule {
+        "UPPERCASE" | "SCREAMING_SNAKE_CASE" => name.to_ascii_uppercase(),
+        "PascalCase" => pascal_case(name),
+        "camelCase" => {
+            let pascal = pascal_case(name);
+            let mut chars = pascal.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+                None => pascal,
+            }
+        }
+        "kebab-case" => name.replace('_', "-"),
+        "SCREAMING-KEBAB-CASE" => name.to_ascii_uppercase().replace('_', "-"),
+        _ => name.to_owned(),
+    }
+}
+
+/// Convert a snake case name to `PascalCase`.
+pub(crate) fn pascal_case(name: &str) -> String {
+    let mut pascal = String::new();
+    let mut capitalize = true;
+    for ch in name.chars() {
+        if ch == '_' {
+            capitalize = true;
+        } else if capitalize {
+            pascal.push(ch.to_ascii_uppercase());
+            capitalize = false;
+        } else {
+            pascal.push(ch);
+        }
+    }
+    pascal
+}
@@ -0,0 +1,107 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote, quote_spanned};
+use syn::ext::IdentExt;
+use syn::{parse_quote, Attribute, Ident};
+
+use crate::{Context, TrackedField};
+
+/// Name of the key that stores the changed fields.
+const KEY: &str = "__changed";
+
+/// Identifier of the module with the functions that (de)serialize the tracker.
+fn module_ident(ctx: &Context) -> Ident {
+    format_ident!("__tracker_serde_state_{}", ctx.ident)
+}
+
+/// The `serde` attribute of the tracker field.
+pub(crate) fn attribute(ctx: &Context) -> Attribute {
+    let module = module_ident(ctx).to_string();
+    parse_quote! { #[serde(rename = #KEY, default, with = #module)] }
+}
+
+/// Generate the module with the functions that (de)serialize
+/// the tracker as a list of changed field names.
+pub(crate) fn generate(ctx: &Context) -> TokenStream2 {
+    let Context {
+        ident,
+        tracker_ty,
+        bits_ty,
+        options,
+        ..
+    } = ctx;
+    let module = module_ident(ctx);
+    // The functions are defined in a child module.
+    let (mask_ty, bits, mask) = if options.typed_mask {
+        (
+            quote! { super::#tracker_ty },
+            quote! { tracker.bits() },
+            if options.bitflags {
+                quote! { super::#tracker_ty::from_bits_retain(bits) }
+            } else {
+                quote! { super::#tracker_ty::from_bits(bits) }
+            },
+        )
+    } else {
+        (quote! { #tracker_ty }, quote! { *tracker }, quote! { bits })
+    };
+
+    let mut push_names = TokenStream2::new();
+    let mut match_arms = TokenStream2::new();
+    for field in &ctx.fields {
+        let TrackedField {
+            cfg, span: id_span, ..
+        } = field;
+        let name = field.ident.unraw().to_string();
+        let bits = field.bits();
+
+        push_names.extend(quote_spanned! { *id_span =>
+            #(#cfg)*
+            if tracker & #bits != 0 {
+                names.push(#name);
+            }
+        });
+        match_arms.extend(quote_spanned! { *id_span =>
+            #(#cfg)*
+            #name => #bits,
+        });
+    }
+
+    quote_spanned! { ident.span() =>
+        /// Serialize the tracker as a list of the names of all changed fields.
+        #[doc(hidden)]
+        #[allow(non_snake_case)]
+        mod #module {
+            #[allow(clippy::trivially_copy_pass_by_ref, unused_mut)]
+            pub(super) fn serialize<S>(
+                tracker: &#mask_ty,
+                serializer: S,
+            ) -> ::core::result::Result<S::Ok, S::Error>
+            where
+                S: ::tracker::__private::serde::Serializer,
+            {
+                let tracker: #bits_ty = #bits;
+                let mut names: ::tracker::__private::Vec<&str> = ::tracker::__private::Vec::new();
+                #push_names
+                ::tracker::__private::serde::Serialize::serialize(&names, serializer)
+            }
+
+            /// Names that don't belong to a tracked field are ignored.
+            pub(super) fn deserialize<'de, D>(
+                deserializer: D,
+            ) -> ::core::result::Result<#mask_ty, D::Error>
+            where
+                D: ::tracker::__private::serde::Deserializer<'de>,
+            {
+                let names: ::tracker::__private::Vec<::tracker::__private::String> =
+                    ::tracker::__private::serde::Deserialize::deserialize(deserializer)?;
+                let bits: #bits_ty = names.iter().fold(0, |bits, name| {
+                    bits | match name.as_str() {
+                        #match_arms
+                        _ => 0,
+                    }
+                });
+                ::core::result::Result::Ok(#mask)
+            }
+        }
+    }
+}
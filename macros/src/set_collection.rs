@@ -0,0 +1,175 @@
+//! Support for fields marked with `#[tracker::set_collection]`.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{GenericArgument, PathArguments, Type};
+
+use crate::{call_site_at, Context, TrackedField};
+
+/// The set type of a field marked with `#[tracker::set_collection]`.
+pub(crate) enum SetCollection {
+    /// `HashSet<T, S>` with the element type and the hasher, if there is one.
+    Hash(Type, Option<Box<Type>>),
+    /// `BTreeSet<T>` with the element type.
+    BTree(Type),
+}
+
+impl SetCollection {
+    /// Detect the set type from the last segment of the path of `ty`.
+    pub(crate) fn of(ty: &Type) -> syn::Result<Self> {
+        let set = match ty {
+            Type::Path(path) => path.path.segments.last().and_then(|last| {
+                let PathArguments::AngleBracketed(args) = &last.arguments else {
+                    return None;
+                };
+                let mut types = args.args.iter().map(|arg| match arg {
+                    GenericArgument::Type(ty) => Some(ty.clone()),
+                    _ => None,
+                });
+                match (types.next().flatten(), types.next(), types.next()) {
+                    (Some(element), None, None) if last.ident == "HashSet" => {
+                        Some(Self::Hash(element, None))
+                    }
+                    (Some(element), Some(Some(hasher)), None) if last.ident == "HashSet" => {
+                        Some(Self::Hash(element, Some(Box::new(hasher))))
+                    }
+                    (Some(element), None, None) if last.ident == "BTreeSet" => {
+                        Some(Self::BTree(element))
+                    }
+                    _ => None,
+                }
+            }),
+            _ => None,
+        };
+        set.ok_or_else(|| {
+            syn::Error::new_spanned(
+                ty,
+                "`set_collection` can only be used for fields of the types `HashSet<T>` and `BTreeSet<T>`",
+            )
+        })
+    }
+
+    fn element(&self) -> &Type {
+        match self {
+            Self::Hash(element, _) | Self::BTree(element) => element,
+        }
+    }
+
+    /// The bounds the set needs for inserting and removing elements.
+    fn bounds(&self, ctx: &Context) -> TokenStream2 {
+        let element = ctx.bound_ty(self.element(), "'__tracker");
+        let span = call_site_at(self.element().span());
+        match self {
+            Self::Hash(_, hasher) => {
+                let hasher = hasher.as_ref().map(|hasher| {
+                    let hasher = ctx.bound_ty(hasher, "'__tracker");
+                    quote_spanned! { span =>
+                        for<'__tracker> #hasher: ::core::hash::BuildHasher,
+                    }
+                });
+                quote_spanned! { span =>
+                    for<'__tracker> #element: ::core::hash::Hash + ::core::cmp::Eq,
+                    #hasher
+                }
+            }
+            Self::BTree(_) => quote_spanned! { span =>
+                for<'__tracker> #element: ::core::cmp::Ord,
+            },
+        }
+    }
+}
+
+/// Generate `insert_#field()`, `remove_#field()` and `clear_#field()` for set fields.
+pub(crate) fn methods(ctx: &Context) -> TokenStream2 {
+    let mut methods = TokenStream2::new();
+    for field in &ctx.fields {
+        let Some(set) = &field.set_collection else {
+            continue;
+        };
+        if ctx.skips_method(field, "set") {
+            continue;
+        }
+        let TrackedField {
+            ident: id,
+            cfg,
+            span,
+            ..
+        } = field;
+        let vis = field.method_vis("set");
+        let element = set.element();
+        let bounds = set.bounds(ctx);
+        let insert_id = field.method_ident("insert_");
+        let remove_id = field.method_ident("remove_");
+        let clear_id = field.method_ident("clear_");
+        let mask_id = field.mask_ident();
+        let this = quote! { self };
+        let before_change = ctx.before_change(&this, field, None);
+        let mark = ctx.mark(&this, quote_spanned! { *span => Self::#mask_id() });
+        let trace_insert = ctx.trace_change(&this, field, "insert");
+        let trace_remove = ctx.trace_change(&this, field, "remove");
+        let trace_clear = ctx.trace_change(&this, field, "clear");
+        let insert_doc = ctx.field_doc(format!(
+            "Insert `value` into the {id} field and mark it as changed if it wasn't present.\n\n\
+             Returns whether the value was inserted."
+        ));
+        let remove_doc = ctx.field_doc(format!(
+            "Remove `value` from the {id} field and mark it as changed if it was present.\n\n\
+             Returns whether the value was removed."
+        ));
+        let clear_doc = ctx.field_doc(format!(
+            "Remove all values from the {id} field and mark it as changed if it wasn't empty.\n\n\
+             Returns whether the field was marked as changed."
+        ));
+        methods.extend(quote_spanned! { *span =>
+            #(#cfg)*
+            #[inline]
+            #insert_doc
+            #vis fn #insert_id(&mut self, value: #element) -> bool
+            where
+                #bounds
+            {
+                if self.#id.contains(&value) {
+                    return false;
+                }
+                #before_change
+                #trace_insert
+                self.#id.insert(value);
+                #mark
+                true
+            }
+
+            #(#cfg)*
+            #[inline]
+            #remove_doc
+            #vis fn #remove_id(&mut self, value: &#element) -> bool
+            where
+                #bounds
+            {
+                if !self.#id.contains(value) {
+                    return false;
+                }
+                #before_change
+                #trace_remove
+                self.#id.remove(value);
+                #mark
+                true
+            }
+
+            #(#cfg)*
+            #[inline]
+            #clear_doc
+            #vis fn #clear_id(&mut self) -> bool {
+                if self.#id.is_empty() {
+                    return false;
+                }
+                #before_change
+                #trace_clear
+                self.#id.clear();
+                #mark
+                true
+            }
+        });
+    }
+    methods
+}
@@ -0,0 +1,114 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+
+use crate::{Context, TrackedField};
+
+/// Generate the snapshot struct as well as the `snapshot()`
+/// and `changed_since()` methods.
+pub(crate) fn generate(ctx: &Context) -> TokenStream2 {
+    let Context {
+        ident,
+        vis: struct_vis,
+        generics,
+        generic_idents,
+        where_clause,
+        tracker_ty,
+        ..
+    } = ctx;
+    let self_ty = ctx.self_ty();
+    let snapshot_ident = ctx.companion_ident("Snapshot");
+
+    let mut snapshot_fields = TokenStream2::new();
+    let mut snapshot_values = TokenStream2::new();
+    let mut checks = TokenStream2::new();
+    let variants = ctx.fields.iter().flat_map(|field| {
+        field
+            .no_eq_variants()
+            .into_iter()
+            .filter(|(no_eq, _)| !no_eq)
+            .map(move |(_, cfg)| (field, cfg))
+    });
+    for (field, cfg) in variants {
+        let TrackedField {
+            ident: id,
+            ty,
+            vis,
+            span: id_span,
+            ..
+        } = field;
+        let mask_id = field.mask_ident();
+        let field_doc = ctx.field_doc(format!(
+            "Value of the {id} field when the snapshot was taken."
+        ));
+
+        snapshot_fields.extend(quote_spanned! { *id_span =>
+            #(#cfg)*
+            #field_doc
+            #vis #id: #ty,
+        });
+        snapshot_values.extend(quote_spanned! { *id_span =>
+            #(#cfg)*
+            #id: ::core::clone::Clone::clone(&self.#id),
+        });
+        let ne = field.ne(quote! { self.#id }, quote! { snapshot.#id });
+        checks.extend(quote_spanned! { *id_span =>
+            #(#cfg)*
+            if #ne {
+                mask |= Self::#mask_id();
+            }
+        });
+    }
+
+    let phantom_field = ctx.phantom_field();
+    let empty_mask = ctx.empty_mask();
+    let phantom_value = ctx.phantom_value();
+
+    let snapshot_doc =
+        format!("Values of the tracked fields of [`{ident}`] created by [`{ident}::snapshot`].");
+
+    let eq_bounds = ctx.eq_bounds();
+
+    let allow_missing_docs = ctx.allow_missing_docs();
+
+    quote_spanned! { ident.span() =>
+        #[doc = #snapshot_doc]
+        #allow_missing_docs
+        #struct_vis struct #snapshot_ident #generics #where_clause {
+            #snapshot_fields
+            #phantom_field
+        }
+
+        impl #generics #self_ty #where_clause {
+            /// Take a snapshot of the current values of all tracked fields.
+            ///
+            /// Fields marked as `no_eq` can't be compared
+            /// and are not part of the snapshot.
+            #[allow(dead_code)]
+            #[must_use]
+            #struct_vis fn snapshot(&self) -> #snapshot_ident < #generic_idents > {
+                #snapshot_ident {
+                    #snapshot_values
+                    #phantom_value
+                }
+            }
+
+            /// Compare the current values with a snapshot and get
+            /// a bit mask of the fields that differ.
+            ///
+            /// In contrast to the tracker, a field that was changed and
+            /// then set back to its old value is not reported.
+            /// Fields marked as `no_eq` are never part of the returned mask.
+            #[allow(dead_code, unused_variables)]
+            #[must_use]
+            #struct_vis fn changed_since(&self, snapshot: &#snapshot_ident < #generic_idents >) -> #tracker_ty
+            where
+                #eq_bounds
+            {
+                #[allow(unused_mut)]
+                let mut mask = #empty_mask;
+                #checks
+                mask
+            }
+        }
+    }
+}
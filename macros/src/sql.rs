@@ -0,0 +1,161 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote_spanned;
+use syn::ext::IdentExt;
+
+use crate::{Context, TrackedField};
+
+/// Keywords that need to be quoted when used as column names.
+const RESERVED: &[&str] = &[
+    "all",
+    "and",
+    "any",
+    "array",
+    "as",
+    "asc",
+    "both",
+    "case",
+    "cast",
+    "check",
+    "column",
+    "constraint",
+    "create",
+    "default",
+    "desc",
+    "distinct",
+    "do",
+    "else",
+    "end",
+    "false",
+    "for",
+    "foreign",
+    "from",
+    "grant",
+    "group",
+    "having",
+    "in",
+    "into",
+    "is",
+    "join",
+    "leading",
+    "limit",
+    "not",
+    "null",
+    "offset",
+    "on",
+    "only",
+    "or",
+    "order",
+    "primary",
+    "references",
+    "select",
+    "table",
+    "then",
+    "to",
+    "true",
+    "union",
+    "unique",
+    "user",
+    "using",
+    "when",
+    "where",
+    "with",
+];
+
+/// Quote a column name unless it's a plain lowercase identifier.
+///
+/// Quoted names are wrapped in double quotes and
+/// double quotes inside the name are doubled.
+pub(crate) fn quote_identifier(name: &str) -> String {
+    let plain = name
+        .chars()
+        .next()
+        .is_some_and(|ch| ch.is_ascii_lowercase() || ch == '_')
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '_')
+        && !RESERVED.contains(&name);
+
+    if plain {
+        name.to_owned()
+    } else {
+        format!("\"{}\"", name.replace('"', "\"\""))
+    }
+}
+
+/// Get the column name of a field.
+pub(crate) fn column_name(field: &TrackedField) -> String {
+    field
+        .column
+        .clone()
+        .unwrap_or_else(|| field.ident.unraw().to_string())
+}
+
+/// Generate the `changed_columns()` and `set_clause_with_placeholders()` methods.
+pub(crate) fn generate(ctx: &Context) -> TokenStream2 {
+    let Context {
+        ident,
+        vis: struct_vis,
+        generics,
+        where_clause,
+        ..
+    } = ctx;
+    let self_ty = ctx.self_ty();
+
+    let mut push_columns = TokenStream2::new();
+    let mut push_quoted = TokenStream2::new();
+    for field in &ctx.fields {
+        let TrackedField {
+            cfg, span: id_span, ..
+        } = field;
+        let mask_id = field.mask_ident();
+        let column = column_name(field);
+        let quoted = quote_identifier(&column);
+
+        push_columns.extend(quote_spanned! { *id_span =>
+            #(#cfg)*
+            if ::tracker::Track::changed(self, Self::#mask_id()) {
+                columns.push(#column);
+            }
+        });
+        push_quoted.extend(quote_spanned! { *id_span =>
+            #(#cfg)*
+            if ::tracker::Track::changed(self, Self::#mask_id()) {
+                columns.push(#quoted);
+            }
+        });
+    }
+
+    quote_spanned! { ident.span() =>
+        impl #generics #self_ty #where_clause {
+            /// Get the column names of all changed fields.
+            #[allow(dead_code)]
+            #[must_use]
+            #struct_vis fn changed_columns(&self) -> ::tracker::__private::Vec<&'static str> {
+                #[allow(unused_mut)]
+                let mut columns = ::tracker::__private::Vec::new();
+                #push_columns
+                columns
+            }
+
+            /// Get the `SET` clause of an SQL `UPDATE` statement for all
+            /// changed fields like `title = $1, count = $2`.
+            ///
+            /// The numbering of the placeholders begins at `start_index` and
+            /// follows the order of [`Self::changed_columns`].
+            /// Column names that aren't plain lowercase identifiers or that are
+            /// reserved keywords are quoted.
+            /// Returns `None` if no field was changed.
+            #[allow(dead_code)]
+            #[must_use]
+            #struct_vis fn set_clause_with_placeholders(
+                &self,
+                start_index: usize,
+            ) -> ::core::option::Option<::tracker::__private::String> {
+                #[allow(unused_mut)]
+                let mut columns = ::tracker::__private::Vec::new();
+                #push_quoted
+                ::tracker::__private::sql_set_clause(&columns, start_index)
+            }
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote_spanned;
+use syn::{parse_quote, Field};
+
+use crate::{delta, Context, TrackedField};
+
+/// The injected field that stores the staged values while changes are staged.
+pub(crate) fn field(ctx: &Context) -> Field {
+    let staged_ident = ctx.companion_ident("Staged");
+    let generic_idents = &ctx.generic_idents;
+    parse_quote! {
+        tracker_staged: ::core::option::Option<#staged_ident < #generic_idents >>
+    }
+}
+
+/// Generate the struct that stores the staged values as well as `begin()`,
+/// `commit()`, `abort()`, `is_staging()` and the `stage_#field()` and
+/// `staged_#field()` methods.
+pub(crate) fn generate(ctx: &Context) -> TokenStream2 {
+    let Context {
+        ident,
+        vis: struct_vis,
+        generics,
+        where_clause,
+        ..
+    } = ctx;
+    let self_ty = ctx.self_ty();
+    let staged_ident = ctx.companion_ident("Staged");
+
+    let mut methods = TokenStream2::new();
+    let mut commit_fields = TokenStream2::new();
+    for field in &ctx.fields {
+        // Fields without a setter can't be staged.
+        if ctx.skips_method(field, "set") {
+            continue;
+        }
+        let TrackedField {
+            ident: id,
+            ty,
+            cfg,
+            span: id_span,
+            ..
+        } = field;
+        let vis = field.method_vis("set");
+        let set_id = field.method_ident("set_");
+        let stage_id = field.method_ident("stage_");
+        let staged_id = field.method_ident("staged_");
+
+        let stage_doc = ctx.field_doc(format!(
+            "Stage `value` for the {id} field, which is set by `commit()`.\n\n\
+             A stage is begun if none was begun yet."
+        ));
+        let staged_doc = ctx.field_doc(format!(
+            "Get the staged value of the {id} field, or its current value if no value is staged."
+        ));
+
+        methods.extend(quote_spanned! { *id_span =>
+            #(#cfg)*
+            #[allow(dead_code)]
+            #[inline]
+            #stage_doc
+            #vis fn #stage_id(&mut self, value: #ty) {
+                self.tracker_staged
+                    .get_or_insert_with(::core::default::Default::default)
+                    .#id = ::core::option::Option::Some(value);
+            }
+
+            #(#cfg)*
+            #[allow(dead_code)]
+            #[must_use]
+            #[inline]
+            #staged_doc
+            #vis fn #staged_id(&self) -> &#ty {
+                match &self.tracker_staged {
+                    ::core::option::Option::Some(#staged_ident {
+                        #id: ::core::option::Option::Some(value),
+                        ..
+                    }) => value,
+                    _ => &self.#id,
+                }
+            }
+        });
+        commit_fields.extend(quote_spanned! { *id_span =>
+            #(#cfg)*
+            if let ::core::option::Option::Some(value) = staged.#id {
+                self.#set_id(value);
+            }
+        });
+    }
+
+    let staged_doc =
+        format!("Staged values of the fields of [`{ident}`] that are set by [`{ident}::commit`].");
+    let staged_struct = delta::option_struct(ctx, &staged_ident, &staged_doc, |id| {
+        format!("Staged value of the {id} field.")
+    });
+    let where_predicates = ctx.where_predicates();
+    let eq_bounds = ctx.eq_bounds();
+    let allow_missing_docs = ctx.allow_missing_docs();
+
+    quote_spanned! { ident.span() =>
+        #staged_struct
+
+        #allow_missing_docs
+        impl #generics #self_ty #where_clause {
+            /// Begin staging changes, which are set together by [`commit`](Self::commit)
+            /// or discarded by [`abort`](Self::abort).
+            ///
+            /// Beginning a stage while changes are staged keeps the staged values,
+            /// so nested stages are flattened into one that ends with the first
+            /// `commit()` or `abort()`.
+            #[allow(dead_code)]
+            #[inline]
+            #struct_vis fn begin(&mut self) {
+                if self.tracker_staged.is_none() {
+                    self.tracker_staged = ::core::option::Option::Some(::core::default::Default::default());
+                }
+            }
+
+            /// Check whether changes are staged, which is the case from `begin()`
+            /// or the first staged value until `commit()` or `abort()`.
+            #[allow(dead_code)]
+            #[must_use]
+            #[inline]
+            #struct_vis fn is_staging(&self) -> bool {
+                self.tracker_staged.is_some()
+            }
+
+            /// Set the staged values with the setters and end the stage.
+            ///
+            /// Only the fields whose staged value isn't equal to their current
+            /// value are marked as changed.
+            #[allow(dead_code, unused_variables)]
+            #struct_vis fn commit(&mut self)
+            where
+                #where_predicates
+                #eq_bounds
+            {
+                if let ::core::option::Option::Some(staged) = self.tracker_staged.take() {
+                    #commit_fields
+                }
+            }
+
+            /// Discard the staged values and end the stage without changing any field.
+            #[allow(dead_code)]
+            #[inline]
+            #struct_vis fn abort(&mut self) {
+                self.tracker_staged = ::core::option::Option::None;
+            }
+
+            #methods
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::{parse_quote, Field};
+
+use crate::Context;
+
+/// The injected field that sends the masks to the streams.
+pub(crate) fn field(ctx: &Context) -> Field {
+    let tracker_ty = &ctx.tracker_ty;
+    parse_quote! {
+        tracker_changes: ::tracker::ChangesSender<#tracker_ty>
+    }
+}
+
+/// Send the newly changed bits in `__tracker_new` to all streams of `this`.
+pub(crate) fn notify(this: &TokenStream2) -> TokenStream2 {
+    quote! { #this.tracker_changes.send(__tracker_new); }
+}
+
+/// Generate the `changes_stream()` method.
+pub(crate) fn generate(ctx: &Context) -> TokenStream2 {
+    let Context {
+        ident,
+        vis: struct_vis,
+        generics,
+        where_clause,
+        tracker_ty,
+        ..
+    } = ctx;
+    let self_ty = ctx.self_ty();
+
+    quote_spanned! { ident.span() =>
+        impl #generics #self_ty #where_clause {
+            /// Get a stream of the bit masks of the fields that were marked as changed
+            /// since the last reset. Masks that weren't received yet are combined.
+            #[allow(dead_code)]
+            #struct_vis fn changes_stream(&self) -> ::tracker::ChangesReceiver<#tracker_ty> {
+                self.tracker_changes.subscribe()
+            }
+        }
+    }
+}
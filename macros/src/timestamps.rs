@@ -0,0 +1,77 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::{parse_quote, Field, Type};
+
+use crate::{Context, TrackedField};
+
+/// The injected field that stores the timestamps, using the clock of the option.
+pub(crate) fn field(ctx: &Context, clock: &Type) -> Field {
+    let len = ctx.bit_count();
+    parse_quote! {
+        tracker_timestamps: ::tracker::Timestamps<#len, #clock>
+    }
+}
+
+/// Set the timestamps of the newly changed bits in `__tracker_new` of `this`.
+pub(crate) fn record(ctx: &Context, this: &TokenStream2) -> TokenStream2 {
+    let bits = ctx.mask_bits(quote! { __tracker_new });
+    quote! { #this.tracker_timestamps.record(#bits); }
+}
+
+/// Generate the `last_changed_#field()` and `elapsed_since_change_#field()` methods.
+pub(crate) fn generate(ctx: &Context) -> TokenStream2 {
+    let Context {
+        ident,
+        generics,
+        where_clause,
+        ..
+    } = ctx;
+    let self_ty = ctx.self_ty();
+    let allow_missing_docs = ctx.allow_missing_docs();
+
+    let mut methods = TokenStream2::new();
+    for field in &ctx.fields {
+        let TrackedField {
+            ident: id,
+            vis,
+            cfg,
+            span: id_span,
+            ..
+        } = field;
+        let mask_id = field.mask_ident();
+        let last_changed_id = field.method_ident("last_changed_");
+        let elapsed_id = field.method_ident("elapsed_since_change_");
+        let bits = ctx.mask_bits(quote_spanned! { *id_span => Self::#mask_id() });
+        let last_changed_doc = ctx.field_doc(format!(
+            "Get the time at which the {id} field was last marked as changed while it was unchanged.\n\n\
+             The timestamp is not affected by `reset()`."
+        ));
+        let elapsed_doc = ctx.field_doc(format!(
+            "Get the time that passed since the {id} field was last marked as changed while it was unchanged."
+        ));
+        methods.extend(quote_spanned! { *id_span =>
+            #(#cfg)*
+            #[allow(dead_code, non_snake_case)]
+            #[must_use]
+            #last_changed_doc
+            #vis fn #last_changed_id(&self) -> ::core::option::Option<::tracker::__private::Instant> {
+                self.tracker_timestamps.last_changed(#bits)
+            }
+
+            #(#cfg)*
+            #[allow(dead_code, non_snake_case)]
+            #[must_use]
+            #elapsed_doc
+            #vis fn #elapsed_id(&self) -> ::core::option::Option<::core::time::Duration> {
+                self.tracker_timestamps.elapsed(#bits)
+            }
+        });
+    }
+
+    quote_spanned! { ident.span() =>
+        #allow_missing_docs
+        impl #generics #self_ty #where_clause {
+            #methods
+        }
+    }
+}
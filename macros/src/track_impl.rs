@@ -0,0 +1,188 @@
+//! The `#[tracker::track_impl]` attribute that marks the fields assigned in methods.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote_spanned, ToTokens};
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
+use syn::visit_mut::{self, VisitMut};
+use syn::{BinOp, Block, Expr, FnArg, Ident, ImplItem, Item, ItemImpl, Member, Stmt, UnOp};
+
+/// Insert statements that mark the fields of `self` that are assigned
+/// or borrowed mutably in the methods of the impl block `item`.
+pub(crate) fn expand(attr: TokenStream2, item: Item) -> syn::Result<TokenStream2> {
+    if !attr.is_empty() {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "`#[tracker::track_impl]` doesn't take arguments",
+        ));
+    }
+    let Item::Impl(mut item) = item else {
+        return Err(syn::Error::new_spanned(
+            item,
+            "`#[tracker::track_impl]` can only be used on impl blocks",
+        ));
+    };
+    mark_assigned_fields(&mut item);
+    Ok(item.into_token_stream())
+}
+
+fn mark_assigned_fields(item: &mut ItemImpl) {
+    for impl_item in &mut item.items {
+        let ImplItem::Fn(method) = impl_item else {
+            continue;
+        };
+        // Associated functions without `self` have no fields to mark.
+        if !matches!(method.sig.inputs.first(), Some(FnArg::Receiver(_))) {
+            continue;
+        }
+        MarkAssigned::default().visit_block_mut(&mut method.block);
+    }
+}
+
+/// Collects the fields of `self` that are modified by a statement
+/// and marks them with a statement that runs before it.
+///
+/// Code that only runs conditionally, like blocks, match arms, closures and
+/// the right side of `&&` and `||`, marks the fields it modifies itself.
+#[derive(Default)]
+struct MarkAssigned {
+    /// The fields modified by the current statement, without duplicates.
+    fields: Vec<Ident>,
+}
+
+impl MarkAssigned {
+    /// Visit `expr`, which only runs conditionally, and mark the fields it modifies in a block around it.
+    fn visit_conditional_mut(&mut self, expr: &mut Expr) {
+        let outer = std::mem::take(&mut self.fields);
+        self.visit_expr_mut(expr);
+        let fields = std::mem::replace(&mut self.fields, outer);
+        if let Some(mark) = mark(&fields, expr.span()) {
+            *expr = syn::parse_quote_spanned! { expr.span() =>
+                {
+                    #mark
+                    #expr
+                }
+            };
+        }
+    }
+
+    /// Remember the field of `self` that is modified through the place expression `place`.
+    fn modifies(&mut self, place: &Expr) {
+        if let Some(field) = self_field(place) {
+            if !self.fields.contains(field) {
+                self.fields.push(field.clone());
+            }
+        }
+    }
+}
+
+impl VisitMut for MarkAssigned {
+    fn visit_block_mut(&mut self, block: &mut Block) {
+        let outer = std::mem::take(&mut self.fields);
+        let mut stmts = Vec::with_capacity(block.stmts.len());
+        for mut stmt in std::mem::take(&mut block.stmts) {
+            self.visit_stmt_mut(&mut stmt);
+            if let Some(mark) = mark(&self.fields, stmt.span()) {
+                stmts.push(mark);
+            }
+            self.fields.clear();
+            stmts.push(stmt);
+        }
+        block.stmts = stmts;
+        self.fields = outer;
+    }
+
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        match expr {
+            Expr::Assign(assign) => self.modifies(&assign.left),
+            Expr::Binary(binary) if is_compound_assignment(&binary.op) => {
+                self.modifies(&binary.left);
+            }
+            Expr::Binary(binary) if matches!(binary.op, BinOp::And(_) | BinOp::Or(_)) => {
+                self.visit_expr_mut(&mut binary.left);
+                self.visit_conditional_mut(&mut binary.right);
+                return;
+            }
+            Expr::Reference(reference) if reference.mutability.is_some() => {
+                self.modifies(&reference.expr);
+            }
+            Expr::Match(expr_match) => {
+                self.visit_expr_mut(&mut expr_match.expr);
+                for arm in &mut expr_match.arms {
+                    if let Some((_, guard)) = &mut arm.guard {
+                        self.visit_expr_mut(guard);
+                    }
+                    self.visit_conditional_mut(&mut arm.body);
+                }
+                return;
+            }
+            Expr::Closure(closure) => {
+                self.visit_conditional_mut(&mut closure.body);
+                return;
+            }
+            _ => {}
+        }
+        visit_mut::visit_expr_mut(self, expr);
+    }
+
+    // `self` refers to something else in nested items.
+    fn visit_item_mut(&mut self, _: &mut Item) {}
+}
+
+fn is_compound_assignment(op: &BinOp) -> bool {
+    matches!(
+        op,
+        BinOp::AddAssign(_)
+            | BinOp::SubAssign(_)
+            | BinOp::MulAssign(_)
+            | BinOp::DivAssign(_)
+            | BinOp::RemAssign(_)
+            | BinOp::BitXorAssign(_)
+            | BinOp::BitAndAssign(_)
+            | BinOp::BitOrAssign(_)
+            | BinOp::ShlAssign(_)
+            | BinOp::ShrAssign(_)
+    )
+}
+
+/// Get the field of `self` that contains the place `place`, like `x` for `self.x.y[0]`.
+fn self_field(place: &Expr) -> Option<&Ident> {
+    match place {
+        Expr::Field(field) => match &field.member {
+            Member::Named(id) if is_self(&field.base) => Some(id),
+            _ => self_field(&field.base),
+        },
+        Expr::Index(index) => self_field(&index.expr),
+        Expr::Paren(paren) => self_field(&paren.expr),
+        Expr::Unary(unary) if matches!(unary.op, UnOp::Deref(_)) => self_field(&unary.expr),
+        _ => None,
+    }
+}
+
+/// Check whether `expr` is `self`, possibly dereferenced like `(*self)`.
+fn is_self(expr: &Expr) -> bool {
+    match expr {
+        Expr::Path(path) => path.path.is_ident("self"),
+        Expr::Paren(paren) => is_self(&paren.expr),
+        Expr::Unary(unary) if matches!(unary.op, UnOp::Deref(_)) => is_self(&unary.expr),
+        _ => false,
+    }
+}
+
+/// The statement that marks `fields` of `self`, using the masks that `#[tracker::track]`
+/// generates for every field, which are empty for untracked fields.
+fn mark(fields: &[Ident], span: proc_macro2::Span) -> Option<Stmt> {
+    if fields.is_empty() {
+        return None;
+    }
+    let masks = fields.iter().map(|id| {
+        let assigned_id = format_ident!("__assigned_{}", id.unraw(), span = id.span());
+        quote_spanned! { id.span() => __tracker_masks.#assigned_id() }
+    });
+    Some(syn::parse_quote_spanned! { span =>
+        {
+            let __tracker_masks = ::tracker::__private::FieldMasks::field_masks(&self);
+            self.__tracker_mark_fields(#(#masks)|*);
+        }
+    })
+}
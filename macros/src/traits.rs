@@ -0,0 +1,283 @@
+//! Implementations of standard traits that ignore the tracker.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
+use syn::{Path, Token};
+
+use crate::{call_site_at, cfg_attrs, Context};
+
+/// Return an error if a trait that is implemented by the macro is also derived.
+pub(crate) fn check_derives(ctx: &Context) -> syn::Result<()> {
+    let options = &ctx.options;
+    let implemented = [
+        ("PartialEq", options.impl_partial_eq, "impl_partial_eq"),
+        ("Eq", options.impl_eq, "impl_eq"),
+        ("Hash", options.impl_hash, "impl_hash"),
+        ("Debug", options.debug, "debug"),
+        ("Clone", options.clone_resets, "clone_resets"),
+        ("Clone", options.clone_keeps, "clone_keeps"),
+        ("Default", options.impl_default, "impl_default"),
+    ];
+
+    // Atomic integers implement none of these traits.
+    let atomic = [
+        ("PartialEq", "impl_partial_eq"),
+        ("Eq", "impl_eq"),
+        ("Hash", "impl_hash"),
+        ("Clone", "clone_keeps"),
+    ];
+
+    for attr in ctx
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("derive"))
+    {
+        let paths =
+            attr.parse_args_with(syn::punctuated::Punctuated::<Path, Token![,]>::parse_terminated)?;
+        for path in paths {
+            let Some(last) = path.segments.last() else {
+                continue;
+            };
+            for (name, enabled, option) in implemented {
+                if enabled && last.ident == name {
+                    return Err(syn::Error::new(
+                        path.span(),
+                        format!("`{name}` is implemented by the `{option}` option, remove it from `#[derive(...)]`"),
+                    ));
+                }
+            }
+            for (name, option) in atomic {
+                if options.atomic && last.ident == name {
+                    return Err(syn::Error::new(
+                        path.span(),
+                        format!("`{name}` can't be derived with the `atomic` option, use the `{option}` option instead"),
+                    ));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Generate the implementations of `PartialEq`, `Eq`, `Hash`, `Debug`, `Clone` and `Default` if requested.
+pub(crate) fn generate(ctx: &Context) -> TokenStream2 {
+    let Context {
+        ident,
+        generics,
+        tracker_field,
+        options,
+        ..
+    } = ctx;
+    let self_ty = ctx.self_ty();
+    let where_predicates = ctx.where_predicates();
+
+    let mut eq_checks = TokenStream2::new();
+    let mut partial_eq_bounds = TokenStream2::new();
+    let mut eq_bounds = TokenStream2::new();
+    let mut hash_fields = TokenStream2::new();
+    let mut hash_bounds = TokenStream2::new();
+    let mut debug_fields = TokenStream2::new();
+    let mut debug_bounds = TokenStream2::new();
+    let mut clone_fields = TokenStream2::new();
+    let mut clone_bounds = TokenStream2::new();
+    let mut default_fields = TokenStream2::new();
+    let mut default_bounds = TokenStream2::new();
+    for field in &ctx.declared_fields {
+        let id = &field.ident;
+        let ty = &field.ty;
+        let cfg = cfg_attrs(&field.attrs);
+        let span = call_site_at(field.span());
+        let redacted = id.as_ref().is_some_and(|id| ctx.redacts(id));
+        let debug_value = match id {
+            Some(id) => ctx.debug_value(id, quote_spanned! { span => &self.#id }),
+            None => quote_spanned! { span => &self.#id },
+        };
+
+        eq_checks.extend(quote_spanned! { span =>
+            #(#cfg)*
+            if self.#id != other.#id {
+                return false;
+            }
+        });
+        hash_fields.extend(quote_spanned! { span =>
+            #(#cfg)*
+            ::core::hash::Hash::hash(&self.#id, state);
+        });
+        clone_fields.extend(quote_spanned! { span =>
+            #(#cfg)*
+            #id: ::core::clone::Clone::clone(&self.#id),
+        });
+        match id.as_ref().and_then(|id| ctx.default_value(id)) {
+            Some(default) => default_fields.extend(quote_spanned! { span =>
+                #(#cfg)*
+                #id: #default,
+            }),
+            None => {
+                default_fields.extend(quote_spanned! { span =>
+                    #(#cfg)*
+                    #id: ::core::default::Default::default(),
+                });
+                if cfg.is_empty() {
+                    let bound_ty = ctx.bound_ty(ty, "'__tracker");
+                    default_bounds.extend(quote_spanned! { span =>
+                        for<'__tracker> #bound_ty: ::core::default::Default,
+                    });
+                }
+            }
+        }
+        if cfg.is_empty() {
+            let bound_ty = ctx.bound_ty(ty, "'__tracker");
+            partial_eq_bounds.extend(quote_spanned! { span =>
+                for<'__tracker> #bound_ty: ::core::cmp::PartialEq,
+            });
+            eq_bounds.extend(quote_spanned! { span =>
+                for<'__tracker> #bound_ty: ::core::cmp::Eq,
+            });
+            hash_bounds.extend(quote_spanned! { span =>
+                for<'__tracker> #bound_ty: ::core::hash::Hash,
+            });
+            if !redacted {
+                debug_bounds.extend(quote_spanned! { span =>
+                    for<'__tracker> #bound_ty: ::core::fmt::Debug,
+                });
+            }
+            clone_bounds.extend(quote_spanned! { span =>
+                for<'__tracker> #bound_ty: ::core::clone::Clone,
+            });
+        }
+
+        let name = id.as_ref().map(|id| id.unraw().to_string());
+        let tracked = ctx
+            .fields
+            .iter()
+            .find(|tracked| Some(&tracked.ident) == id.as_ref());
+        if let Some(tracked) = tracked {
+            let mask_id = tracked.mask_ident();
+            debug_fields.extend(quote_spanned! { span =>
+                #(#cfg)*
+                if ::tracker::Track::changed(self, Self::#mask_id()) {
+                    debug.field(#name, &::tracker::__private::ChangedField(#debug_value));
+                } else {
+                    debug.field(#name, #debug_value);
+                }
+            });
+        } else {
+            debug_fields.extend(quote_spanned! { span =>
+                #(#cfg)*
+                debug.field(#name, #debug_value);
+            });
+        }
+    }
+
+    let mut output = TokenStream2::new();
+    if options.impl_partial_eq {
+        output.extend(quote_spanned! { ident.span() =>
+            impl #generics ::core::cmp::PartialEq for #self_ty
+            where
+                #where_predicates
+                #partial_eq_bounds
+            {
+                #[allow(unused_variables)]
+                fn eq(&self, other: &Self) -> bool {
+                    #eq_checks
+                    true
+                }
+            }
+        });
+    }
+    if options.impl_eq {
+        output.extend(quote_spanned! { ident.span() =>
+            impl #generics ::core::cmp::Eq for #self_ty
+            where
+                #where_predicates
+                #eq_bounds
+            {
+            }
+        });
+    }
+    if options.impl_hash {
+        output.extend(quote_spanned! { ident.span() =>
+            impl #generics ::core::hash::Hash for #self_ty
+            where
+                #where_predicates
+                #hash_bounds
+            {
+                #[allow(unused_variables)]
+                fn hash<H: ::core::hash::Hasher>(&self, state: &mut H) {
+                    #hash_fields
+                }
+            }
+        });
+    }
+    if options.debug {
+        let name = ident.to_string();
+        output.extend(quote_spanned! { ident.span() =>
+            impl #generics ::core::fmt::Debug for #self_ty
+            where
+                #where_predicates
+                #debug_bounds
+            {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    let mut debug = f.debug_struct(#name);
+                    #debug_fields
+                    debug.finish()
+                }
+            }
+        });
+    }
+    if options.clone_resets || options.clone_keeps {
+        for field in ctx.extra_fields() {
+            let id = &field.ident;
+            clone_fields.extend(quote! {
+                #id: ::core::clone::Clone::clone(&self.#id),
+            });
+        }
+        let tracker = ctx.tracker_init(ctx.tracker_value(&quote! { self }));
+        let reset = options.clone_resets.then(|| {
+            quote! { ::tracker::Track::reset(&mut clone); }
+        });
+        output.extend(quote_spanned! { ident.span() =>
+            impl #generics ::core::clone::Clone for #self_ty
+            where
+                #where_predicates
+                #clone_bounds
+            {
+                fn clone(&self) -> Self {
+                    #[allow(unused_mut)]
+                    let mut clone = Self {
+                        #clone_fields
+                        #tracker_field: #tracker,
+                    };
+                    #reset
+                    clone
+                }
+            }
+        });
+    }
+    if options.impl_default {
+        for field in ctx.extra_fields() {
+            let id = &field.ident;
+            default_fields.extend(quote! {
+                #id: ::core::default::Default::default(),
+            });
+        }
+        let tracker = ctx.tracker_init(ctx.empty_mask());
+        output.extend(quote_spanned! { ident.span() =>
+            impl #generics ::core::default::Default for #self_ty
+            where
+                #where_predicates
+                #default_bounds
+            {
+                fn default() -> Self {
+                    Self {
+                        #default_fields
+                        #tracker_field: #tracker,
+                    }
+                }
+            }
+        });
+    }
+    output
+}
@@ -0,0 +1,114 @@
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{quote, quote_spanned};
+
+use crate::nested::Nested;
+use crate::{Context, TrackedField};
+
+/// Generate `Deref`, `DerefMut` and `set()` for a struct with a single tracked field.
+pub(crate) fn generate(ctx: &Context, span: Span) -> syn::Result<TokenStream2> {
+    let field = match ctx.fields.as_slice() {
+        [field] => field,
+        [] => {
+            return Err(syn::Error::new(
+                span,
+                "`transparent` needs a tracked field that the struct dereferences to",
+            ));
+        }
+        [_, other, ..] => {
+            return Err(syn::Error::new(
+                other.ident.span(),
+                format!(
+                    "`transparent` structs can only track one field, mark `{}` with `#[tracker::do_not_track]`",
+                    other.ident
+                ),
+            ));
+        }
+    };
+    let Context {
+        generics,
+        where_clause,
+        ..
+    } = ctx;
+    let TrackedField {
+        ident: id,
+        ty,
+        cfg,
+        span: id_span,
+        ..
+    } = field;
+    let id_span = *id_span;
+    let self_ty = ctx.self_ty();
+    let mask_id = field.mask_ident();
+    let set_id = field.method_ident("set_");
+    let set_vis = field.method_vis("set");
+    let track_caller = ctx.track_caller();
+
+    let this = quote! { self };
+    // Nested structs track changes made through references themselves.
+    let (before_change, trace, mark) = if field.nested == Some(Nested::Direct) {
+        Default::default()
+    } else {
+        (
+            ctx.before_change(&this, field, None),
+            ctx.trace_change(&this, field, "deref_mut"),
+            ctx.mark(&this, quote_spanned! { id_span => Self::#mask_id() }),
+        )
+    };
+
+    let mut output = quote_spanned! { id_span =>
+        #(#cfg)*
+        impl #generics ::core::ops::Deref for #self_ty #where_clause {
+            type Target = #ty;
+
+            #[inline]
+            fn deref(&self) -> &#ty {
+                &self.#id
+            }
+        }
+
+        #(#cfg)*
+        impl #generics ::core::ops::DerefMut for #self_ty #where_clause {
+            /// Get a mutable reference to the tracked field and mark it as changed.
+            #[inline]
+            #track_caller
+            fn deref_mut(&mut self) -> &mut #ty {
+                #before_change
+                #trace
+                #mark
+                &mut self.#id
+            }
+        }
+    };
+    // `set()` is skipped together with the setter of the field.
+    if !ctx.skips_method(field, "set") {
+        for (no_eq, cfg) in field.no_eq_variants() {
+            let (set_bound, set_doc) = if no_eq {
+                (
+                    None,
+                    "Set the value of the tracked field and mark it as changed.",
+                )
+            } else {
+                (
+                    Some(field.eq_bound(ctx)),
+                    "Set the value of the tracked field and mark it as changed if it's not equal to the previous value.",
+                )
+            };
+            output.extend(quote_spanned! { id_span =>
+                #(#cfg)*
+                impl #generics #self_ty #where_clause {
+                    #[doc = #set_doc]
+                    #[allow(dead_code)]
+                    #[inline]
+                    #track_caller
+                    #set_vis fn set(&mut self, value: #ty)
+                    where
+                        #set_bound
+                    {
+                        self.#set_id(value);
+                    }
+                }
+            });
+        }
+    }
+    Ok(output)
+}
@@ -0,0 +1,148 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote_spanned};
+use syn::ext::IdentExt;
+use syn::Ident;
+
+use crate::options::Options;
+use crate::Context;
+
+/// Identifier of the mask type of a struct like `#structMask`
+/// or `#structChanges` with the `bitflags` option.
+pub(crate) fn ident(struct_ident: &Ident, options: &Options) -> Ident {
+    if options.bitflags {
+        format_ident!("{}Changes", struct_ident)
+    } else {
+        format_ident!("{}Mask", struct_ident)
+    }
+}
+
+/// Generate the mask type with its operators.
+pub(crate) fn generate(ctx: &Context) -> TokenStream2 {
+    let Context {
+        ident,
+        vis: struct_vis,
+        tracker_ty: mask_ident,
+        bits_ty,
+        ..
+    } = ctx;
+    let mask_name = mask_ident.to_string();
+    let mask_doc = format!(
+        "Bit mask of the tracked fields of [`{ident}`], returned by functions like [`{ident}::track_all`]."
+    );
+    if ctx.options.bitflags {
+        return generate_bitflags(ctx, &mask_doc);
+    }
+
+    let mut output = quote_spanned! { ident.span() =>
+        #[doc = #mask_doc]
+        #[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+        #struct_vis struct #mask_ident(#bits_ty);
+
+        impl #mask_ident {
+            /// Create a mask from its raw bits.
+            #[allow(dead_code)]
+            #[must_use]
+            #struct_vis const fn from_bits(bits: #bits_ty) -> Self {
+                Self(bits)
+            }
+
+            /// Get the raw bits of the mask.
+            #[allow(dead_code)]
+            #[must_use]
+            #struct_vis const fn bits(self) -> #bits_ty {
+                self.0
+            }
+
+            /// Check whether no bits are set.
+            #[allow(dead_code)]
+            #[must_use]
+            #struct_vis const fn is_empty(self) -> bool {
+                self.0 == 0
+            }
+        }
+
+        impl ::core::fmt::Debug for #mask_ident {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.debug_tuple(#mask_name)
+                    .field(&::core::format_args!("{:#b}", self.0))
+                    .finish()
+            }
+        }
+
+        impl ::core::ops::Not for #mask_ident {
+            type Output = Self;
+
+            fn not(self) -> Self {
+                Self(!self.0)
+            }
+        }
+    };
+
+    for (op, method, assign_op, assign_method) in [
+        (
+            quote_spanned! { ident.span() => BitOr },
+            quote_spanned! { ident.span() => bitor },
+            quote_spanned! { ident.span() => BitOrAssign },
+            quote_spanned! { ident.span() => bitor_assign },
+        ),
+        (
+            quote_spanned! { ident.span() => BitAnd },
+            quote_spanned! { ident.span() => bitand },
+            quote_spanned! { ident.span() => BitAndAssign },
+            quote_spanned! { ident.span() => bitand_assign },
+        ),
+    ] {
+        output.extend(quote_spanned! { ident.span() =>
+            impl ::core::ops::#op for #mask_ident {
+                type Output = Self;
+
+                fn #method(self, rhs: Self) -> Self {
+                    Self(::core::ops::#op::#method(self.0, rhs.0))
+                }
+            }
+
+            impl ::core::ops::#assign_op for #mask_ident {
+                fn #assign_method(&mut self, rhs: Self) {
+                    ::core::ops::#assign_op::#assign_method(&mut self.0, rhs.0);
+                }
+            }
+        });
+    }
+
+    output
+}
+
+/// Generate the mask type of the `bitflags` option with a flag for each field,
+/// named like the bit mask function in upper case.
+fn generate_bitflags(ctx: &Context, mask_doc: &str) -> TokenStream2 {
+    let Context {
+        ident,
+        vis: struct_vis,
+        tracker_ty: mask_ident,
+        bits_ty,
+        ..
+    } = ctx;
+    let mut flags = TokenStream2::new();
+    for field in &ctx.fields {
+        let mask_id = field.mask_ident().unraw();
+        let flag = format_ident!("{}", mask_id.to_string().to_uppercase(), span = field.span);
+        let doc = format!("The `{}` field.", field.ident.unraw());
+        let cfg = &field.cfg;
+        let bits = field.bits();
+        flags.extend(quote_spanned! { field.span =>
+            #(#cfg)*
+            #[doc = #doc]
+            const #flag = #bits;
+        });
+    }
+
+    quote_spanned! { ident.span() =>
+        ::tracker::__private::bitflags::bitflags! {
+            #[doc = #mask_doc]
+            #[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Debug)]
+            #struct_vis struct #mask_ident: #bits_ty {
+                #flags
+            }
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote_spanned;
+
+use crate::Context;
+
+/// Generate the `Drop` impl of the `debug_unconsumed` option that panics
+/// if the struct still has changes, only in builds with debug assertions.
+///
+/// The impl is spanned at the option, so a conflict with an existing
+/// `Drop` impl of the struct points at it.
+pub(crate) fn generate(ctx: &Context, span: Span) -> TokenStream2 {
+    let Context {
+        generics,
+        where_clause,
+        ..
+    } = ctx;
+    let self_ty = ctx.self_ty();
+
+    quote_spanned! { span =>
+        #[cfg(debug_assertions)]
+        impl #generics ::core::ops::Drop for #self_ty #where_clause {
+            fn drop(&mut self) {
+                ::tracker::__private::assert_consumed(self);
+            }
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+
+use syn::Ident;
+
+use crate::{delta, Context, TrackedField};
+
+/// Generate the code that assigns optional values through the same logic as
+/// `set_#field()` and evaluates to a bit mask of the fields that changed.
+///
+/// `value` creates the expression of type `Option<T>` for each field.
+pub(crate) fn apply_options(ctx: &Context, value: impl Fn(&Ident) -> TokenStream2) -> TokenStream2 {
+    let mark = ctx.mark(&quote! { self }, quote! { mask });
+    let empty_mask = ctx.empty_mask();
+
+    let mut apply_fields = TokenStream2::new();
+    for field in &ctx.fields {
+        let TrackedField {
+            ident: id,
+            span: id_span,
+            ..
+        } = field;
+        let mask_id = field.mask_ident();
+        let before_change = ctx.before_change(&quote! { self }, field, Some(&quote! { mask }));
+        let value = value(id);
+
+        for (no_eq, cfg) in field.no_eq_variants() {
+            if no_eq {
+                apply_fields.extend(quote_spanned! { *id_span =>
+                    #(#cfg)*
+                    if let ::core::option::Option::Some(value) = #value {
+                        #before_change
+                        mask |= Self::#mask_id();
+                        self.#id = value;
+                    }
+                });
+            } else if ctx.uses_shared_setter(field) {
+                apply_fields.extend(quote_spanned! { *id_span =>
+                    #(#cfg)*
+                    if let ::core::option::Option::Some(value) = #value {
+                        ::tracker::__private::set(&mut self.#id, value, &mut mask, Self::#mask_id());
+                    }
+                });
+            } else {
+                let ne = field.ne(quote! { self.#id }, quote! { value });
+                apply_fields.extend(quote_spanned! { *id_span =>
+                    #(#cfg)*
+                    if let ::core::option::Option::Some(value) = #value {
+                        if #ne {
+                            #before_change
+                            mask |= Self::#mask_id();
+                        }
+                        self.#id = value;
+                    }
+                });
+            }
+        }
+    }
+
+    quote! {
+        #[allow(unused_mut)]
+        let mut mask = #empty_mask;
+        #apply_fields
+        #mark
+        mask
+    }
+}
+
+/// Generate the update struct with one `Option` per tracked field
+/// as well as the `apply_update()` method.
+pub(crate) fn generate(ctx: &Context) -> TokenStream2 {
+    let Context {
+        ident,
+        vis: struct_vis,
+        generics,
+        generic_idents,
+        where_clause,
+        tracker_ty,
+        ..
+    } = ctx;
+    let self_ty = ctx.self_ty();
+    let update_ident = ctx.companion_ident("Update");
+    let apply_fields = apply_options(ctx, |id| quote! { update.#id });
+
+    let update_doc = format!(
+        "Partial modification of [`{ident}`] that can be applied with [`{ident}::apply_update`]."
+    );
+    let update_struct = delta::option_struct(ctx, &update_ident, &update_doc, |id| {
+        format!("New value of the {id} field or `None` to keep the current value.")
+    });
+
+    let eq_bounds = ctx.eq_bounds();
+
+    quote_spanned! { ident.span() =>
+        #update_struct
+
+        impl #generics #self_ty #where_clause {
+            /// Assign all values provided by the update and get
+            /// a bit mask of the fields that actually changed.
+            ///
+            /// Just like with `set_#field_name()`, fields are only marked
+            /// as changed if their new value is not equal to the previous value.
+            #[allow(dead_code, unused_variables)]
+            #struct_vis fn apply_update(&mut self, update: #update_ident < #generic_idents >) -> #tracker_ty
+            where
+                #eq_bounds
+            {
+                #apply_fields
+            }
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::{parse_quote, Field};
+
+use crate::Context;
+
+/// The injected field that stores the versions.
+pub(crate) fn field(ctx: &Context) -> Field {
+    let len = ctx.bit_count();
+    parse_quote! {
+        tracker_versions: ::tracker::Versions<#len>
+    }
+}
+
+/// Record a new version for all fields in `mask`.
+pub(crate) fn record(this: &TokenStream2, mask: &TokenStream2) -> TokenStream2 {
+    quote! { #this.tracker_versions.record(#mask); }
+}
+
+/// Generate the `version()` and `changed_since()` methods.
+pub(crate) fn generate(ctx: &Context) -> TokenStream2 {
+    let Context {
+        ident,
+        vis: struct_vis,
+        generics,
+        where_clause,
+        tracker_ty,
+        bits_ty,
+        ..
+    } = ctx;
+    let self_ty = ctx.self_ty();
+    let mask =
+        ctx.mask_from_bits(quote! { self.tracker_versions.changed_since(version) as #bits_ty });
+
+    quote_spanned! { ident.span() =>
+        impl #generics #self_ty #where_clause {
+            /// Get the current version of this struct.
+            ///
+            /// The version is incremented on every change and
+            /// is not affected by `reset()`.
+            #[allow(dead_code)]
+            #[must_use]
+            #struct_vis fn version(&self) -> u64 {
+                self.tracker_versions.version()
+            }
+
+            /// Get a bit mask of all fields that changed after the given version.
+            #[allow(dead_code)]
+            #[must_use]
+            #struct_vis fn changed_since(&self, version: u64) -> #tracker_ty {
+                #mask
+            }
+        }
+    }
+}
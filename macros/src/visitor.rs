@@ -0,0 +1,60 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote_spanned;
+use syn::ext::IdentExt;
+
+use crate::{Context, TrackedField};
+
+/// Generate the `visit_changed()` method.
+pub(crate) fn generate(ctx: &Context) -> TokenStream2 {
+    let Context {
+        ident,
+        vis: struct_vis,
+        generics,
+        ..
+    } = ctx;
+    let self_ty = ctx.self_ty();
+    let where_predicates = ctx.where_predicates();
+
+    let mut debug_bounds = TokenStream2::new();
+    let mut visits = TokenStream2::new();
+    for field in &ctx.fields {
+        let TrackedField {
+            ident: id,
+            ty,
+            cfg,
+            span: id_span,
+            ..
+        } = field;
+        let mask_id = field.mask_ident();
+        let name = id.unraw().to_string();
+
+        if cfg.is_empty() && !ctx.redacts(id) {
+            let bound_ty = ctx.bound_ty(ty, "'__tracker");
+            debug_bounds.extend(quote_spanned! { *id_span =>
+                for<'__tracker> #bound_ty: ::core::fmt::Debug,
+            });
+        }
+        let value = ctx.debug_value(id, quote_spanned! { *id_span => &self.#id });
+        visits.extend(quote_spanned! { *id_span =>
+            #(#cfg)*
+            if ::tracker::Track::changed(self, Self::#mask_id()) {
+                ::tracker::ChangedVisitor::field(visitor, #name, #value);
+            }
+        });
+    }
+
+    quote_spanned! { ident.span() =>
+        impl #generics #self_ty where #where_predicates {
+            /// Call the visitor once for every changed field in the order of declaration.
+            ///
+            /// The types of all tracked fields need to implement `Debug`.
+            #[allow(dead_code, unused_variables)]
+            #struct_vis fn visit_changed<V: ::tracker::ChangedVisitor>(&self, visitor: &mut V)
+            where
+                #debug_bounds
+            {
+                #visits
+            }
+        }
+    }
+}
@@ -0,0 +1,75 @@
+//! Tracked structs that must build with `#![no_std]`,
+//! checked with and without the `alloc` feature.
+
+#![no_std]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+use tracker::{Track, Tracked, TrackedCell};
+
+/// A struct with the default options.
+#[tracker::track]
+#[derive(Default)]
+pub struct Basic {
+    /// A tracked field.
+    pub x: u8,
+    /// A nested tracked value.
+    #[tracker::nested]
+    pub y: Tracked<u16>,
+    #[tracker::do_not_track]
+    z: TrackedCell<u32>,
+}
+
+/// A struct with the options that don't need an allocator.
+#[tracker::track(
+    snapshot,
+    previous,
+    typed_mask,
+    debug,
+    debug_values,
+    impl_eq,
+    impl_hash,
+    clone_resets,
+    project
+)]
+#[derive(Default)]
+pub struct Options {
+    /// A tracked field.
+    pub a: u8,
+    /// Another tracked field.
+    pub b: i64,
+}
+
+/// A struct whose changes are marked through `&self`.
+#[tracker::track(atomic)]
+#[derive(Default)]
+pub struct Atomic {
+    /// A tracked field.
+    pub a: u8,
+}
+
+/// A struct with the options that need an allocator.
+#[cfg(feature = "alloc")]
+#[tracker::track(sql, listeners)]
+#[derive(Default)]
+pub struct Allocating {
+    /// A tracked field.
+    pub a: u8,
+    /// A tracked list.
+    #[tracker::nested]
+    pub list: tracker::TrackedVec<alloc::string::String>,
+}
+
+/// Use the generated methods.
+pub fn touch() -> bool {
+    let mut basic = Basic::default();
+    basic.set_x(1);
+    basic.get_mut_y().set(2);
+    basic.z.set(3);
+    let mut options = Options::default();
+    options.set_a(1);
+    let atomic = Atomic::default();
+    atomic.mark_changed(Atomic::a());
+    basic.changed(Basic::track_all()) && options.changed_any() && Track::changed_any(&atomic)
+}
@@ -0,0 +1,67 @@
+use std::fmt;
+
+use serde_json::{Map, Value};
+
+/// Error returned when applying a JSON object fails.
+#[derive(Debug)]
+pub enum ApplyJsonError {
+    /// The value is not a JSON object.
+    NotAnObject,
+    /// The object contains keys that don't belong to a tracked field.
+    UnknownKeys(Vec<String>),
+    /// A value couldn't be deserialized into the type of its field.
+    Deserialize(serde_json::Error),
+}
+
+impl fmt::Display for ApplyJsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotAnObject => write!(f, "expected a JSON object"),
+            Self::UnknownKeys(keys) => write!(f, "unknown keys: {}", keys.join(", ")),
+            Self::Deserialize(err) => write!(f, "failed to deserialize value: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for ApplyJsonError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Deserialize(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for ApplyJsonError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Deserialize(err)
+    }
+}
+
+/// Get the entries of a JSON object.
+#[doc(hidden)]
+pub fn object(value: &Value) -> Result<&Map<String, Value>, ApplyJsonError> {
+    value.as_object().ok_or(ApplyJsonError::NotAnObject)
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::{object, ApplyJsonError};
+
+    #[test]
+    fn objects() {
+        assert_eq!(object(&json!({ "a": 1 })).unwrap().len(), 1);
+        assert!(matches!(
+            object(&json!([1, 2])),
+            Err(ApplyJsonError::NotAnObject)
+        ));
+    }
+
+    #[test]
+    fn display() {
+        let err = ApplyJsonError::UnknownKeys(vec!["a".into(), "b".into()]);
+        assert_eq!(err.to_string(), "unknown keys: a, b");
+    }
+}
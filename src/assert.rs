@@ -0,0 +1,116 @@
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::Track;
+
+/// Assert that the fields of a tracked struct are marked as changed.
+///
+/// The fields are given by the names of their bit mask functions. On failure
+/// the message lists the expected fields and all fields that actually changed.
+/// Like with [`assert!`], a custom message can be passed after the fields.
+/// To check a struct behind a reference `r`, use `assert_changed!(*r, ...)`.
+///
+/// ```
+/// #[tracker::track]
+/// #[derive(Default)]
+/// struct Test {
+///     x: u8,
+///     y: u8,
+/// }
+///
+/// let mut t = Test::default();
+/// t.set_x(1);
+/// tracker::assert_changed!(t, x);
+/// tracker::assert_unchanged!(t, y, "only x was set to {}", t.x);
+/// ```
+#[macro_export]
+macro_rules! assert_changed {
+    ($value:expr, $($rest:tt)+) => {
+        $crate::__assert_fields!(true, $value, [], $($rest)+)
+    };
+}
+
+/// Assert that the fields of a tracked struct are not marked as changed.
+///
+/// This is the counterpart of [`assert_changed!`](crate::assert_changed).
+#[macro_export]
+macro_rules! assert_unchanged {
+    ($value:expr, $($rest:tt)+) => {
+        $crate::__assert_fields!(false, $value, [], $($rest)+)
+    };
+}
+
+/// Collect the field names of `assert_changed!` and `assert_unchanged!`
+/// until the end of the input or the start of the custom message.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __assert_fields {
+    ($changed:expr, $value:expr, [$($names:expr),*], $field:ident $(,)?) => {
+        $crate::__private::assert_fields(
+            &$value,
+            &[$($names,)* ::core::stringify!($field)],
+            $changed,
+            ::core::option::Option::None,
+        )
+    };
+    ($changed:expr, $value:expr, [$($names:expr),*], $field:ident, $($rest:tt)+) => {
+        $crate::__assert_fields!($changed, $value, [$($names,)* ::core::stringify!($field)], $($rest)+)
+    };
+    ($changed:expr, $value:expr, [], $($rest:tt)+) => {
+        ::core::compile_error!("expected the name of a tracked field before the message")
+    };
+    ($changed:expr, $value:expr, [$($names:expr),*], $($message:tt)+) => {
+        $crate::__private::assert_fields(
+            &$value,
+            &[$($names),*],
+            $changed,
+            ::core::option::Option::Some(::core::format_args!($($message)+)),
+        )
+    };
+}
+
+/// Panic unless all fields in `names` are marked as `changed`, used by [`assert_changed!`].
+#[doc(hidden)]
+#[track_caller]
+pub fn assert_fields<T: Track>(
+    value: &T,
+    names: &[&str],
+    changed: bool,
+    message: Option<fmt::Arguments<'_>>,
+) {
+    let mut passed = true;
+    for name in names {
+        let Some(mask) = T::field_mask(name) else {
+            panic!(
+                "`{}` has no tracked field `{name}`",
+                core::any::type_name::<T>()
+            );
+        };
+        passed &= value.changed(mask) == changed;
+    }
+    if passed {
+        return;
+    }
+
+    let actual: Vec<&str> = T::field_names()
+        .iter()
+        .copied()
+        .filter(|name| T::field_mask(name).is_some_and(|mask| value.changed(mask)))
+        .collect();
+    let kind = if changed { "changed" } else { "unchanged" };
+    let message = message
+        .map(|message| format!(": {message}"))
+        .unwrap_or_default();
+    panic!(
+        "assertion `{kind}({})` failed{message}\n  expected {kind}: {}\n  actually changed: {}",
+        names.join(", "),
+        names.join(", "),
+        if actual.is_empty() {
+            "none".to_owned()
+        } else {
+            actual.join(", ")
+        },
+    );
+}
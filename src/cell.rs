@@ -0,0 +1,121 @@
+use core::cell::Cell;
+use core::fmt;
+
+use crate::Track;
+
+/// A `Copy` value that tracks whether it changed and can be modified through a shared reference.
+///
+/// Like [`Cell`] this is interior mutability for a single thread, so `TrackedCell` is not `Sync`.
+/// Use it for values that are refreshed behind `&self`, for example lazily computed fields.
+#[derive(Default)]
+pub struct TrackedCell<T: Copy> {
+    value: Cell<T>,
+    changed: Cell<bool>,
+}
+
+impl<T: Copy> TrackedCell<T> {
+    /// Wrap a value that is marked as unchanged.
+    #[must_use]
+    pub fn new(value: T) -> Self {
+        Self {
+            value: Cell::new(value),
+            changed: Cell::new(false),
+        }
+    }
+
+    /// Get a copy of the value.
+    #[must_use]
+    pub fn get(&self) -> T {
+        self.value.get()
+    }
+
+    /// Set the value and mark it as changed.
+    pub fn set(&self, value: T) {
+        self.value.set(value);
+        self.changed.set(true);
+    }
+
+    /// Check whether the value changed.
+    #[must_use]
+    pub fn changed(&self) -> bool {
+        self.changed.get()
+    }
+
+    /// Mark the value as unchanged again.
+    pub fn reset(&self) {
+        self.changed.set(false);
+    }
+
+    /// Get the value without the change information.
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        self.value.get()
+    }
+}
+
+impl<T: Copy> Clone for TrackedCell<T> {
+    fn clone(&self) -> Self {
+        Self {
+            value: Cell::new(self.value.get()),
+            changed: Cell::new(self.changed.get()),
+        }
+    }
+}
+
+impl<T: Copy> From<T> for TrackedCell<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T: Copy + fmt::Debug> fmt::Debug for TrackedCell<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TrackedCell")
+            .field("value", &self.value.get())
+            .field("changed", &self.changed.get())
+            .finish()
+    }
+}
+
+/// Like [`Tracked`](crate::Tracked) with a `bool` mask, so cells can be used as `nested` fields.
+impl<T: Copy> Track for TrackedCell<T> {
+    type Mask = bool;
+
+    fn changed(&self, mask: bool) -> bool {
+        mask && self.changed.get()
+    }
+
+    fn changed_any(&self) -> bool {
+        self.changed.get()
+    }
+
+    fn reset(&mut self) {
+        self.changed.set(false);
+    }
+
+    fn mark_all_changed(&mut self) {
+        self.changed.set(true);
+    }
+
+    fn track_all() -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TrackedCell;
+
+    #[test]
+    fn set_through_shared_reference() {
+        let cell = TrackedCell::new(1);
+        let shared = &cell;
+        assert!(!shared.changed());
+        shared.set(2);
+        assert_eq!(shared.get(), 2);
+        assert!(shared.changed());
+        shared.reset();
+        assert!(!shared.changed());
+        assert_eq!(cell.into_inner(), 2);
+    }
+}
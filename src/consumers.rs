@@ -0,0 +1,95 @@
+use core::hash::{Hash, Hasher};
+
+/// The masks of the additional consumers of a struct that uses the `consumers` option.
+///
+/// Consumer 0 uses the tracker of the struct itself, so this stores the
+/// masks of the consumers `1..=N`. Every change is recorded for all of them
+/// and each consumer can be reset independently.
+///
+/// Like the tracker, the masks aren't compared by `PartialEq` and ignored by `Hash`.
+#[derive(Debug, Clone, Copy)]
+pub struct Consumers<const N: usize> {
+    masks: [u128; N],
+}
+
+impl<const N: usize> Default for Consumers<N> {
+    fn default() -> Self {
+        Self { masks: [0; N] }
+    }
+}
+
+impl<const N: usize> Consumers<N> {
+    /// Mark the bits of `mask` as changed for all consumers.
+    pub fn record(&mut self, mask: impl Into<u128>) {
+        let mask = mask.into();
+        for consumer in &mut self.masks {
+            *consumer |= mask;
+        }
+    }
+
+    /// Check whether any bit of `mask` is marked as changed for `consumer`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `consumer` is 0 or greater than `N`.
+    #[must_use]
+    #[track_caller]
+    pub fn changed(&self, consumer: usize, mask: impl Into<u128>) -> bool {
+        self.masks[Self::index(consumer)] & mask.into() != 0
+    }
+
+    /// Mark all bits as unchanged for `consumer`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `consumer` is 0 or greater than `N`.
+    #[track_caller]
+    pub fn reset(&mut self, consumer: usize) {
+        self.masks[Self::index(consumer)] = 0;
+    }
+
+    #[track_caller]
+    fn index(consumer: usize) -> usize {
+        assert!(
+            consumer != 0 && consumer <= N,
+            "consumer {consumer} is out of range, the struct has {} consumers",
+            N + 1
+        );
+        consumer - 1
+    }
+}
+
+impl<const N: usize> PartialEq for Consumers<N> {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl<const N: usize> Eq for Consumers<N> {}
+
+impl<const N: usize> Hash for Consumers<N> {
+    fn hash<H: Hasher>(&self, _: &mut H) {}
+}
+
+#[cfg(test)]
+mod test {
+    use super::Consumers;
+
+    #[test]
+    fn consumers_are_independent() {
+        let mut consumers = Consumers::<2>::default();
+        consumers.record(0b01u8);
+        consumers.reset(1);
+        consumers.record(0b10u8);
+        assert!(!consumers.changed(1, 0b01u8));
+        assert!(consumers.changed(1, 0b10u8));
+        assert!(consumers.changed(2, 0b11u8));
+    }
+
+    #[test]
+    #[should_panic(expected = "consumer 3 is out of range, the struct has 3 consumers")]
+    fn out_of_range() {
+        let consumers = Consumers::<2>::default();
+        let _ = consumers.changed(3, 1u8);
+    }
+}
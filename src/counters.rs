@@ -0,0 +1,81 @@
+use core::hash::{Hash, Hasher};
+
+/// Change counters of a struct that uses the `counters` option.
+///
+/// Each bit of the tracker has a counter that is incremented whenever the
+/// bit is newly set, so `reset()` doesn't affect the counters.
+/// `N` is the number of bits of the tracked fields.
+///
+/// Like the tracker, the counters aren't compared by `PartialEq`
+/// and ignored by `Hash`.
+#[derive(Debug, Clone, Copy)]
+pub struct Counters<const N: usize> {
+    counts: [u32; N],
+}
+
+impl<const N: usize> Default for Counters<N> {
+    fn default() -> Self {
+        Self { counts: [0; N] }
+    }
+}
+
+impl<const N: usize> Counters<N> {
+    /// Increment the counters of all bits in `mask`.
+    ///
+    /// The counters saturate at `u32::MAX` and bits that don't belong to a field are ignored.
+    pub fn record(&mut self, mask: impl Into<u128>) {
+        let mask = mask.into();
+        for (index, count) in self.counts.iter_mut().enumerate() {
+            if mask & (1 << index) != 0 {
+                *count = count.saturating_add(1);
+            }
+        }
+    }
+
+    /// Get the sum of the counters of all bits in `mask`.
+    #[must_use]
+    pub fn count(&self, mask: impl Into<u128>) -> u32 {
+        let mask = mask.into();
+        self.counts
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| mask & (1 << index) != 0)
+            .fold(0, |sum, (_, count)| sum.saturating_add(*count))
+    }
+
+    /// Set all counters to zero.
+    pub fn reset(&mut self) {
+        self.counts = [0; N];
+    }
+}
+
+impl<const N: usize> PartialEq for Counters<N> {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl<const N: usize> Eq for Counters<N> {}
+
+impl<const N: usize> Hash for Counters<N> {
+    fn hash<H: Hasher>(&self, _: &mut H) {}
+}
+
+#[cfg(test)]
+mod test {
+    use super::Counters;
+
+    #[test]
+    fn record_and_count() {
+        let mut counters = Counters::<3>::default();
+        counters.record(0b011u8);
+        counters.record(0b010u8);
+        counters.record(u8::MAX);
+        assert_eq!(counters.count(0b001u8), 2);
+        assert_eq!(counters.count(0b010u8), 3);
+        assert_eq!(counters.count(0b110u8), 4);
+
+        counters.reset();
+        assert_eq!(counters.count(u8::MAX), 0);
+    }
+}
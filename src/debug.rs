@@ -0,0 +1,49 @@
+use core::fmt;
+
+/// Prints the `Debug` output of a changed field followed by a marker.
+#[doc(hidden)]
+pub struct ChangedField<'a, T: ?Sized>(pub &'a T);
+
+impl<T: fmt::Debug + ?Sized> fmt::Debug for ChangedField<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)?;
+        f.write_str(" (changed)")
+    }
+}
+
+/// Printed and serialized instead of the value of a field marked with `#[tracker::redact]`.
+#[doc(hidden)]
+pub struct Redacted;
+
+const PLACEHOLDER: &str = "<redacted>";
+
+impl fmt::Debug for Redacted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(PLACEHOLDER)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Redacted {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(PLACEHOLDER)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ChangedField, Redacted};
+
+    #[test]
+    fn marker() {
+        assert_eq!(format!("{:?}", ChangedField(&"hi")), "\"hi\" (changed)");
+        assert_eq!(
+            format!("{:#?}", ChangedField(&(1,))),
+            "(\n    1,\n) (changed)"
+        );
+        assert_eq!(
+            format!("{:?}", ChangedField(&Redacted)),
+            "<redacted> (changed)"
+        );
+    }
+}
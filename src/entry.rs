@@ -0,0 +1,258 @@
+use alloc::collections::btree_map;
+use core::fmt;
+use core::ops::{BitOrAssign, Deref, DerefMut};
+#[cfg(feature = "std")]
+use std::collections::hash_map;
+
+/// The entry of a `HashMap` or `BTreeMap`, used by [`FieldEntry`].
+pub trait MapEntry<'a> {
+    /// The key type of the map.
+    type Key;
+    /// The value type of the map.
+    type Value: 'a;
+
+    /// Get the key of the entry.
+    fn key(&self) -> &Self::Key;
+
+    /// Get the value if the key is in the map.
+    fn get(&self) -> Option<&Self::Value>;
+
+    /// Get the value mutably if the key is in the map.
+    fn get_mut(&mut self) -> Option<&mut Self::Value>;
+
+    /// Get the value and insert the result of `default` first if there's none.
+    ///
+    /// Returns whether the value was inserted.
+    fn or_insert_with<F: FnOnce() -> Self::Value>(self, default: F) -> (&'a mut Self::Value, bool);
+}
+
+#[cfg(feature = "std")]
+impl<'a, K, V> MapEntry<'a> for hash_map::Entry<'a, K, V> {
+    type Key = K;
+    type Value = V;
+
+    fn key(&self) -> &K {
+        hash_map::Entry::key(self)
+    }
+
+    fn get(&self) -> Option<&V> {
+        match self {
+            Self::Occupied(entry) => Some(entry.get()),
+            Self::Vacant(_) => None,
+        }
+    }
+
+    fn get_mut(&mut self) -> Option<&mut V> {
+        match self {
+            Self::Occupied(entry) => Some(entry.get_mut()),
+            Self::Vacant(_) => None,
+        }
+    }
+
+    fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> (&'a mut V, bool) {
+        match self {
+            Self::Occupied(entry) => (entry.into_mut(), false),
+            Self::Vacant(entry) => (entry.insert(default()), true),
+        }
+    }
+}
+
+impl<'a, K: Ord, V> MapEntry<'a> for btree_map::Entry<'a, K, V> {
+    type Key = K;
+    type Value = V;
+
+    fn key(&self) -> &K {
+        btree_map::Entry::key(self)
+    }
+
+    fn get(&self) -> Option<&V> {
+        match self {
+            Self::Occupied(entry) => Some(entry.get()),
+            Self::Vacant(_) => None,
+        }
+    }
+
+    fn get_mut(&mut self) -> Option<&mut V> {
+        match self {
+            Self::Occupied(entry) => Some(entry.get_mut()),
+            Self::Vacant(_) => None,
+        }
+    }
+
+    fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> (&'a mut V, bool) {
+        match self {
+            Self::Occupied(entry) => (entry.into_mut(), false),
+            Self::Vacant(entry) => (entry.insert(default()), true),
+        }
+    }
+}
+
+/// The entry of a key in a field marked with `#[tracker::map_collection]`,
+/// created with `entry_#field_name(key)`.
+///
+/// The field is only marked as changed when the entry is modified:
+/// when a value is inserted, by `and_modify()`, or when the value
+/// returned by `or_insert()` is accessed mutably.
+pub struct FieldEntry<'a, E, M> {
+    entry: E,
+    tracker: &'a mut M,
+    /// The bits of the field, taken when the field is marked.
+    mask: Option<M>,
+}
+
+impl<'a, E: MapEntry<'a>, M: BitOrAssign> FieldEntry<'a, E, M> {
+    /// Wrap `entry` of the field with the bits `mask` in `tracker`.
+    #[must_use]
+    pub fn new(entry: E, tracker: &'a mut M, mask: M) -> Self {
+        Self {
+            entry,
+            tracker,
+            mask: Some(mask),
+        }
+    }
+
+    /// Get the key of the entry.
+    #[must_use]
+    pub fn key(&self) -> &E::Key {
+        self.entry.key()
+    }
+
+    /// Get the value if the key is in the map, without marking the field.
+    #[must_use]
+    pub fn get(&self) -> Option<&E::Value> {
+        self.entry.get()
+    }
+
+    /// Modify the value with `f` and mark the field as changed if the key is in the map.
+    #[must_use]
+    pub fn and_modify<F: FnOnce(&mut E::Value)>(mut self, f: F) -> Self {
+        if let Some(value) = self.entry.get_mut() {
+            f(value);
+            mark(self.tracker, &mut self.mask);
+        }
+        self
+    }
+
+    /// Get the value of the key and insert `default` first if there's none.
+    pub fn or_insert(self, default: E::Value) -> FieldValue<'a, E::Value, M> {
+        self.or_insert_with(|| default)
+    }
+
+    /// Get the value of the key and insert the result of `default` first if there's none.
+    pub fn or_insert_with<F: FnOnce() -> E::Value>(
+        self,
+        default: F,
+    ) -> FieldValue<'a, E::Value, M> {
+        let Self {
+            entry,
+            tracker,
+            mask,
+        } = self;
+        let (value, inserted) = entry.or_insert_with(default);
+        let mut value = FieldValue {
+            value,
+            tracker,
+            mask,
+        };
+        if inserted {
+            mark(value.tracker, &mut value.mask);
+        }
+        value
+    }
+
+    /// Get the value of the key and insert the default value first if there's none.
+    pub fn or_default(self) -> FieldValue<'a, E::Value, M>
+    where
+        E::Value: Default,
+    {
+        self.or_insert_with(Default::default)
+    }
+}
+
+/// Add the bits of the field to the tracker unless they were already added.
+fn mark<M: BitOrAssign>(tracker: &mut M, mask: &mut Option<M>) {
+    if let Some(mask) = mask.take() {
+        *tracker |= mask;
+    }
+}
+
+impl<'a, E: MapEntry<'a>, M> fmt::Debug for FieldEntry<'a, E, M>
+where
+    E::Key: fmt::Debug,
+    E::Value: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FieldEntry")
+            .field("key", &self.entry.key())
+            .field("value", &self.entry.get())
+            .finish()
+    }
+}
+
+/// A value of a field marked with `#[tracker::map_collection]`,
+/// returned by [`FieldEntry::or_insert`] and similar methods.
+///
+/// The value can be read through `Deref`, mutable access through
+/// `DerefMut` marks the field as changed.
+pub struct FieldValue<'a, V, M> {
+    value: &'a mut V,
+    tracker: &'a mut M,
+    /// The bits of the field, taken when the field is marked.
+    mask: Option<M>,
+}
+
+impl<'a, V, M: BitOrAssign> FieldValue<'a, V, M> {
+    /// Get the mutable reference to the value and mark the field as changed.
+    pub fn into_mut(mut self) -> &'a mut V {
+        mark(self.tracker, &mut self.mask);
+        self.value
+    }
+}
+
+impl<V, M> Deref for FieldValue<'_, V, M> {
+    type Target = V;
+
+    fn deref(&self) -> &V {
+        self.value
+    }
+}
+
+impl<V, M: BitOrAssign> DerefMut for FieldValue<'_, V, M> {
+    fn deref_mut(&mut self) -> &mut V {
+        mark(self.tracker, &mut self.mask);
+        self.value
+    }
+}
+
+impl<V: fmt::Debug, M> fmt::Debug for FieldValue<'_, V, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("FieldValue").field(&self.value).finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::collections::BTreeMap;
+
+    use super::FieldEntry;
+
+    #[test]
+    fn only_modifications_mark() {
+        let mut map = BTreeMap::from([("a", 1)]);
+        let mut tracker = 0u8;
+        assert_eq!(
+            *FieldEntry::new(map.entry("a"), &mut tracker, 0b10).or_insert(0),
+            1
+        );
+        assert_eq!(tracker, 0);
+
+        let _ = FieldEntry::new(map.entry("b"), &mut tracker, 0b10).and_modify(|value| *value += 1);
+        assert_eq!(tracker, 0);
+        *FieldEntry::new(map.entry("a"), &mut tracker, 0b10).or_insert(0) += 1;
+        assert_eq!((map["a"], tracker), (2, 0b10));
+
+        tracker = 0;
+        FieldEntry::new(map.entry("b"), &mut tracker, 0b10).or_default();
+        assert_eq!((map["b"], tracker), (0, 0b10));
+    }
+}
@@ -0,0 +1,10 @@
+/// Used for the `PartialEq` bounds of compared fields to suggest `no_eq` if the bound isn't met.
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+    message = "the tracked field of type `{Self}` can't be compared",
+    label = "`{Self}` doesn't implement `PartialEq`",
+    note = "mark the field with `#[tracker::no_eq]` to mark it as changed without comparing values"
+)]
+pub trait FieldEq: PartialEq {}
+
+impl<T: PartialEq + ?Sized> FieldEq for T {}
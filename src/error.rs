@@ -0,0 +1,70 @@
+use alloc::string::String;
+use core::fmt;
+
+/// Error returned when a field name doesn't belong to a tracked field.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UnknownField {
+    name: String,
+}
+
+impl UnknownField {
+    /// Create a new error for the given field name.
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into() }
+    }
+
+    /// The name that didn't match any tracked field.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl fmt::Display for UnknownField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown field `{}`", self.name)
+    }
+}
+
+impl core::error::Error for UnknownField {}
+
+/// Error returned when a field chosen at runtime can't be set, see the `dynamic` option.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SetFieldError {
+    /// The name doesn't belong to a tracked field.
+    UnknownField(UnknownField),
+    /// The value doesn't have the type of the field.
+    WrongType {
+        /// The name of the field.
+        field: &'static str,
+        /// The name of the type of the field.
+        expected: &'static str,
+    },
+}
+
+impl fmt::Display for SetFieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownField(err) => err.fmt(f),
+            Self::WrongType { field, expected } => {
+                write!(f, "the value of field `{field}` needs to be a `{expected}`")
+            }
+        }
+    }
+}
+
+impl core::error::Error for SetFieldError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::UnknownField(err) => Some(err),
+            Self::WrongType { .. } => None,
+        }
+    }
+}
+
+impl From<UnknownField> for SetFieldError {
+    fn from(err: UnknownField) -> Self {
+        Self::UnknownField(err)
+    }
+}
@@ -0,0 +1,57 @@
+/// Implemented by tracked structs to provide the masks of their
+/// fields to [`fields_mut!`](crate::fields_mut).
+#[doc(hidden)]
+pub trait FieldMasks {
+    type Masks;
+    type Mask;
+
+    fn field_masks(&self) -> Self::Masks;
+
+    /// The mask of the struct without any bits set, which the masks of the fields are added to.
+    fn empty_mask(&self) -> Self::Mask;
+}
+
+impl<T: FieldMasks + ?Sized> FieldMasks for &mut T {
+    type Masks = T::Masks;
+    type Mask = T::Mask;
+
+    fn field_masks(&self) -> Self::Masks {
+        T::field_masks(self)
+    }
+
+    fn empty_mask(&self) -> Self::Mask {
+        T::empty_mask(self)
+    }
+}
+
+/// Get mutable references to several fields at once and mark them as changed.
+///
+/// Borrowing the fields one after another with `get_mut_#field_name()` isn't possible
+/// because every call borrows the whole struct. This macro borrows the fields directly
+/// and evaluates to a tuple of mutable references in the given order.
+/// To borrow the fields through a mutable reference `r`, use `fields_mut!(*r => ...)`.
+///
+/// ```
+/// #[tracker::track]
+/// struct Test {
+///     x: u8,
+///     y: u8,
+/// }
+///
+/// let mut t = Test { x: 1, y: 2, tracker: 0 };
+/// let (x, y) = tracker::fields_mut!(t => x, y);
+/// std::mem::swap(x, y);
+///
+/// assert_eq!((t.x, t.y), (2, 1));
+/// assert!(t.changed(Test::x()) && t.changed(Test::y()));
+/// ```
+#[macro_export]
+macro_rules! fields_mut {
+    ($value:expr => $($field:ident),+ $(,)?) => {{
+        let value = &mut $value;
+        let masks = $crate::__private::FieldMasks::field_masks(&value);
+        let mask = $crate::__private::FieldMasks::empty_mask(&value);
+        value.__tracker_mark_fields(mask $(| masks.$field())+);
+        ($(&mut value.$field,)+)
+    }};
+}
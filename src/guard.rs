@@ -0,0 +1,61 @@
+use core::fmt;
+use core::ops::{Deref, DerefMut};
+
+/// Guard returned by `track_scope()` that resets
+/// the tracker of the struct when dropped.
+///
+/// The guard dereferences to the struct, so it can be used just like the struct itself.
+pub struct TrackGuard<'a, T> {
+    inner: &'a mut T,
+    reset: fn(&mut T),
+}
+
+impl<'a, T> TrackGuard<'a, T> {
+    /// Create a guard that calls `reset` on `inner` when dropped.
+    #[must_use]
+    pub fn new(inner: &'a mut T, reset: fn(&mut T)) -> Self {
+        Self { inner, reset }
+    }
+}
+
+impl<T> Deref for TrackGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.inner
+    }
+}
+
+impl<T> DerefMut for TrackGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.inner
+    }
+}
+
+impl<T> Drop for TrackGuard<'_, T> {
+    fn drop(&mut self) {
+        (self.reset)(self.inner);
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for TrackGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("TrackGuard").field(&self.inner).finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TrackGuard;
+
+    #[test]
+    fn reset_on_drop() {
+        let mut value = 1;
+        {
+            let mut guard = TrackGuard::new(&mut value, |value| *value = 0);
+            *guard += 1;
+            assert_eq!(*guard, 2);
+        }
+        assert_eq!(value, 0);
+    }
+}
@@ -0,0 +1,101 @@
+use core::hash::{Hash, Hasher};
+
+/// Get the 64-bit FNV-1a hash of `value`, used by the setters of fields marked with `#[tracker::hash_eq]`.
+///
+/// The hash only depends on the value, so it's the same for every run and doesn't need `std`.
+#[doc(hidden)]
+#[must_use]
+pub fn field_hash<T: Hash + ?Sized>(value: &T) -> u64 {
+    let mut hasher = FnvHasher(FNV_OFFSET_BASIS);
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+struct FnvHasher(u64);
+
+impl Hasher for FnvHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// The cached hashes of the fields marked with `#[tracker::hash_eq]`.
+///
+/// A hash is computed from the current value the first time it's needed
+/// and is forgotten whenever the field is modified by a generated method
+/// other than the setter, which stores the hash of the new value instead.
+///
+/// Like the tracker, the hashes aren't compared by `PartialEq` and ignored by `Hash`.
+#[derive(Debug, Clone, Copy)]
+pub struct FieldHashes<const N: usize> {
+    hashes: [Option<u64>; N],
+}
+
+impl<const N: usize> Default for FieldHashes<N> {
+    fn default() -> Self {
+        Self { hashes: [None; N] }
+    }
+}
+
+impl<const N: usize> FieldHashes<N> {
+    /// Check whether `hash` differs from the hash of `current`, the value of the field at `index`.
+    ///
+    /// The hash of `current` is computed and cached if it isn't cached yet.
+    pub fn differs<T: Hash + ?Sized>(&mut self, index: usize, current: &T, hash: u64) -> bool {
+        *self.hashes[index].get_or_insert_with(|| field_hash(current)) != hash
+    }
+
+    /// Cache `hash` as the hash of the field at `index`.
+    pub fn store(&mut self, index: usize, hash: u64) {
+        self.hashes[index] = Some(hash);
+    }
+
+    /// Forget the hash of the field at `index`, because the field is about to be modified.
+    pub fn invalidate(&mut self, index: usize) {
+        self.hashes[index] = None;
+    }
+}
+
+impl<const N: usize> PartialEq for FieldHashes<N> {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl<const N: usize> Eq for FieldHashes<N> {}
+
+impl<const N: usize> Hash for FieldHashes<N> {
+    fn hash<H: Hasher>(&self, _: &mut H) {}
+}
+
+#[cfg(test)]
+mod test {
+    use super::{field_hash, FieldHashes};
+
+    #[test]
+    fn hashes_are_cached() {
+        assert_eq!(field_hash(""), field_hash(""));
+        assert_ne!(field_hash("a"), field_hash("b"));
+
+        let mut hashes = FieldHashes::<2>::default();
+        assert!(!hashes.differs(0, "a", field_hash("a")));
+        assert!(hashes.differs(0, "a", field_hash("b")));
+        // The cached hash is used instead of the value.
+        assert!(!hashes.differs(0, "c", field_hash("a")));
+
+        hashes.store(1, field_hash("b"));
+        assert!(!hashes.differs(1, "a", field_hash("b")));
+        hashes.invalidate(1);
+        assert!(hashes.differs(1, "a", field_hash("b")));
+    }
+}
@@ -0,0 +1,141 @@
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::panic::Location;
+
+/// A change recorded by the `history` option.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChangeEvent<M> {
+    mask: M,
+    location: &'static Location<'static>,
+}
+
+impl<M: Copy> ChangeEvent<M> {
+    /// Get the bit mask of the fields that were newly marked as changed.
+    #[must_use]
+    pub fn mask(&self) -> M {
+        self.mask
+    }
+
+    /// Get the location of the code that marked the fields.
+    ///
+    /// This is the caller of the setters, `get_mut_#field_name()` and `update_#field_name()`
+    /// and the location of the struct for changes marked by other generated methods.
+    #[must_use]
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+}
+
+/// The last `N` changes of a struct with the `history` option.
+///
+/// Like [`Listeners`](crate::Listeners), the history is not part of the state
+/// of the struct: clones start with an empty history, all histories are equal
+/// and hashing ignores them. `Debug` only prints the number of events.
+pub struct History<M, const N: usize> {
+    events: [Option<ChangeEvent<M>>; N],
+    /// The index of the oldest event once the buffer is full.
+    next: usize,
+    len: usize,
+}
+
+impl<M: Copy, const N: usize> History<M, N> {
+    /// Record a change, replacing the oldest one once `N` changes are recorded.
+    pub fn record(&mut self, mask: M, location: &'static Location<'static>) {
+        if N == 0 {
+            return;
+        }
+        self.events[self.next] = Some(ChangeEvent { mask, location });
+        self.next = (self.next + 1) % N;
+        self.len = (self.len + 1).min(N);
+    }
+
+    /// Iterate over the recorded changes from the oldest to the newest.
+    pub fn iter(&self) -> impl Iterator<Item = &ChangeEvent<M>> + '_ {
+        let start = if self.len == N { self.next } else { 0 };
+        (0..self.len).filter_map(move |offset| self.events[(start + offset) % N].as_ref())
+    }
+
+    /// Get the number of recorded changes.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check whether no changes are recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Forget all recorded changes.
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+impl<M, const N: usize> Default for History<M, N> {
+    fn default() -> Self {
+        Self {
+            events: core::array::from_fn(|_| None),
+            next: 0,
+            len: 0,
+        }
+    }
+}
+
+impl<M, const N: usize> Clone for History<M, N> {
+    /// The history belongs to a single struct, so the clone has none.
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl<M, const N: usize> PartialEq for History<M, N> {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl<M, const N: usize> Eq for History<M, N> {}
+
+impl<M, const N: usize> Hash for History<M, N> {
+    fn hash<H: Hasher>(&self, _: &mut H) {}
+}
+
+impl<M, const N: usize> fmt::Debug for History<M, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("History")
+            .field("len", &self.len)
+            .field("capacity", &N)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::panic::Location;
+
+    use super::History;
+
+    #[test]
+    fn oldest_events_are_replaced() {
+        let mut history = History::<u8, 3>::default();
+        let location = Location::caller();
+        for mask in 1..=2 {
+            history.record(mask, location);
+        }
+        let masks =
+            |history: &History<u8, 3>| history.iter().map(|event| event.mask()).collect::<Vec<_>>();
+        assert_eq!(masks(&history), [1, 2]);
+
+        for mask in 3..=5 {
+            history.record(mask, location);
+        }
+        assert_eq!(masks(&history), [3, 4, 5]);
+        assert_eq!(history.len(), 3);
+
+        history.clear();
+        assert!(history.is_empty());
+        assert_eq!(history.iter().count(), 0);
+    }
+}
@@ -0,0 +1,123 @@
+use std::fmt;
+
+use serde_json::Value;
+
+/// Error returned when applying a JSON Patch fails.
+#[derive(Debug)]
+pub enum JsonPatchError {
+    /// The patch is not an array of valid operations.
+    InvalidPatch(String),
+    /// The patch contains operations that are not supported.
+    ///
+    /// Only `add` and `replace` operations on top level fields are supported.
+    UnsupportedOperation(String),
+    /// The patch contains paths that don't belong to a tracked field.
+    UnknownPaths(Vec<String>),
+    /// A value couldn't be deserialized into the type of its field.
+    Deserialize(serde_json::Error),
+}
+
+impl fmt::Display for JsonPatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidPatch(msg) => write!(f, "invalid JSON Patch: {msg}"),
+            Self::UnsupportedOperation(op) => write!(f, "unsupported JSON Patch operation `{op}`"),
+            Self::UnknownPaths(paths) => write!(f, "unknown paths: {}", paths.join(", ")),
+            Self::Deserialize(err) => write!(f, "failed to deserialize value: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for JsonPatchError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Deserialize(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for JsonPatchError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Deserialize(err)
+    }
+}
+
+/// Create a `replace` operation.
+#[doc(hidden)]
+#[must_use]
+pub fn replace_op(path: &str, value: Value) -> Value {
+    let mut op = serde_json::Map::new();
+    op.insert("op".into(), Value::String("replace".into()));
+    op.insert("path".into(), Value::String(path.into()));
+    op.insert("value".into(), value);
+    Value::Object(op)
+}
+
+/// Get the path and value of all operations of a patch.
+#[doc(hidden)]
+pub fn operations(patch: &Value) -> Result<Vec<(&str, &Value)>, JsonPatchError> {
+    let ops = patch
+        .as_array()
+        .ok_or_else(|| JsonPatchError::InvalidPatch("expected an array".into()))?;
+
+    ops.iter()
+        .map(|op| {
+            let field = |name: &str| {
+                op.get(name).ok_or_else(|| {
+                    JsonPatchError::InvalidPatch(format!("operation without `{name}`"))
+                })
+            };
+
+            let kind = field("op")?
+                .as_str()
+                .ok_or_else(|| JsonPatchError::InvalidPatch("`op` is not a string".into()))?;
+            if kind != "add" && kind != "replace" {
+                return Err(JsonPatchError::UnsupportedOperation(kind.into()));
+            }
+
+            let path = field("path")?
+                .as_str()
+                .ok_or_else(|| JsonPatchError::InvalidPatch("`path` is not a string".into()))?;
+            Ok((path, field("value")?))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::{operations, replace_op, JsonPatchError};
+
+    #[test]
+    fn parse_operations() {
+        let patch = json!([
+            { "op": "replace", "path": "/x", "value": 1 },
+            { "op": "add", "path": "/y", "value": "a" },
+        ]);
+        let ops = operations(&patch).unwrap();
+        assert_eq!(ops, vec![("/x", &json!(1)), ("/y", &json!("a"))]);
+
+        assert_eq!(
+            replace_op("/x", json!(1)),
+            json!({ "op": "replace", "path": "/x", "value": 1 })
+        );
+    }
+
+    #[test]
+    fn reject_invalid_patches() {
+        assert!(matches!(
+            operations(&json!({})),
+            Err(JsonPatchError::InvalidPatch(_))
+        ));
+        assert!(matches!(
+            operations(&json!([{ "op": "replace", "value": 1 }])),
+            Err(JsonPatchError::InvalidPatch(_))
+        ));
+        assert!(matches!(
+            operations(&json!([{ "op": "remove", "path": "/x" }])),
+            Err(JsonPatchError::UnsupportedOperation(op)) if op == "remove"
+        ));
+    }
+}
@@ -18,8 +18,82 @@
 //!
 //! To check for changes you can call `var_name.changed(StructName::field_name())` and it will return a bool.
 //!
+//! To run code only for certain changes, `var_name.on_changed(StructName::field_name(), |value| { ... })` calls the closure
+//! if any field of the bit mask has changed and returns the struct, so multiple calls can be chained.
+//! `on_changed_mut()` does the same with mutable access.
+//!
+//! To inspect the changed fields generically, `var_name.visit_changed(&mut visitor)` calls
+//! `ChangedVisitor::field(name, value)` once for every changed field in the order of declaration.
+//!
+//! To borrow several fields mutably at once, `tracker::fields_mut!(var_name => field_1, field_2)` returns
+//! a tuple of mutable references and marks all of these fields as changed.
+//!
+//! `#[tracker::track_impl]` on an impl block of a tracked struct marks the fields that its methods modify
+//! directly. Before every statement that assigns to a field of `self`, like `self.count += 1`, or borrows one
+//! mutably, like `let items = &mut self.items`, the field is marked as changed, also if the new value is equal.
+//! Code that only runs conditionally, like a branch, a match arm or a closure, only marks the fields when it runs.
+//! Untracked fields as well as modifications through method calls like `self.items.push(1)`, inside
+//! macros and through other references to the struct are left alone. Marking borrows the whole struct,
+//! so a field can't be marked while a mutable borrow of another field is used, which `fields_mut!` allows.
+//!
+//! `changed(mask)`, `changed_any()`, `reset()`, `reset_all()`, `mark_all_changed()` and `track_all()` are implemented in the
+//! `tracker::Track` trait, so code can work with any tracked struct, for example `fn save<T: tracker::Track>(value: &mut T)`.
+//! The inherent methods of the same names forward to the trait, so it doesn't need to be imported.
+//! The trait also provides `field_names()` and `field_mask(name)`, which look up the bit masks by the names of their functions.
+//!
+//! In tests, `tracker::assert_changed!(value, x, y)` and `tracker::assert_unchanged!(value, title)` check the fields with
+//! these names. On failure they list the expected fields and all fields that actually changed, and like `assert!`
+//! they take an optional message after the fields.
+//!
+//! Enums are tracked as a whole by a generated `#enum_nameTracker` wrapper. It provides `new(value)`, `get()`, `get_mut()`,
+//! `update(f)`, `set(value)`, `replace(value)`, `changed()`, `reset()` and `variant_changed()`, which only reports
+//! changes that replaced the value with another variant.
+//!
+//! Single values that don't need a tracked struct can be wrapped in `tracker::Tracked<T>`. It dereferences to the
+//! value and provides `get_mut()`, `update(f)`, `set(value)`, `set_unchecked(value)`, `replace(value)`, `changed()` and `reset()`.
+//! `Tracked` implements the `Track` trait with a `bool` mask, so it can also be used for `nested` fields.
+//! Values that are refreshed behind a shared reference can use `tracker::TrackedCell<T: Copy>` instead, its `set(value)`
+//! and `reset()` methods only take `&self`. Like `Cell` it's only meant for a single thread and is not `Sync`.
+//!
+//! Lists can use `tracker::TrackedVec<T>`, which records the indices of changed elements. Elements are modified with
+//! `get_mut(index)` and `set(index, value)`, and `push`, `insert`, `remove`, `pop` and `clear` record the insertion or removal
+//! as a `VecChange`. `changed_indices()` returns the current indices of all changed and inserted elements, so these
+//! follow their elements when indices shift, and `structurally_changed()` reports whether elements were inserted or removed.
+//! Fields of this type are best marked with `nested`, so the struct reports the changes of the list itself.
+//! Comparing two lists only compares their elements.
+//! Maps can use `tracker::TrackedHashMap<K, V>` in the same way. `insert`, `remove`, `get_mut`, `entry` and `clear` record
+//! the affected keys, which are available with `inserted()`, `updated()`, `removed()` and `changed_keys()`. Inserting a value
+//! that is equal to the current one records nothing, and the records always describe the difference to the map at the last
+//! reset, so removing an inserted key forgets it. Reading the map never records or allocates anything.
+//!
+//! Fields with `#[cfg(...)]` attributes keep their bit even when they are disabled, so the bit masks are the
+//! same for all feature combinations. The methods generated for these fields get the same attributes. Trait bounds
+//! of generated impls can't be configured and are not added for these fields.
+//!
 //! To reset all previous changes you can call `var_name.reset()`.
 //!
+//! Alternatively, `var_name.track_scope()` returns a guard that dereferences to the struct
+//! and resets it when dropped, even on early returns.
+//!
+//! To modify several fields at once you can call `var_name.update_all(|proxy| { ... })`.
+//! Only the fields modified through the proxy are marked as changed.
+//!
+//! To find out which fields differ between two instances you can call `var_name.diff(&other)`.
+//! It returns a bit mask of all differing fields, fields marked as `no_eq` are never included.
+//! To replace the whole state, for example with a reloaded config, you can call `var_name.sync_from(new)`.
+//! It moves all fields from `new` without cloning, marks and returns the tracked fields that differ
+//! and always marks fields marked as `no_eq`. Untracked fields are moved without being marked.
+//!
+//! To send changes somewhere else you can call `var_name.to_delta()`.
+//! It returns a `StructNameDelta` struct with an `Option` for each tracked field that is `Some` for every changed field.
+//! Deltas can be applied to other instances with `var_name.apply_delta(delta)`.
+//!
+//! Partial modifications like command line overrides can be applied with `var_name.apply_update(update)`.
+//! The `StructNameUpdate` struct has an `Option` for each tracked field and the method returns a bit mask of the fields that actually changed.
+//!
+//! The names of all changed fields can be retrieved with `var_name.field_mask_paths()`, for example to create a protobuf `FieldMask`.
+//! In the other direction, `StructName::mask_from_paths(&paths)` returns the bit mask for a list of field names.
+//!
 //!
 //! ## How it works
 //!
@@ -114,20 +188,505 @@
 //!
 //! + `do_not_track` if you don't want tracker to implement anything for this field
 //! + `no_eq` if the type of the field doesn't implement PartialEq or tracker should not check for equality when calling `set_#field_name(value)`
-//! so that even overwriting with the same value marks the field as changed.
-//! pub use tracker_macros::track;
+//!   so that even overwriting with the same value marks the field as changed.
+//!   Without it, using the setter of a field whose type doesn't implement PartialEq is an error
+//!   that points at the type of the field.
+//! + `tracker::tracker_field` to use an unsigned integer field declared by you as tracker instead of adding one
+//!   at the end of the struct, for example to keep control over the layout of `#[repr(C)]` structs.
+//!   The type of the field needs enough bits for all tracked fields.
+//! + `tracker::vis(get = "pub", set = "pub(crate)")` to set the visibility of single generated methods of the field
+//!   (`get`, `get_mut`, `update`, `set` and `changed_field`). The other methods keep the visibility of the field.
+//! + `tracker::compare_with = "path::to::function"` to compare values with a `fn(&T, &T) -> bool` that returns
+//!   whether both values are equal instead of `PartialEq`, for example to ignore parts of the value.
+//! + `tracker::no_get`, `tracker::no_mut`, `tracker::no_update` and `tracker::no_set` to skip the getter,
+//!   `get_mut_#field_name()`, `update_#field_name(fn)` or the setter of the field, also on the proxy of `update_all()`.
+//!   The bit of the field and all other methods are still generated. Like with `skip_methods(...)`, the setter is kept
+//!   private if the delta struct needs it.
+//! + `tracker::rename = "name"` to use `name` instead of the name of the field in all generated methods,
+//!   including the bit mask function, for example `set_name(value)` and `StructName::name()`.
+//! + `tracker::nested` for fields that are tracked structs themselves. Changes made with the methods of the
+//!   child count as changes of the field, so `changed_#field_name()`, `changed()` and `changed_any()` of the parent
+//!   also check the child. `get_mut_#field_name()` and `update_#field_name()` don't mark the field because the child
+//!   tracks its own changes, replacing the child is only recorded by the setter.
+//!   `reset()` only resets the parent, `reset_all()` resets the parent and all nested structs recursively.
+//!   Fields of the types `Option<T>` and `Vec<T>` of tracked structs check the contained children, and for
+//!   `Vec<T>` `changed_#field_name_indices()` returns an iterator over the indices of the changed elements.
+//!   Their children are only checked when the changes are queried. Because elements can be added or removed
+//!   through references, `get_mut_#field_name()` and `update_#field_name()` mark these fields as changed.
+//! + `tracker::indexed` for fields of the types `Vec<T>` and `[T; N]` to also generate `get_#field_name_at(index)`,
+//!   which returns `None` if the index is out of bounds, and `set_#field_name_at(index, value)`, which compares
+//!   the new element with the one at the index and returns whether the field was marked as changed.
+//!   Like indexing, the setter panics if the index is out of bounds. Elements are compared with `PartialEq`
+//!   unless the field is marked as `no_eq`.
+//! + `tracker::set_collection` for fields of the types `HashSet<T>` and `BTreeSet<T>` to also generate
+//!   `insert_#field_name(value)`, `remove_#field_name(&value)` and `clear_#field_name()`. Unlike changes through
+//!   `get_mut_#field_name()`, they only mark the field if the set changed, so inserting a value that is already
+//!   present or clearing an empty set leaves it unchanged. All three return whether the field was marked as changed
+//!   and are skipped together with the setter.
+//! + `tracker::map_collection` for fields of the types `HashMap<K, V>` and `BTreeMap<K, V>` to also generate
+//!   `insert_#field_name(key, value)`, `remove_#field_name(&key)` and `clear_#field_name()`, which only mark the field
+//!   if the map changed. Inserted values are compared with `PartialEq` unless the field is marked as `no_eq`.
+//!   `entry_#field_name(key)` returns a `tracker::FieldEntry` that wraps the entry of the map and marks the field
+//!   lazily: inserting a value into a vacant entry and `and_modify()` mark it, while the value returned by
+//!   `or_insert()` and similar methods only marks it when it's accessed mutably. Because the entry only sets the bits
+//!   in the tracker, `entry_#field_name()` isn't generated with the `atomic` option or options that record changes,
+//!   like `previous` or `history`.
+//! + `tracker::hash_eq` for large fields like long strings or byte buffers, whose setter stores a 64-bit hash of the
+//!   new value and compares it with the cached hash of the current value first. Only if the hashes are equal, the
+//!   values are compared with `PartialEq`, so a change is never missed and a collision costs one comparison.
+//!   With `tracker::hash_eq = "hash_only"` equal hashes count as equal values without the comparison, which misses
+//!   the change if two different values collide. The field type needs to implement `Hash`, and the hash is computed
+//!   from the current value the first time it's needed. Other generated methods that modify the field forget the
+//!   hash. Modifying the field directly doesn't, so the next set can mark the field as changed although the value is
+//!   equal, and with `hash_only` it can even miss a change. The hashes are stored in an injected field, so
+//!   `#[derive(Track)]` doesn't support `hash_eq`.
+//! + `tracker::elements` for fields of the type `[T; N]` to track each element with its own bit, so the field
+//!   uses N bits of the tracker. `set_#field_name_elem(index, value)` compares and marks a single element,
+//!   `changed_#field_name_elem(index)` checks it, and both panic if the index is out of bounds.
+//!   `StructName::#field_name_all()` and the bit mask function of the field contain the bits of all elements,
+//!   so `changed_#field_name()` checks whether any element changed and the other methods of the field mark all elements.
+//!   The length needs to be an integer literal and all bits together can't exceed 128.
+//! + `tracker::redact` to print `<redacted>` instead of the value of the field in all generated output that formats
+//!   values: the `Debug` impls of the `debug` option and of the generated structs like the delta,
+//!   `changed_values()` and `visit_changed()`. The field is tracked as usual, and untracked fields can be redacted
+//!   as well. Use the `debug` option instead of deriving `Debug`, because the derive prints all values.
+//!   `changed_view()` serializes the string `"<redacted>"` instead of the value, and tracked fields can't be
+//!   redacted together with the `json_patch` option, whose patches contain the values.
+//! + `tracker::default = expr` sets the value that the field gets in `new()` of the `encapsulate` option and in the
+//!   `Default` impl of the `impl_default` option. The expression is evaluated where the struct is defined, so it can
+//!   use constants and functions of the module, and works for untracked fields as well. Fields with a default
+//!   aren't arguments of `new()`, instead `with_#field_name(value)` replaces the default without marking the field.
+//!
+//! All of them can also be combined in a single attribute like
+//! `#[tracker(no_eq, column = "name", vis(set = "pub(crate)"))]`.
+//!
+//! Methods that compare values, like `set_#field_name(value)` or `diff(other)`, only require `PartialEq`
+//! for the compared fields on the methods themselves. Generic parameters of the struct don't need the bound
+//! as long as these methods aren't used.
+//!
+//! `no_eq` can also be set only for some builds with `cfg_attr`, for example
+//! `#[cfg_attr(debug_assertions, tracker::no_eq)]`. The code that depends on it is then generated
+//! for both cases. The other attributes can't be used inside of `cfg_attr`.
+//!
+//! Doc comments of tracked fields, including `#[doc = include_str!("...")]`, are copied onto the getters,
+//! `update_#field_name(fn)`, `changed_#field_name()` and the setter of the field after the generated summary,
+//! so the documentation of the field is also shown on its methods.
+//!
+//! ## Derive
+//!
+//! If the struct can't be modified by an attribute macro, `#[derive(tracker::Track)]` generates the same methods.
+//! Derives can't add fields, so the struct needs to declare the tracker field itself, either called `tracker`
+//! or marked with `#[tracker_field]`. Options are passed with `#[tracker(...)]`:
+//!
+//! ```rust
+//! #[derive(tracker::Track)]
+//! #[tracker(apply_changes)]
+//! struct Test {
+//!     x: u8,
+//!     #[no_eq]
+//!     y: u8,
+//!     tracker: u8,
+//! }
+//! ```
+//!
+//! Field attributes can't use the `tracker::` prefix here, write `#[tracker(column = "...")]` or the bare
+//! `#[no_eq]`, `#[do_not_track]` and `#[tracker_field]` instead.
+//! The options `encapsulate`, `previous`, `versioned`, `atomic`, `serde`, `serde_state`, `tracker_attrs` and `tracker_vis` aren't supported
+//! because they modify the struct.
+//!
+//! ## Function-like macro
+//!
+//! `tracker::tracked!` takes struct definitions and generates the same code as `#[tracker::track]`,
+//! which helps if the structs are generated by a `macro_rules!` macro. Options of all structs are passed
+//! with `#![track(...)]` at the start, options of single structs with `#[track(...)]` on the struct:
+//!
+//! ```rust
+//! tracker::tracked! {
+//!     #![track(debug)]
+//!
+//!     #[derive(Default)]
+//!     pub struct Model {
+//!         x: u8,
+//!         #[no_eq]
+//!         y: u8,
+//!     }
+//!
+//!     #[track(sql)]
+//!     struct Row {
+//!         id: u32,
+//!     }
+//! }
+//! ```
+//!
+//! ## Options
+//!
+//! Additional methods can be enabled by passing options to the `track` attribute:
+//!
+//! ```rust
+//! #[tracker::track(apply_changes)]
+//! #[derive(Clone)]
+//! struct Test {
+//!     x: u8,
+//! }
+//! ```
+//!
+//! + `apply_changes` generates `apply_changes_from(&other)` which copies all fields marked as changed in `other`
+//!   and marks them as changed. The types of tracked fields need to implement `Clone`.
+//! + `merge` generates `merge_from(&other)` which merges the changes of two copies of a value that were changed
+//!   independently. Fields that only `other` changed are copied and marked as changed, fields that only `self`
+//!   changed are kept. If both changed a field, the value of `self` wins and the field is a conflict unless both
+//!   values are equal, and fields marked as `no_eq` always conflict. The returned `tracker::MergeOutcome` has the
+//!   masks of the `merged()` fields and the `conflicts()` for the caller to resolve. The copies should start
+//!   without changes, for example using `clone_resets`. The types of tracked fields need to implement `Clone`.
+//! + `staged` generates `begin()`, `commit()`, `abort()` and `is_staging()` for applying the changes of several
+//!   fields together. `stage_#field_name(value)` stores a value in the injected `StructNameStaged` struct
+//!   without changing the field and `staged_#field_name()` returns the staged value, or the current value if
+//!   none is staged. `commit()` sets the staged values with the setters, so only the fields whose value differs
+//!   are marked as changed, and `abort()` discards them. Staging a value without `begin()` begins a stage, and
+//!   calling `begin()` while staging keeps the staged values, so nested stages are flattened into one that ends
+//!   with the first `commit()` or `abort()`. Fields whose setter is skipped can't be staged.
+//! + `no_delta` disables the generation of the `StructNameDelta` struct and its methods.
+//! + `no_update` disables the generation of the `StructNameUpdate` struct and its method.
+//! + `snapshot` generates `snapshot()` which stores the values of all tracked fields in a `StructNameSnapshot`
+//!   and `changed_since(&snapshot)` which returns a bit mask of all fields that differ from the snapshot.
+//!   Fields marked as `no_eq` are not part of snapshots. The types of tracked fields need to implement `Clone`.
+//! + `data_struct` generates a `StructNameData` struct with all declared fields, tracked or not, but without the
+//!   tracker, for example to send the values to another thread or to store them. The name can be changed with
+//!   `data_struct = "Name"`. The struct and its fields have the same visibility, generics and field attributes
+//!   as the tracked struct and its `#[derive(...)]` and `#[serde(...)]` attributes are copied, which isn't
+//!   possible with `#[derive(Track)]`. `From` converts in both directions with all fields marked as unchanged,
+//!   `from_data_changed(data)` marks all fields as changed instead and `as_data()` clones the values into a new
+//!   data struct. This option can't be combined with `debug_unconsumed` because it moves the fields out of the struct.
+//! + `transparent` makes a struct with a single tracked field behave like the value of this field, which is
+//!   useful for newtypes. It implements `Deref` and `DerefMut` with the type of the field as target, where `DerefMut`
+//!   marks the field as changed like `get_mut_#field_name()`, and generates `set(value)` which calls the setter of
+//!   the field. Other fields need to be marked with `#[tracker::do_not_track]`.
+//! + `dynamic` gives access to the tracked fields chosen at runtime, for example for scripting or property editors.
+//!   It generates a `StructNameField` enum with a variant for each tracked field, with `name()` and `from_name(name)`,
+//!   and `get_field(field)` which returns a `&dyn Any`. `set_field(field, Box::new(value))` and
+//!   `set_field_by_name(name, Box::new(value))` call the setter of the field and return a `tracker::SetFieldError`
+//!   if the name is unknown or the value has another type. The types of the fields need to be `'static`.
+//! + `commands` generates a `StructNameCommand` enum with a `Set#FieldName(value)` variant for each tracked field,
+//!   so changes can be queued as data, for example for undo or to send them to another thread.
+//!   `apply(command)` sets the field with its setter and returns whether it was marked as changed, and
+//!   `command_for_set_#field_name(value)` creates a command. The enum implements `Debug` and `Clone` if the
+//!   types of the fields do.
+//! + `previous` stores the value each field had before it was changed for the first time since the last reset.
+//!   It generates `previous_#field_name()` and `revert_#field_name()` which restores the previous value.
+//!   The values are stored in an additional `tracker_previous` field that needs to be initialized with `Default::default()`.
+//!   The types of tracked fields need to implement `Clone`.
+//! + `versioned` maintains a version counter that is incremented on every change and stores for each field the version it was
+//!   changed last. It generates `version()` and `changed_since(version)` which returns a bit mask of all fields changed after
+//!   the given version, so multiple observers can track changes without resetting the struct.
+//!   The versions are stored in an additional `tracker_versions` field that needs to be initialized with `Default::default()`.
+//! + `atomic` stores the tracker in an atomic integer like `AtomicU8`, so changes can be checked and marked through `&self`,
+//!   for example by threads that share the struct behind an `RwLock` and only hold a read lock. It generates
+//!   `mark_changed(&self, mask)`, the setters still take `&mut self` because they modify the values.
+//!   All accesses use `Ordering::Relaxed`, so marks are never lost but don't synchronize anything else:
+//!   the values themselves still need to be published with a lock or a channel.
+//!   Atomic integers don't implement `Clone`, `PartialEq` or `Hash`, use `clone_keeps`, `impl_partial_eq` and `impl_hash`
+//!   instead of deriving them. This option supports up to 64 fields and can't be combined with `versioned` and `serde_state`.
+//! + `on_change = path::to::function` calls the function with a bit mask of the fields that were just marked as changed.
+//!   It's called by all generated methods after the tracker was updated, but only for bits that weren't set before,
+//!   so setting an equal value or changing a field again before the next reset doesn't call it. The function gets
+//!   no access to the struct, if it triggers other changes of the same struct it needs to defer them.
+//! + `trace` emits a `tracing` event with the fields `struct_name`, `field`, `method` and `newly_changed` whenever the
+//!   `set_#field_name()`, `get_mut_#field_name()` and `update_#field_name()` methods mark a field as changed, and an event
+//!   with the `struct_name` on `reset()` and `mark_all_changed()`. The events are emitted at the trace level with the
+//!   module of the struct as target. This requires the `tracing` feature, without this option the generated code doesn't change.
+//! + `listeners` adds a `tracker_listeners` field with a list of callbacks that are called like the `on_change` function,
+//!   and generates `add_change_listener(f)` which returns a `ListenerId` and `remove_change_listener(id)`.
+//!   The list isn't part of the state of the struct: clones start without listeners, `PartialEq` and `Hash` ignore it
+//!   and `Default` and `Debug` work, so these traits can still be derived. With `serde` the list is skipped.
+//!   Because the listeners are neither `Send` nor `Sync`, the struct isn't either. If a listener panics, the other listeners
+//!   are still called and the panic is resumed afterwards, setters assign the value before listeners are called.
+//!   This option can't be combined with `atomic`.
+//! + `stream` adds a `tracker_changes` field and generates `changes_stream()` which returns a `ChangesReceiver`,
+//!   a `futures_core::Stream` of the bits that are passed to the `on_change` function. Masks that weren't received yet
+//!   are combined with `|` instead of being queued, and the stream ends when the struct is dropped. Receivers can be
+//!   cloned and sent to other threads. Like with `listeners`, clones of the struct start without receivers, the common
+//!   traits can still be derived and `serde` skips the field. This option works with `atomic` and requires the `stream` feature.
+//! + `remote` adds a `tracker_remote` field and generates `change_handle()`, which returns a `StructNameChangeHandle`
+//!   that marks fields from other threads without access to the struct. The handle is `Clone`, `Send` and `Sync`,
+//!   `mark(mask)` and `mark_#field_name()` collect the bits in an atomic integer shared with the struct, and the owner
+//!   adds them to the tracker with `absorb_remote()`, for example at the start of every update. Until then, `changed()`
+//!   doesn't see them. Clones of the struct have their own handles, the common traits can still be derived and `serde`
+//!   skips the field. This option can track up to 64 fields, like `atomic`.
+//! + `history = N` records the last `N` changes in a ring buffer. Each change has the mask of
+//!   the fields that were newly marked as changed and the location of the caller of the
+//!   setter, `get_mut_#field_name()`, `update_#field_name()` or `mark_all_changed()`.
+//!   `change_history()` iterates over them from the oldest to the newest and `clear_history()`
+//!   forgets them. Like the listeners, the history isn't part of the state of the struct and
+//!   `serde` skips it. This option can't be combined with `atomic`.
+//! + `counters` counts how often each field was marked as changed while it was unchanged. `change_count_#field_name()`
+//!   returns the count of a field and `reset_counters()` sets all counts to zero, `reset()` doesn't affect them.
+//!   Like the tracker, the counters don't affect `PartialEq` and `Hash` and `serde` skips them.
+//!   This option can't be combined with `atomic`.
+//! + `timestamps` stores the time at which each field was last marked as changed while it was unchanged.
+//!   `last_changed_#field_name()` returns it as `Instant` and `elapsed_since_change_#field_name()` returns the
+//!   time that passed since then, `reset()` doesn't affect them. The time comes from `tracker::SystemClock`,
+//!   other implementations of `tracker::Clock` can be used with `timestamps = MyClock` to control the time in tests.
+//!   Like the counters, the timestamps don't affect `PartialEq` and `Hash` and `serde` skips them.
+//!   This option can't be combined with `atomic`.
+//! + `ordered` stores the order in which the fields were marked as changed since the last reset.
+//!   `changes_in_order()` returns an iterator over the bit masks of the changed fields in this order, fields that
+//!   were marked at once follow the order of their bits. Fields marked with `#[tracker::elements]` have a mask for
+//!   every changed element. Like the tracker, the order doesn't affect `PartialEq` and `Hash` and `serde` skips it.
+//!   This option can't be combined with `atomic`.
+//! + `persistent` adds a second bit mask that collects all changes and isn't cleared by `reset()`, for example
+//!   to know which fields need to be saved even though the changes were already rendered. `ever_changed(mask)` and
+//!   `ever_changed_any()` check it and `clear_persistent()` clears it. Changes that nested fields record in their
+//!   own tracker are only included once the parent marks the field. Like the tracker, the mask doesn't
+//!   affect `PartialEq` and `Hash` and `serde` skips it.
+//!   This option can't be combined with `atomic`.
+//! + `double_buffer` keeps the changes of the last cycle: `reset()` moves the tracker into a second mask before
+//!   clearing it, which overwrites the changes of the cycle before. `previous_changes()` returns this mask and
+//!   `previously_changed(mask)` checks it, so consumers that run after the owner reset the struct still see
+//!   what changed. Like the tracker, the mask doesn't affect `PartialEq` and `Hash` and `serde` skips it.
+//! + `consumers = N` stores a mask for each of `N` consumers that handle the changes independently, for example
+//!   rendering and saving. Every change is marked for all consumers and `changed_for(consumer, mask)`,
+//!   `changed_any_for(consumer)` and `reset_for(consumer)` use the mask of one consumer, panicking if the index
+//!   isn't smaller than `N`. Consumer 0 is the tracker of the struct, so the other methods like `changed(mask)`
+//!   and `reset()` work with it. For the other consumers, changes that nested fields record in their own tracker
+//!   are only included once the parent marks the field. Like the tracker, the masks don't affect `PartialEq`
+//!   and `Hash` and `serde` skips them. This option can't be combined with `atomic`.
+//! + `debug_unconsumed` implements `Drop` in builds with debug assertions to panic if the struct is dropped
+//!   while fields are still marked as changed, listing the names of these fields. This catches changes that are
+//!   never handled because the consumer didn't run. Release builds don't get a `Drop` impl. Structs with their own
+//!   `Drop` impl can't use this option, and in debug builds fields can't be moved out of the struct.
+//! + `json_patch` generates `to_json_patch()` which exports all changed fields as JSON Patch (RFC 6902)
+//!   and `apply_json_patch(&patch)` which applies a JSON Patch and returns a bit mask of all changed fields.
+//!   Field names respect `#[serde(rename = "...")]` and `#[serde(rename_all = "...")]` attributes.
+//!   This requires the `serde_json` feature and the types of tracked fields need to implement `Serialize` and `Deserialize`.
+//! + `sql` generates `changed_columns()` which returns the column names of all changed fields and
+//!   `set_clause_with_placeholders(start_index)` which returns the `SET` clause of an SQL `UPDATE` statement
+//!   like `title = $1, count = $2` or `None` if nothing changed. Column names default to the field names
+//!   and can be overwritten with `#[tracker::column = "..."]`.
+//! + `diesel(table = crate::schema::users)` generates the `StructNameChangeset` struct that derives Diesel's
+//!   `AsChangeset` for the given table with an `Option` of a reference to every tracked field, and `as_changeset()`
+//!   which only sets the changed fields, so `diesel::update(users).set(user.as_changeset())` only writes the changed
+//!   columns. The columns are named like with `sql`. This option requires the `diesel` feature and a dependency on
+//!   `diesel` 2.2 or later.
+//! + `serde` adds `#[serde(skip)]` to the `tracker` field and all other fields added by the macro,
+//!   so deriving `Serialize` and `Deserialize` works without exposing the tracker state.
+//! + `changed_view` generates `changed_view()` which returns a view that serializes only the changed fields as a map,
+//!   for example `serde_json::to_string(&value.changed_view())`. Field names respect `#[serde(rename = "...")]` attributes.
+//!   This requires the `serde` feature and the types of tracked fields need to implement `Serialize`.
+//! + `apply_json` generates `apply_json(&value)` which assigns the fields present in a JSON object
+//!   and returns a bit mask of all changed fields. Unknown keys are reported as an error.
+//!   This requires the `serde_json` feature and the types of tracked fields need to implement `Deserialize`.
+//! + `serde_state` serializes the `tracker` field as a list of changed field names under the `__changed` key,
+//!   so pending changes survive a round trip even if fields were added or removed in between.
+//!   Names that don't belong to a tracked field are ignored during deserialization.
+//!   All other fields added by the macro are skipped. This requires the `serde` feature.
+//! + `impl_partial_eq`, `impl_eq` and `impl_hash` implement `PartialEq`, `Eq` (including `PartialEq`) and `Hash`
+//!   by comparing or hashing all declared fields, including untracked ones, but not the tracker.
+//!   The same traits can't be derived in addition.
+//! + `impl_default` implements `Default` with the values set with `#[tracker::default = ...]` and
+//!   `Default::default()` for all other fields, and the tracker starts without changes. Unlike the derive,
+//!   only the types of fields without a default need to implement `Default`.
+//! + `debug` implements `Debug` by printing all declared fields and marking changed fields
+//!   like `title: "hi" (changed)`. The tracker itself is not printed and `Debug` can't be derived in addition.
+//! + `clone_resets` implements `Clone` so that the clone has no changed fields.
+//!   `clone_keeps` implements `Clone` and keeps the tracker explicitly. `Clone` can't be derived in addition.
+//! + `project` generates `project()` which returns a view with a `FieldView` for every tracked field
+//!   that provides the value with `value()` and whether it changed with `changed()`.
+//!   Fields marked as `do_not_track` are not part of the view.
+//! + `typed_mask` generates a `#struct_nameMask` type that is used for all bit masks instead of an integer,
+//!   so masks of different structs can't be mixed up. It supports `|`, `&` and `!` and is converted from and to
+//!   integers with `from_bits()` and `bits()`. The `tracker` field then needs to be initialized with `Default::default()`.
+//! + `bitflags` works like `typed_mask`, but generates a `#struct_nameChanges` type with the `bitflags!` macro.
+//!   It has a flag named like the bit mask function in upper case for each field, for example `TestChanges::X`
+//!   for the field `x`, all methods of `bitflags` types like `contains()` and a `Debug` implementation that prints
+//!   the names of the set flags. This option requires the `bitflags` feature and can't be combined with `typed_mask`.
+//! + `debug_values` generates `changed_values()` which iterates over the names and values of all changed fields
+//!   as `(&'static str, &dyn Debug)`. The types of tracked fields need to implement `Debug`.
+//! + `skip_methods(...)` doesn't generate the listed methods. Per-field methods are named after their prefix
+//!   (`get`, `get_mut`, `update`, `set` and `changed_field` for `changed_#field_name()`), struct-level methods
+//!   by their name (`diff`, `sync_from`, `track_all`, `mark_all_changed`, `changed_any`, `update_all`, `on_changed`, `track_scope`,
+//!   `visit_changed`, `field_mask_paths`, `mask_from_paths` and `fields_mut`). `changed()`, `reset()` and the bit mask
+//!   functions are always generated. Skipped setters are still generated privately if the delta struct needs them.
+//! + `minimal` skips all of these methods except the setters and implies `no_delta` and `no_update`.
+//! + `compact` leaves out the doc comments of the methods and struct fields generated for each field,
+//!   which keeps the expansion and the documentation of structs with many fields short.
+//! + `encapsulate` makes all tracked fields private so they can only be modified through the generated methods,
+//!   which keep the visibility of the fields. It also generates `new()` which takes the values of all declared fields
+//!   without a default in order, because the struct can't be constructed with a literal outside of its module anymore.
+//! + `field = "..."` renames the injected `tracker` field, for example if the struct already has a field
+//!   called `tracker`. All generated code uses the new name.
+//! + `trait = "TestTracked"` generates a trait with the given name and the visibility of the generated methods
+//!   that declares the getters, `update_#field_name(fn)`, `changed_#field_name()` and the setters of all fields
+//!   together with `changed(mask)`, `changed_any()`, `reset()` and `mark_all_changed()`, and implements it for the struct
+//!   by forwarding to these methods. Code that only uses the trait can then be tested with mock implementations.
+//!   Methods skipped with `skip_methods(...)` or the field attributes aren't part of the trait, generic struct
+//!   parameters become parameters of the trait and `update_#field_name(fn)` requires `Self: Sized`,
+//!   so the trait can still be used as `dyn` trait.
+//! + `tracker_attrs(...)` copies the given attributes onto the injected `tracker` field, for example
+//!   `tracker_attrs(serde(skip, default), builder(setter(skip)))` for other derives on the struct
+//!   that need to know about the field. It can't be used with a field marked with `#[tracker::tracker_field]`.
+//! + `tracker_vis = "pub"` sets the visibility of the injected `tracker` field and the other injected fields,
+//!   like `tracker_previous` of the `previous` option, which are private otherwise. This allows struct literals
+//!   outside of the module, for example `Point { x: 1, y: 2, tracker: 0 }`. The downside is that code outside of
+//!   the module can change the tracker directly and bypass the generated methods, so prefer a constructor for
+//!   public APIs. A field marked with `#[tracker::tracker_field]` keeps its own visibility instead.
+//! + `ty = u32` uses the given unsigned integer type for the tracker and all bit masks instead of the smallest
+//!   type that fits all tracked fields, so adding fields doesn't change the type. It's an error if the type is too narrow.
+//! + `vis = "pub(crate)"` sets the visibility of all generated methods and types instead of copying the visibility
+//!   of the struct and its fields. Any visibility is accepted, `vis = ""` makes everything private.
+//! + `prefix = "t_"` adds a prefix to all generated per-field methods including the bit mask functions,
+//!   for example `t_set_x(value)` and `StructName::t_x()`, to avoid collisions with other methods.
+//! + `trait_only` doesn't generate the inherent methods that forward to the `tracker::Track` trait,
+//!   so `changed()`, `reset()` and the other methods of the trait are only available if the trait is imported.
+//! + `no_eq` treats all fields as `no_eq`, so setters mark fields as changed without comparing values.
+//!   Single fields can be compared again by marking them with `#[tracker::eq]`.
+//! + `strict_attrs` only recognizes the namespaced field attributes like `#[tracker::no_eq]` and `#[tracker(...)]`.
+//!   Bare attributes like `#[no_eq]`, `#[do_not_track]` and `#[tracker_field]` are left on the field,
+//!   so they can be used as helper attributes of other derive macros on the same struct.
+//!
+//! ## no_std
+//!
+//! Tracked structs work without the standard library if the default `std` feature is disabled.
+//! The `alloc` feature enables everything that needs an allocator: `TrackedVec`, `Listeners`, the `assert_changed!`
+//! and `assert_unchanged!` macros and the `sql`, `json_patch`, `apply_json`, `serde_state`, `listeners`, `remote` and `dynamic` options.
+//! Without it, using one of these options is an error and `field_mask_paths()` and `mask_from_paths()` aren't generated.
+//! `map_collection` fields need `alloc` for `BTreeMap` and `std` for `HashMap`.
+//! `TrackedHashMap`, the `timestamps` option and the `serde_json` and `stream` features require `std`.
+//!
+//! ```toml
+//! [dependencies]
+//! tracker = { version = "0.2", default-features = false, features = ["alloc"] }
+//! ```
 
+#![cfg_attr(not(feature = "std"), no_std)]
 #![warn(
     missing_debug_implementations,
     missing_docs,
     rust_2018_idioms,
     unreachable_pub,
     clippy::cargo,
-    clippy::must_use_candidate,
-    clippy::cargo
+    clippy::must_use_candidate
 )]
+// Optional dependencies can pull in different versions of the same crate.
+#![allow(clippy::multiple_crate_versions)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+pub use tracker_macros::{track, track_impl, tracked, Track};
+
+#[cfg(feature = "serde_json")]
+mod apply_json;
+#[cfg(feature = "alloc")]
+mod assert;
+mod cell;
+mod consumers;
+mod counters;
+mod debug;
+#[cfg(feature = "alloc")]
+mod entry;
+mod eq;
+#[cfg(feature = "alloc")]
+mod error;
+mod fields_mut;
+mod guard;
+mod hashes;
+mod history;
+#[cfg(feature = "serde_json")]
+mod json_patch;
+#[cfg(feature = "alloc")]
+mod listeners;
+#[cfg(feature = "std")]
+mod map;
+mod merge;
+mod order;
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+mod remote;
+mod set;
+#[cfg(feature = "alloc")]
+mod sql;
+#[cfg(feature = "stream")]
+mod stream;
+#[cfg(feature = "std")]
+mod timestamps;
+mod track;
+mod tracked;
+mod unconsumed;
+#[cfg(feature = "alloc")]
+mod vec;
+mod versions;
+mod view;
+mod visitor;
 
-pub use tracker_macros::track;
+#[cfg(feature = "serde_json")]
+pub use apply_json::ApplyJsonError;
+pub use cell::TrackedCell;
+pub use consumers::Consumers;
+pub use counters::Counters;
+#[cfg(feature = "alloc")]
+pub use entry::{FieldEntry, FieldValue, MapEntry};
+#[cfg(feature = "alloc")]
+pub use error::{SetFieldError, UnknownField};
+pub use guard::TrackGuard;
+pub use hashes::FieldHashes;
+pub use history::{ChangeEvent, History};
+#[cfg(feature = "serde_json")]
+pub use json_patch::JsonPatchError;
+#[cfg(feature = "alloc")]
+pub use listeners::{ListenerId, Listeners};
+#[cfg(feature = "std")]
+pub use map::{TrackedEntry, TrackedHashMap};
+pub use merge::MergeOutcome;
+pub use order::ChangeOrder;
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+pub use remote::{AtomicMask, ChangeHandle, RemoteChanges};
+#[cfg(feature = "stream")]
+pub use stream::{ChangesReceiver, ChangesSender};
+#[cfg(feature = "std")]
+pub use timestamps::{Clock, SystemClock, Timestamps};
+pub use track::Track;
+pub use tracked::Tracked;
+#[cfg(feature = "alloc")]
+pub use vec::{TrackedVec, VecChange};
+pub use versions::Versions;
+pub use view::FieldView;
+pub use visitor::ChangedVisitor;
+
+/// Items used by the generated code, not part of the public API.
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "serde_json")]
+    pub use crate::apply_json::object as apply_json_object;
+    #[cfg(feature = "alloc")]
+    pub use crate::assert::assert_fields;
+    pub use crate::debug::{ChangedField, Redacted};
+    pub use crate::eq::FieldEq;
+    pub use crate::fields_mut::FieldMasks;
+    pub use crate::hashes::field_hash;
+    #[cfg(feature = "serde_json")]
+    pub use crate::json_patch::{
+        operations as json_patch_operations, replace_op as json_patch_replace,
+    };
+    pub use crate::set::set;
+    #[cfg(feature = "alloc")]
+    pub use crate::sql::set_clause as sql_set_clause;
+    pub use crate::unconsumed::assert_consumed;
+    #[cfg(feature = "alloc")]
+    pub use alloc::collections::btree_map;
+    #[cfg(feature = "alloc")]
+    pub use alloc::{borrow::ToOwned, boxed::Box, string::String, vec::Vec};
+    #[cfg(feature = "bitflags")]
+    pub use bitflags;
+    #[cfg(feature = "serde")]
+    pub use serde;
+    #[cfg(feature = "serde_json")]
+    pub use serde_json;
+    #[cfg(feature = "std")]
+    pub use std::collections::hash_map;
+    #[cfg(feature = "std")]
+    pub use std::time::Instant;
+    #[cfg(feature = "tracing")]
+    pub use tracing;
+}
 
 #[cfg(test)]
 mod test {
@@ -139,6 +698,7 @@ mod test {
         _Copy,
     }
 
+    #[allow(clippy::derivable_impls)]
     impl Default for NoCopy {
         fn default() -> Self {
             NoCopy::Do
@@ -198,9 +758,11 @@ mod test {
 
     #[test]
     fn test_all() {
-        let mut empty = Empty { tracker: 1 };
-        assert!(empty.changed(Empty::track_all()));
-        assert!(empty.changed_any());
+        let mut empty = Empty { tracker: 0 };
+        assert_eq!(Empty::track_all(), 0);
+        empty.mark_all_changed();
+        assert!(!empty.changed(Empty::track_all()));
+        assert!(!empty.changed_any());
         empty.reset();
 
         let mut t = Test::default();
@@ -0,0 +1,140 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+#[cfg(feature = "std")]
+use std::panic::{self, AssertUnwindSafe};
+
+type Listener<M> = Box<dyn FnMut(M)>;
+
+/// Identifies a listener added with `add_change_listener()` to remove it again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ListenerId(u64);
+
+/// The listeners of a struct with the `listeners` option.
+///
+/// Listeners are not part of the state of the struct: clones start without
+/// listeners, all lists are equal and hashing ignores them, so deriving
+/// `Default`, `Clone`, `PartialEq`, `Hash` and `Debug` works as if the field didn't exist.
+pub struct Listeners<M> {
+    listeners: Vec<(ListenerId, Listener<M>)>,
+    next_id: u64,
+}
+
+impl<M: Copy> Listeners<M> {
+    /// Add a listener and get the id to remove it.
+    pub fn add(&mut self, f: impl FnMut(M) + 'static) -> ListenerId {
+        let id = ListenerId(self.next_id);
+        self.next_id += 1;
+        self.listeners.push((id, Box::new(f)));
+        id
+    }
+
+    /// Remove a listener, returns `false` if there was none with this id.
+    pub fn remove(&mut self, id: ListenerId) -> bool {
+        let len = self.listeners.len();
+        self.listeners.retain(|(listener, _)| *listener != id);
+        self.listeners.len() != len
+    }
+
+    /// Get the number of listeners.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.listeners.len()
+    }
+
+    /// Check whether there are no listeners.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.listeners.is_empty()
+    }
+
+    /// Call all listeners with `mask`.
+    ///
+    /// If a listener panics, the other listeners are still called
+    /// and the first panic is resumed afterwards. Without the `std`
+    /// feature panics can't be caught and the remaining listeners are skipped.
+    #[cfg(feature = "std")]
+    pub fn notify(&mut self, mask: M) {
+        let mut panic = None;
+        for (_, listener) in &mut self.listeners {
+            if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| listener(mask))) {
+                panic.get_or_insert(payload);
+            }
+        }
+        if let Some(payload) = panic {
+            panic::resume_unwind(payload);
+        }
+    }
+
+    /// Call all listeners with `mask`.
+    #[cfg(not(feature = "std"))]
+    pub fn notify(&mut self, mask: M) {
+        for (_, listener) in &mut self.listeners {
+            listener(mask);
+        }
+    }
+}
+
+impl<M> Default for Listeners<M> {
+    fn default() -> Self {
+        Self {
+            listeners: Vec::new(),
+            next_id: 0,
+        }
+    }
+}
+
+impl<M> Clone for Listeners<M> {
+    /// Listeners can't be cloned, so the clone has none.
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl<M> PartialEq for Listeners<M> {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl<M> Eq for Listeners<M> {}
+
+impl<M> Hash for Listeners<M> {
+    fn hash<H: Hasher>(&self, _: &mut H) {}
+}
+
+impl<M> fmt::Debug for Listeners<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Listeners")
+            .field("len", &self.listeners.len())
+            .finish()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use std::cell::RefCell;
+    use std::panic::{self, AssertUnwindSafe};
+    use std::rc::Rc;
+
+    use super::Listeners;
+
+    #[test]
+    fn panics_are_resumed_after_all_listeners() {
+        let calls = Rc::new(RefCell::new(Vec::new()));
+        let mut listeners = Listeners::default();
+        listeners.add(|_: u8| panic!("listener"));
+        let recorded = Rc::clone(&calls);
+        let id = listeners.add(move |mask| recorded.borrow_mut().push(mask));
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| listeners.notify(1)));
+        assert!(result.is_err());
+        assert_eq!(*calls.borrow(), [1]);
+        assert_eq!(listeners.len(), 2);
+
+        assert!(listeners.remove(id));
+        assert!(!listeners.remove(id));
+        assert_eq!(listeners.len(), 1);
+    }
+}
@@ -0,0 +1,359 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::hash::Hash;
+use std::ops::Deref;
+
+use crate::Track;
+
+/// A `HashMap` that records which keys were inserted, updated or removed.
+///
+/// Reading works like with a `HashMap` through `Deref`, all mutable access goes through
+/// methods that record the affected keys. The records describe the difference to the
+/// map at the last reset: removing an inserted key forgets it and inserting
+/// a removed key again counts as an update.
+#[derive(Clone)]
+pub struct TrackedHashMap<K: Eq + Hash + Clone, V> {
+    map: HashMap<K, V>,
+    inserted: HashSet<K>,
+    updated: HashSet<K>,
+    removed: HashSet<K>,
+}
+
+impl<K: Eq + Hash + Clone, V> TrackedHashMap<K, V> {
+    /// Create an empty map.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::from(HashMap::new())
+    }
+
+    /// Insert a value and record the key unless the map already contains an equal value.
+    ///
+    /// Returns the previous value of the key, if any.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V>
+    where
+        V: PartialEq,
+    {
+        if self.map.get(&key) == Some(&value) {
+            return self.map.insert(key, value);
+        }
+        self.insert_unchecked(key, value)
+    }
+
+    /// Insert a value and record the key without comparing the value to the previous one.
+    ///
+    /// Returns the previous value of the key, if any.
+    pub fn insert_unchecked(&mut self, key: K, value: V) -> Option<V> {
+        if self.map.contains_key(&key) {
+            self.mark_updated(&key);
+        } else if self.removed.remove(&key) {
+            self.updated.insert(key.clone());
+        } else {
+            self.inserted.insert(key.clone());
+        }
+        self.map.insert(key, value)
+    }
+
+    /// Remove a key and record it as removed if it was in the map.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let value = self.map.remove(key)?;
+        if !self.inserted.remove(key) {
+            self.updated.remove(key);
+            self.removed.insert(key.clone());
+        }
+        Some(value)
+    }
+
+    /// Get a mutable reference to the value of a key and record the key as updated.
+    ///
+    /// Returns `None` and records nothing if the key is not in the map.
+    #[must_use]
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        if !self.map.contains_key(key) {
+            return None;
+        }
+        self.mark_updated(key);
+        self.map.get_mut(key)
+    }
+
+    /// Get the entry of a key for in-place modification.
+    pub fn entry(&mut self, key: K) -> TrackedEntry<'_, K, V> {
+        TrackedEntry { map: self, key }
+    }
+
+    /// Remove all keys and record the ones that were in the map at the last reset as removed.
+    pub fn clear(&mut self) {
+        for key in self.map.keys() {
+            if !self.inserted.contains(key) {
+                self.removed.insert(key.clone());
+            }
+        }
+        self.map.clear();
+        self.inserted.clear();
+        self.updated.clear();
+    }
+
+    /// Get the keys that were inserted.
+    #[must_use]
+    pub fn inserted(&self) -> &HashSet<K> {
+        &self.inserted
+    }
+
+    /// Get the keys whose values were updated.
+    #[must_use]
+    pub fn updated(&self) -> &HashSet<K> {
+        &self.updated
+    }
+
+    /// Get the keys that were removed.
+    #[must_use]
+    pub fn removed(&self) -> &HashSet<K> {
+        &self.removed
+    }
+
+    /// Get all keys that were inserted, updated or removed.
+    pub fn changed_keys(&self) -> impl Iterator<Item = &K> + '_ {
+        self.inserted
+            .iter()
+            .chain(&self.updated)
+            .chain(&self.removed)
+    }
+
+    /// Forget all recorded keys.
+    pub fn reset(&mut self) {
+        self.inserted.clear();
+        self.updated.clear();
+        self.removed.clear();
+    }
+
+    /// Get the map without the change information.
+    #[must_use]
+    pub fn into_inner(self) -> HashMap<K, V> {
+        self.map
+    }
+
+    /// Record a key that is in the map as updated unless it was inserted.
+    fn mark_updated(&mut self, key: &K) {
+        if !self.inserted.contains(key) && !self.updated.contains(key) {
+            self.updated.insert(key.clone());
+        }
+    }
+}
+
+/// The entry of a key in a [`TrackedHashMap`], created with [`TrackedHashMap::entry`].
+///
+/// All methods that return a mutable reference record the key, as inserted if it
+/// wasn't in the map and as updated otherwise.
+pub struct TrackedEntry<'a, K: Eq + Hash + Clone, V> {
+    map: &'a mut TrackedHashMap<K, V>,
+    key: K,
+}
+
+impl<'a, K: Eq + Hash + Clone, V> TrackedEntry<'a, K, V> {
+    /// Get the key of the entry.
+    #[must_use]
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Modify the value with `f` if the key is in the map.
+    #[must_use]
+    pub fn and_modify<F: FnOnce(&mut V)>(self, f: F) -> Self {
+        if let Some(value) = self.map.get_mut(&self.key) {
+            f(value);
+        }
+        self
+    }
+
+    /// Get the value of the key and insert `default` first if there's none.
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        self.or_insert_with(|| default)
+    }
+
+    /// Get the value of the key and insert the result of `default` first if there's none.
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        let Self { map, key } = self;
+        if map.map.contains_key(&key) {
+            map.mark_updated(&key);
+        } else {
+            map.insert_unchecked(key.clone(), default());
+        }
+        map.map.get_mut(&key).unwrap()
+    }
+
+    /// Get the value of the key and insert the default value first if there's none.
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+}
+
+impl<K: Eq + Hash + Clone + fmt::Debug, V: fmt::Debug> fmt::Debug for TrackedEntry<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TrackedEntry")
+            .field("key", &self.key)
+            .field("value", &self.map.get(&self.key))
+            .finish()
+    }
+}
+
+impl<K: Eq + Hash + Clone, V> Default for TrackedHashMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Eq + Hash + Clone, V> From<HashMap<K, V>> for TrackedHashMap<K, V> {
+    /// Wrap the map without recording any keys.
+    fn from(map: HashMap<K, V>) -> Self {
+        Self {
+            map,
+            inserted: HashSet::new(),
+            updated: HashSet::new(),
+            removed: HashSet::new(),
+        }
+    }
+}
+
+impl<K: Eq + Hash + Clone, V> FromIterator<(K, V)> for TrackedHashMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<HashMap<K, V>>())
+    }
+}
+
+impl<K: Eq + Hash + Clone, V> Deref for TrackedHashMap<K, V> {
+    type Target = HashMap<K, V>;
+
+    fn deref(&self) -> &HashMap<K, V> {
+        &self.map
+    }
+}
+
+impl<'a, K: Eq + Hash + Clone, V> IntoIterator for &'a TrackedHashMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = std::collections::hash_map::Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.map.iter()
+    }
+}
+
+/// Only the entries are compared, the recorded keys are ignored.
+impl<K: Eq + Hash + Clone, V: PartialEq> PartialEq for TrackedHashMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.map == other.map
+    }
+}
+
+impl<K: Eq + Hash + Clone, V: Eq> Eq for TrackedHashMap<K, V> {}
+
+impl<K: Eq + Hash + Clone + fmt::Debug, V: fmt::Debug> fmt::Debug for TrackedHashMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TrackedHashMap")
+            .field("map", &self.map)
+            .field("inserted", &self.inserted)
+            .field("updated", &self.updated)
+            .field("removed", &self.removed)
+            .finish()
+    }
+}
+
+/// Like [`Tracked`](crate::Tracked) with a `bool` mask, so maps can be used as `nested` fields.
+impl<K: Eq + Hash + Clone, V> Track for TrackedHashMap<K, V> {
+    type Mask = bool;
+
+    fn changed(&self, mask: bool) -> bool {
+        mask && self.changed_any()
+    }
+
+    fn changed_any(&self) -> bool {
+        self.changed_keys().next().is_some()
+    }
+
+    fn reset(&mut self) {
+        TrackedHashMap::reset(self);
+    }
+
+    /// Records all keys in the map as updated unless they were inserted.
+    fn mark_all_changed(&mut self) {
+        let keys: Vec<K> = self.map.keys().cloned().collect();
+        for key in &keys {
+            self.mark_updated(key);
+        }
+    }
+
+    fn track_all() -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use super::TrackedHashMap;
+
+    fn map() -> TrackedHashMap<&'static str, u8> {
+        [("a", 1), ("b", 2)].into_iter().collect()
+    }
+
+    fn keys(keys: &HashSet<&'static str>) -> Vec<&'static str> {
+        let mut keys: Vec<_> = keys.iter().copied().collect();
+        keys.sort_unstable();
+        keys
+    }
+
+    #[test]
+    fn mutations_record_keys() {
+        let mut map = map();
+        assert_eq!(map.insert("a", 1), Some(1));
+        assert_eq!(map.changed_keys().count(), 0);
+
+        map.insert("a", 3);
+        map.insert("c", 4);
+        *map.get_mut(&"b").unwrap() += 1;
+        assert!(map.get_mut(&"d").is_none());
+        assert_eq!(map.remove(&"d"), None);
+        assert_eq!(keys(map.inserted()), ["c"]);
+        assert_eq!(keys(map.updated()), ["a", "b"]);
+        assert!(map.removed().is_empty());
+
+        map.reset();
+        assert_eq!(map.changed_keys().count(), 0);
+        assert_eq!(map[&"b"], 3);
+    }
+
+    #[test]
+    fn records_describe_difference_to_reset() {
+        let mut map = map();
+        map.insert("c", 3);
+        map.remove(&"c");
+        map.insert("a", 5);
+        map.remove(&"a");
+        assert!(map.inserted().is_empty() && map.updated().is_empty());
+        assert_eq!(keys(map.removed()), ["a"]);
+
+        map.insert("a", 1);
+        assert!(map.removed().is_empty());
+        assert_eq!(keys(map.updated()), ["a"]);
+
+        map.insert("d", 4);
+        map.clear();
+        assert!(map.inserted().is_empty() && map.updated().is_empty());
+        assert_eq!(keys(map.removed()), ["a", "b"]);
+    }
+
+    #[test]
+    fn entries_record_keys() {
+        let mut map = map();
+        *map.entry("c").or_insert(0) += 1;
+        map.entry("a").and_modify(|value| *value += 1).or_default();
+        let _ = map.entry("d").and_modify(|value| *value += 1);
+        assert_eq!(map.entry("b").key(), &"b");
+        assert_eq!(keys(map.inserted()), ["c"]);
+        assert_eq!(keys(map.updated()), ["a"]);
+        assert_eq!((map[&"a"], map[&"c"]), (2, 1));
+        assert!(!map.contains_key(&"d"));
+    }
+}
@@ -0,0 +1,36 @@
+/// The result of `merge_from()` of a struct with the `merge` option.
+///
+/// `M` is the mask type of the struct.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MergeOutcome<M> {
+    merged: M,
+    conflicts: M,
+}
+
+impl<M: Copy + Default + PartialEq> MergeOutcome<M> {
+    /// Create an outcome with the masks of the merged and the conflicting fields.
+    #[must_use]
+    pub fn new(merged: M, conflicts: M) -> Self {
+        Self { merged, conflicts }
+    }
+
+    /// The mask of the fields that were copied from the other value.
+    #[must_use]
+    pub fn merged(&self) -> M {
+        self.merged
+    }
+
+    /// The mask of the fields that both values changed to different values.
+    ///
+    /// These fields keep their own value and need to be resolved by the caller.
+    #[must_use]
+    pub fn conflicts(&self) -> M {
+        self.conflicts
+    }
+
+    /// Check whether any field has a conflict.
+    #[must_use]
+    pub fn has_conflicts(&self) -> bool {
+        self.conflicts != M::default()
+    }
+}
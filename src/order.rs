@@ -0,0 +1,127 @@
+use core::hash::{Hash, Hasher};
+
+/// The order in which the bits of a struct with the `ordered` option were set.
+///
+/// Every bit is stored once when it's newly set, so the order doesn't
+/// need more than `N` entries. `N` is the number of bits of the tracked fields.
+///
+/// Like the tracker, the order isn't compared by `PartialEq` and ignored by `Hash`.
+#[derive(Debug, Clone, Copy)]
+pub struct ChangeOrder<const N: usize> {
+    bits: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> Default for ChangeOrder<N> {
+    fn default() -> Self {
+        Self {
+            bits: [0; N],
+            len: 0,
+        }
+    }
+}
+
+impl<const N: usize> ChangeOrder<N> {
+    /// Append the bits of `mask` that aren't stored yet, from the lowest to the highest bit.
+    ///
+    /// Bits that don't belong to a field are ignored.
+    pub fn record(&mut self, mask: impl Into<u128>) {
+        let mask = mask.into();
+        for index in 0..N {
+            if mask & (1 << index) != 0 && !self.contains(index) {
+                // There are only `N` different bits, so the order never gets full.
+                self.bits[self.len] = index as u8;
+                self.len += 1;
+            }
+        }
+    }
+
+    /// Forget the order of the bits of `mask`, so they are appended again when they're set.
+    ///
+    /// Bits that don't belong to a field are ignored.
+    pub fn remove(&mut self, mask: impl Into<u128>) {
+        let mask = mask.into();
+        let mut len = 0;
+        for index in 0..self.len {
+            let bit = self.bits[index];
+            if mask & (1 << bit) == 0 {
+                self.bits[len] = bit;
+                len += 1;
+            }
+        }
+        self.len = len;
+    }
+
+    /// Forget the order of all bits.
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Iterate over the bits of `current` in the order they were set, each as its own mask.
+    ///
+    /// Bits of `current` that weren't recorded, for example because the tracker was
+    /// restored, follow the recorded ones from the lowest to the highest bit.
+    pub fn iter(&self, current: impl Into<u128>) -> impl Iterator<Item = u128> + '_ {
+        let current = current.into();
+        let recorded = self.bits[..self.len]
+            .iter()
+            .map(|index| usize::from(*index));
+        let unrecorded = (0..N).filter(|index| !self.contains(*index));
+        recorded
+            .chain(unrecorded)
+            .map(|index| 1 << index)
+            .filter(move |bit| current & bit != 0)
+    }
+
+    fn contains(&self, index: usize) -> bool {
+        self.bits[..self.len]
+            .iter()
+            .any(|bit| usize::from(*bit) == index)
+    }
+}
+
+impl<const N: usize> PartialEq for ChangeOrder<N> {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl<const N: usize> Eq for ChangeOrder<N> {}
+
+impl<const N: usize> Hash for ChangeOrder<N> {
+    fn hash<H: Hasher>(&self, _: &mut H) {}
+}
+
+#[cfg(test)]
+mod test {
+    use super::ChangeOrder;
+
+    fn bits(order: &ChangeOrder<4>, current: u8) -> Vec<u128> {
+        order.iter(current).collect()
+    }
+
+    #[test]
+    fn first_changed_order() {
+        let mut order = ChangeOrder::<4>::default();
+        order.record(0b0100u8);
+        order.record(0b0011u8);
+        order.record(0b0100u8);
+        assert_eq!(bits(&order, 0b1111), [0b0100, 0b0001, 0b0010, 0b1000]);
+        assert_eq!(bits(&order, 0b0110), [0b0100, 0b0010]);
+
+        order.clear();
+        order.record(0b1000u8);
+        assert_eq!(bits(&order, 0b1001), [0b1000, 0b0001]);
+    }
+
+    #[test]
+    fn removed_bits_are_appended_again() {
+        let mut order = ChangeOrder::<4>::default();
+        order.record(0b0001u8);
+        order.record(0b0100u8);
+        order.record(0b0010u8);
+        order.remove(0b0001u8);
+        order.record(0b0001u8);
+        assert_eq!(bits(&order, 0b0111), [0b0100, 0b0010, 0b0001]);
+    }
+}
@@ -0,0 +1,149 @@
+use alloc::sync::Arc;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+#[cfg(target_has_atomic = "16")]
+use core::sync::atomic::AtomicU16;
+#[cfg(target_has_atomic = "32")]
+use core::sync::atomic::AtomicU32;
+#[cfg(target_has_atomic = "64")]
+use core::sync::atomic::AtomicU64;
+#[cfg(target_has_atomic = "8")]
+use core::sync::atomic::AtomicU8;
+use core::sync::atomic::Ordering;
+
+/// An atomic integer that collects the bits marked through a [`ChangeHandle`].
+pub trait AtomicMask: Default + Send + Sync + fmt::Debug {
+    /// The integer type of the bits.
+    type Bits: Copy;
+
+    /// Add `bits` to the marked bits.
+    fn mark(&self, bits: Self::Bits);
+
+    /// Get the marked bits and clear them.
+    fn take(&self) -> Self::Bits;
+}
+
+macro_rules! atomic_mask {
+    ($($size:literal: $atomic:ident($bits:ty)),* $(,)?) => {$(
+        #[cfg(target_has_atomic = $size)]
+        impl AtomicMask for $atomic {
+            type Bits = $bits;
+
+            fn mark(&self, bits: $bits) {
+                self.fetch_or(bits, Ordering::Relaxed);
+            }
+
+            fn take(&self) -> $bits {
+                self.swap(0, Ordering::Relaxed)
+            }
+        }
+    )*};
+}
+
+atomic_mask!(
+    "8": AtomicU8(u8),
+    "16": AtomicU16(u16),
+    "32": AtomicU32(u32),
+    "64": AtomicU64(u64),
+);
+
+/// The changes of a struct that uses the `remote` option
+/// which were marked through its [`ChangeHandle`]s.
+///
+/// A clone starts without remote changes and has its own handles.
+/// Like the tracker, the changes aren't compared by `PartialEq` and ignored by `Hash`.
+#[derive(Default)]
+pub struct RemoteChanges<A> {
+    bits: Arc<A>,
+}
+
+impl<A: AtomicMask> RemoteChanges<A> {
+    /// Create a handle that marks bits from anywhere, including other threads.
+    #[must_use]
+    pub fn handle(&self) -> ChangeHandle<A> {
+        ChangeHandle {
+            bits: Arc::clone(&self.bits),
+        }
+    }
+
+    /// Get the bits that were marked through the handles and clear them.
+    #[must_use]
+    pub fn take(&self) -> A::Bits {
+        self.bits.take()
+    }
+}
+
+impl<A: Default> Clone for RemoteChanges<A> {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl<A: fmt::Debug> fmt::Debug for RemoteChanges<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RemoteChanges").field(&*self.bits).finish()
+    }
+}
+
+impl<A> PartialEq for RemoteChanges<A> {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl<A> Eq for RemoteChanges<A> {}
+
+impl<A> Hash for RemoteChanges<A> {
+    fn hash<H: Hasher>(&self, _: &mut H) {}
+}
+
+/// A handle that marks bits of the [`RemoteChanges`] it was created from.
+///
+/// Clones mark the same bits, and the handle can be sent to other threads.
+/// The bits are added to the tracker when the owner of the struct absorbs them.
+pub struct ChangeHandle<A> {
+    bits: Arc<A>,
+}
+
+impl<A: AtomicMask> ChangeHandle<A> {
+    /// Mark `bits` as changed.
+    pub fn mark(&self, bits: A::Bits) {
+        self.bits.mark(bits);
+    }
+}
+
+impl<A> Clone for ChangeHandle<A> {
+    fn clone(&self) -> Self {
+        Self {
+            bits: Arc::clone(&self.bits),
+        }
+    }
+}
+
+impl<A: fmt::Debug> fmt::Debug for ChangeHandle<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ChangeHandle").field(&*self.bits).finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::sync::atomic::AtomicU8;
+
+    use super::RemoteChanges;
+
+    #[test]
+    fn handles_mark_the_shared_bits() {
+        let changes = RemoteChanges::<AtomicU8>::default();
+        let handle = changes.handle();
+        handle.mark(0b01);
+        handle.clone().mark(0b10);
+        assert_eq!(changes.take(), 0b11);
+        assert_eq!(changes.take(), 0);
+
+        let clone = changes.clone();
+        handle.mark(0b100);
+        assert_eq!(clone.take(), 0);
+        assert_eq!(changes.take(), 0b100);
+    }
+}
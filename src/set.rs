@@ -0,0 +1,28 @@
+use core::ops::BitOrAssign;
+
+/// Set `slot` to `value` and add `mask` to `tracker` if the values aren't equal.
+///
+/// Shared by the generated setters so they don't all repeat the comparison.
+#[doc(hidden)]
+#[inline]
+pub fn set<T: PartialEq, M: BitOrAssign>(slot: &mut T, value: T, tracker: &mut M, mask: M) {
+    if *slot != value {
+        *tracker |= mask;
+    }
+    *slot = value;
+}
+
+#[cfg(test)]
+mod test {
+    use super::set;
+
+    #[test]
+    fn marks_different_values() {
+        let mut slot = 1;
+        let mut tracker = 0u8;
+        set(&mut slot, 1, &mut tracker, 0b10);
+        assert_eq!((slot, tracker), (1, 0));
+        set(&mut slot, 2, &mut tracker, 0b10);
+        assert_eq!((slot, tracker), (2, 0b10));
+    }
+}
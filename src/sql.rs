@@ -0,0 +1,35 @@
+use alloc::string::String;
+use core::fmt::Write;
+
+/// Create a `SET` clause with numbered placeholders for already quoted columns.
+#[doc(hidden)]
+#[must_use]
+pub fn set_clause(columns: &[&str], start_index: usize) -> Option<String> {
+    if columns.is_empty() {
+        return None;
+    }
+
+    let mut clause = String::new();
+    for (index, column) in columns.iter().enumerate() {
+        if index != 0 {
+            clause.push_str(", ");
+        }
+        write!(clause, "{column} = ${}", start_index + index).unwrap();
+    }
+    Some(clause)
+}
+
+#[cfg(test)]
+mod test {
+    use super::set_clause;
+
+    #[test]
+    fn placeholders() {
+        assert_eq!(set_clause(&[], 1), None);
+        assert_eq!(set_clause(&["title"], 1).unwrap(), "title = $1");
+        assert_eq!(
+            set_clause(&["title", "\"Count\""], 3).unwrap(),
+            "title = $3, \"Count\" = $4"
+        );
+    }
+}
@@ -0,0 +1,222 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::BitOr;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, PoisonError, Weak};
+use std::task::{Context, Poll, Waker};
+
+use futures_core::Stream;
+
+/// The state of a single receiver, shared with the sender.
+struct Slot<M> {
+    /// The bits that were sent since the receiver was polled last.
+    pending: Option<M>,
+    waker: Option<Waker>,
+    closed: bool,
+}
+
+type Slots<M> = Mutex<Vec<Weak<Mutex<Slot<M>>>>>;
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// The sender of a struct with the `stream` option.
+///
+/// Like [`Listeners`](crate::Listeners), the sender is not part of the state of the struct:
+/// clones start without receivers, all senders are equal and hashing ignores them.
+pub struct ChangesSender<M> {
+    slots: Arc<Slots<M>>,
+}
+
+impl<M: Copy + BitOr<Output = M>> ChangesSender<M> {
+    /// Create a receiver that gets all masks sent from now on.
+    #[must_use]
+    pub fn subscribe(&self) -> ChangesReceiver<M> {
+        subscribe(&self.slots, None)
+    }
+
+    /// Send `mask` to all receivers.
+    ///
+    /// Masks that weren't received yet are combined with `|`, so a slow
+    /// receiver gets all changed bits at once instead of a queue of masks.
+    pub fn send(&self, mask: M) {
+        lock(&self.slots).retain(|slot| {
+            let Some(slot) = slot.upgrade() else {
+                return false;
+            };
+            let mut slot = lock(&slot);
+            slot.pending = Some(match slot.pending {
+                Some(pending) => pending | mask,
+                None => mask,
+            });
+            if let Some(waker) = slot.waker.take() {
+                waker.wake();
+            }
+            true
+        });
+    }
+
+    /// Get the number of receivers that weren't dropped.
+    #[must_use]
+    pub fn receiver_count(&self) -> usize {
+        lock(&self.slots)
+            .iter()
+            .filter(|slot| slot.strong_count() > 0)
+            .count()
+    }
+}
+
+fn subscribe<M>(slots: &Arc<Slots<M>>, pending: Option<M>) -> ChangesReceiver<M> {
+    let slot = Arc::new(Mutex::new(Slot {
+        pending,
+        waker: None,
+        closed: false,
+    }));
+    lock(slots).push(Arc::downgrade(&slot));
+    ChangesReceiver {
+        slot,
+        slots: Arc::downgrade(slots),
+    }
+}
+
+impl<M> Default for ChangesSender<M> {
+    fn default() -> Self {
+        Self {
+            slots: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+}
+
+impl<M> Clone for ChangesSender<M> {
+    /// Receivers belong to a single struct, so the clone has none.
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl<M> PartialEq for ChangesSender<M> {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl<M> Eq for ChangesSender<M> {}
+
+impl<M> Hash for ChangesSender<M> {
+    fn hash<H: Hasher>(&self, _: &mut H) {}
+}
+
+impl<M> fmt::Debug for ChangesSender<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChangesSender").finish_non_exhaustive()
+    }
+}
+
+impl<M> Drop for ChangesSender<M> {
+    /// End the streams of all receivers once they received the pending masks.
+    fn drop(&mut self) {
+        for slot in lock(&self.slots).drain(..) {
+            if let Some(slot) = slot.upgrade() {
+                let mut slot = lock(&slot);
+                slot.closed = true;
+                if let Some(waker) = slot.waker.take() {
+                    waker.wake();
+                }
+            }
+        }
+    }
+}
+
+/// A [`Stream`] of the masks of newly changed fields, created with `changes_stream()`.
+///
+/// Each item combines all masks that were sent since the last item.
+/// The stream ends when the struct is dropped.
+pub struct ChangesReceiver<M> {
+    slot: Arc<Mutex<Slot<M>>>,
+    slots: Weak<Slots<M>>,
+}
+
+impl<M> ChangesReceiver<M> {
+    /// Check whether the struct was dropped, so no more masks will be sent.
+    #[must_use]
+    pub fn is_closed(&self) -> bool {
+        lock(&self.slot).closed
+    }
+}
+
+impl<M: Copy> Clone for ChangesReceiver<M> {
+    /// The clone receives the same masks, starting with the pending ones.
+    fn clone(&self) -> Self {
+        let slot = lock(&self.slot);
+        match self.slots.upgrade() {
+            Some(slots) if !slot.closed => subscribe(&slots, slot.pending),
+            _ => Self {
+                slot: Arc::new(Mutex::new(Slot {
+                    pending: slot.pending,
+                    waker: None,
+                    closed: true,
+                })),
+                slots: Weak::new(),
+            },
+        }
+    }
+}
+
+impl<M> Stream for ChangesReceiver<M> {
+    type Item = M;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<M>> {
+        let mut slot = lock(&self.slot);
+        if let Some(mask) = slot.pending.take() {
+            Poll::Ready(Some(mask))
+        } else if slot.closed {
+            Poll::Ready(None)
+        } else {
+            slot.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+impl<M> fmt::Debug for ChangesReceiver<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChangesReceiver")
+            .field("closed", &self.is_closed())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::pin::Pin;
+    use std::task::{Context, Poll, Waker};
+
+    use futures_core::Stream;
+
+    use super::{ChangesReceiver, ChangesSender};
+
+    fn poll(receiver: &mut ChangesReceiver<u8>) -> Poll<Option<u8>> {
+        Pin::new(receiver).poll_next(&mut Context::from_waker(Waker::noop()))
+    }
+
+    #[test]
+    fn masks_are_coalesced() {
+        let sender = ChangesSender::default();
+        let mut receiver = sender.subscribe();
+        assert_eq!(poll(&mut receiver), Poll::Pending);
+
+        sender.send(0b01);
+        sender.send(0b10);
+        let mut clone = receiver.clone();
+        assert_eq!(poll(&mut receiver), Poll::Ready(Some(0b11)));
+        assert_eq!(poll(&mut receiver), Poll::Pending);
+
+        sender.send(0b100);
+        drop(sender);
+        assert!(clone.is_closed());
+        assert_eq!(poll(&mut clone), Poll::Ready(Some(0b111)));
+        assert_eq!(poll(&mut clone), Poll::Ready(None));
+        assert_eq!(poll(&mut receiver), Poll::Ready(Some(0b100)));
+    }
+}
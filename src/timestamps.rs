@@ -0,0 +1,156 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::time::{Duration, Instant};
+
+/// The source of the current time for the `timestamps` option.
+///
+/// [`SystemClock`] is used by default, other clocks can be set with
+/// `timestamps = MyClock` to control the time in tests.
+pub trait Clock {
+    /// Get the current time.
+    fn now() -> Instant;
+}
+
+/// The clock that uses [`Instant::now()`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now() -> Instant {
+        Instant::now()
+    }
+}
+
+/// The times at which the fields of a struct with the `timestamps` option were marked as changed.
+///
+/// Each bit of the tracker has a timestamp that is updated whenever the
+/// bit is newly set, so `reset()` doesn't affect the timestamps.
+/// `N` is the number of bits of the tracked fields.
+///
+/// Like the tracker, the timestamps aren't compared by `PartialEq`
+/// and ignored by `Hash`.
+pub struct Timestamps<const N: usize, C = SystemClock> {
+    changed_at: [Option<Instant>; N],
+    clock: PhantomData<fn() -> C>,
+}
+
+impl<const N: usize, C: Clock> Timestamps<N, C> {
+    /// Set the timestamps of all bits in `mask` to the current time.
+    ///
+    /// Bits that don't belong to a field are ignored.
+    pub fn record(&mut self, mask: impl Into<u128>) {
+        let mask = mask.into();
+        if mask == 0 {
+            return;
+        }
+        let now = C::now();
+        for (index, changed_at) in self.changed_at.iter_mut().enumerate() {
+            if mask & (1 << index) != 0 {
+                *changed_at = Some(now);
+            }
+        }
+    }
+
+    /// Get the latest timestamp of the bits in `mask`,
+    /// or `None` if none of them was ever set.
+    #[must_use]
+    pub fn last_changed(&self, mask: impl Into<u128>) -> Option<Instant> {
+        let mask = mask.into();
+        self.changed_at
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| mask & (1 << index) != 0)
+            .filter_map(|(_, changed_at)| *changed_at)
+            .max()
+    }
+
+    /// Get the time that passed since the latest timestamp of the bits in `mask`.
+    #[must_use]
+    pub fn elapsed(&self, mask: impl Into<u128>) -> Option<Duration> {
+        self.last_changed(mask)
+            .map(|changed_at| C::now().saturating_duration_since(changed_at))
+    }
+}
+
+impl<const N: usize, C> Default for Timestamps<N, C> {
+    fn default() -> Self {
+        Self {
+            changed_at: [None; N],
+            clock: PhantomData,
+        }
+    }
+}
+
+impl<const N: usize, C> Clone for Timestamps<N, C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<const N: usize, C> Copy for Timestamps<N, C> {}
+
+impl<const N: usize, C> PartialEq for Timestamps<N, C> {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl<const N: usize, C> Eq for Timestamps<N, C> {}
+
+impl<const N: usize, C> Hash for Timestamps<N, C> {
+    fn hash<H: Hasher>(&self, _: &mut H) {}
+}
+
+impl<const N: usize, C> fmt::Debug for Timestamps<N, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Timestamps")
+            .field("changed_at", &self.changed_at)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::Cell;
+    use std::time::{Duration, Instant};
+
+    use super::{Clock, Timestamps};
+
+    thread_local! {
+        static NOW: Cell<Option<Instant>> = const { Cell::new(None) };
+    }
+
+    struct Manual;
+
+    impl Clock for Manual {
+        fn now() -> Instant {
+            NOW.with(|now| {
+                let time = now.get().unwrap_or_else(Instant::now);
+                now.set(Some(time));
+                time
+            })
+        }
+    }
+
+    fn advance(duration: Duration) {
+        NOW.with(|now| now.set(Some(Manual::now() + duration)));
+    }
+
+    #[test]
+    fn record_and_elapse() {
+        let mut timestamps = Timestamps::<3, Manual>::default();
+        assert_eq!(timestamps.last_changed(u8::MAX), None);
+
+        timestamps.record(0b001u8);
+        let first = Manual::now();
+        advance(Duration::from_millis(10));
+        timestamps.record(0b010u8);
+        advance(Duration::from_millis(5));
+
+        assert_eq!(timestamps.last_changed(0b001u8), Some(first));
+        assert_eq!(timestamps.elapsed(0b001u8), Some(Duration::from_millis(15)));
+        assert_eq!(timestamps.elapsed(0b011u8), Some(Duration::from_millis(5)));
+        assert_eq!(timestamps.elapsed(0b100u8), None);
+    }
+}
@@ -0,0 +1,53 @@
+/// Methods to check and reset the changes of a tracked struct.
+///
+/// The trait is implemented for all structs with `#[tracker::track]` and
+/// `#[derive(tracker::Track)]`, so it can be used to write code that works
+/// with any tracked struct. The inherent methods of the same names forward
+/// to this trait unless the `trait_only` option is set.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` isn't a tracked struct",
+    note = "use `#[tracker::track]` or `#[derive(tracker::Track)]` on `{Self}`, fields marked with `#[tracker::nested]` need to be tracked structs"
+)]
+pub trait Track {
+    /// The type of the bit masks, an unsigned integer or the type generated
+    /// by the `typed_mask` option.
+    type Mask;
+
+    /// Check for changes made to this struct with a given bit mask.
+    fn changed(&self, mask: Self::Mask) -> bool;
+
+    /// Check for any changes made to this struct.
+    fn changed_any(&self) -> bool;
+
+    /// Mark all fields as unchanged again.
+    fn reset(&mut self);
+
+    /// Reset this struct and all tracked structs in fields marked with `#[tracker::nested]`.
+    ///
+    /// Structs without nested fields only reset themselves.
+    fn reset_all(&mut self) {
+        self.reset();
+    }
+
+    /// Mark all fields as changed.
+    fn mark_all_changed(&mut self);
+
+    /// Get a bit mask to look for changes on all fields.
+    fn track_all() -> Self::Mask;
+
+    /// Get the names of the bit mask functions of all tracked fields in the order of their bits.
+    ///
+    /// Types that don't have named fields return an empty slice.
+    #[must_use]
+    fn field_names() -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Get the bit mask of a field by the name of its bit mask function,
+    /// or `None` if there's no tracked field with this name.
+    #[must_use]
+    fn field_mask(name: &str) -> Option<Self::Mask> {
+        let _ = name;
+        None
+    }
+}
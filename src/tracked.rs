@@ -0,0 +1,176 @@
+use core::fmt;
+use core::ops::Deref;
+
+use crate::Track;
+
+/// A single value that tracks whether it changed, for values that don't
+/// need a tracked struct.
+///
+/// The value can be read through `Deref`, all mutable access goes through
+/// methods that mark the value as changed.
+#[derive(Clone, Default)]
+pub struct Tracked<T> {
+    value: T,
+    changed: bool,
+}
+
+impl<T> Tracked<T> {
+    /// Wrap a value that is marked as unchanged.
+    #[must_use]
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            changed: false,
+        }
+    }
+
+    /// Get an immutable reference to the value.
+    #[must_use]
+    pub fn get(&self) -> &T {
+        &self.value
+    }
+
+    /// Get a mutable reference to the value and mark it as changed.
+    #[must_use]
+    pub fn get_mut(&mut self) -> &mut T {
+        self.changed = true;
+        &mut self.value
+    }
+
+    /// Use a closure to update the value and mark it as changed.
+    pub fn update<F: FnOnce(&mut T)>(&mut self, f: F) {
+        self.changed = true;
+        f(&mut self.value);
+    }
+
+    /// Set the value and mark it as changed if it's not equal to the previous value.
+    pub fn set(&mut self, value: T)
+    where
+        T: PartialEq,
+    {
+        if self.value != value {
+            self.changed = true;
+        }
+        self.value = value;
+    }
+
+    /// Set the value and mark it as changed without comparing it to the previous value.
+    pub fn set_unchecked(&mut self, value: T) {
+        self.changed = true;
+        self.value = value;
+    }
+
+    /// Replace the value, mark it as changed and return the previous value.
+    pub fn replace(&mut self, value: T) -> T {
+        self.changed = true;
+        core::mem::replace(&mut self.value, value)
+    }
+
+    /// Check whether the value changed.
+    #[must_use]
+    pub fn changed(&self) -> bool {
+        self.changed
+    }
+
+    /// Mark the value as unchanged again.
+    pub fn reset(&mut self) {
+        self.changed = false;
+    }
+
+    /// Get the value without the change information.
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> Deref for Tracked<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> From<T> for Tracked<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Tracked<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tracked")
+            .field("value", &self.value)
+            .field("changed", &self.changed)
+            .finish()
+    }
+}
+
+/// The mask of a single value is `true` to check the value and `false` to check nothing,
+/// so `Tracked` values can be used as fields marked with `#[tracker::nested]`.
+impl<T> Track for Tracked<T> {
+    type Mask = bool;
+
+    fn changed(&self, mask: bool) -> bool {
+        mask && self.changed
+    }
+
+    fn changed_any(&self) -> bool {
+        self.changed
+    }
+
+    fn reset(&mut self) {
+        self.changed = false;
+    }
+
+    fn mark_all_changed(&mut self) {
+        self.changed = true;
+    }
+
+    fn track_all() -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Tracked;
+
+    #[test]
+    fn set_compares_values() {
+        let mut value = Tracked::new(1);
+        value.set(1);
+        assert!(!value.changed());
+        value.set(2);
+        assert!(value.changed());
+        assert_eq!(*value, 2);
+
+        value.reset();
+        value.set_unchecked(2);
+        assert!(value.changed());
+    }
+
+    #[test]
+    fn mutable_access_marks_changed() {
+        let mut value = Tracked::new(vec![1]);
+        value.get_mut().push(2);
+        assert!(value.changed());
+        value.reset();
+        value.update(|value| value.clear());
+        assert!(value.changed());
+        value.reset();
+        assert_eq!(value.replace(vec![3]), Vec::<i32>::new());
+        assert!(value.changed());
+        assert_eq!(value.into_inner(), vec![3]);
+    }
+
+    #[test]
+    fn debug_output() {
+        let value = Tracked::new("hi");
+        assert_eq!(
+            format!("{value:?}"),
+            r#"Tracked { value: "hi", changed: false }"#
+        );
+    }
+}
@@ -0,0 +1,96 @@
+use core::fmt;
+
+use crate::Track;
+
+/// Panic if `value` still has changes, called when a struct with the
+/// `debug_unconsumed` option is dropped in a debug build.
+///
+/// Nothing is checked while the thread is already panicking
+/// because a second panic would abort the process.
+#[doc(hidden)]
+pub fn assert_consumed<T: Track>(value: &T) {
+    if !value.changed_any() || panicking() {
+        return;
+    }
+    panic!(
+        "`{}` was dropped with unconsumed changes\n  changed fields: {}",
+        core::any::type_name::<T>(),
+        ChangedNames(value),
+    );
+}
+
+#[cfg(feature = "std")]
+fn panicking() -> bool {
+    std::thread::panicking()
+}
+
+#[cfg(not(feature = "std"))]
+fn panicking() -> bool {
+    false
+}
+
+/// Lists the names of the changed fields without allocating.
+struct ChangedNames<'a, T>(&'a T);
+
+impl<T: Track> fmt::Display for ChangedNames<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut names = T::field_names()
+            .iter()
+            .filter(|name| T::field_mask(name).is_some_and(|mask| self.0.changed(mask)));
+        let Some(first) = names.next() else {
+            return f.write_str("none");
+        };
+        f.write_str(first)?;
+        for name in names {
+            write!(f, ", {name}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ChangedNames;
+    use crate::Track;
+
+    struct Flags(u8);
+
+    impl Track for Flags {
+        type Mask = u8;
+
+        fn changed(&self, mask: u8) -> bool {
+            self.0 & mask != 0
+        }
+
+        fn changed_any(&self) -> bool {
+            self.0 != 0
+        }
+
+        fn reset(&mut self) {
+            self.0 = 0;
+        }
+
+        fn mark_all_changed(&mut self) {
+            self.0 = 0b111;
+        }
+
+        fn track_all() -> u8 {
+            0b111
+        }
+
+        fn field_names() -> &'static [&'static str] {
+            &["a", "b", "c"]
+        }
+
+        fn field_mask(name: &str) -> Option<u8> {
+            let index = Self::field_names().iter().position(|n| *n == name)?;
+            Some(1 << index)
+        }
+    }
+
+    #[test]
+    fn names_of_changed_fields() {
+        assert_eq!(ChangedNames(&Flags(0)).to_string(), "none");
+        assert_eq!(ChangedNames(&Flags(0b101)).to_string(), "a, c");
+    }
+}
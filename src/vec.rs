@@ -0,0 +1,320 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Deref;
+
+use crate::Track;
+
+/// An insertion or removal recorded by [`TrackedVec`].
+///
+/// The index is the position of the element at the time of the change,
+/// so replaying the changes in order turns the old list into the new one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VecChange {
+    /// An element was inserted at the index.
+    Inserted(usize),
+    /// The element at the index was removed.
+    Removed(usize),
+}
+
+/// A `Vec` that tracks which elements changed and which elements were inserted or removed.
+///
+/// Reading works like with a slice through `Deref`, all mutable access goes through
+/// methods that mark the affected elements. Inserted elements count as changed and
+/// the changes of the other elements move with them when indices shift.
+#[derive(Clone)]
+pub struct TrackedVec<T> {
+    items: Vec<T>,
+    /// Whether the element at the same index changed.
+    changed: Vec<bool>,
+    structural: Vec<VecChange>,
+}
+
+impl<T> TrackedVec<T> {
+    /// Create an empty list.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::from(Vec::new())
+    }
+
+    /// Get a mutable reference to the element at `index` and mark it as changed.
+    ///
+    /// Returns `None` and marks nothing if the index is out of bounds.
+    #[must_use]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        let item = self.items.get_mut(index)?;
+        self.changed[index] = true;
+        Some(item)
+    }
+
+    /// Set the element at `index` and mark it as changed if it's not equal to the previous value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn set(&mut self, index: usize, value: T)
+    where
+        T: PartialEq,
+    {
+        if self.items[index] != value {
+            self.changed[index] = true;
+        }
+        self.items[index] = value;
+    }
+
+    /// Append an element, which is marked as changed.
+    pub fn push(&mut self, value: T) {
+        self.structural.push(VecChange::Inserted(self.items.len()));
+        self.items.push(value);
+        self.changed.push(true);
+    }
+
+    /// Insert an element at `index`, which is marked as changed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn insert(&mut self, index: usize, value: T) {
+        self.items.insert(index, value);
+        self.changed.insert(index, true);
+        self.structural.push(VecChange::Inserted(index));
+    }
+
+    /// Remove and return the element at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn remove(&mut self, index: usize) -> T {
+        let value = self.items.remove(index);
+        self.changed.remove(index);
+        self.structural.push(VecChange::Removed(index));
+        value
+    }
+
+    /// Remove and return the last element, if any.
+    pub fn pop(&mut self) -> Option<T> {
+        let value = self.items.pop()?;
+        self.changed.pop();
+        self.structural.push(VecChange::Removed(self.items.len()));
+        Some(value)
+    }
+
+    /// Remove all elements.
+    ///
+    /// The elements are recorded as removed from the back to the front.
+    pub fn clear(&mut self) {
+        self.structural
+            .extend((0..self.items.len()).rev().map(VecChange::Removed));
+        self.items.clear();
+        self.changed.clear();
+    }
+
+    /// Check whether the element at `index` changed or was inserted.
+    #[must_use]
+    pub fn changed_at(&self, index: usize) -> bool {
+        self.changed.get(index).copied().unwrap_or(false)
+    }
+
+    /// Get the current indices of all elements that changed or were inserted.
+    pub fn changed_indices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.changed
+            .iter()
+            .enumerate()
+            .filter_map(|(index, changed)| changed.then_some(index))
+    }
+
+    /// Check whether elements were inserted or removed.
+    #[must_use]
+    pub fn structurally_changed(&self) -> bool {
+        !self.structural.is_empty()
+    }
+
+    /// Get all insertions and removals in the order they happened.
+    #[must_use]
+    pub fn structural_changes(&self) -> &[VecChange] {
+        &self.structural
+    }
+
+    /// Mark all elements as unchanged and forget the insertions and removals.
+    pub fn reset(&mut self) {
+        self.changed.fill(false);
+        self.structural.clear();
+    }
+
+    /// Get the elements without the change information.
+    #[must_use]
+    pub fn into_inner(self) -> Vec<T> {
+        self.items
+    }
+}
+
+impl<T> Default for TrackedVec<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> From<Vec<T>> for TrackedVec<T> {
+    /// Wrap the elements, which are marked as unchanged.
+    fn from(items: Vec<T>) -> Self {
+        Self {
+            changed: vec![false; items.len()],
+            items,
+            structural: Vec::new(),
+        }
+    }
+}
+
+impl<T> FromIterator<T> for TrackedVec<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<Vec<T>>())
+    }
+}
+
+impl<T> Deref for TrackedVec<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.items
+    }
+}
+
+impl<'a, T> IntoIterator for &'a TrackedVec<T> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter()
+    }
+}
+
+/// Only the elements are compared, the change information is ignored.
+impl<T: PartialEq> PartialEq for TrackedVec<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.items == other.items
+    }
+}
+
+impl<T: Eq> Eq for TrackedVec<T> {}
+
+impl<T: fmt::Debug> fmt::Debug for TrackedVec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TrackedVec")
+            .field("items", &self.items)
+            .field("changed", &self.changed_indices().collect::<Vec<_>>())
+            .field("structural", &self.structural)
+            .finish()
+    }
+}
+
+/// Like [`Tracked`](crate::Tracked) with a `bool` mask, so lists can be used as `nested` fields.
+impl<T> Track for TrackedVec<T> {
+    type Mask = bool;
+
+    fn changed(&self, mask: bool) -> bool {
+        mask && self.changed_any()
+    }
+
+    fn changed_any(&self) -> bool {
+        self.structurally_changed() || self.changed.contains(&true)
+    }
+
+    fn reset(&mut self) {
+        TrackedVec::reset(self);
+    }
+
+    fn mark_all_changed(&mut self) {
+        self.changed.fill(true);
+    }
+
+    fn track_all() -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{TrackedVec, VecChange};
+
+    fn list() -> TrackedVec<char> {
+        "abcd".chars().collect()
+    }
+
+    fn changed(list: &TrackedVec<char>) -> Vec<usize> {
+        list.changed_indices().collect()
+    }
+
+    #[test]
+    fn mutations_mark_indices() {
+        let mut list = list();
+        assert!(changed(&list).is_empty());
+
+        list.set(1, 'b');
+        assert!(changed(&list).is_empty());
+        list.set(1, 'x');
+        *list.get_mut(3).unwrap() = 'y';
+        assert!(list.get_mut(4).is_none());
+        assert_eq!(changed(&list), [1, 3]);
+        assert!(list.changed_at(1) && !list.changed_at(2) && !list.changed_at(4));
+        assert!(!list.structurally_changed());
+
+        list.reset();
+        assert!(changed(&list).is_empty());
+        assert_eq!(&*list, ['a', 'x', 'c', 'y']);
+    }
+
+    #[test]
+    fn changes_follow_shifted_elements() {
+        let mut list = list();
+        list.set(2, 'x');
+        list.insert(0, 'n');
+        // `x` moved from 2 to 3.
+        assert_eq!(changed(&list), [0, 3]);
+
+        list.remove(1);
+        assert_eq!(changed(&list), [0, 2]);
+        list.remove(2);
+        assert_eq!(changed(&list), [0]);
+
+        list.push('p');
+        list.insert(4, 'q');
+        assert_eq!(&*list, ['n', 'b', 'd', 'p', 'q']);
+        assert_eq!(changed(&list), [0, 3, 4]);
+        assert_eq!(
+            list.structural_changes(),
+            [
+                VecChange::Inserted(0),
+                VecChange::Removed(1),
+                VecChange::Removed(2),
+                VecChange::Inserted(3),
+                VecChange::Inserted(4),
+            ]
+        );
+
+        list.reset();
+        assert!(!list.structurally_changed());
+        list.set(4, 'z');
+        assert_eq!(changed(&list), [4]);
+    }
+
+    #[test]
+    fn removing_changed_elements_keeps_structural_change() {
+        let mut list = list();
+        list.push('e');
+        assert_eq!(list.pop(), Some('e'));
+        assert!(changed(&list).is_empty());
+        assert!(list.structurally_changed());
+
+        list.reset();
+        list.set(0, 'x');
+        list.clear();
+        assert!(changed(&list).is_empty());
+        assert_eq!(
+            list.structural_changes(),
+            [3, 2, 1, 0].map(VecChange::Removed)
+        );
+        assert_eq!(list.pop(), None);
+        assert_eq!(list.structural_changes().len(), 4);
+    }
+}
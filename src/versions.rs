@@ -0,0 +1,91 @@
+/// Version counters of a struct that uses the `versioned` option.
+///
+/// Every change increments the version of the struct and stores
+/// the new version for each changed field.
+/// `N` is the number of tracked fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Versions<const N: usize> {
+    version: u64,
+    changed_at: [u64; N],
+}
+
+impl<const N: usize> Default for Versions<N> {
+    fn default() -> Self {
+        Self {
+            version: 0,
+            changed_at: [0; N],
+        }
+    }
+}
+
+impl<const N: usize> Versions<N> {
+    /// Get the current version.
+    #[must_use]
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Increment the version and record it for all fields in `mask`.
+    ///
+    /// Bits that don't belong to a field are ignored,
+    /// and an empty mask doesn't create a new version.
+    pub fn record(&mut self, mask: impl Into<u128>) {
+        let mask = mask.into();
+        if mask == 0 {
+            return;
+        }
+        self.version += 1;
+        for (index, changed_at) in self.changed_at.iter_mut().enumerate() {
+            if mask & (1 << index) != 0 {
+                *changed_at = self.version;
+            }
+        }
+    }
+
+    /// Get a bit mask of all fields that changed after `version`.
+    #[must_use]
+    pub fn changed_since(&self, version: u64) -> u128 {
+        let mut mask = 0;
+        for (index, changed_at) in self.changed_at.iter().enumerate() {
+            if *changed_at > version {
+                mask |= 1 << index;
+            }
+        }
+        mask
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Versions;
+
+    #[test]
+    fn record_and_compare() {
+        let mut versions = Versions::<3>::default();
+        assert_eq!(versions.version(), 0);
+        assert_eq!(versions.changed_since(0), 0);
+
+        versions.record(0b001u8);
+        let first = versions.version();
+        versions.record(0b110u8);
+        assert_eq!(versions.version(), 2);
+
+        assert_eq!(versions.changed_since(0), 0b111);
+        assert_eq!(versions.changed_since(first), 0b110);
+        assert_eq!(versions.changed_since(versions.version()), 0);
+    }
+
+    #[test]
+    fn ignore_unknown_bits() {
+        let mut versions = Versions::<2>::default();
+        versions.record(u8::MAX);
+        assert_eq!(versions.changed_since(0), 0b11);
+    }
+
+    #[test]
+    fn empty_mask_keeps_the_version() {
+        let mut versions = Versions::<2>::default();
+        versions.record(0u8);
+        assert_eq!(versions.version(), 0);
+    }
+}
@@ -0,0 +1,47 @@
+/// A field of a struct paired with the information whether it changed.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct FieldView<'a, T: ?Sized> {
+    value: &'a T,
+    changed: bool,
+}
+
+impl<'a, T: ?Sized> FieldView<'a, T> {
+    /// Create a view of a field.
+    #[must_use]
+    pub fn new(value: &'a T, changed: bool) -> Self {
+        Self { value, changed }
+    }
+
+    /// The value of the field.
+    #[must_use]
+    pub fn value(&self) -> &'a T {
+        self.value
+    }
+
+    /// Whether the field changed.
+    #[must_use]
+    pub fn changed(&self) -> bool {
+        self.changed
+    }
+}
+
+impl<T: ?Sized> Clone for FieldView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: ?Sized> Copy for FieldView<'_, T> {}
+
+#[cfg(test)]
+mod test {
+    use super::FieldView;
+
+    #[test]
+    fn accessors() {
+        let view = FieldView::new("hi", true);
+        let copy = view;
+        assert_eq!(copy.value(), "hi");
+        assert!(view.changed());
+    }
+}
@@ -0,0 +1,10 @@
+use core::fmt;
+
+/// Visitor for the changed fields of a tracked struct,
+/// see the generated `visit_changed()` method.
+pub trait ChangedVisitor {
+    /// Visit a changed field.
+    ///
+    /// Called once for every changed field in the order of declaration.
+    fn field<T: fmt::Debug + ?Sized>(&mut self, name: &'static str, value: &T);
+}
@@ -0,0 +1,50 @@
+#[tracker::track(apply_changes)]
+#[derive(Clone, Default)]
+struct Test {
+    x: u8,
+    y: String,
+    #[no_eq]
+    z: Vec<u8>,
+    #[do_not_track]
+    untracked: u8,
+}
+
+#[test]
+fn copies_only_changed_fields() {
+    let mut primary = Test::default();
+    let mut replica = Test::default();
+
+    primary.set_x(1);
+    primary.set_z(vec![1, 2]);
+    // Not marked as changed, so it must not be copied.
+    primary.y = "ignored".to_owned();
+    primary.untracked = 1;
+
+    replica.apply_changes_from(&primary);
+
+    assert_eq!(replica.x, 1);
+    assert_eq!(replica.z, vec![1, 2]);
+    assert_eq!(replica.y, "");
+    assert_eq!(replica.untracked, 0);
+    assert!(replica.changed(Test::x() | Test::z()));
+    assert!(!replica.changed_y());
+}
+
+#[test]
+fn other_is_not_mutated() {
+    let mut primary = Test::default();
+    let mut replica = Test::default();
+
+    primary.set_y("hello".to_owned());
+    let tracker = primary.tracker;
+
+    replica.apply_changes_from(&primary);
+    assert_eq!(primary.tracker, tracker);
+    assert_eq!(primary.y, "hello");
+    assert_eq!(replica.y, "hello");
+
+    replica.reset();
+    primary.reset();
+    replica.apply_changes_from(&primary);
+    assert!(!replica.changed_any());
+}
@@ -0,0 +1,68 @@
+#![cfg(feature = "serde_json")]
+
+use serde::Deserialize;
+use serde_json::json;
+use tracker::ApplyJsonError;
+
+#[tracker::track(apply_json)]
+#[derive(Default, Deserialize)]
+struct Test {
+    count: u32,
+    #[serde(rename = "displayName")]
+    display_name: String,
+    #[no_eq]
+    tags: Vec<String>,
+    #[do_not_track]
+    #[serde(skip)]
+    untracked: u8,
+}
+
+#[test]
+fn provided_fields() {
+    let mut t = Test::default();
+    let mask = t.apply_json(&json!({ "count": 3, "tags": ["a"] })).unwrap();
+    assert_eq!(mask, Test::count() | Test::tags());
+    assert_eq!(t.count, 3);
+    assert_eq!(t.tags, vec!["a"]);
+    assert!(!t.changed(Test::display_name()));
+
+    t.reset();
+    let mask = t
+        .apply_json(&json!({ "count": 3, "displayName": "Alice", "tags": ["a"] }))
+        .unwrap();
+    assert_eq!(mask, Test::display_name() | Test::tags());
+    assert_eq!(t.display_name, "Alice");
+    assert!(!t.changed(Test::count()));
+}
+
+#[test]
+fn empty_object() {
+    let mut t = Test::default();
+    assert_eq!(t.apply_json(&json!({})).unwrap(), 0);
+    assert!(!t.changed_any());
+}
+
+#[test]
+fn unknown_keys() {
+    let mut t = Test::default();
+    let err = t
+        .apply_json(&json!({ "count": 1, "untracked": 2, "missing": 3 }))
+        .unwrap_err();
+    assert!(matches!(&err, ApplyJsonError::UnknownKeys(keys) if keys == &["missing", "untracked"]));
+    assert_eq!(t.count, 0);
+    assert_eq!(t.untracked, 0);
+}
+
+#[test]
+fn invalid_values() {
+    let mut t = Test::default();
+    let err = t
+        .apply_json(&json!({ "displayName": "Alice", "count": "one" }))
+        .unwrap_err();
+    assert!(matches!(err, ApplyJsonError::Deserialize(_)));
+    assert!(!t.changed_any());
+    assert_eq!(t.display_name, "");
+
+    let err = t.apply_json(&json!([1])).unwrap_err();
+    assert!(matches!(err, ApplyJsonError::NotAnObject));
+}
@@ -0,0 +1,64 @@
+#[tracker::track(prefix = "p_")]
+#[derive(Default)]
+struct Prefixed {
+    a: u8,
+}
+
+#[tracker::track]
+#[derive(Default)]
+struct Model {
+    x: u8,
+    y: u8,
+    #[tracker::rename = "heading"]
+    title: String,
+}
+
+fn changed_x() -> Model {
+    let mut model = Model::default();
+    model.set_x(1);
+    model
+}
+
+#[test]
+fn passing_assertions() {
+    let model = changed_x();
+    tracker::assert_changed!(model, x);
+    tracker::assert_unchanged!(model, y, heading,);
+    tracker::assert_unchanged!(model, y, heading, "message with {}", "arguments");
+
+    let reference = &model;
+    tracker::assert_changed!(*reference, x, "reference");
+
+    let mut prefixed = Prefixed::default();
+    prefixed.p_set_a(1);
+    tracker::assert_changed!(prefixed, p_a);
+}
+
+#[test]
+#[should_panic(
+    expected = "assertion `changed(x, y)` failed\n  expected changed: x, y\n  actually changed: x"
+)]
+fn changed_lists_actual_changes() {
+    tracker::assert_changed!(changed_x(), x, y);
+}
+
+#[test]
+#[should_panic(
+    expected = "assertion `unchanged(x)` failed: x was set\n  expected unchanged: x\n  actually changed: x"
+)]
+fn unchanged_with_message() {
+    let model = changed_x();
+    tracker::assert_unchanged!(model, x, "{} was set", "x");
+}
+
+#[test]
+#[should_panic(expected = "actually changed: none")]
+fn nothing_changed() {
+    tracker::assert_changed!(Model::default(), heading);
+}
+
+#[test]
+#[should_panic(expected = "has no tracked field `title`")]
+fn unknown_field() {
+    tracker::assert_changed!(Model::default(), title);
+}
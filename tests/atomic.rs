@@ -0,0 +1,66 @@
+use std::sync::{Arc, RwLock};
+use std::thread;
+
+#[tracker::track(atomic, previous, clone_keeps, impl_eq)]
+#[derive(Debug, Default)]
+struct Model {
+    x: u8,
+    name: String,
+}
+
+#[tracker::track(atomic, typed_mask, encapsulate)]
+struct Typed {
+    a: u8,
+    b: u8,
+}
+
+#[test]
+fn marks_through_shared_references() {
+    let model = Arc::new(RwLock::new(Model::default()));
+    let threads: Vec<_> = [Model::x(), Model::name()]
+        .into_iter()
+        .map(|mask| {
+            let model = Arc::clone(&model);
+            thread::spawn(move || model.read().unwrap().mark_changed(mask))
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+
+    let mut model = model.write().unwrap();
+    assert!(model.changed_x());
+    assert!(model.changed_name());
+    model.reset();
+    assert!(!model.changed_any());
+}
+
+#[test]
+fn setters_use_atomic_tracker() {
+    let mut model = Model::default();
+    model.set_x(0);
+    assert!(!model.changed_any());
+    model.set_x(1);
+    model.get_mut_name().push('a');
+    assert!(model.changed_x() && model.changed_name());
+
+    let clone = model.clone();
+    assert!(clone.changed_x());
+    assert_eq!(clone, model);
+
+    model.revert_x();
+    assert_eq!(model.x, 0);
+    assert!(!model.changed_x() && model.changed_name());
+}
+
+#[test]
+fn typed_masks_are_converted() {
+    let mut typed = Typed::new(0, 0);
+    typed.mark_changed(Typed::a());
+    typed.set_b(1);
+    assert!(typed.changed(Typed::a() | Typed::b()));
+    typed.reset();
+    assert!(!typed.changed_any());
+    typed.mark_all_changed();
+    assert!(typed.changed(Typed::track_all()));
+}
@@ -0,0 +1,49 @@
+#[tracker::track(bitflags, previous, serde_state)]
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+struct Test {
+    x: u8,
+    r#type: String,
+    #[do_not_track]
+    untracked: u8,
+}
+
+#[tracker::track(bitflags, atomic)]
+#[derive(Default)]
+struct Shared {
+    a: u8,
+    b: u8,
+}
+
+#[test]
+fn masks_are_flags() {
+    let mut t = Test::default();
+    assert_eq!(Test::x(), TestChanges::X);
+    assert_eq!(Test::r#type(), TestChanges::TYPE);
+    assert_eq!(Test::track_all(), TestChanges::all());
+    assert_eq!(t.tracker, TestChanges::empty());
+
+    t.set_x(1);
+    t.get_mut_type().push('a');
+    assert!(t.changed(TestChanges::X));
+    assert!(t.tracker.contains(Test::x() | Test::r#type()));
+    assert_eq!(format!("{:?}", Test::x()), "TestChanges(X)");
+    assert_eq!(format!("{:?}", t.tracker), "TestChanges(X | TYPE)");
+
+    t.reset();
+    assert!(t.tracker.is_empty());
+}
+
+#[test]
+fn flags_work_with_other_options() {
+    let mut t = Test::default();
+    t.set_type("a".to_owned());
+    assert_eq!(t.previous_type().map(String::as_str), Some(""));
+    let json = serde_json::to_string(&t).unwrap();
+    let t: Test = serde_json::from_str(&json).unwrap();
+    assert_eq!(t.tracker, TestChanges::TYPE);
+
+    let shared = Shared::default();
+    shared.mark_changed(SharedChanges::B);
+    assert!(shared.changed(Shared::b()));
+    assert!(!shared.changed(Shared::a()));
+}
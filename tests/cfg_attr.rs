@@ -0,0 +1,51 @@
+#[tracker::track(snapshot)]
+#[derive(Default)]
+struct Test {
+    // Tests are always built with `cfg(test)`.
+    #[cfg_attr(test, tracker::no_eq)]
+    always: f32,
+    #[cfg_attr(not(test), no_eq)]
+    compared: f32,
+    #[cfg_attr(test, tracker::no_eq, allow(unused))]
+    other: u8,
+}
+
+#[test]
+fn setters_follow_condition() {
+    let mut t = Test::default();
+    t.set_always(0.0);
+    t.set_compared(0.0);
+    assert!(t.changed_always());
+    assert!(!t.changed_compared());
+    t.reset();
+
+    t.update_all(|proxy| {
+        proxy.set_always(0.0);
+        proxy.set_compared(0.0);
+    });
+    assert!(!t.changed_compared());
+    assert!(t.changed(Test::always()));
+    t.reset();
+
+    let mask = t.apply_update(TestUpdate {
+        always: Some(0.0),
+        compared: Some(0.0),
+        ..Default::default()
+    });
+    assert_eq!(mask, Test::always());
+}
+
+#[test]
+fn comparisons_follow_condition() {
+    let mut t = Test::default();
+    let snapshot = t.snapshot();
+    t.always = 1.0;
+    t.compared = 1.0;
+    t.other = 1;
+    assert_eq!(t.diff(&Test::default()), Test::compared());
+    assert_eq!(t.changed_since(&snapshot), Test::compared());
+
+    t.set_other(1);
+    assert!(t.changed_other());
+    t.reset();
+}
@@ -0,0 +1,109 @@
+// The same struct with the `gui` field enabled and disabled.
+#[tracker::track(
+    previous,
+    snapshot,
+    apply_changes,
+    encapsulate,
+    debug,
+    impl_eq,
+    impl_hash,
+    clone_keeps,
+    project,
+    debug_values,
+    sql
+)]
+#[derive(Default)]
+struct Enabled {
+    x: u8,
+    #[cfg(test)]
+    gui: String,
+    #[do_not_track]
+    #[cfg(test)]
+    widget: u8,
+    y: u8,
+}
+
+#[tracker::track(
+    previous,
+    snapshot,
+    apply_changes,
+    encapsulate,
+    debug,
+    impl_eq,
+    impl_hash,
+    clone_keeps,
+    project,
+    debug_values,
+    sql
+)]
+#[derive(Default)]
+struct Disabled {
+    x: u8,
+    #[cfg(not(test))]
+    gui: String,
+    #[do_not_track]
+    #[cfg(not(test))]
+    widget: u8,
+    y: u8,
+}
+
+#[test]
+fn bits_are_reserved() {
+    assert_eq!(Enabled::x(), Disabled::x());
+    assert_eq!(Enabled::y(), Disabled::y());
+    assert_eq!(Enabled::gui(), 1 << 1);
+    assert_eq!(Disabled::y(), 1 << 2);
+    assert_eq!(Enabled::track_all(), Disabled::track_all());
+}
+
+#[test]
+fn enabled_field_is_tracked() {
+    let mut e = Enabled::new(0, String::new(), 0, 0);
+    e.set_gui("button".to_owned());
+    assert_eq!(
+        (*e.get_x(), e.get_gui().as_str(), *e.get_y()),
+        (0, "button", 0)
+    );
+    assert!(e.changed_gui());
+    assert_eq!(e.previous_gui().map(String::as_str), Some(""));
+    assert_eq!(e.to_delta().gui.as_deref(), Some("button"));
+    assert_eq!(e.changed_columns(), vec!["gui"]);
+    let view = e.project();
+    assert!(view.gui.changed() && !view.x.changed() && !view.y.changed());
+    assert_eq!(e.changed_values().count(), 1);
+    assert!(format!("{e:?}").contains("widget"));
+    assert_eq!(e.clone(), e);
+
+    e.revert_gui();
+    assert!(!e.changed_any());
+    e.set_widget_free();
+}
+
+#[test]
+fn disabled_field_is_skipped() {
+    let mut d = Disabled::new(0, 0);
+    d.set_x(1);
+    d.set_y(2);
+    assert_eq!((*d.get_x(), *d.get_y()), (1, 2));
+    assert_eq!(d.changed_columns(), vec!["x", "y"]);
+    assert_eq!(d.changed_values().count(), 2);
+    let view = d.project();
+    assert!(view.x.changed() && view.y.changed());
+    assert!(!format!("{d:?}").contains("gui"));
+
+    let mut other = Disabled::default();
+    other.apply_changes_from(&d);
+    assert_eq!(other, d);
+    assert_eq!(other.diff(&d), 0);
+    let snapshot = other.snapshot();
+    other.reset();
+    assert_eq!(other.changed_since(&snapshot), 0);
+}
+
+impl Enabled {
+    // Untracked fields can use the same cfg.
+    fn set_widget_free(&mut self) {
+        self.widget = 1;
+        self.reset();
+    }
+}
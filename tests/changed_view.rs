@@ -0,0 +1,92 @@
+#![cfg(feature = "serde")]
+
+use serde::Serialize;
+
+#[tracker::track(changed_view, serde)]
+#[derive(Default, Serialize)]
+struct Test {
+    name: String,
+    #[serde(rename = "amount")]
+    count: u32,
+    kind: u8,
+    #[do_not_track]
+    untracked: u8,
+}
+
+#[tracker::track(changed_view)]
+#[derive(Default)]
+#[allow(dead_code)]
+struct Generic<'a, T: Serialize + PartialEq> {
+    value: T,
+    text: &'a str,
+}
+
+#[test]
+fn nothing_changed() {
+    let t = Test::default();
+    assert_eq!(serde_json::to_string(&t.changed_view()).unwrap(), "{}");
+}
+
+#[test]
+fn changed_fields() {
+    let mut t = Test::default();
+    t.set_kind(2);
+    t.set_name("tracker".to_owned());
+    t.untracked = 1;
+    assert_eq!(
+        serde_json::to_string(&t.changed_view()).unwrap(),
+        r#"{"name":"tracker","kind":2}"#
+    );
+
+    t.set_count(3);
+    assert_eq!(
+        serde_json::to_string(&t.changed_view()).unwrap(),
+        r#"{"name":"tracker","amount":3,"kind":2}"#
+    );
+
+    t.reset();
+    t.mark_all_changed();
+    assert_eq!(
+        serde_json::to_value(t.changed_view()).unwrap(),
+        serde_json::json!({ "name": "tracker", "amount": 3, "kind": 2 })
+    );
+}
+
+#[test]
+fn generics() {
+    let mut g = Generic::<Vec<u8>>::default();
+    g.set_value(vec![1, 2]);
+    assert_eq!(
+        serde_json::to_string(&g.changed_view()).unwrap(),
+        r#"{"value":[1,2]}"#
+    );
+
+    g.reset();
+    assert_eq!(serde_json::to_string(&g.changed_view()).unwrap(), "{}");
+}
+
+/// A secret that can't be serialized at all.
+#[derive(Default, PartialEq)]
+struct Key(u64);
+
+#[tracker::track(changed_view)]
+#[derive(Default)]
+struct Login {
+    user: String,
+    #[tracker::redact]
+    password: String,
+    #[tracker::redact]
+    key: Key,
+}
+
+#[test]
+fn redacted_fields() {
+    let mut login = Login::default();
+    login.set_user("admin".to_string());
+    login.set_password("hunter2".to_string());
+    login.set_key(Key(42));
+    assert_eq!(
+        serde_json::to_value(login.changed_view()).unwrap(),
+        serde_json::json!({ "user": "admin", "password": "<redacted>", "key": "<redacted>" })
+    );
+}
@@ -0,0 +1,43 @@
+#[tracker::track(clone_resets, previous)]
+#[derive(Default)]
+struct Resets {
+    value: u8,
+    name: String,
+    #[do_not_track]
+    untracked: u8,
+}
+
+#[tracker::track(clone_keeps)]
+#[derive(Default)]
+struct Keeps {
+    value: u8,
+}
+
+#[test]
+fn clone_resets() {
+    let mut original = Resets::default();
+    original.set_value(1);
+    original.set_name("tracker".to_owned());
+    original.untracked = 2;
+
+    let clone = original.clone();
+    assert!(!clone.changed_any());
+    assert!(clone.previous_value().is_none());
+    assert_eq!(clone.value, 1);
+    assert_eq!(clone.name, "tracker");
+    assert_eq!(clone.untracked, 2);
+
+    assert!(original.changed(Resets::value()));
+    assert_eq!(original.previous_value(), Some(&0));
+}
+
+#[test]
+fn clone_keeps() {
+    let mut original = Keeps::default();
+    original.set_value(1);
+
+    let mut clone = original.clone();
+    assert!(clone.changed(Keeps::value()));
+    clone.reset();
+    assert!(original.changed_any());
+}
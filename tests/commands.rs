@@ -0,0 +1,71 @@
+use std::collections::VecDeque;
+
+#[tracker::track(commands)]
+#[derive(Default)]
+struct Document {
+    x: u8,
+    title: String,
+    #[no_eq]
+    cursor: usize,
+    #[do_not_track]
+    cache: Vec<u8>,
+}
+
+#[tracker::track(commands)]
+#[derive(Default)]
+struct Generic<T, U> {
+    value: T,
+    #[do_not_track]
+    other: Option<U>,
+}
+
+/// A type without `Debug` or `Clone`.
+#[derive(Default, PartialEq)]
+struct Opaque;
+
+#[tracker::track(commands)]
+#[derive(Default)]
+struct Unprintable {
+    value: Opaque,
+}
+
+#[test]
+fn commands_set_the_fields() {
+    let mut queue = VecDeque::new();
+    queue.push_back(DocumentCommand::SetX(3));
+    queue.push_back(Document::command_for_set_title("Draft".to_string()));
+    queue.push_back(DocumentCommand::SetX(3));
+
+    let mut doc = Document::default();
+    let applied: Vec<bool> = queue.drain(..).map(|command| doc.apply(command)).collect();
+    assert_eq!(applied, [true, true, false]);
+    assert_eq!(doc.x, 3);
+    assert_eq!(doc.title, "Draft");
+    assert!(doc.changed(Document::x() | Document::title()));
+    assert!(!doc.changed_cursor());
+    assert!(doc.cache.is_empty());
+
+    doc.reset();
+    assert!(!doc.apply(DocumentCommand::SetX(3)));
+    assert!(!doc.changed_any());
+    assert!(doc.apply(Document::command_for_set_cursor(0)));
+    assert!(doc.changed_cursor());
+}
+
+#[test]
+fn commands_derive_debug_and_clone() {
+    let command = DocumentCommand::SetTitle("Draft".to_string());
+    assert_eq!(format!("{:?}", command.clone()), r#"SetTitle("Draft")"#);
+}
+
+#[test]
+fn commands_keep_the_generics() {
+    let mut generic = Generic::<u8, String>::default();
+    let command: GenericCommand<u8, String> = Generic::command_for_set_value(1);
+    assert!(generic.apply(command));
+    assert!(generic.changed_value());
+    assert!(generic.other.is_none());
+
+    let mut unprintable = Unprintable::default();
+    assert!(!unprintable.apply(UnprintableCommand::SetValue(Opaque)));
+}
@@ -0,0 +1,28 @@
+//! The `compact` option leaves out the doc comments of per-field items
+//! without triggering `missing_docs`.
+#![deny(missing_docs)]
+
+/// A struct with all per-field items.
+#[tracker::track(compact, previous, snapshot, project)]
+#[derive(Default)]
+pub struct Test {
+    /// A tracked field.
+    pub x: u8,
+    /// A field that isn't compared.
+    #[no_eq]
+    pub y: u8,
+}
+
+#[test]
+fn methods_are_generated() {
+    let mut t = Test::default();
+    t.set_x(0);
+    t.set_y(0);
+    assert!(!t.changed_x() && t.changed_y());
+    assert_eq!(t.previous_y(), Some(&0));
+
+    t.update_all(|proxy| proxy.set_x(1));
+    assert_eq!(*t.get_x(), 1);
+    assert_eq!(t.to_delta().x, Some(1));
+    assert!(t.project().x.changed());
+}
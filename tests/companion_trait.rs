@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+
+#[tracker::track(trait = "TestTracked")]
+#[derive(Default)]
+pub struct Test {
+    /// The x coordinate.
+    x: u8,
+    name: String,
+    #[tracker::no_eq]
+    list: Vec<u8>,
+    #[tracker::no_set]
+    fixed: u8,
+    #[do_not_track]
+    untracked: u8,
+}
+
+#[tracker::track(trait = "GenericTracked", prefix = "t_", skip_methods(get_mut))]
+#[derive(Default)]
+struct Generic<T: Clone> {
+    value: T,
+}
+
+/// A mock that records the values that were set.
+#[derive(Default)]
+struct Mock {
+    x: u8,
+    name: String,
+    list: Vec<u8>,
+    fixed: u8,
+    sets: HashMap<&'static str, usize>,
+}
+
+impl Mock {
+    fn record(&mut self, name: &'static str) {
+        *self.sets.entry(name).or_default() += 1;
+    }
+}
+
+impl TestTracked for Mock {
+    fn get_x(&self) -> &u8 {
+        &self.x
+    }
+    fn get_mut_x(&mut self) -> &mut u8 {
+        &mut self.x
+    }
+    fn update_x<F: FnOnce(&mut u8)>(&mut self, f: F) {
+        f(&mut self.x);
+    }
+    fn changed_x(&self) -> bool {
+        self.sets.contains_key("x")
+    }
+    fn set_x(&mut self, value: u8) {
+        self.record("x");
+        self.x = value;
+    }
+    fn get_name(&self) -> &String {
+        &self.name
+    }
+    fn get_mut_name(&mut self) -> &mut String {
+        &mut self.name
+    }
+    fn update_name<F: FnOnce(&mut String)>(&mut self, f: F) {
+        f(&mut self.name);
+    }
+    fn changed_name(&self) -> bool {
+        self.sets.contains_key("name")
+    }
+    fn set_name(&mut self, value: String) {
+        self.record("name");
+        self.name = value;
+    }
+    fn get_list(&self) -> &Vec<u8> {
+        &self.list
+    }
+    fn get_mut_list(&mut self) -> &mut Vec<u8> {
+        &mut self.list
+    }
+    fn update_list<F: FnOnce(&mut Vec<u8>)>(&mut self, f: F) {
+        f(&mut self.list);
+    }
+    fn changed_list(&self) -> bool {
+        self.sets.contains_key("list")
+    }
+    fn set_list(&mut self, value: Vec<u8>) {
+        self.record("list");
+        self.list = value;
+    }
+    fn get_fixed(&self) -> &u8 {
+        &self.fixed
+    }
+    fn get_mut_fixed(&mut self) -> &mut u8 {
+        &mut self.fixed
+    }
+    fn update_fixed<F: FnOnce(&mut u8)>(&mut self, f: F) {
+        f(&mut self.fixed);
+    }
+    fn changed_fixed(&self) -> bool {
+        false
+    }
+    fn changed(&self, mask: u8) -> bool {
+        mask != 0 && !self.sets.is_empty()
+    }
+    fn changed_any(&self) -> bool {
+        !self.sets.is_empty()
+    }
+    fn reset(&mut self) {
+        self.sets.clear();
+    }
+    fn mark_all_changed(&mut self) {}
+}
+
+/// Code under test that only knows the trait.
+fn rename(model: &mut impl TestTracked, name: &str) -> bool {
+    model.set_name(name.to_owned());
+    model.update_x(|x| *x += 1);
+    model.changed_any()
+}
+
+#[test]
+fn trait_forwards_to_struct() {
+    let mut t = Test::default();
+    assert!(rename(&mut t, "a"));
+    assert_eq!((t.get_x(), t.get_name().as_str()), (&1, "a"));
+    assert!(TestTracked::changed(&t, Test::name() | Test::x()));
+    TestTracked::reset(&mut t);
+    assert!(!TestTracked::changed_any(&t));
+    t.untracked = 1;
+
+    let mut generic = Generic::<u8>::default();
+    GenericTracked::t_set_value(&mut generic, 2);
+    assert!(GenericTracked::t_changed_value(&generic));
+    assert_eq!(*GenericTracked::t_get_value(&generic), 2);
+}
+
+#[test]
+fn trait_can_be_mocked() {
+    let mut mock = Mock::default();
+    assert!(rename(&mut mock, "b"));
+    assert_eq!(mock.sets["name"], 1);
+    assert_eq!(mock.x, 1);
+
+    let models: [&mut dyn TestTracked; 2] = [&mut mock, &mut Test::default()];
+    for model in models {
+        model.set_list(vec![1]);
+        assert!(model.changed_list());
+    }
+}
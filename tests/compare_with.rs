@@ -0,0 +1,57 @@
+mod compare {
+    // Comparators get references to the type of the field.
+    #[allow(clippy::ptr_arg)]
+    pub fn case_insensitive(a: &String, b: &String) -> bool {
+        a.eq_ignore_ascii_case(b)
+    }
+}
+
+/// Compared by id only, other fields don't matter.
+#[derive(Clone, Default)]
+struct Handle {
+    id: u32,
+    _cache: Vec<u8>,
+}
+
+fn same_id(a: &Handle, b: &Handle) -> bool {
+    a.id == b.id
+}
+
+#[tracker::track(snapshot)]
+#[derive(Default)]
+struct Test {
+    #[tracker::compare_with = "compare::case_insensitive"]
+    name: String,
+    #[tracker(compare_with = "same_id")]
+    handle: Handle,
+}
+
+#[test]
+fn setters_use_comparator() {
+    let mut t = Test {
+        name: "Tracker".to_owned(),
+        ..Default::default()
+    };
+    t.set_name("TRACKER".to_owned());
+    assert!(!t.changed_name());
+    assert_eq!(t.get_name(), "TRACKER");
+    t.set_name("other".to_owned());
+    assert!(t.changed_name());
+    t.reset();
+
+    let snapshot = t.snapshot();
+    t.set_handle(Handle {
+        id: 0,
+        _cache: vec![1],
+    });
+    assert!(!t.changed_handle());
+    t.update_all(|proxy| proxy.set_handle(Handle::default()));
+    assert_eq!(t.changed_since(&snapshot), 0);
+    t.handle.id = 1;
+    let other = Test {
+        name: "OTHER".to_owned(),
+        ..Default::default()
+    };
+    assert_eq!(t.diff(&other), Test::handle());
+    t.reset();
+}
@@ -0,0 +1,41 @@
+#[tracker::track]
+struct Buf<const N: usize> {
+    data: [u8; N],
+    len: usize,
+}
+
+#[tracker::track]
+#[derive(Default)]
+struct Pool<T: Default, const N: usize = 16> {
+    values: Vec<T>,
+    capacity: usize,
+}
+
+#[test]
+fn const_generic_struct() {
+    let mut buf = Buf {
+        data: [0; 4],
+        len: 0,
+        tracker: 0,
+    };
+    buf.set_data([1, 2, 3, 4]);
+    assert!(buf.changed(Buf::<4>::data()));
+    assert!(!buf.changed(Buf::<4>::len()));
+    buf.reset();
+    buf.set_len(4);
+    assert!(buf.changed(Buf::<4>::len()));
+    assert_eq!(buf.get_data(), &[1, 2, 3, 4]);
+}
+
+#[test]
+fn const_generic_with_default() {
+    let mut pool = Pool::<u8>::default();
+    pool.set_capacity(16);
+    pool.get_mut_values().push(1);
+    assert!(pool.changed(Pool::<u8>::capacity()));
+    assert!(pool.changed(Pool::<u8>::values()));
+
+    let mut small = Pool::<u8, 2>::default();
+    small.set_capacity(0);
+    assert!(!small.changed_any());
+}
@@ -0,0 +1,63 @@
+const VIEW: usize = 0;
+const STORAGE: usize = 1;
+
+#[tracker::track(consumers = 2)]
+#[derive(Default)]
+struct Model {
+    name: String,
+    count: u32,
+}
+
+#[tracker::track(consumers = 3, typed_mask)]
+#[derive(Default)]
+struct Typed {
+    a: u8,
+    b: u8,
+}
+
+#[test]
+fn consumers_are_reset_independently() {
+    let mut model = Model::default();
+    model.set_name("a".to_string());
+    assert!(model.changed_for(VIEW, Model::name()));
+    assert!(model.changed_for(STORAGE, Model::name()));
+
+    // The view renders every frame, storage is only updated later.
+    model.reset();
+    assert!(!model.changed_any_for(VIEW));
+    assert!(model.changed_for(STORAGE, Model::name()));
+
+    model.update_count(|count| *count += 1);
+    assert!(model.changed_for(VIEW, Model::count()));
+    assert!(!model.changed_for(VIEW, Model::name()));
+    assert!(model.changed_for(STORAGE, Model::name() | Model::count()));
+
+    model.reset_for(STORAGE);
+    assert!(!model.changed_any_for(STORAGE));
+    assert!(model.changed_any_for(VIEW));
+    model.reset_for(VIEW);
+    assert!(!model.changed_any());
+}
+
+#[test]
+fn all_consumers_see_every_change() {
+    let mut typed = Typed::default();
+    typed.mark_all_changed();
+    typed.reset_for(2);
+    typed.set_b(1);
+    assert!(typed.changed_for(1, Typed::a() | Typed::b()));
+    assert!(!typed.changed_for(2, Typed::a()));
+    assert!(typed.changed_for(2, Typed::b()));
+
+    // A field that is already marked for consumer 0 is still recorded for the others.
+    typed.reset_for(1);
+    typed.set_a(1);
+    assert!(typed.changed_for(1, Typed::a()));
+}
+
+#[test]
+#[should_panic(expected = "consumer 2 is out of range, the struct has 2 consumers")]
+fn out_of_range_consumers_panic() {
+    let model = Model::default();
+    let _ = model.changed_for(2, Model::name());
+}
@@ -0,0 +1,80 @@
+#[tracker::track(counters, serde)]
+#[derive(Default, Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Model {
+    x: u8,
+    #[tracker::no_eq]
+    y: u8,
+    name: String,
+}
+
+#[tracker::track(counters, typed_mask)]
+#[derive(Default)]
+struct Grid {
+    #[tracker::elements]
+    cells: [u8; 3],
+    z: u8,
+}
+
+#[test]
+fn counters_count_newly_set_bits() {
+    let mut model = Model::default();
+    model.set_x(1);
+    // Already marked, so the counter isn't incremented again.
+    model.set_x(2);
+    model.set_y(0);
+    assert_eq!(model.change_count_x(), 1);
+    assert_eq!(model.change_count_y(), 1);
+    assert_eq!(model.change_count_name(), 0);
+
+    model.reset();
+    model.set_x(2);
+    model.set_x(3);
+    model.get_mut_name().push('a');
+    assert_eq!(model.change_count_x(), 2);
+    assert_eq!(model.change_count_name(), 1);
+
+    model.reset();
+    model.mark_all_changed();
+    assert_eq!(model.change_count_x(), 3);
+    assert_eq!(model.change_count_y(), 2);
+    assert_eq!(model.change_count_name(), 2);
+}
+
+#[test]
+fn reset_keeps_the_counters() {
+    let mut model = Model::default();
+    model.set_x(1);
+    model.reset();
+    assert!(!model.changed_any());
+    assert_eq!(model.change_count_x(), 1);
+
+    model.reset_counters();
+    assert_eq!(model.change_count_x(), 0);
+    assert!(!model.changed_any());
+}
+
+#[test]
+fn counters_are_not_part_of_the_state() {
+    let mut model = Model::default();
+    model.set_x(1);
+    let mut other = model.clone();
+    other.reset_counters();
+    assert_eq!(model, other);
+    assert_eq!(
+        serde_json::to_string(&model).unwrap(),
+        r#"{"x":1,"y":0,"name":""}"#
+    );
+
+    let model: Model = serde_json::from_str(r#"{"x":1,"y":0,"name":""}"#).unwrap();
+    assert_eq!(model.change_count_x(), 0);
+}
+
+#[test]
+fn elements_are_counted_separately() {
+    let mut grid = Grid::default();
+    grid.set_cells_elem(0, 1);
+    grid.set_cells_elem(2, 1);
+    grid.set_z(1);
+    assert_eq!(grid.change_count_cells(), 2);
+    assert_eq!(grid.change_count_z(), 1);
+}
@@ -0,0 +1,83 @@
+mod model {
+    #[tracker::track(data_struct, serde, encapsulate)]
+    #[derive(Default, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Profile {
+        pub user_name: String,
+        pub age: u8,
+        #[do_not_track]
+        #[serde(skip)]
+        pub cache: Vec<u8>,
+    }
+}
+
+use model::{Profile, ProfileData};
+
+#[tracker::track(data_struct = "Message", no_eq)]
+#[derive(Clone)]
+struct Wrapper<T> {
+    value: T,
+}
+
+#[derive(tracker::Track)]
+#[tracker(data_struct)]
+struct Derived {
+    #[no_eq]
+    x: u8,
+    tracker: u8,
+}
+
+#[test]
+fn data_struct_has_all_fields() {
+    let mut profile = Profile::default();
+    profile.set_user_name("ferris".to_string());
+
+    let data = profile.as_data();
+    assert_eq!(
+        data,
+        ProfileData {
+            user_name: "ferris".to_string(),
+            age: 0,
+            cache: Vec::new(),
+        }
+    );
+    assert_eq!(
+        serde_json::to_string(&data).unwrap(),
+        r#"{"userName":"ferris","age":0}"#
+    );
+    assert_eq!(ProfileData::from(profile), data);
+}
+
+#[test]
+fn data_converts_into_the_struct() {
+    let data = ProfileData {
+        user_name: "ferris".to_string(),
+        age: 7,
+        cache: vec![1],
+    };
+
+    let profile = Profile::from(data);
+    assert!(!profile.changed_any());
+    assert_eq!(*profile.get_age(), 7);
+    assert_eq!(profile.cache, [1]);
+
+    let profile = Profile::from_data_changed(profile.as_data());
+    assert!(profile.changed(Profile::track_all()));
+}
+
+#[test]
+fn data_struct_keeps_the_generics() {
+    let wrapper = Wrapper::from(Message { value: 'a' });
+    let message: Message<char> = wrapper.as_data();
+    assert_eq!(message.value, 'a');
+    let wrapper: Wrapper<char> = message.into();
+    assert_eq!(*wrapper.get_value(), 'a');
+}
+
+#[test]
+fn derives_leave_out_the_tracker() {
+    let data = DerivedData { x: 1 };
+    let derived = Derived::from_data_changed(data);
+    assert_eq!(derived.tracker, Derived::x());
+    assert_eq!(derived.as_data().x, 1);
+}
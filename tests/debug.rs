@@ -0,0 +1,31 @@
+#[tracker::track(debug)]
+#[derive(Default)]
+struct Test<T: PartialEq> {
+    title: String,
+    value: T,
+    #[do_not_track]
+    untracked: u8,
+}
+
+#[test]
+fn changed_marker() {
+    let mut t = Test::<u8>::default();
+    assert_eq!(
+        format!("{t:?}"),
+        r#"Test { title: "", value: 0, untracked: 0 }"#
+    );
+
+    t.set_title("hi".to_owned());
+    t.untracked = 1;
+    assert_eq!(
+        format!("{t:?}"),
+        r#"Test { title: "hi" (changed), value: 0, untracked: 1 }"#
+    );
+
+    t.reset();
+    t.set_value(2);
+    assert_eq!(
+        format!("{t:#?}"),
+        "Test {\n    title: \"hi\",\n    value: 2 (changed),\n    untracked: 1,\n}"
+    );
+}
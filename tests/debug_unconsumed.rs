@@ -0,0 +1,88 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+#[tracker::track(debug_unconsumed)]
+#[derive(Default)]
+struct Model {
+    x: u8,
+    y: u8,
+    name: String,
+}
+
+#[tracker::track(debug_unconsumed)]
+#[derive(Default)]
+struct Wrapper<T: Clone + PartialEq> {
+    value: T,
+}
+
+#[derive(tracker::Track, Default)]
+#[tracker(debug_unconsumed)]
+struct Derived {
+    x: u8,
+    tracker: u8,
+}
+
+fn panic_message(f: impl FnOnce()) -> String {
+    let payload = catch_unwind(AssertUnwindSafe(f)).unwrap_err();
+    match payload.downcast_ref::<&str>() {
+        Some(message) => message.to_string(),
+        None => payload.downcast_ref::<String>().unwrap().clone(),
+    }
+}
+
+#[test]
+fn consumed_changes_drop_silently() {
+    let mut model = Model::default();
+    model.set_x(1);
+    model.reset();
+    drop(model);
+
+    let mut wrapper = Wrapper {
+        value: 1,
+        tracker: 0,
+    };
+    wrapper.set_value(2);
+    wrapper.reset();
+}
+
+#[test]
+#[cfg(debug_assertions)]
+fn unconsumed_changes_panic_with_the_fields() {
+    assert!(std::mem::needs_drop::<Wrapper<u8>>());
+    let message = panic_message(|| {
+        let mut model = Model::default();
+        model.set_x(1);
+        model.get_mut_name().push('a');
+    });
+    assert!(message.contains("`debug_unconsumed::Model` was dropped with unconsumed changes"));
+    assert!(message.ends_with("changed fields: x, name"));
+
+    let message = panic_message(|| {
+        let mut wrapper = Wrapper::<u8>::default();
+        wrapper.mark_all_changed();
+    });
+    assert!(message.ends_with("changed fields: value"));
+
+    let message = panic_message(|| {
+        let mut derived = Derived::default();
+        derived.set_x(1);
+    });
+    assert!(message.ends_with("changed fields: x"));
+}
+
+#[test]
+#[cfg(debug_assertions)]
+fn no_second_panic_while_panicking() {
+    let message = panic_message(|| {
+        let mut model = Model::default();
+        model.set_y(1);
+        panic!("first panic");
+    });
+    assert_eq!(message, "first panic");
+}
+
+#[test]
+#[cfg(not(debug_assertions))]
+fn release_builds_have_no_drop_impl() {
+    assert!(!std::mem::needs_drop::<Wrapper<u8>>());
+    assert!(!std::mem::needs_drop::<Derived>());
+}
@@ -0,0 +1,26 @@
+#[tracker::track(debug_values)]
+#[derive(Default)]
+struct Test<T: PartialEq> {
+    title: String,
+    count: u32,
+    value: T,
+    #[do_not_track]
+    untracked: u8,
+}
+
+#[test]
+fn changed_values() {
+    let mut t = Test::<Option<u8>>::default();
+    assert_eq!(t.changed_values().count(), 0);
+
+    t.set_value(Some(2));
+    t.set_title("hi".to_owned());
+    t.untracked = 1;
+
+    let log: Vec<String> = t
+        .changed_values()
+        .map(|(name, value)| format!("{name} = {value:?}"))
+        .collect();
+    assert_eq!(log, vec!["title = \"hi\"", "value = Some(2)"]);
+    t.reset();
+}
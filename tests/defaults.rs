@@ -0,0 +1,64 @@
+mod player {
+    const MAX_VOLUME: f32 = 1.0;
+
+    #[tracker::track(encapsulate, impl_default)]
+    pub struct Player {
+        pub name: String,
+        #[tracker::default = MAX_VOLUME]
+        pub volume: f32,
+        #[tracker(default = vec!["intro".to_string()], no_eq)]
+        pub playlist: Vec<String>,
+        #[do_not_track]
+        #[tracker::default = 3]
+        pub retries: u8,
+    }
+}
+
+use player::Player;
+
+/// A type without a `Default` impl.
+#[derive(Debug, PartialEq)]
+struct Port(u16);
+
+#[tracker::track(impl_default)]
+struct Server<T> {
+    #[tracker::default = Port(8080)]
+    port: Port,
+    state: T,
+}
+
+#[test]
+fn new_only_takes_the_required_fields() {
+    let player = Player::new("Ferris".to_string());
+    assert_eq!(player.get_name(), "Ferris");
+    assert_eq!(*player.get_volume(), 1.0);
+    assert_eq!(player.get_playlist(), &["intro"]);
+    assert_eq!(player.retries, 3);
+    assert!(!player.changed_any());
+
+    let player = Player::new("Ferris".to_string())
+        .with_volume(0.5)
+        .with_playlist(Vec::new())
+        .with_retries(0);
+    assert_eq!(*player.get_volume(), 0.5);
+    assert!(player.get_playlist().is_empty());
+    assert_eq!(player.retries, 0);
+    assert!(!player.changed_any());
+}
+
+#[test]
+fn default_uses_the_expressions() {
+    let player = Player::default();
+    assert_eq!(player.get_name(), "");
+    assert_eq!(*player.get_volume(), 1.0);
+    assert_eq!(player.get_playlist(), &["intro"]);
+    assert_eq!(player.retries, 3);
+    assert!(!player.changed_any());
+
+    let mut server = Server::<bool>::default();
+    assert_eq!(server.port, Port(8080));
+    assert!(!server.state);
+    assert!(!server.changed_any());
+    server.set_port(Port(80));
+    assert!(server.changed_port());
+}
@@ -0,0 +1,93 @@
+#[tracker::track]
+#[derive(Default)]
+struct Test {
+    x: u8,
+    title: String,
+    count: u32,
+    #[no_eq]
+    no_eq: u8,
+    #[do_not_track]
+    untracked: u8,
+}
+
+#[tracker::track]
+struct Generic<'a, T: Clone + PartialEq> {
+    value: T,
+    #[do_not_track]
+    reference: &'a str,
+}
+
+#[tracker::track(no_delta)]
+struct NoDelta {
+    x: u8,
+}
+
+// Make sure no delta type was generated.
+struct NoDeltaDelta;
+
+#[test]
+fn delta_round_trip() {
+    let mut t = Test::default();
+    t.set_x(1);
+    t.set_title("hello".to_owned());
+
+    let delta = t.to_delta();
+    assert_eq!(delta.x, Some(1));
+    assert_eq!(delta.title.as_deref(), Some("hello"));
+    assert_eq!(delta.count, None);
+    assert_eq!(delta.no_eq, None);
+
+    let mut fresh = Test::default();
+    fresh.apply_delta(delta);
+    assert_eq!(fresh.x, t.x);
+    assert_eq!(fresh.title, t.title);
+    assert_eq!(fresh.diff(&t), 0);
+    assert_eq!(fresh.tracker, t.tracker);
+
+    fresh.reset();
+    assert!(!fresh.changed_any());
+}
+
+#[test]
+fn apply_delta_checks_equality() {
+    let mut t = Test::default();
+    t.apply_delta(TestDelta {
+        x: Some(0),
+        no_eq: Some(0),
+        count: Some(3),
+        ..Default::default()
+    });
+
+    assert!(!t.changed_x());
+    assert!(t.changed_no_eq());
+    assert!(t.changed_count());
+    assert_eq!(t.untracked, 0);
+}
+
+#[test]
+fn generic_delta() {
+    let mut g = Generic {
+        value: 1u16,
+        reference: "",
+        tracker: 0,
+    };
+    assert_eq!(g.to_delta().value, None);
+
+    g.set_value(2);
+    let delta = g.to_delta();
+    assert_eq!(delta.value, Some(2));
+
+    g.reset();
+    g.apply_delta(delta);
+    assert!(!g.changed_value());
+    assert_eq!(g.reference, "");
+}
+
+#[test]
+fn no_delta() {
+    let mut t = NoDelta { x: 0, tracker: 0 };
+    t.set_x(1);
+    assert!(t.changed_x());
+    t.reset();
+    let _ = NoDeltaDelta;
+}
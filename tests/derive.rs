@@ -0,0 +1,60 @@
+use tracker::Track;
+
+#[derive(Track, Default)]
+struct Named {
+    x: u8,
+    #[no_eq]
+    y: u8,
+    #[do_not_track]
+    z: u8,
+    tracker: u8,
+}
+
+#[derive(Track, Default, Debug, PartialEq)]
+#[tracker(sql, skip_methods(get_mut))]
+struct Marked<T: Default> {
+    #[tracker_field]
+    changes: u16,
+    value: T,
+}
+
+#[derive(Track, Default)]
+#[tracker(field = "dirty")]
+struct Renamed {
+    x: u8,
+    dirty: u8,
+}
+
+#[test]
+fn derive_generates_methods() {
+    let mut n = Named::default();
+    n.set_x(0);
+    n.set_y(0);
+    n.z = 1;
+    assert_eq!(n.tracker, Named::y());
+    assert_eq!(Named::track_all(), Named::x() | Named::y());
+    n.reset();
+    assert!(!n.changed_any());
+}
+
+#[test]
+fn derive_with_options() {
+    let mut m = Marked::<u8>::default();
+    m.set_value(1);
+    assert_eq!(m.changes, Marked::<u8>::value());
+    assert_eq!(m.changed_columns(), vec!["value"]);
+    assert_eq!(*m.get_value(), 1);
+    m.reset();
+    assert_eq!(
+        m,
+        Marked {
+            changes: 0,
+            value: 1
+        }
+    );
+
+    let mut r = Renamed::default();
+    r.update_x(|x| *x = 1);
+    assert_eq!(r.dirty, Renamed::x());
+    r.reset();
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "diesel")]
+
+use diesel::QueryDsl;
+
+mod schema {
+    diesel::table! {
+        users (id) {
+            id -> Integer,
+            name -> Text,
+            display_name -> Text,
+            age -> Integer,
+        }
+    }
+}
+
+use schema::users;
+
+#[tracker::track(diesel(table = schema::users))]
+#[derive(Default)]
+struct User {
+    #[do_not_track]
+    id: i32,
+    name: String,
+    #[tracker::column = "display_name"]
+    nick: String,
+    age: i32,
+}
+
+/// The generics need the phantom field that is skipped with `#[diesel(skip_update)]`.
+#[tracker::track(diesel(table = schema::users))]
+struct Generic<'a, T> {
+    name: String,
+    #[do_not_track]
+    source: &'a T,
+}
+
+#[test]
+fn changeset_only_contains_changed_fields() {
+    let mut user = User::default();
+    user.set_name("Ferris".to_string());
+    user.set_age(7);
+
+    let changeset = user.as_changeset();
+    assert_eq!(changeset.name, Some(&"Ferris".to_string()));
+    assert_eq!(changeset.nick, None);
+    assert_eq!(changeset.age, Some(&7));
+
+    // Build the update statement to check that the changeset fits the table.
+    let _ = diesel::update(users::table.find(user.id)).set(user.as_changeset());
+}
+
+#[test]
+fn generics() {
+    let mut generic = Generic {
+        name: String::new(),
+        source: &0u8,
+        tracker: 0,
+    };
+    generic.set_name("Ferris".to_string());
+    assert_eq!(generic.as_changeset().name, Some(&"Ferris".to_string()));
+    let _ = diesel::update(users::table).set(generic.as_changeset());
+}
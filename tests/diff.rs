@@ -0,0 +1,40 @@
+#[tracker::track]
+#[derive(Clone, Default)]
+struct Test {
+    x: u8,
+    y: String,
+    z: Option<u64>,
+    #[no_eq]
+    no_eq: u8,
+    #[do_not_track]
+    untracked: u8,
+}
+
+#[test]
+fn diff_reports_differing_fields() {
+    let mut a = Test::default();
+    let mut b = a.clone();
+    assert_eq!(a.diff(&b), 0);
+
+    b.x = 1;
+    b.z = Some(2);
+    let mask = a.diff(&b);
+    assert_eq!(mask, Test::x() | Test::z());
+    assert_eq!(mask, b.diff(&a));
+
+    // The mask can be used like any other mask.
+    a.tracker = mask;
+    assert!(a.changed(Test::x()));
+    assert!(!a.changed(Test::y()));
+    a.reset();
+}
+
+#[test]
+fn diff_ignores_no_eq_and_untracked_fields() {
+    let a = Test::default();
+    let mut b = a.clone();
+
+    b.no_eq = 1;
+    b.untracked = 1;
+    assert_eq!(a.diff(&b), 0);
+}
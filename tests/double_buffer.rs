@@ -0,0 +1,44 @@
+#[tracker::track(double_buffer)]
+#[derive(Default)]
+struct Frame {
+    position: (i32, i32),
+    color: u32,
+}
+
+#[tracker::track(double_buffer, atomic)]
+#[derive(Default)]
+struct Shared {
+    value: u8,
+}
+
+#[test]
+fn reset_keeps_the_last_cycle() {
+    let mut frame = Frame::default();
+    assert_eq!(frame.previous_changes(), 0);
+
+    frame.set_position((1, 2));
+    assert!(!frame.previously_changed(Frame::position()));
+    frame.reset();
+    assert!(!frame.changed_any());
+    assert_eq!(frame.previous_changes(), Frame::position());
+    assert!(frame.previously_changed(Frame::position()));
+    assert!(!frame.previously_changed(Frame::color()));
+
+    // Changes of the new cycle don't affect the previous slot.
+    frame.set_color(3);
+    assert_eq!(frame.previous_changes(), Frame::position());
+
+    // Every reset overwrites the previous slot.
+    frame.reset();
+    assert_eq!(frame.previous_changes(), Frame::color());
+    frame.reset();
+    assert_eq!(frame.previous_changes(), 0);
+}
+
+#[test]
+fn atomic_changes_are_kept() {
+    let mut shared = Shared::default();
+    shared.mark_changed(Shared::value());
+    shared.reset();
+    assert!(shared.previously_changed(Shared::value()));
+}
@@ -0,0 +1,96 @@
+use tracker::{SetFieldError, UnknownField};
+
+#[tracker::track(dynamic)]
+#[derive(Default)]
+struct Settings {
+    volume: f32,
+    display_name: String,
+    #[no_eq]
+    seed: u64,
+    #[do_not_track]
+    cache: Vec<u8>,
+}
+
+#[tracker::track(dynamic, skip_methods(set))]
+#[derive(Default)]
+struct Generic<T> {
+    value: T,
+}
+
+#[test]
+fn fields_are_read_at_runtime() {
+    let settings = Settings {
+        volume: 0.5,
+        ..Settings::default()
+    };
+    let volume = settings.get_field(SettingsField::Volume);
+    assert_eq!(volume.downcast_ref::<f32>(), Some(&0.5));
+    assert!(volume.downcast_ref::<f64>().is_none());
+
+    assert_eq!(SettingsField::DisplayName.name(), "display_name");
+    assert_eq!(SettingsField::from_name("seed"), Some(SettingsField::Seed));
+    assert_eq!(SettingsField::from_name("cache"), None);
+}
+
+#[test]
+fn setting_fields_marks_them() {
+    let mut settings = Settings::default();
+    settings
+        .set_field(SettingsField::Volume, Box::new(0.0f32))
+        .unwrap();
+    assert!(!settings.changed_any());
+
+    settings
+        .set_field(SettingsField::Volume, Box::new(1.0f32))
+        .unwrap();
+    assert_eq!(settings.volume, 1.0);
+    assert!(settings.changed(Settings::volume()));
+
+    settings.reset();
+    settings
+        .set_field_by_name("display_name", Box::new("Mixer".to_string()))
+        .unwrap();
+    settings.set_field_by_name("seed", Box::new(0u64)).unwrap();
+    assert_eq!(settings.display_name, "Mixer");
+    assert!(settings.changed(Settings::display_name() | Settings::seed()));
+    assert!(!settings.changed_volume());
+}
+
+#[test]
+fn errors_are_returned() {
+    let mut settings = Settings::default();
+    let err = settings
+        .set_field(SettingsField::Volume, Box::new(1.0f64))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        SetFieldError::WrongType {
+            field: "volume",
+            expected: "f32",
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "the value of field `volume` needs to be a `f32`"
+    );
+
+    let err = settings
+        .set_field_by_name("cache", Box::new(Vec::<u8>::new()))
+        .unwrap_err();
+    assert_eq!(err, SetFieldError::UnknownField(UnknownField::new("cache")));
+    assert_eq!(err.to_string(), "unknown field `cache`");
+    assert!(!settings.changed_any());
+}
+
+#[test]
+fn generic_fields() {
+    let mut generic = Generic::<u8>::default();
+    generic
+        .set_field(GenericField::Value, Box::new(3u8))
+        .unwrap();
+    assert_eq!(
+        generic.get_field(GenericField::Value).downcast_ref(),
+        Some(&3u8)
+    );
+    assert!(generic.changed_value());
+}
@@ -0,0 +1,68 @@
+#[tracker::track(previous, versioned)]
+#[derive(Default)]
+struct Mixer {
+    name: String,
+    #[tracker::elements]
+    levels: [f32; 8],
+    #[tracker(elements, no_eq)]
+    mutes: [bool; 2],
+    master: f32,
+}
+
+#[tracker::track(typed_mask)]
+struct Wide {
+    #[tracker::elements]
+    bits: [u8; 127],
+    last: u8,
+}
+
+#[test]
+fn elements_have_their_own_bits() {
+    assert_eq!(Mixer::name(), 1);
+    assert_eq!(Mixer::levels(), 0b1_1111_1110);
+    assert_eq!(Mixer::levels_all(), Mixer::levels());
+    assert_eq!(Mixer::mutes(), 0b110_0000_0000);
+    assert_eq!(Mixer::master(), 1 << 11);
+    assert_eq!(Mixer::track_all(), 0xfff);
+    assert_eq!(
+        <Mixer as tracker::Track>::field_mask("levels"),
+        Some(Mixer::levels())
+    );
+
+    let mut mixer = Mixer::default();
+    let version = mixer.version();
+    mixer.set_levels_elem(3, 0.0);
+    assert!(!mixer.changed_levels());
+    mixer.set_levels_elem(3, 0.5);
+    mixer.set_mutes_elem(1, false);
+    assert_eq!(mixer.tracker, 1 << 4 | 1 << 10);
+    assert!(mixer.changed_levels());
+    assert!(mixer.changed_levels_elem(3));
+    assert!(!mixer.changed_levels_elem(2));
+    assert!(mixer.changed_mutes_elem(1));
+    assert_eq!(mixer.changed_since(version), 1 << 4 | 1 << 10);
+    assert_eq!(mixer.previous_levels(), Some(&[0.0; 8]));
+
+    mixer.reset();
+    mixer.set_levels([1.0; 8]);
+    assert_eq!(mixer.tracker, Mixer::levels());
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds")]
+fn element_checks_panic_out_of_bounds() {
+    let _ = Mixer::default().changed_levels_elem(8);
+}
+
+#[test]
+fn elements_count_toward_tracker_width() {
+    let mut wide = Wide {
+        bits: [0; 127],
+        last: 0,
+        tracker: WideMask::default(),
+    };
+    assert_eq!(Wide::last(), WideMask::from_bits(1 << 127));
+    wide.set_bits_elem(126, 1);
+    assert_eq!(wide.tracker.bits(), 1 << 126);
+    let _: u128 = wide.tracker.bits();
+}
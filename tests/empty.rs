@@ -0,0 +1,59 @@
+#[tracker::track]
+struct Unit;
+
+#[tracker::track(versioned)]
+#[derive(Default)]
+struct Untracked {
+    #[do_not_track]
+    value: u8,
+}
+
+#[tracker::track]
+#[derive(Default)]
+struct Full {
+    a: u8,
+    b: u8,
+    c: u8,
+    d: u8,
+    e: u8,
+    f: u8,
+    g: u8,
+    h: u8,
+}
+
+#[test]
+fn unit_struct() {
+    let mut unit = Unit { tracker: 0 };
+    assert_eq!(Unit::track_all(), 0);
+    unit.mark_all_changed();
+    assert!(!unit.changed_any());
+    unit.reset();
+}
+
+#[test]
+fn no_tracked_fields() {
+    let mut u = Untracked::default();
+    assert_eq!(Untracked::track_all(), 0);
+    u.mark_all_changed();
+    assert!(!u.changed_any());
+    assert!(!u.changed(Untracked::track_all()));
+    assert_eq!(u.version(), 0);
+    assert_eq!(
+        u.diff(&Untracked {
+            value: 1,
+            ..Default::default()
+        }),
+        0
+    );
+    u.value = 1;
+    u.reset();
+}
+
+#[test]
+fn all_bits_used() {
+    let mut f = Full::default();
+    assert_eq!(Full::track_all(), u8::MAX);
+    f.mark_all_changed();
+    assert!(f.changed(Full::h()));
+    f.reset();
+}
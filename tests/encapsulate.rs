@@ -0,0 +1,37 @@
+mod model {
+    #[tracker::track(encapsulate, previous)]
+    pub struct Test {
+        pub title: String,
+        pub(crate) count: u32,
+        #[do_not_track]
+        pub untracked: u8,
+    }
+
+    pub mod child {
+        #[test]
+        fn accessors_in_child_module() {
+            let mut t = super::Test::new("hi".to_owned(), 1, 2);
+            t.set_count(2);
+            assert!(t.changed(super::Test::count()));
+            assert_eq!(t.get_title(), "hi");
+        }
+    }
+}
+
+use model::Test;
+
+#[test]
+fn constructor() {
+    let mut t = Test::new("hi".to_owned(), 1, 2);
+    assert!(!t.changed_any());
+    assert_eq!(t.get_title(), "hi");
+    assert_eq!(*t.get_count(), 1);
+    assert_eq!(t.untracked, 2);
+
+    t.set_title("changed".to_owned());
+    t.update_count(|count| *count += 1);
+    assert!(t.changed(Test::title() | Test::count()));
+    assert_eq!(t.previous_title().map(String::as_str), Some("hi"));
+    t.untracked = 3;
+    t.reset();
+}
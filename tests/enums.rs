@@ -0,0 +1,61 @@
+#[tracker::track]
+#[derive(Debug, Default, PartialEq)]
+enum Page {
+    #[default]
+    Overview,
+    Details {
+        id: u32,
+    },
+}
+
+#[tracker::track]
+enum Generic<T> {
+    Value(T),
+    Empty,
+}
+
+#[test]
+fn set() {
+    let mut page = PageTracker::default();
+    assert!(!page.changed());
+
+    page.set(Page::Overview);
+    assert!(!page.changed());
+
+    page.set(Page::Details { id: 1 });
+    assert!(page.changed());
+    assert!(page.variant_changed());
+    assert_eq!(*page, Page::Details { id: 1 });
+
+    page.reset();
+    page.set(Page::Details { id: 2 });
+    assert!(page.changed());
+    assert!(!page.variant_changed());
+}
+
+#[test]
+fn update() {
+    let mut page = PageTracker::new(Page::Details { id: 1 });
+    page.update(|page| {
+        if let Page::Details { id } = page {
+            *id += 1;
+        }
+    });
+    assert!(page.changed() && !page.variant_changed());
+    assert_eq!(page.get(), &Page::Details { id: 2 });
+
+    page.reset();
+    *page.get_mut() = Page::Overview;
+    assert!(page.changed() && page.variant_changed());
+    assert_eq!(page.into_inner(), Page::Overview);
+}
+
+#[test]
+fn generic_without_eq() {
+    let mut value = GenericTracker::from(Generic::Value(String::new()));
+    let old = value.replace(Generic::Empty);
+    assert!(matches!(old, Generic::Value(_)));
+    assert!(value.variant_changed());
+    assert!(matches!(*value, Generic::Empty));
+    value.reset();
+}
@@ -0,0 +1,47 @@
+#[tracker::track(sql, serde)]
+#[derive(Default, serde::Serialize)]
+struct Test {
+    /// Doc comment before a tracker attribute.
+    #[tracker::no_eq]
+    /// Doc comment between tracker attributes.
+    #[serde(skip)]
+    #[tracker::column = "column_a"]
+    a: u8,
+    #[serde(rename = "bee")]
+    /// Doc comment before `do_not_track`.
+    #[do_not_track]
+    /// Doc comment after `do_not_track`.
+    b: u8,
+    #[no_eq]
+    #[tracker::column = "column_c"]
+    #[serde(rename = "cee")]
+    /// Doc comment after all tracker attributes.
+    c: u8,
+    #[serde(rename = "dee")]
+    #[tracker::do_not_track]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    d: Option<u8>,
+}
+
+#[test]
+fn tracker_attributes_are_applied() {
+    let mut t = Test::default();
+    assert_eq!(Test::track_all(), Test::a() | Test::c());
+
+    t.set_a(0);
+    t.set_c(0);
+    assert!(t.changed_a());
+    assert!(t.changed_c());
+    assert_eq!(t.changed_columns(), vec!["column_a", "column_c"]);
+    t.reset();
+}
+
+#[test]
+fn other_attributes_are_kept() {
+    let t = Test {
+        b: 1,
+        d: None,
+        ..Default::default()
+    };
+    assert_eq!(serde_json::to_string(&t).unwrap(), r#"{"bee":1,"cee":0}"#);
+}
@@ -0,0 +1,49 @@
+#[tracker::track]
+#[derive(Default)]
+struct Test {
+    display_name: String,
+    count: u32,
+    kind: u8,
+    #[do_not_track]
+    untracked: u8,
+}
+
+#[test]
+fn changed_paths() {
+    let mut t = Test::default();
+    assert!(t.field_mask_paths().is_empty());
+
+    t.set_count(1);
+    t.set_kind(2);
+    assert_eq!(t.field_mask_paths(), vec!["count", "kind"]);
+}
+
+#[test]
+fn mask_from_paths() {
+    assert_eq!(Test::mask_from_paths::<&str>(&[]), Ok(0));
+    assert_eq!(
+        Test::mask_from_paths(&["display_name", "kind"]),
+        Ok(Test::display_name() | Test::kind())
+    );
+
+    let paths = vec![
+        "count".to_owned(),
+        "untracked".to_owned(),
+        "missing".to_owned(),
+    ];
+    let err = Test::mask_from_paths(&paths).unwrap_err();
+    assert_eq!(err.name(), "untracked");
+    assert_eq!(err.to_string(), "unknown field `untracked`");
+}
+
+#[test]
+fn round_trip() {
+    let mut t = Test::default();
+    t.set_display_name("a".to_owned());
+    t.set_count(2);
+
+    let mask = Test::mask_from_paths(&t.field_mask_paths()).unwrap();
+    assert_eq!(mask, t.tracker);
+    t.reset();
+    assert_eq!(t.untracked, 0);
+}
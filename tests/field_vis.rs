@@ -0,0 +1,36 @@
+mod inner {
+    #[tracker::track]
+    #[derive(Default)]
+    pub struct Test {
+        #[tracker::vis(get = "pub", set = "pub(crate)", update = pub(self), changed_field = "pub")]
+        x: u8,
+        #[tracker::vis(get_mut = "")]
+        pub y: u8,
+    }
+
+    impl Test {
+        pub fn increment(&mut self) {
+            self.update_x(|x| *x += 1);
+            *self.get_mut_y() += 1;
+        }
+    }
+}
+
+#[test]
+fn methods_use_their_visibility() {
+    let mut t = inner::Test::default();
+    t.increment();
+    assert_eq!(*t.get_x(), 1);
+    assert!(t.changed_x() && t.changed_y());
+    t.reset();
+
+    t.set_x(1);
+    assert!(!t.changed_x());
+    t.set_y(2);
+    assert_eq!(*t.get_y(), 2);
+    t.update_y(|y| *y += 1);
+
+    t.update_all(|proxy| proxy.set_x(2));
+    assert!(t.changed_x());
+    t.reset();
+}
@@ -0,0 +1,81 @@
+#[tracker::track(previous)]
+#[derive(Default)]
+struct Test {
+    x: u8,
+    y: String,
+    z: u8,
+}
+
+#[tracker::track]
+#[derive(Default)]
+struct Generic<T: PartialEq> {
+    value: T,
+    other: T,
+}
+
+fn update(t: &mut Test) {
+    let (z, x) = tracker::fields_mut!(*t => z, x);
+    *z = *x + 1;
+}
+
+#[test]
+fn split_borrows() {
+    let mut t = Test::default();
+    let (x, y) = tracker::fields_mut!(t => x, y);
+    *x = 1;
+    y.push_str("changed");
+
+    assert!(t.changed(Test::x()));
+    assert!(t.changed(Test::y()));
+    assert!(!t.changed(Test::z()));
+    assert_eq!(t.x, 1);
+    assert_eq!(t.y, "changed");
+    assert_eq!(t.previous_y().map(String::as_str), Some(""));
+}
+
+#[test]
+fn through_reference() {
+    let mut t = Test::default();
+    update(&mut t);
+    assert_eq!(t.z, 1);
+    assert!(t.changed(Test::z() | Test::x()));
+    t.reset();
+    assert!(!t.changed_any());
+}
+
+#[test]
+fn generics() {
+    let mut g = Generic::<u8>::default();
+    let (value, other) = tracker::fields_mut!(g => value, other,);
+    std::mem::swap(value, other);
+    assert!(g.changed(Generic::<u8>::value() | Generic::<u8>::other()));
+    g.reset();
+}
+
+#[tracker::track(typed_mask)]
+#[derive(Default)]
+struct Typed {
+    x: u8,
+    y: u8,
+}
+
+#[tracker::track(bitflags)]
+#[derive(Default)]
+struct Flags {
+    x: u8,
+    y: u8,
+    z: u8,
+}
+
+#[test]
+fn typed_masks() {
+    let mut typed = Typed::default();
+    let (x, y) = tracker::fields_mut!(typed => x, y);
+    std::mem::swap(x, y);
+    assert_eq!(typed.tracker, Typed::x() | Typed::y());
+
+    let mut flags = Flags::default();
+    let (z,) = tracker::fields_mut!(flags => z);
+    *z = 1;
+    assert_eq!(flags.tracker, Flags::z());
+}
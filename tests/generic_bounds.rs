@@ -0,0 +1,47 @@
+// Doesn't implement `PartialEq`.
+#[derive(Clone, Default)]
+struct NotEq(u8);
+
+#[tracker::track]
+#[derive(Default)]
+struct Generic<T> {
+    value: T,
+    count: u8,
+}
+
+#[test]
+fn setters_without_eq() {
+    let mut g = Generic::<NotEq>::default();
+    g.get_mut_value().0 = 1;
+    g.update_value(|value| value.0 += 1);
+    assert_eq!(g.get_value().0, 2);
+    assert!(g.changed_value());
+
+    g.set_count(1);
+    g.update_all(|proxy| proxy.get_mut_value().0 = 3);
+    assert!(g.changed(Generic::<NotEq>::count()));
+    let _delta = g.to_delta();
+    g.reset();
+}
+
+#[test]
+fn setters_with_eq() {
+    let mut g = Generic::<u8>::default();
+    g.set_value(0);
+    assert!(!g.changed_value());
+    g.update_all(|proxy| proxy.set_value(1));
+    assert!(g.changed_value());
+
+    let other = Generic::<u8>::default();
+    assert_eq!(g.diff(&other), Generic::<u8>::value());
+
+    let mut fresh = Generic::<u8>::default();
+    fresh.apply_delta(g.to_delta());
+    let mask = fresh.apply_update(GenericUpdate {
+        count: Some(1),
+        ..Default::default()
+    });
+    assert_eq!(mask, Generic::<u8>::count());
+    fresh.reset();
+    assert_eq!(fresh.value, 1);
+}
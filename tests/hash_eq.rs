@@ -0,0 +1,118 @@
+use std::cell::Cell;
+use std::hash::{Hash, Hasher};
+
+thread_local! {
+    static COMPARISONS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// A blob that counts how often it's compared.
+#[derive(Clone, Default, Debug)]
+struct Blob(Vec<u8>);
+
+impl PartialEq for Blob {
+    fn eq(&self, other: &Self) -> bool {
+        COMPARISONS.with(|count| count.set(count.get() + 1));
+        self.0 == other.0
+    }
+}
+
+impl Hash for Blob {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+/// A value whose hashes always collide.
+#[derive(Clone, Default, PartialEq)]
+struct Colliding(u8);
+
+impl Hash for Colliding {
+    fn hash<H: Hasher>(&self, _: &mut H) {}
+}
+
+fn comparisons() -> usize {
+    COMPARISONS.with(Cell::get)
+}
+
+#[tracker::track(previous)]
+#[derive(Default)]
+struct Document {
+    #[tracker::hash_eq]
+    content: Blob,
+    #[tracker(hash_eq)]
+    colliding: Colliding,
+    #[tracker::hash_eq = "hash_only"]
+    trusted: Colliding,
+}
+
+#[test]
+fn differing_hashes_skip_the_comparison() {
+    let mut doc = Document::default();
+    let before = comparisons();
+    doc.set_content(Blob(vec![1; 1024]));
+    doc.set_content(Blob(vec![2; 1024]));
+    assert_eq!(comparisons(), before);
+    assert!(doc.changed_content());
+    assert_eq!(doc.previous_content(), Some(&Blob::default()));
+
+    // Equal hashes fall back to the full comparison.
+    doc.reset();
+    let before = comparisons();
+    doc.set_content(Blob(vec![2; 1024]));
+    assert_eq!(comparisons(), before + 1);
+    assert!(!doc.changed_content());
+}
+
+#[test]
+fn collisions_are_compared() {
+    let mut doc = Document::default();
+    doc.set_colliding(Colliding(0));
+    assert!(!doc.changed_colliding());
+    doc.set_colliding(Colliding(1));
+    assert!(doc.changed_colliding());
+
+    // Without the comparison, colliding values count as equal.
+    doc.set_trusted(Colliding(1));
+    assert!(!doc.changed_trusted());
+    assert_eq!(doc.trusted.0, 1);
+}
+
+#[test]
+fn other_methods_forget_the_hash() {
+    let mut doc = Document::default();
+    doc.set_content(Blob(vec![1]));
+    doc.get_mut_content().0.push(2);
+    doc.reset();
+    doc.set_content(Blob(vec![1]));
+    assert!(doc.changed_content());
+
+    doc.reset();
+    doc.update_content(|content| content.0.clear());
+    doc.reset();
+    doc.set_content(Blob(Vec::new()));
+    assert!(!doc.changed_content());
+}
+
+#[tracker::track(previous, encapsulate)]
+struct Reverted {
+    #[tracker::hash_eq = "hash_only"]
+    #[tracker::default = "a".to_string()]
+    s: String,
+}
+
+#[test]
+fn revert_and_with_forget_the_hash() {
+    let mut reverted = Reverted::new();
+    reverted.set_s("b".to_string());
+    reverted.revert_s();
+    assert_eq!(reverted.s, "a");
+    assert!(!reverted.changed_s());
+    reverted.set_s("b".to_string());
+    assert!(reverted.changed_s());
+
+    let mut replaced = Reverted::new();
+    replaced.set_s("a".to_string());
+    let mut replaced = replaced.with_s("c".to_string());
+    replaced.set_s("a".to_string());
+    assert!(replaced.changed_s());
+}
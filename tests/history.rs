@@ -0,0 +1,80 @@
+#[tracker::track(history = 3)]
+#[derive(Default)]
+struct Model {
+    x: u8,
+    y: u8,
+    name: String,
+}
+
+#[tracker::track(history = 2, serde)]
+#[derive(Default, Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Stored {
+    x: u8,
+}
+
+fn masks(model: &Model) -> Vec<u8> {
+    model.change_history().map(|event| event.mask()).collect()
+}
+
+#[test]
+fn history_records_newly_changed_bits() {
+    let mut model = Model::default();
+    assert_eq!(model.change_history().count(), 0);
+
+    model.set_x(1);
+    // Marked fields and unchanged values aren't recorded again.
+    model.set_x(2);
+    model.set_y(0);
+    model.update_name(|name| name.push('a'));
+    assert_eq!(masks(&model), [Model::x(), Model::name()]);
+
+    model.reset();
+    model.set_y(1);
+    assert_eq!(masks(&model), [Model::x(), Model::name(), Model::y()]);
+}
+
+#[test]
+fn history_keeps_the_last_changes() {
+    let mut model = Model::default();
+    model.set_x(1);
+    model.reset();
+    model.set_y(1);
+    model.reset();
+    model.get_mut_name().push('a');
+    model.reset();
+    model.mark_all_changed();
+    assert_eq!(
+        masks(&model),
+        [Model::y(), Model::name(), Model::track_all()]
+    );
+
+    model.clear_history();
+    assert_eq!(model.change_history().count(), 0);
+    assert!(model.changed_any());
+}
+
+#[test]
+fn history_records_the_caller() {
+    let mut model = Model::default();
+    let line = line!() + 1;
+    model.set_x(1);
+    let event = model.change_history().next().unwrap();
+    assert_eq!(event.location().file(), file!());
+    assert_eq!(event.location().line(), line);
+}
+
+#[test]
+fn history_is_not_part_of_the_state() {
+    let mut stored = Stored::default();
+    stored.set_x(1);
+    assert_eq!(stored.change_history().count(), 1);
+
+    let clone = stored.clone();
+    assert_eq!(clone.change_history().count(), 0);
+    assert_eq!(clone, stored);
+    assert_eq!(serde_json::to_string(&stored).unwrap(), r#"{"x":1}"#);
+    assert!(format!("{stored:?}").contains("History { len: 1, capacity: 2 }"));
+
+    let stored: Stored = serde_json::from_str(r#"{"x":2}"#).unwrap();
+    assert_eq!(stored.change_history().count(), 0);
+}
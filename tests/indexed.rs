@@ -0,0 +1,48 @@
+use std::panic::{self, AssertUnwindSafe};
+
+#[tracker::track(previous)]
+#[derive(Default)]
+struct Grid {
+    #[tracker::indexed]
+    cells: Vec<u8>,
+    #[tracker(indexed, no_eq)]
+    row: [u8; 3],
+    #[tracker::indexed]
+    #[tracker::no_set]
+    fixed: [String; 2],
+}
+
+#[test]
+fn setters_compare_elements() {
+    let mut grid = Grid {
+        cells: vec![0, 1, 2],
+        ..Default::default()
+    };
+    assert!(!grid.set_cells_at(1, 1));
+    assert!(!grid.changed_cells());
+    assert!(grid.set_cells_at(1, 5));
+    assert!(!grid.set_cells_at(2, 2));
+    assert_eq!(grid.cells, [0, 5, 2]);
+    assert!(grid.changed_cells());
+    assert_eq!(grid.previous_cells(), Some(&vec![0, 1, 2]));
+
+    assert!(grid.set_row_at(0, 0));
+    assert!(grid.changed_row());
+    assert_eq!(grid.get_row_at(0), Some(&0));
+    assert_eq!(grid.get_cells_at(1), Some(&5));
+    assert_eq!(grid.get_cells_at(3), None);
+    assert_eq!(grid.get_fixed_at(1).map(String::as_str), Some(""));
+}
+
+#[test]
+fn out_of_bounds_setters_panic_without_recording() {
+    let mut grid = Grid::default();
+    for result in [
+        panic::catch_unwind(AssertUnwindSafe(|| grid.set_cells_at(0, 1))),
+        panic::catch_unwind(AssertUnwindSafe(|| grid.set_row_at(3, 1))),
+    ] {
+        assert!(result.is_err());
+    }
+    assert!(!grid.changed_any());
+    assert_eq!(grid.previous_row(), None);
+}
@@ -0,0 +1,97 @@
+#![cfg(feature = "serde_json")]
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tracker::JsonPatchError;
+
+#[tracker::track(json_patch)]
+#[derive(Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Test {
+    count: u32,
+    display_name: String,
+    #[serde(rename = "a/b")]
+    renamed: u8,
+    #[do_not_track]
+    #[serde(skip)]
+    untracked: u8,
+    #[serde(skip)]
+    #[no_eq]
+    no_eq: Vec<u8>,
+}
+
+#[test]
+fn export_changed_fields() {
+    let mut t = Test::default();
+    assert_eq!(t.to_json_patch().unwrap(), json!([]));
+
+    t.set_count(3);
+    t.set_display_name("Alice".to_owned());
+    t.set_renamed(1);
+    assert_eq!(
+        t.to_json_patch().unwrap(),
+        json!([
+            { "op": "replace", "path": "/count", "value": 3 },
+            { "op": "replace", "path": "/displayName", "value": "Alice" },
+            { "op": "replace", "path": "/a~1b", "value": 1 },
+        ])
+    );
+}
+
+#[test]
+fn apply_patch() {
+    let mut source = Test::default();
+    source.set_display_name("Bob".to_owned());
+    source.set_no_eq(vec![1]);
+
+    let mut t = Test::default();
+    let mask = t
+        .apply_json_patch(&source.to_json_patch().unwrap())
+        .unwrap();
+    assert_eq!(mask, Test::display_name() | Test::no_eq());
+    assert_eq!(t.display_name, "Bob");
+    assert_eq!(t.no_eq, vec![1]);
+    assert!(!t.changed_count());
+
+    // Equal values don't mark fields as changed.
+    t.reset();
+    let patch = json!([{ "op": "add", "path": "/count", "value": 0 }]);
+    assert_eq!(t.apply_json_patch(&patch).unwrap(), 0);
+    assert!(!t.changed_any());
+}
+
+#[test]
+fn reject_unknown_paths() {
+    let mut t = Test::default();
+    let patch = json!([
+        { "op": "replace", "path": "/count", "value": 1 },
+        { "op": "replace", "path": "/untracked", "value": 1 },
+        { "op": "replace", "path": "/missing", "value": 1 },
+    ]);
+
+    match t.apply_json_patch(&patch) {
+        Err(JsonPatchError::UnknownPaths(paths)) => {
+            assert_eq!(paths, vec!["/untracked", "/missing"])
+        }
+        other => panic!("unexpected result {other:?}"),
+    }
+    assert_eq!(t.count, 0);
+    assert_eq!(t.untracked, 0);
+}
+
+#[test]
+fn invalid_values_modify_nothing() {
+    let mut t = Test::default();
+    let patch = json!([
+        { "op": "replace", "path": "/count", "value": 1 },
+        { "op": "replace", "path": "/displayName", "value": 2 },
+    ]);
+
+    assert!(matches!(
+        t.apply_json_patch(&patch),
+        Err(JsonPatchError::Deserialize(_))
+    ));
+    assert_eq!(t.count, 0);
+    assert!(!t.changed_any());
+    t.reset();
+}
@@ -0,0 +1,57 @@
+// Both fields need the same bounds, for example for `Debug`, with different lifetimes.
+#[tracker::track(debug, debug_values)]
+struct Refs<'a, 'b: 'a> {
+    x: &'b u8,
+    y: &'a u8,
+}
+
+#[tracker::track(snapshot, previous)]
+struct Mixed<'a, 'b: 'a, T: Clone + 'a>
+where
+    T: PartialEq,
+{
+    value: &'a T,
+    name: &'b str,
+    owned: T,
+}
+
+#[test]
+fn bounded_lifetimes() {
+    let (a, b) = (1, 2);
+    let mut refs = Refs {
+        x: &a,
+        y: &a,
+        tracker: 0,
+    };
+    refs.set_y(&b);
+    assert!(refs.changed_y());
+    assert_eq!(**refs.get_y(), 2);
+    refs.set_x(&a);
+    assert!(!refs.changed_x());
+    assert_eq!(refs.changed_values().count(), 1);
+    assert_eq!(format!("{refs:?}"), "Refs { x: 1, y: 2 (changed) }");
+    assert_eq!(refs.to_delta().y, Some(&2));
+}
+
+#[test]
+fn bounded_lifetimes_with_type_parameters() {
+    let value = 1u8;
+    let other = 2u8;
+    let mut mixed = Mixed {
+        value: &value,
+        name: "first",
+        owned: 3u8,
+        tracker: 0,
+        tracker_previous: Default::default(),
+    };
+    let snapshot = mixed.snapshot();
+    mixed.set_value(&other);
+    mixed.set_name("second");
+    assert_eq!(
+        mixed.changed_since(&snapshot),
+        Mixed::<u8>::value() | Mixed::<u8>::name()
+    );
+    assert_eq!(mixed.previous_name(), Some(&"first"));
+    mixed.set_owned(3);
+    assert!(!mixed.changed_owned());
+}
@@ -0,0 +1,73 @@
+use std::cell::RefCell;
+use std::panic::{self, AssertUnwindSafe};
+use std::rc::Rc;
+
+#[tracker::track(listeners, serde)]
+#[derive(Default, Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Model {
+    x: u8,
+    name: String,
+}
+
+fn recorder(model: &mut Model) -> (Rc<RefCell<Vec<u8>>>, tracker::ListenerId) {
+    let calls = Rc::new(RefCell::new(Vec::new()));
+    let recorded = Rc::clone(&calls);
+    let id = model.add_change_listener(move |mask| recorded.borrow_mut().push(mask));
+    (calls, id)
+}
+
+#[test]
+fn listeners_get_newly_changed_bits() {
+    let mut model = Model::default();
+    let (first, first_id) = recorder(&mut model);
+    let (second, _) = recorder(&mut model);
+
+    model.set_x(0);
+    model.set_x(1);
+    model.set_x(2);
+    model.get_mut_name().push('a');
+    assert_eq!(*first.borrow(), [Model::x(), Model::name()]);
+
+    model.remove_change_listener(first_id);
+    model.reset();
+    model.mark_all_changed();
+    assert_eq!(first.borrow().len(), 2);
+    assert_eq!(
+        *second.borrow(),
+        [Model::x(), Model::name(), Model::x() | Model::name()]
+    );
+}
+
+#[test]
+fn listeners_are_not_state() {
+    let mut model = Model::default();
+    let (calls, _) = recorder(&mut model);
+    let mut clone = model.clone();
+    assert_eq!(clone, model);
+    assert!(format!("{model:?}").contains("Listeners { len: 1 }"));
+    assert_eq!(
+        serde_json::to_string(&model).unwrap(),
+        r#"{"x":0,"name":""}"#
+    );
+
+    clone.set_x(1);
+    assert!(calls.borrow().is_empty());
+}
+
+#[test]
+fn panicking_listener_keeps_struct_consistent() {
+    let mut model = Model::default();
+    model.add_change_listener(|_| panic!("listener"));
+    let (calls, _) = recorder(&mut model);
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| model.set_x(1)));
+    assert!(result.is_err());
+    assert_eq!(model.x, 1);
+    assert!(model.changed_x());
+    assert_eq!(*calls.borrow(), [Model::x()]);
+
+    model.reset();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| model.set_x(2)));
+    assert!(result.is_err());
+    assert_eq!(*calls.borrow(), [Model::x(), Model::x()]);
+}
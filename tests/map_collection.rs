@@ -0,0 +1,89 @@
+use std::collections::{BTreeMap, HashMap};
+
+#[tracker::track]
+#[derive(Default)]
+struct Scores {
+    #[tracker::map_collection]
+    points: HashMap<String, u32>,
+    #[tracker(map_collection, no_eq)]
+    ranks: BTreeMap<u32, String>,
+}
+
+#[tracker::track(previous)]
+#[derive(Default)]
+struct Saved {
+    #[tracker::map_collection]
+    points: BTreeMap<u8, u8>,
+}
+
+fn scores() -> Scores {
+    let mut scores = Scores::default();
+    scores.points.insert("a".to_string(), 1);
+    scores
+}
+
+#[test]
+fn occupied_entry_without_write() {
+    let mut scores = scores();
+    let value = scores.entry_points("a".to_string()).or_insert(0);
+    assert_eq!(*value, 1);
+    let _ = scores
+        .entry_points("b".to_string())
+        .and_modify(|value| *value += 1);
+    assert_eq!(scores.entry_points("a".to_string()).get(), Some(&1));
+    assert!(!scores.changed_points());
+    assert!(!scores.points.contains_key("b"));
+}
+
+#[test]
+fn occupied_entry_with_write() {
+    let mut scores = scores();
+    *scores.entry_points("a".to_string()).or_insert(0) += 1;
+    assert!(scores.changed_points());
+    assert_eq!(scores.points["a"], 2);
+
+    scores.reset();
+    let _ = scores
+        .entry_points("a".to_string())
+        .and_modify(|value| *value += 1);
+    assert!(scores.changed_points());
+    assert_eq!(scores.points["a"], 3);
+}
+
+#[test]
+fn vacant_entry_insert() {
+    let mut scores = scores();
+    let value = scores.entry_points("b".to_string()).or_default();
+    assert_eq!(*value, 0);
+    assert!(scores.changed_points());
+    assert!(!scores.changed_ranks());
+
+    scores.entry_ranks(1).or_insert_with(|| "first".to_string());
+    assert!(scores.changed_ranks());
+    assert_eq!(scores.ranks[&1], "first");
+}
+
+#[test]
+fn insert_and_remove() {
+    let mut scores = scores();
+    assert_eq!(scores.insert_points("a".to_string(), 1), Some(1));
+    assert!(!scores.changed_points());
+    assert_eq!(scores.remove_points(&"b".to_string()), None);
+    assert!(!scores.clear_ranks());
+    assert!(!scores.changed_any());
+
+    assert_eq!(scores.insert_ranks(1, "first".to_string()), None);
+    assert!(scores.changed_ranks());
+    assert_eq!(scores.remove_points(&"a".to_string()), Some(1));
+    assert!(scores.changed_points());
+}
+
+#[test]
+fn changes_are_recorded_without_entries() {
+    let mut saved = Saved::default();
+    saved.insert_points(1, 1);
+    assert_eq!(saved.previous_points(), Some(&BTreeMap::new()));
+    saved.reset();
+    assert!(saved.clear_points());
+    assert_eq!(saved.previous_points(), Some(&BTreeMap::from([(1, 1)])));
+}
@@ -0,0 +1,54 @@
+#[tracker::track(merge, clone_resets)]
+#[derive(Default, Debug)]
+struct Model {
+    name: String,
+    count: u32,
+    enabled: bool,
+    #[no_eq]
+    notes: Vec<String>,
+}
+
+#[test]
+fn changes_of_both_copies_are_merged() {
+    let base = Model::default();
+    let mut mine = base.clone();
+    let mut theirs = base.clone();
+
+    mine.set_name("mine".to_string());
+    theirs.set_count(2);
+    theirs.set_enabled(true);
+
+    let outcome = mine.merge_from(&theirs);
+    assert!(!outcome.has_conflicts());
+    assert_eq!(outcome.merged(), Model::count() | Model::enabled());
+    assert_eq!(mine.name, "mine");
+    assert_eq!(mine.count, 2);
+    assert!(mine.enabled);
+    assert!(mine.changed(Model::name() | Model::count() | Model::enabled()));
+    assert!(!mine.changed_notes());
+}
+
+#[test]
+fn mine_wins_conflicts() {
+    let base = Model::default();
+    let mut mine = base.clone();
+    let mut theirs = base.clone();
+
+    mine.set_name("mine".to_string());
+    theirs.set_name("theirs".to_string());
+    // Equal changes aren't conflicts.
+    mine.set_count(1);
+    theirs.set_count(1);
+    mine.get_mut_notes().push("a".to_string());
+    theirs.get_mut_notes().push("a".to_string());
+
+    let outcome = mine.merge_from(&theirs);
+    assert_eq!(outcome.conflicts(), Model::name() | Model::notes());
+    assert_eq!(outcome.merged(), 0);
+    assert!(outcome.has_conflicts());
+    assert_eq!(mine.name, "mine");
+
+    // The caller resolves the conflict in favor of theirs.
+    mine.set_name(theirs.name.clone());
+    assert_eq!(mine.name, "theirs");
+}
@@ -0,0 +1,112 @@
+#[tracker::track(debug)]
+#[derive(Default)]
+struct Header {
+    title: String,
+    visible: bool,
+}
+
+#[tracker::track(debug)]
+#[derive(Default)]
+struct App {
+    #[tracker::nested]
+    #[no_eq]
+    header: Header,
+    #[tracker(nested)]
+    footer: Header,
+    count: u8,
+}
+
+#[test]
+fn child_changes_are_reported() {
+    let mut app = App::default();
+    assert!(!app.changed_any());
+
+    app.get_mut_header().set_visible(true);
+    assert!(app.changed_header());
+    assert!(!app.changed_footer());
+    assert!(app.changed(App::header() | App::count()));
+    assert!(!app.changed(App::footer() | App::count()));
+    assert!(app.changed_any());
+    assert!(format!("{app:?}").contains("(changed)"));
+
+    // Only the parent is reset.
+    app.reset();
+    assert!(app.changed_header());
+    app.get_mut_header().reset();
+    assert!(!app.changed_any());
+}
+
+#[test]
+fn references_dont_mark_the_parent() {
+    let mut app = App::default();
+    assert!(!app.get_mut_header().changed_any());
+    app.update_footer(|footer| {
+        let _ = footer.get_title();
+    });
+    app.update_all(|proxy| {
+        let _ = proxy.get_mut_header();
+    });
+    assert!(!app.changed_any());
+
+    app.set_count(1);
+    app.update_all(|proxy| proxy.get_mut_footer().set_title("footer".to_owned()));
+    assert!(app.changed_footer() && app.changed_count());
+    assert_eq!(app.get_footer().get_title(), "footer");
+}
+
+#[test]
+fn setters_mark_the_parent() {
+    let mut app = App::default();
+    app.set_header(Header::default());
+    assert!(app.changed_header());
+    assert!(!app.get_header().changed_any());
+}
+
+#[tracker::track]
+#[derive(Default)]
+struct Row {
+    value: u8,
+}
+
+#[tracker::track]
+#[derive(Default)]
+struct Table {
+    #[tracker::nested]
+    #[no_eq]
+    detail: Option<Header>,
+    #[tracker::nested]
+    #[no_eq]
+    rows: Vec<Row>,
+}
+
+#[test]
+fn wrapped_children_are_checked() {
+    let mut table = Table {
+        detail: Some(Header::default()),
+        rows: (0..3).map(|_| Row::default()).collect(),
+        tracker: 0,
+    };
+    assert!(!table.changed_any());
+    assert_eq!(table.changed_rows_indices().count(), 0);
+
+    table.detail.as_mut().unwrap().set_visible(true);
+    assert!(table.changed_detail() && !table.changed_rows());
+    table.detail.as_mut().unwrap().reset();
+
+    table.rows[1].set_value(1);
+    table.rows[2].set_value(2);
+    assert!(table.changed_rows() && !table.changed_detail());
+    assert_eq!(table.changed_rows_indices().collect::<Vec<_>>(), vec![1, 2]);
+}
+
+#[test]
+fn wrapped_children_mark_references() {
+    let mut table = Table::default();
+    table.get_mut_rows().push(Row::default());
+    assert!(table.changed_rows());
+    assert_eq!(table.changed_rows_indices().count(), 0);
+    table.reset();
+
+    table.update_detail(|detail| *detail = Some(Header::default()));
+    assert!(table.changed_detail());
+}
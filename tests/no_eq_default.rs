@@ -0,0 +1,32 @@
+#[derive(Default)]
+struct NoPartialEq(u8);
+
+#[tracker::track(no_eq)]
+#[derive(Default)]
+struct Test {
+    a: NoPartialEq,
+    #[no_eq]
+    b: NoPartialEq,
+    #[tracker::eq]
+    c: u8,
+    #[tracker(eq)]
+    d: u8,
+}
+
+#[test]
+fn fields_are_not_compared_by_default() {
+    let mut t = Test::default();
+    t.set_a(NoPartialEq(0));
+    t.set_b(NoPartialEq(0));
+    assert_eq!(t.get_a().0 + t.get_b().0, 0);
+    assert!(t.changed_a() && t.changed_b());
+    t.reset();
+
+    t.set_c(0);
+    t.set_d(0);
+    assert!(!t.changed_any());
+    t.set_c(1);
+    assert!(t.changed_c());
+    assert_eq!(t.diff(&Test::default()), Test::c());
+    t.reset();
+}
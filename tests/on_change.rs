@@ -0,0 +1,86 @@
+use std::cell::RefCell;
+
+thread_local! {
+    static CHANGES: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) };
+}
+
+fn record(mask: u8) {
+    CHANGES.with(|changes| changes.borrow_mut().push(mask));
+}
+
+fn take_changes() -> Vec<u8> {
+    CHANGES.with(|changes| changes.take())
+}
+
+#[tracker::track(on_change = record, previous)]
+#[derive(Default)]
+struct Model {
+    x: u8,
+    #[tracker::no_eq]
+    y: u8,
+    name: String,
+}
+
+mod hooks {
+    pub fn redraw(mask: super::TypedMask) {
+        super::record(mask.bits());
+    }
+}
+
+#[tracker::track(on_change = "hooks::redraw", typed_mask, atomic)]
+#[derive(Default)]
+struct Typed {
+    a: u8,
+    b: u8,
+}
+
+#[test]
+fn fires_for_newly_changed_bits() {
+    let mut model = Model::default();
+    model.set_x(0);
+    assert!(take_changes().is_empty());
+
+    model.set_x(1);
+    model.set_x(2);
+    model.get_mut_name().push('a');
+    assert_eq!(take_changes(), [Model::x(), Model::name()]);
+
+    model.mark_all_changed();
+    assert_eq!(take_changes(), [Model::y()]);
+
+    model.reset();
+    model.set_y(0);
+    model.update_all(|proxy| {
+        proxy.set_x(2);
+        proxy.set_x(3);
+        proxy.get_mut_name().clear();
+    });
+    assert_eq!(take_changes(), [Model::y(), Model::x() | Model::name()]);
+}
+
+#[test]
+fn fires_for_generated_mutation_paths() {
+    let mut model = Model::default();
+    let mask = model.apply_update(ModelUpdate {
+        x: Some(1),
+        name: Some(String::new()),
+        ..Default::default()
+    });
+    assert_eq!(mask, Model::x());
+    assert_eq!(take_changes(), [Model::x()]);
+
+    model.revert_x();
+    model.apply_delta(ModelDelta {
+        x: Some(1),
+        ..Default::default()
+    });
+    assert_eq!(take_changes(), [Model::x()]);
+}
+
+#[test]
+fn fires_through_shared_references() {
+    let typed = Typed::default();
+    typed.mark_changed(Typed::a());
+    typed.mark_changed(Typed::a() | Typed::b());
+    assert_eq!(take_changes(), [Typed::a().bits(), Typed::b().bits()]);
+}
@@ -0,0 +1,38 @@
+use std::cell::RefCell;
+
+#[tracker::track]
+#[derive(Default)]
+struct Test {
+    x: u8,
+    y: u8,
+    title: String,
+}
+
+#[test]
+fn chaining() {
+    let mut t = Test::default();
+    t.set_y(1);
+
+    let calls = RefCell::new(Vec::new());
+    t.on_changed(Test::x(), |_| calls.borrow_mut().push("x"))
+        .on_changed(Test::y() | Test::title(), |t| {
+            calls.borrow_mut().push("y or title");
+            assert_eq!(t.y, 1);
+        })
+        .on_changed(Test::track_all(), |_| calls.borrow_mut().push("any"));
+    assert_eq!(*calls.borrow(), vec!["y or title", "any"]);
+}
+
+#[test]
+fn mutable_chaining() {
+    let mut t = Test::default();
+    t.set_x(2);
+
+    t.on_changed_mut(Test::x(), |t| t.set_title(format!("x = {}", t.x)))
+        .on_changed_mut(Test::title(), |t| t.set_y(3));
+    assert_eq!(t.title, "x = 2");
+    assert_eq!(t.y, 3);
+
+    t.reset();
+    t.on_changed_mut(Test::track_all(), |_| unreachable!());
+}
@@ -0,0 +1,107 @@
+use tracker::Track;
+
+#[tracker::track(ordered)]
+#[derive(Default, Clone, Debug, PartialEq)]
+struct List {
+    items: Vec<String>,
+    selection: usize,
+    filter: String,
+}
+
+#[tracker::track(ordered, typed_mask, serde_state)]
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+struct Typed {
+    a: u8,
+    b: u8,
+    c: u8,
+}
+
+fn order(list: &List) -> Vec<u8> {
+    list.changes_in_order().collect()
+}
+
+#[test]
+fn changes_are_in_first_changed_order() {
+    let mut list = List::default();
+    assert_eq!(list.changes_in_order().count(), 0);
+
+    list.set_selection(1);
+    list.get_mut_items().push("a".to_string());
+    list.set_selection(2);
+    assert_eq!(order(&list), [List::selection(), List::items()]);
+
+    list.reset();
+    assert_eq!(list.changes_in_order().count(), 0);
+    list.get_mut_items().clear();
+    list.set_filter("b".to_string());
+    list.set_selection(0);
+    assert_eq!(
+        order(&list),
+        [List::items(), List::filter(), List::selection()]
+    );
+}
+
+#[test]
+fn fields_marked_together_follow_their_bits() {
+    let mut list = List::default();
+    list.set_filter("a".to_string());
+    list.mark_all_changed();
+    assert_eq!(
+        order(&list),
+        [List::filter(), List::items(), List::selection()]
+    );
+}
+
+#[test]
+fn order_is_not_part_of_the_state() {
+    let mut list = List::default();
+    list.set_selection(1);
+    list.set_filter("a".to_string());
+    let mut other = List::default();
+    other.set_filter("a".to_string());
+    other.set_selection(1);
+    assert_eq!(list, other);
+    assert_eq!(order(&list.clone()), order(&list));
+}
+
+#[test]
+fn restored_changes_follow_the_recorded_ones() {
+    let mut typed = Typed::default();
+    typed.set_c(1);
+    typed.set_a(1);
+    assert_eq!(
+        typed.changes_in_order().collect::<Vec<_>>(),
+        [Typed::c(), Typed::a()]
+    );
+
+    let json = serde_json::to_string(&typed).unwrap();
+    let mut restored: Typed = serde_json::from_str(&json).unwrap();
+    restored.set_b(1);
+    assert_eq!(
+        restored.changes_in_order().collect::<Vec<_>>(),
+        [Typed::b(), Typed::a(), Typed::c()]
+    );
+    assert!(Track::changed_any(&restored));
+}
+
+#[tracker::track(ordered, previous)]
+#[derive(Default)]
+struct Revertible {
+    a: u8,
+    b: u8,
+}
+
+#[test]
+fn reverted_fields_leave_the_order() {
+    let mut r = Revertible::default();
+    r.set_a(1);
+    r.set_b(1);
+    r.revert_a();
+    assert_eq!(r.changes_in_order().collect::<Vec<_>>(), [Revertible::b()]);
+
+    r.set_a(2);
+    assert_eq!(
+        r.changes_in_order().collect::<Vec<_>>(),
+        [Revertible::b(), Revertible::a()]
+    );
+}
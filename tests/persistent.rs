@@ -0,0 +1,72 @@
+#[tracker::track(persistent)]
+#[derive(Default)]
+struct Document {
+    title: String,
+    body: String,
+    #[tracker::nested]
+    meta: Meta,
+}
+
+#[tracker::track]
+#[derive(Default, Clone, PartialEq)]
+struct Meta {
+    author: String,
+}
+
+#[tracker::track(persistent, typed_mask)]
+#[derive(Default)]
+struct Typed {
+    a: u8,
+    b: u8,
+}
+
+#[test]
+fn persistent_changes_survive_reset() {
+    let mut doc = Document::default();
+    assert!(!doc.ever_changed_any());
+
+    doc.set_title("Draft".to_string());
+    doc.reset();
+    assert!(!doc.changed_any());
+    assert!(doc.ever_changed_any());
+    assert!(doc.ever_changed(Document::title()));
+    assert!(!doc.ever_changed(Document::body()));
+
+    doc.get_mut_body().push('a');
+    doc.reset();
+    assert!(doc.ever_changed(Document::title() | Document::body()));
+
+    doc.clear_persistent();
+    assert!(!doc.ever_changed_any());
+}
+
+#[test]
+fn changes_after_clearing_are_recorded() {
+    let mut doc = Document::default();
+    doc.set_title("Draft".to_string());
+    // Saved without rendering, so the tracker still has the title.
+    doc.clear_persistent();
+    assert!(doc.changed_title());
+
+    doc.set_title("Final".to_string());
+    assert!(doc.ever_changed(Document::title()));
+
+    doc.clear_persistent();
+    doc.mark_all_changed();
+    assert!(doc.ever_changed(Document::track_all()));
+
+    doc.clear_persistent();
+    let mut meta = Meta::default();
+    meta.set_author("Me".to_string());
+    doc.set_meta(meta);
+    assert!(doc.ever_changed(Document::meta()));
+}
+
+#[test]
+fn persistent_changes_use_the_mask_type() {
+    let mut typed = Typed::default();
+    typed.set_b(1);
+    typed.reset();
+    assert!(typed.ever_changed(Typed::b()));
+    assert!(!typed.ever_changed(Typed::a()));
+}
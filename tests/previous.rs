@@ -0,0 +1,75 @@
+#[tracker::track(previous)]
+#[derive(Clone, Debug, Default)]
+struct Test {
+    x: u8,
+    title: String,
+    #[no_eq]
+    no_eq: u8,
+}
+
+#[test]
+fn stores_value_before_first_change() {
+    let mut t = Test::default();
+    assert_eq!(t.previous_x(), None);
+
+    t.set_x(1);
+    t.set_x(2);
+    assert_eq!(t.previous_x(), Some(&0));
+
+    t.get_mut_title().push_str("hello");
+    assert_eq!(t.previous_title().map(String::as_str), Some(""));
+
+    // Fields marked as no_eq are always marked as changed.
+    t.set_no_eq(0);
+    assert_eq!(t.previous_no_eq(), Some(&0));
+
+    t.reset();
+    assert_eq!(t.previous_x(), None);
+    assert_eq!(t.previous_title(), None);
+
+    t.set_x(3);
+    assert_eq!(t.previous_x(), Some(&2));
+}
+
+#[test]
+fn eq_suppressed_sets_store_nothing() {
+    let mut t = Test::default();
+    t.set_x(0);
+    assert_eq!(t.previous_x(), None);
+    assert!(!t.changed_any());
+}
+
+#[test]
+fn revert_restores_value() {
+    let mut t = Test::default();
+    t.set_x(5);
+    t.update_title(|title| title.push('a'));
+
+    t.revert_x();
+    assert_eq!(t.x, 0);
+    assert!(!t.changed_x());
+    assert!(t.changed_title());
+    assert_eq!(t.previous_x(), None);
+
+    t.revert_title();
+    assert_eq!(t.title, "");
+    assert!(!t.changed_any());
+}
+
+#[test]
+fn batch_updates_store_previous_values() {
+    let mut t = Test::default();
+    t.update_all(|proxy| {
+        proxy.set_x(1);
+        proxy.set_x(2);
+    });
+    assert_eq!(t.previous_x(), Some(&0));
+
+    t.reset();
+    t.apply_update(TestUpdate {
+        x: Some(3),
+        ..Default::default()
+    });
+    assert_eq!(t.previous_x(), Some(&2));
+    assert!(format!("{t:?}").contains("tracker_previous"));
+}
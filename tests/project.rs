@@ -0,0 +1,57 @@
+use tracker::FieldView;
+
+#[tracker::track(project)]
+#[derive(Default)]
+struct Test {
+    title: String,
+    count: u32,
+    #[do_not_track]
+    untracked: u8,
+}
+
+#[tracker::track(project)]
+struct Generic<'a, T: PartialEq, U> {
+    value: &'a T,
+    #[do_not_track]
+    untracked: U,
+}
+
+fn render(field: FieldView<'_, String>) -> String {
+    if field.changed() {
+        format!("*{}*", field.value())
+    } else {
+        field.value().clone()
+    }
+}
+
+#[test]
+fn changed_flags() {
+    let mut t = Test::default();
+    t.set_title("hi".to_owned());
+    t.untracked = 1;
+
+    let view = t.project();
+    assert_eq!(view.title.value(), "hi");
+    assert!(view.title.changed());
+    assert_eq!(*view.count.value(), 0);
+    assert!(!view.count.changed());
+    assert_eq!(render(view.title), "*hi*");
+
+    t.reset();
+    assert_eq!(render(t.project().title), "hi");
+}
+
+#[test]
+fn generics() {
+    let value = 1;
+    let mut g = Generic {
+        value: &value,
+        untracked: (),
+        tracker: 0,
+    };
+    g.mark_all_changed();
+    assert_eq!(**g.project().value.value(), 1);
+    assert!(g.project().value.changed());
+    g.reset();
+    assert_eq!(g.untracked, ());
+}
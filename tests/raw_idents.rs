@@ -0,0 +1,75 @@
+#[tracker::track(previous, debug)]
+#[derive(Clone, Default)]
+struct Test {
+    r#type: u8,
+    r#async: String,
+    #[no_eq]
+    r#loop: u8,
+}
+
+#[test]
+fn accessors_strip_raw_prefix() {
+    let mut t = Test::default();
+    assert_eq!(*t.get_type(), 0);
+
+    t.set_type(1);
+    assert!(t.changed_type());
+    assert!(t.changed(Test::r#type()));
+    assert_eq!(t.r#type, 1);
+
+    t.get_mut_async().push_str("hello");
+    assert!(t.changed_async());
+    assert_eq!(t.get_async(), "hello");
+
+    t.update_loop(|value| *value = 2);
+    assert!(t.changed_loop());
+    assert!(t.changed_any());
+
+    t.reset();
+    assert!(!t.changed_any());
+}
+
+#[test]
+fn companion_methods_strip_raw_prefix() {
+    let mut t = Test::default();
+    t.update_all(|proxy| {
+        proxy.set_type(3);
+        proxy.get_mut_async().push('a');
+        let _ = proxy.get_loop();
+    });
+    assert!(t.changed(Test::r#type() | Test::r#async()));
+    assert!(!t.changed_loop());
+    assert_eq!(
+        Test::track_all(),
+        Test::r#type() | Test::r#async() | Test::r#loop()
+    );
+
+    assert_eq!(t.previous_type(), Some(&0));
+    t.revert_type();
+    assert_eq!(t.r#type, 0);
+
+    let delta = t.to_delta();
+    assert_eq!(delta.r#async.as_deref(), Some("a"));
+    let mut other = Test::default();
+    other.apply_delta(delta);
+    assert_eq!(other.diff(&t), 0);
+    other.mark_all_changed();
+    other.reset();
+
+    // Reverting `type` cleared its change.
+    assert_eq!(t.field_mask_paths(), vec!["async".to_owned()]);
+    assert_eq!(Test::mask_from_paths(&["loop"]).unwrap(), Test::r#loop());
+}
+
+#[test]
+fn names_in_debug_output_are_not_raw() {
+    let mut t = Test::default();
+    t.set_type(1);
+    let output = format!("{t:?}");
+    assert!(output.contains("type"));
+    assert!(!output.contains("r#"));
+
+    let delta = format!("{:?}", t.to_delta());
+    assert!(delta.contains("type"));
+    assert!(!delta.contains("r#"));
+}
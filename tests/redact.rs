@@ -0,0 +1,119 @@
+use std::fmt::Debug;
+
+use tracker::ChangedVisitor;
+
+const PASSWORD: &str = "hunter2";
+
+#[tracker::track(debug, debug_values, previous)]
+#[derive(Default)]
+struct Login {
+    user: String,
+    #[tracker::redact]
+    password: String,
+    #[do_not_track]
+    #[tracker::redact]
+    token: String,
+}
+
+#[tracker::track(previous)]
+#[derive(Default, Debug)]
+struct Account {
+    #[tracker::redact]
+    password: String,
+}
+
+/// A secret that can't be printed at all.
+#[derive(Default, Clone, PartialEq)]
+struct Key(u64);
+
+#[tracker::track(debug, debug_values)]
+#[derive(Default)]
+struct Vault {
+    #[tracker(redact, no_eq)]
+    key: Key,
+}
+
+#[derive(Default)]
+struct Logger(Vec<String>);
+
+impl ChangedVisitor for Logger {
+    fn field<T: Debug + ?Sized>(&mut self, name: &'static str, value: &T) {
+        self.0.push(format!("{name} = {value:?}"));
+    }
+}
+
+fn changed_login() -> Login {
+    let mut login = Login {
+        token: PASSWORD.to_string(),
+        ..Login::default()
+    };
+    login.set_user("admin".to_string());
+    login.set_password(PASSWORD.to_string());
+    login
+}
+
+#[test]
+fn debug_hides_redacted_fields() {
+    let login = changed_login();
+    let output = format!("{login:?}");
+    assert!(!output.contains(PASSWORD), "{output}");
+    assert_eq!(
+        output,
+        r#"Login { user: "admin" (changed), password: <redacted> (changed), token: <redacted> }"#
+    );
+    assert!(!format!("{login:#?}").contains(PASSWORD));
+
+    let mut vault = Vault::default();
+    assert_eq!(format!("{vault:?}"), "Vault { key: <redacted> }");
+    vault.set_key(Key(42));
+    assert_eq!(format!("{vault:?}"), "Vault { key: <redacted> (changed) }");
+}
+
+#[test]
+fn changed_values_hide_redacted_fields() {
+    let login = changed_login();
+    let values: Vec<String> = login
+        .changed_values()
+        .map(|(name, value)| format!("{name} = {value:?}"))
+        .collect();
+    assert_eq!(values, [r#"user = "admin""#, "password = <redacted>"]);
+
+    let mut vault = Vault::default();
+    vault.set_key(Key(42));
+    let values: Vec<String> = vault
+        .changed_values()
+        .map(|(name, value)| format!("{name} = {value:?}"))
+        .collect();
+    assert_eq!(values, ["key = <redacted>"]);
+}
+
+#[test]
+fn visitor_gets_the_placeholder() {
+    let login = changed_login();
+    let mut logger = Logger::default();
+    login.visit_changed(&mut logger);
+    assert_eq!(logger.0, [r#"user = "admin""#, "password = <redacted>"]);
+}
+
+#[test]
+fn generated_structs_hide_redacted_fields() {
+    let login = changed_login();
+    let delta = format!("{:?}", login.to_delta());
+    assert!(!delta.contains(PASSWORD), "{delta}");
+    assert!(delta.contains("password: Some(<redacted>)"), "{delta}");
+
+    // The previous values are part of the derived `Debug` output.
+    let mut account = Account {
+        password: PASSWORD.to_string(),
+        ..Account::default()
+    };
+    account.set_password("new".to_string());
+    let output = format!("{account:?}");
+    assert!(!output.contains(PASSWORD), "{output}");
+    assert!(output.contains("password: Some(<redacted>)"), "{output}");
+
+    // The value itself is still tracked and accessible.
+    assert!(account.changed_password());
+    assert_eq!(account.get_password(), "new");
+    assert_eq!(account.previous_password().unwrap(), PASSWORD);
+}
@@ -0,0 +1,80 @@
+use std::thread;
+
+#[tracker::track(remote, previous)]
+#[derive(Default, Clone)]
+struct Model {
+    x: u8,
+    y: String,
+    #[do_not_track]
+    cache: Vec<u8>,
+}
+
+#[tracker::track(remote, typed_mask)]
+#[derive(Default)]
+struct Typed<T> {
+    value: T,
+    other: u8,
+}
+
+fn assert_send_sync<T: Send + Sync + Clone>(_: &T) {}
+
+#[test]
+fn handles_mark_fields_from_other_threads() {
+    let mut model = Model::default();
+    let handle = model.change_handle();
+    assert_send_sync(&handle);
+
+    thread::spawn(move || handle.mark_y()).join().unwrap();
+    assert!(!model.changed_any());
+
+    model.absorb_remote();
+    assert_eq!(model.tracker, Model::y());
+    assert_eq!(model.previous_y().map(String::as_str), Some(""));
+    assert!(model.cache.is_empty());
+
+    // The remote changes are cleared when they are absorbed.
+    model.reset();
+    model.absorb_remote();
+    assert!(!model.changed_any());
+}
+
+#[test]
+fn handles_share_the_changes() {
+    let mut model = Model::default();
+    let handle = model.change_handle();
+    let threads: Vec<_> = [Model::x(), Model::y()]
+        .into_iter()
+        .map(|mask| {
+            let handle = handle.clone();
+            thread::spawn(move || handle.mark(mask))
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+
+    // Clones have their own remote changes.
+    let mut clone = model.clone();
+    clone.absorb_remote();
+    assert!(!clone.changed_any());
+
+    model.absorb_remote();
+    assert_eq!(model.tracker, Model::x() | Model::y());
+}
+
+#[test]
+fn typed_masks() {
+    let mut typed = Typed::<String>::default();
+    let handle = typed.change_handle();
+    let mask = Typed::<String>::value();
+    thread::spawn(move || {
+        handle.mark_other();
+        handle.mark(mask);
+    })
+    .join()
+    .unwrap();
+
+    typed.absorb_remote();
+    assert!(typed.changed(Typed::<String>::value()));
+    assert!(typed.changed_other());
+}
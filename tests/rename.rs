@@ -0,0 +1,57 @@
+trait Titled {
+    fn set_title(&mut self, title: &str);
+}
+
+#[tracker::track(previous, debug)]
+#[derive(Default)]
+struct Window {
+    #[tracker::rename = "tracked_title"]
+    title: String,
+    #[tracker(rename = "type")]
+    kind: u8,
+}
+
+impl Titled for Window {
+    fn set_title(&mut self, title: &str) {
+        self.set_tracked_title(title.to_uppercase());
+    }
+}
+
+#[tracker::track(prefix = "t_")]
+#[derive(Default)]
+struct Prefixed {
+    x: u8,
+    #[tracker::rename = "why"]
+    y: u8,
+}
+
+#[test]
+fn renamed_methods() {
+    let mut w = Window::default();
+    Titled::set_title(&mut w, "main");
+    assert_eq!(w.get_tracked_title(), "MAIN");
+    assert!(w.changed_tracked_title());
+    assert!(w.changed(Window::tracked_title()));
+    assert_eq!(w.previous_tracked_title().map(String::as_str), Some(""));
+    assert!(format!("{w:?}").contains("title"));
+
+    w.set_type(1);
+    assert_eq!(*w.get_type(), 1);
+    assert_eq!(Window::r#type(), 1 << 1);
+    w.revert_type();
+    w.reset();
+}
+
+#[test]
+fn prefixed_methods() {
+    let mut p = Prefixed::default();
+    p.t_set_x(1);
+    p.t_update_why(|y| *y = 2);
+    assert_eq!((*p.t_get_x(), *p.t_get_why()), (1, 2));
+    assert!(p.t_changed_x() && p.changed(Prefixed::t_why()));
+    assert_eq!(Prefixed::track_all(), Prefixed::t_x() | Prefixed::t_why());
+    *p.t_get_mut_x() = 3;
+    p.update_all(|proxy| proxy.t_set_why(3));
+    assert_eq!(p.to_delta().y, Some(3));
+    p.reset();
+}
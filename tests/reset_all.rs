@@ -0,0 +1,69 @@
+use tracker::Track;
+
+#[tracker::track]
+#[derive(Default)]
+struct Leaf {
+    value: u8,
+}
+
+#[tracker::track]
+#[derive(Default)]
+struct Child {
+    #[tracker::nested]
+    #[no_eq]
+    leaf: Leaf,
+    count: u8,
+}
+
+#[tracker::track]
+#[derive(Default)]
+struct Parent {
+    #[tracker::nested]
+    #[no_eq]
+    child: Child,
+    #[tracker::nested]
+    #[no_eq]
+    optional: Option<Child>,
+    #[tracker::nested]
+    #[no_eq]
+    children: Vec<Child>,
+    name: String,
+}
+
+fn dirty() -> Parent {
+    let mut parent = Parent {
+        optional: Some(Child::default()),
+        children: vec![Child::default(), Child::default()],
+        ..Default::default()
+    };
+    parent.set_name("parent".to_owned());
+    parent.child.leaf.set_value(1);
+    parent.child.set_count(1);
+    parent.optional.as_mut().unwrap().leaf.set_value(1);
+    parent.children[1].leaf.set_value(1);
+    parent
+}
+
+#[test]
+fn reset_all_resets_children() {
+    let mut parent = dirty();
+    assert!(parent.changed_any());
+    parent.reset_all();
+    assert!(!parent.changed_any());
+    assert!(!parent.child.leaf.changed_any());
+    assert!(!parent.children[1].changed_any());
+}
+
+#[test]
+fn reset_is_shallow() {
+    let mut parent = dirty();
+    parent.reset();
+    assert!(!parent.changed_name());
+    assert!(parent.changed_child() && parent.changed_optional() && parent.changed_children());
+
+    // Structs without nested fields only reset themselves.
+    let mut leaf = Leaf::default();
+    leaf.set_value(1);
+    Track::reset_all(&mut leaf);
+    assert!(!leaf.changed_any());
+}
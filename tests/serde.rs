@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+
+#[tracker::track(serde)]
+#[derive(Default, Serialize, Deserialize)]
+struct Test {
+    name: String,
+    count: u32,
+}
+
+#[tracker::track(serde, previous, versioned)]
+#[derive(Default, Serialize, Deserialize)]
+struct Extra {
+    value: u8,
+}
+
+#[test]
+fn tracker_skipped() {
+    let mut t = Test::default();
+    t.set_count(3);
+    assert_eq!(
+        serde_json::to_string(&t).unwrap(),
+        r#"{"name":"","count":3}"#
+    );
+}
+
+#[test]
+fn round_trip() {
+    let mut t = Test::default();
+    t.set_name("tracker".to_owned());
+
+    let json = serde_json::to_string(&t).unwrap();
+    let mut t: Test = serde_json::from_str(&json).unwrap();
+    assert_eq!(t.get_name(), "tracker");
+    assert!(!t.changed_any());
+
+    t.set_count(1);
+    assert!(t.changed(Test::count()));
+    t.reset();
+}
+
+#[test]
+fn extra_fields_skipped() {
+    let mut e = Extra::default();
+    e.set_value(1);
+    let json = serde_json::to_string(&e).unwrap();
+    assert_eq!(json, r#"{"value":1}"#);
+
+    let mut e: Extra = serde_json::from_str(&json).unwrap();
+    assert_eq!(e.version(), 0);
+    assert!(e.previous_value().is_none());
+    e.set_value(2);
+    assert_eq!(e.previous_value(), Some(&1));
+    assert_eq!(e.changed_since(0), Extra::value());
+    e.reset();
+}
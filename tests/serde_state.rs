@@ -0,0 +1,92 @@
+#![cfg(feature = "serde")]
+
+use serde::{Deserialize, Serialize};
+
+#[tracker::track(serde_state)]
+#[derive(Default, Serialize, Deserialize)]
+struct Test {
+    x: u8,
+    title: String,
+    removed: bool,
+}
+
+/// `Test` after the `removed` field was removed and a new field was added.
+#[tracker::track(serde_state)]
+#[derive(Default, Serialize, Deserialize)]
+struct NewTest {
+    #[serde(default)]
+    added: u32,
+    x: u8,
+    title: String,
+}
+
+#[tracker::track(serde_state, versioned)]
+#[derive(Default, Serialize, Deserialize)]
+struct Versioned {
+    value: u8,
+}
+
+#[test]
+fn changed_names() {
+    let mut t = Test::default();
+    assert_eq!(
+        serde_json::to_string(&t).unwrap(),
+        r#"{"x":0,"title":"","removed":false,"__changed":[]}"#
+    );
+
+    t.set_title("hi".to_owned());
+    t.set_x(1);
+    assert_eq!(
+        serde_json::to_string(&t).unwrap(),
+        r#"{"x":1,"title":"hi","removed":false,"__changed":["x","title"]}"#
+    );
+}
+
+#[test]
+fn round_trip() {
+    let mut t = Test::default();
+    t.set_title("hi".to_owned());
+    t.set_removed(true);
+
+    let json = serde_json::to_string(&t).unwrap();
+    let mut t: Test = serde_json::from_str(&json).unwrap();
+    assert!(t.changed(Test::title()));
+    assert!(t.changed(Test::removed()));
+    assert!(!t.changed(Test::x()));
+    t.reset();
+    assert!(!t.changed_any());
+}
+
+#[test]
+fn removed_field() {
+    let mut t = Test::default();
+    t.set_x(3);
+    t.set_removed(true);
+
+    let json = serde_json::to_string(&t).unwrap();
+    let mut new: NewTest = serde_json::from_str(&json).unwrap();
+    assert_eq!(*new.get_x(), 3);
+    assert!(new.changed(NewTest::x()));
+    assert!(!new.changed(NewTest::added()));
+    assert!(!new.changed(NewTest::title()));
+    new.reset();
+}
+
+#[test]
+fn missing_state() {
+    let t: Test = serde_json::from_str(r#"{"x":1,"title":"","removed":false}"#).unwrap();
+    assert!(!t.changed_any());
+}
+
+#[test]
+fn extra_fields_skipped() {
+    let mut v = Versioned::default();
+    v.set_value(1);
+    let json = serde_json::to_string(&v).unwrap();
+    assert_eq!(json, r#"{"value":1,"__changed":["value"]}"#);
+
+    let mut v: Versioned = serde_json::from_str(&json).unwrap();
+    assert!(v.changed(Versioned::value()));
+    assert_eq!(v.version(), 0);
+    v.reset();
+}
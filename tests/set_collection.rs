@@ -0,0 +1,70 @@
+use std::collections::{BTreeSet, HashSet};
+
+#[tracker::track(previous)]
+#[derive(Default)]
+struct Model {
+    #[tracker::set_collection]
+    tags: HashSet<String>,
+    #[tracker::set_collection]
+    ids: BTreeSet<u32>,
+    #[tracker::set_collection]
+    #[tracker::no_set]
+    fixed: BTreeSet<u32>,
+}
+
+#[tracker::track]
+struct Generic<T> {
+    #[tracker::set_collection]
+    items: BTreeSet<T>,
+}
+
+#[test]
+fn inserting_a_duplicate_leaves_the_bit_clear() {
+    let mut model = Model::default();
+    assert!(model.insert_tags("a".to_string()));
+    assert!(model.changed_tags());
+    assert_eq!(model.previous_tags(), Some(&HashSet::new()));
+
+    model.reset();
+    assert!(!model.insert_tags("a".to_string()));
+    assert!(!model.changed_tags());
+    assert_eq!(model.tags.len(), 1);
+}
+
+#[test]
+fn removing_marks_only_present_values() {
+    let mut model = Model {
+        ids: BTreeSet::from([1, 2]),
+        ..Default::default()
+    };
+    assert!(!model.remove_ids(&3));
+    assert!(!model.changed_ids());
+    assert!(model.remove_ids(&1));
+    assert!(model.changed_ids());
+    assert_eq!(model.ids, BTreeSet::from([2]));
+}
+
+#[test]
+fn clearing_marks_only_non_empty_sets() {
+    let mut model = Model::default();
+    assert!(!model.clear_tags());
+    assert!(!model.changed_tags());
+
+    model.insert_ids(1);
+    model.reset();
+    assert!(model.clear_ids());
+    assert!(model.changed_ids());
+    assert!(model.ids.is_empty());
+    assert!(model.fixed.is_empty());
+}
+
+#[test]
+fn generic_elements() {
+    let mut generic = Generic {
+        items: BTreeSet::new(),
+        tracker: 0,
+    };
+    assert!(generic.insert_items(1u8));
+    assert!(!generic.insert_items(1));
+    assert!(generic.changed_items());
+}
@@ -0,0 +1,47 @@
+#[tracker::track(no_delta)]
+#[derive(Default)]
+struct Test {
+    #[tracker::no_set]
+    derived: u8,
+    #[tracker::no_mut]
+    #[tracker(no_update)]
+    shared: Vec<u8>,
+    #[tracker(no_get, no_mut, no_update, no_set)]
+    hidden: u8,
+    x: u8,
+}
+
+impl Test {
+    fn set_x_and_derived(&mut self, x: u8) {
+        self.set_x(x);
+        if self.changed_x() {
+            self.derived = x * 2;
+            self.tracker |= Test::derived();
+        }
+    }
+}
+
+#[test]
+fn remaining_methods_are_generated() {
+    let mut t = Test::default();
+    t.set_x_and_derived(2);
+    assert!(t.changed_derived());
+    assert_eq!(*t.get_derived(), 4);
+    t.update_derived(|derived| *derived += 1);
+    assert_eq!(*t.get_mut_derived(), 5);
+
+    t.set_shared(vec![1]);
+    assert_eq!(t.get_shared(), &[1]);
+
+    t.hidden = 1;
+    assert!(!t.changed_hidden());
+    assert_eq!(Test::hidden(), 1 << 2);
+    assert_eq!(Test::track_all().count_ones(), 4);
+
+    t.update_all(|proxy| {
+        proxy.set_x(3);
+        let _ = proxy.get_derived();
+        let _ = proxy.get_shared();
+    });
+    t.reset();
+}
@@ -0,0 +1,45 @@
+#[tracker::track(minimal)]
+#[derive(Default)]
+struct Minimal {
+    value: u8,
+    #[no_eq]
+    list: Vec<u8>,
+}
+
+#[tracker::track(skip_methods(get_mut, set, track_all))]
+#[derive(Default)]
+struct Skipped {
+    value: u8,
+}
+
+#[test]
+fn minimal() {
+    let mut m = Minimal::default();
+    m.set_value(1);
+    m.set_list(vec![1]);
+    assert!(m.changed(Minimal::value() | Minimal::list()));
+    m.reset();
+    assert!(!m.changed(Minimal::value()));
+}
+
+#[test]
+fn skipped_setters_for_delta() {
+    let mut s = Skipped::default();
+    *s.get_mut_value_instead() = 1;
+    assert!(s.changed_any());
+
+    let delta = s.to_delta();
+    let mut other = Skipped::default();
+    other.apply_delta(delta);
+    assert_eq!(*other.get_value(), 1);
+    assert!(other.changed(Skipped::value()));
+    other.mark_all_changed();
+    other.reset();
+}
+
+impl Skipped {
+    fn get_mut_value_instead(&mut self) -> &mut u8 {
+        self.tracker |= Self::value();
+        &mut self.value
+    }
+}
@@ -0,0 +1,57 @@
+#[tracker::track(snapshot)]
+#[derive(Default)]
+struct Test {
+    x: u8,
+    title: String,
+    #[no_eq]
+    no_eq: u8,
+    #[do_not_track]
+    untracked: u8,
+}
+
+#[tracker::track(snapshot)]
+struct Generic<T: Clone + PartialEq> {
+    value: T,
+}
+
+#[test]
+fn reports_net_changes() {
+    let mut t = Test::default();
+    let snapshot = t.snapshot();
+    assert_eq!(t.changed_since(&snapshot), 0);
+
+    t.set_x(1);
+    t.set_title("hello".to_owned());
+    assert_eq!(t.changed_since(&snapshot), Test::x() | Test::title());
+
+    // Setting the value back is still tracked, but not a net change.
+    t.set_x(0);
+    assert!(t.changed_x());
+    assert_eq!(t.changed_since(&snapshot), Test::title());
+    assert_eq!(snapshot.title, "");
+
+    t.reset();
+    assert!(!t.changed_any());
+}
+
+#[test]
+fn ignores_no_eq_and_untracked_fields() {
+    let mut t = Test::default();
+    let snapshot = t.snapshot();
+
+    t.set_no_eq(1);
+    t.untracked = 1;
+    assert_eq!(t.changed_since(&snapshot), 0);
+}
+
+#[test]
+fn generic_snapshot() {
+    let mut g = Generic {
+        value: 1u8,
+        tracker: 0,
+    };
+    let snapshot = g.snapshot();
+    g.set_value(2);
+    assert_eq!(g.changed_since(&snapshot), Generic::<u8>::value());
+    g.reset();
+}
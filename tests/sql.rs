@@ -0,0 +1,68 @@
+#[tracker::track(sql)]
+#[derive(Default)]
+struct Post {
+    title: String,
+    count: u32,
+    #[tracker::column = "Published At"]
+    published_at: u64,
+    #[tracker::column = "order"]
+    position: u32,
+    #[tracker::column = "say \"hi\""]
+    greeting: String,
+    #[do_not_track]
+    untracked: u8,
+}
+
+#[test]
+fn changed_columns() {
+    let mut post = Post::default();
+    assert!(post.changed_columns().is_empty());
+
+    post.set_count(1);
+    post.set_published_at(2);
+    assert_eq!(post.changed_columns(), vec!["count", "Published At"]);
+}
+
+#[test]
+fn no_changes() {
+    let mut post = Post::default();
+    assert_eq!(post.set_clause_with_placeholders(1), None);
+
+    post.set_count(0);
+    post.untracked = 1;
+    assert_eq!(post.set_clause_with_placeholders(1), None);
+}
+
+#[test]
+fn placeholders() {
+    let mut post = Post::default();
+    post.set_title("Hello".to_owned());
+    post.set_count(2);
+    assert_eq!(
+        post.set_clause_with_placeholders(1).unwrap(),
+        "title = $1, count = $2"
+    );
+    assert_eq!(
+        post.set_clause_with_placeholders(3).unwrap(),
+        "title = $3, count = $4"
+    );
+
+    post.reset();
+    assert_eq!(post.set_clause_with_placeholders(1), None);
+}
+
+#[test]
+fn quoting() {
+    let mut post = Post::default();
+    post.set_published_at(1);
+    post.set_position(2);
+    post.set_greeting("hi".to_owned());
+    assert_eq!(
+        post.set_clause_with_placeholders(1).unwrap(),
+        r#""Published At" = $1, "order" = $2, "say ""hi""" = $3"#
+    );
+    assert_eq!(
+        post.changed_columns(),
+        vec!["Published At", "order", "say \"hi\""]
+    );
+}
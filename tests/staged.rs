@@ -0,0 +1,96 @@
+#[tracker::track(staged)]
+#[derive(Default)]
+struct Form {
+    name: String,
+    age: u8,
+    #[no_eq]
+    submitted: bool,
+    #[do_not_track]
+    cache: Vec<u8>,
+}
+
+#[tracker::track(staged, previous)]
+#[derive(Default)]
+struct Generic<T: Clone> {
+    value: T,
+    count: u32,
+}
+
+#[test]
+fn abort_discards_the_staged_values() {
+    let mut form = Form::default();
+    form.begin();
+    assert!(form.is_staging());
+    form.stage_name("Ferris".to_string());
+    form.stage_age(7);
+    assert_eq!(form.staged_name(), "Ferris");
+    assert_eq!(*form.staged_age(), 7);
+    assert!(!form.changed_any());
+
+    form.abort();
+    assert!(!form.is_staging());
+    assert!(!form.changed_any());
+    assert_eq!(form.name, "");
+    assert_eq!(form.age, 0);
+    assert_eq!(*form.staged_age(), 0);
+
+    // Committing without a stage doesn't change anything.
+    form.commit();
+    assert!(!form.changed_any());
+}
+
+#[test]
+fn commit_marks_the_differing_fields() {
+    let mut form = Form::default();
+    form.set_age(3);
+    form.reset();
+
+    form.begin();
+    form.stage_name("Ferris".to_string());
+    form.stage_age(3);
+    form.stage_submitted(false);
+    assert_eq!(form.staged_name(), "Ferris");
+    assert_eq!(*form.staged_age(), 3);
+    form.commit();
+
+    assert!(!form.is_staging());
+    assert_eq!(form.name, "Ferris");
+    assert_eq!(form.age, 3);
+    assert_eq!(form.tracker, Form::name() | Form::submitted());
+    assert!(form.cache.is_empty());
+}
+
+#[test]
+fn nested_stages_are_flattened() {
+    let mut form = Form::default();
+    form.stage_age(1);
+    assert!(form.is_staging());
+    form.begin();
+    form.stage_name("Ferris".to_string());
+    form.begin();
+    form.commit();
+
+    assert!(!form.is_staging());
+    assert_eq!(form.tracker, Form::name() | Form::age());
+
+    form.reset();
+    form.begin();
+    form.stage_age(2);
+    form.begin();
+    form.abort();
+    form.commit();
+    assert_eq!(form.age, 1);
+    assert!(!form.changed_any());
+}
+
+#[test]
+fn commit_runs_the_setters() {
+    let mut generic = Generic::<String>::default();
+    generic.stage_value("new".to_string());
+    generic.stage_count(0);
+    generic.commit();
+
+    assert_eq!(generic.tracker, Generic::<String>::value());
+    assert_eq!(generic.previous_value().map(String::as_str), Some(""));
+    assert_eq!(generic.previous_count(), None);
+}
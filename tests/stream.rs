@@ -0,0 +1,94 @@
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+
+use futures_core::Stream;
+use tracker::ChangesReceiver;
+
+#[tracker::track(stream, serde)]
+#[derive(Default, Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Model {
+    x: u8,
+    name: String,
+}
+
+#[tracker::track(stream, atomic, typed_mask)]
+#[derive(Default)]
+struct Shared {
+    a: u8,
+    b: u8,
+}
+
+/// Counts how often the stream woke up its task.
+#[derive(Default)]
+struct CountingWaker(AtomicUsize);
+
+impl Wake for CountingWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+fn poll<M>(stream: &mut ChangesReceiver<M>, waker: &Waker) -> Poll<Option<M>> {
+    Pin::new(stream).poll_next(&mut Context::from_waker(waker))
+}
+
+#[test]
+fn stream_gets_coalesced_masks() {
+    let counter = Arc::new(CountingWaker::default());
+    let waker = Waker::from(Arc::clone(&counter));
+    let mut model = Model::default();
+    let mut stream = model.changes_stream();
+    assert_eq!(poll(&mut stream, &waker), Poll::Pending);
+
+    model.set_x(0);
+    model.set_x(1);
+    model.set_x(2);
+    model.get_mut_name().push('a');
+    assert_eq!(counter.0.load(Ordering::Relaxed), 1);
+    assert_eq!(
+        poll(&mut stream, &waker),
+        Poll::Ready(Some(Model::x() | Model::name()))
+    );
+    assert_eq!(poll(&mut stream, &waker), Poll::Pending);
+
+    model.reset();
+    model.mark_all_changed();
+    assert_eq!(counter.0.load(Ordering::Relaxed), 2);
+    drop(model);
+    assert_eq!(
+        poll(&mut stream, &waker),
+        Poll::Ready(Some(Model::track_all()))
+    );
+    assert_eq!(poll(&mut stream, &waker), Poll::Ready(None));
+}
+
+#[test]
+fn sender_is_not_state() {
+    let model = Model::default();
+    let mut stream = model.changes_stream();
+    let mut clone = model.clone();
+    assert_eq!(clone, model);
+    assert_eq!(
+        serde_json::to_string(&model).unwrap(),
+        r#"{"x":0,"name":""}"#
+    );
+
+    clone.set_x(1);
+    assert_eq!(poll(&mut stream, Waker::noop()), Poll::Pending);
+}
+
+#[test]
+fn atomic_changes_are_streamed() {
+    let shared = Shared::default();
+    let mut stream = shared.changes_stream();
+    std::thread::scope(|scope| {
+        scope.spawn(|| shared.mark_changed(Shared::a()));
+        scope.spawn(|| shared.mark_changed(Shared::b()));
+    });
+    assert_eq!(
+        poll(&mut stream, Waker::noop()),
+        Poll::Ready(Some(Shared::a() | Shared::b()))
+    );
+}
@@ -0,0 +1,30 @@
+#[tracker::track(strict_attrs)]
+#[derive(Default)]
+struct Strict {
+    #[tracker::no_eq]
+    a: u8,
+    #[tracker(no_eq)]
+    b: u8,
+    #[tracker::do_not_track]
+    c: u8,
+    #[cfg_attr(test, tracker::no_eq)]
+    d: u8,
+    e: u8,
+}
+
+#[test]
+fn namespaced_attributes_are_applied() {
+    let mut t = Strict::default();
+    assert_eq!(
+        Strict::track_all(),
+        Strict::a() | Strict::b() | Strict::d() | Strict::e()
+    );
+    t.c = 1;
+
+    t.set_a(0);
+    t.set_b(0);
+    t.set_d(0);
+    t.set_e(0);
+    assert!(t.changed_a() && t.changed_b() && t.changed_d());
+    assert!(!t.changed_e());
+}
@@ -0,0 +1,64 @@
+/// Neither `Clone` nor `Default`, so fields can only be moved.
+#[derive(Debug, PartialEq)]
+struct Handle(u32);
+
+#[tracker::track]
+struct Test {
+    x: u8,
+    handle: Handle,
+    #[no_eq]
+    no_eq: u8,
+    #[do_not_track]
+    untracked: Handle,
+}
+
+fn test(x: u8, handle: u32, untracked: u32) -> Test {
+    Test {
+        x,
+        handle: Handle(handle),
+        no_eq: 0,
+        untracked: Handle(untracked),
+        tracker: 0,
+    }
+}
+
+#[tracker::track(previous, versioned)]
+#[derive(Default)]
+struct Versioned {
+    a: u8,
+    b: String,
+}
+
+#[test]
+fn sync_from_marks_differing_fields() {
+    let mut t = test(1, 2, 3);
+    let mut new = test(1, 5, 6);
+    new.set_x(1);
+    new.mark_all_changed();
+
+    let mask = t.sync_from(new);
+    assert_eq!(mask, Test::handle() | Test::no_eq());
+    assert_eq!(t.tracker, mask);
+    assert_eq!((t.x, &t.handle, &t.untracked), (1, &Handle(5), &Handle(6)));
+
+    t.reset();
+    assert_eq!(t.sync_from(test(2, 5, 7)), Test::x() | Test::no_eq());
+    assert_eq!(t.untracked, Handle(7));
+}
+
+#[test]
+fn sync_from_records_previous_values_and_versions() {
+    let mut v = Versioned::default();
+    let version = v.version();
+    let mask = v.sync_from(Versioned {
+        b: "new".to_owned(),
+        ..Default::default()
+    });
+    assert_eq!(mask, Versioned::b());
+    assert_eq!(v.previous_b().map(String::as_str), Some(""));
+    assert_eq!(v.changed_since(version), Versioned::b());
+
+    assert_eq!(v.sync_from(Versioned::default()), Versioned::b());
+    assert_eq!(v.previous_b().map(String::as_str), Some(""));
+    assert_eq!(v.previous_a(), None);
+}
@@ -0,0 +1,92 @@
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+use tracker::Clock;
+
+thread_local! {
+    static START: Instant = Instant::now();
+    static OFFSET: Cell<Duration> = const { Cell::new(Duration::ZERO) };
+}
+
+/// A clock that only moves with `advance()`.
+struct TestClock;
+
+impl Clock for TestClock {
+    fn now() -> Instant {
+        START.with(|start| *start + OFFSET.with(Cell::get))
+    }
+}
+
+fn advance(millis: u64) {
+    OFFSET.with(|offset| offset.set(offset.get() + Duration::from_millis(millis)));
+}
+
+#[tracker::track(timestamps = TestClock, serde)]
+#[derive(Default, Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Search {
+    query: String,
+    page: u32,
+}
+
+#[tracker::track(timestamps)]
+#[derive(Default)]
+struct Model {
+    x: u8,
+}
+
+#[test]
+fn timestamps_are_set_when_fields_are_newly_marked() {
+    let mut search = Search::default();
+    assert_eq!(search.last_changed_query(), None);
+    assert_eq!(search.elapsed_since_change_query(), None);
+
+    search.get_mut_query().push('a');
+    let typed = TestClock::now();
+    advance(100);
+    // Already marked, so the timestamp stays the same.
+    search.get_mut_query().push('b');
+    assert_eq!(search.last_changed_query(), Some(typed));
+    assert_eq!(
+        search.elapsed_since_change_query(),
+        Some(Duration::from_millis(100))
+    );
+
+    search.reset();
+    assert_eq!(search.last_changed_query(), Some(typed));
+    search.set_query("abc".to_string());
+    advance(300);
+    assert_eq!(
+        search.elapsed_since_change_query(),
+        Some(Duration::from_millis(300))
+    );
+    assert_eq!(search.last_changed_page(), None);
+
+    search.mark_all_changed();
+    assert_eq!(search.last_changed_page(), Some(TestClock::now()));
+}
+
+#[test]
+fn timestamps_are_not_part_of_the_state() {
+    let mut search = Search::default();
+    search.set_page(1);
+    advance(10);
+    let mut other = Search::default();
+    other.set_page(1);
+    assert_ne!(search.last_changed_page(), other.last_changed_page());
+    assert_eq!(search, other);
+    assert_eq!(
+        serde_json::to_string(&search).unwrap(),
+        r#"{"query":"","page":1}"#
+    );
+}
+
+#[test]
+fn system_clock_is_the_default() {
+    let before = Instant::now();
+    let mut model = Model::default();
+    model.set_x(1);
+    let changed = model.last_changed_x().unwrap();
+    assert!(changed >= before);
+    assert!(changed <= Instant::now());
+    assert!(model.elapsed_since_change_x().is_some());
+}
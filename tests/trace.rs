@@ -0,0 +1,79 @@
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+#[tracker::track(trace)]
+#[derive(Default)]
+struct Model {
+    x: u8,
+    name: String,
+}
+
+/// Records the fields of all events as `name=value` lists.
+#[derive(Clone, Default)]
+struct Recorder(Arc<Mutex<Vec<String>>>);
+
+struct EventFields(Vec<String>);
+
+impl Visit for EventFields {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0.push(format!("{}={value:?}", field.name()));
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, _: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = EventFields(Vec::new());
+        event.record(&mut fields);
+        self.0.lock().unwrap().push(fields.0.join(" "));
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+fn record_events(f: impl FnOnce()) -> Vec<String> {
+    let recorder = Recorder::default();
+    tracing::subscriber::with_default(recorder.clone(), f);
+    let events = recorder.0.lock().unwrap().clone();
+    events
+}
+
+#[test]
+fn mutations_emit_events() {
+    let events = record_events(|| {
+        let mut model = Model::default();
+        model.set_x(0);
+        model.set_x(1);
+        model.get_mut_name().push('a');
+        model.update_x(|x| *x += 1);
+        model.reset();
+        model.mark_all_changed();
+    });
+    assert_eq!(
+        events,
+        [
+            r#"message=field marked as changed struct_name="Model" field="x" method="set" newly_changed=true"#,
+            r#"message=field marked as changed struct_name="Model" field="name" method="get_mut" newly_changed=true"#,
+            r#"message=field marked as changed struct_name="Model" field="x" method="update" newly_changed=false"#,
+            r#"message=tracker reset struct_name="Model""#,
+            r#"message=all fields marked as changed struct_name="Model""#,
+        ]
+    );
+}
@@ -0,0 +1,157 @@
+#[tracker::track(previous)]
+#[derive(Default)]
+struct Model {
+    count: u8,
+    name: String,
+    items: Vec<u8>,
+    last: Option<u8>,
+    #[do_not_track]
+    cache: Vec<u8>,
+}
+
+#[tracker::track_impl]
+impl Model {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn increment(&mut self) {
+        self.count += 1;
+    }
+
+    fn rename(&mut self, name: &str) {
+        self.name = name.to_string();
+    }
+
+    fn clear_cache(&mut self) {
+        self.cache = Vec::new();
+    }
+
+    fn push(&mut self, value: u8) {
+        let items = &mut self.items;
+        items.push(value);
+    }
+
+    fn update_first(&mut self, value: u8) {
+        if let Some(first) = self.items.first() {
+            if *first != value {
+                self.items[0] = value;
+            }
+        }
+    }
+
+    fn remember(&mut self, value: Option<u8>) {
+        match value {
+            Some(0) => {}
+            Some(value) => self.last = Some(value),
+            None => self.count += 0,
+        }
+    }
+
+    fn count_up_to(&mut self, max: u8) -> u8 {
+        while self.count < max {
+            self.count += 1;
+        }
+        self.count
+    }
+
+    fn replace_name(&mut self, name: String) -> String {
+        std::mem::replace(&mut self.name, name)
+    }
+
+    fn reset_tracker(&mut self) {
+        self.tracker = 0;
+    }
+}
+
+#[test]
+fn assignments_mark_fields() {
+    let mut model = Model::new();
+    model.increment();
+    assert!(model.changed_count());
+    assert!(!model.changed_name());
+    assert_eq!(model.previous_count(), Some(&0));
+
+    model.reset();
+    model.rename("Ferris");
+    assert_eq!(model.tracker, Model::name());
+    assert_eq!(model.name, "Ferris");
+}
+
+#[test]
+fn mutable_borrows_mark_fields() {
+    let mut model = Model::new();
+    model.push(1);
+    assert_eq!(model.tracker, Model::items());
+
+    model.reset();
+    assert_eq!(model.replace_name("Ferris".to_string()), "");
+    assert_eq!(model.tracker, Model::name());
+}
+
+#[test]
+fn conditional_assignments_only_mark_when_executed() {
+    let mut model = Model::new();
+    model.update_first(1);
+    model.remember(Some(0));
+    assert!(!model.changed_any());
+
+    model.push(1);
+    model.reset();
+    model.update_first(1);
+    assert!(!model.changed_any());
+    model.update_first(2);
+    assert_eq!(model.tracker, Model::items());
+
+    model.reset();
+    model.remember(Some(3));
+    assert_eq!(model.tracker, Model::last());
+    model.remember(None);
+    assert_eq!(model.tracker, Model::last() | Model::count());
+}
+
+#[test]
+fn loops() {
+    let mut model = Model::new();
+    assert_eq!(model.count_up_to(0), 0);
+    assert!(!model.changed_any());
+    assert_eq!(model.count_up_to(3), 3);
+    assert_eq!(model.tracker, Model::count());
+}
+
+#[test]
+fn untracked_and_injected_fields_are_left_alone() {
+    let mut model = Model::new();
+    model.cache.push(1);
+    model.clear_cache();
+    assert!(!model.changed_any());
+    assert!(model.cache.is_empty());
+
+    model.increment();
+    model.reset_tracker();
+    assert!(!model.changed_any());
+}
+
+#[tracker::track(minimal)]
+#[derive(Default)]
+struct Minimal {
+    count: u8,
+    #[do_not_track]
+    cache: u8,
+}
+
+#[tracker::track_impl]
+impl Minimal {
+    fn increment(&mut self) {
+        self.count += 1;
+        self.cache += 1;
+    }
+}
+
+#[test]
+fn minimal_structs() {
+    let mut minimal = Minimal::default();
+    minimal.increment();
+    assert_eq!(minimal.tracker, Minimal::count());
+    assert_eq!(minimal.cache, 1);
+}
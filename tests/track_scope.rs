@@ -0,0 +1,56 @@
+use std::panic::{self, AssertUnwindSafe};
+
+#[tracker::track]
+#[derive(Default)]
+struct Test {
+    value: u8,
+    name: String,
+}
+
+fn update(t: &mut Test, value: Option<u8>) -> Option<u8> {
+    let mut t = t.track_scope();
+    t.set_name("update".to_owned());
+    let value = value?;
+    t.set_value(value);
+    assert!(t.changed(Test::value()));
+    Some(value)
+}
+
+#[test]
+fn reset_on_drop() {
+    let mut t = Test::default();
+    {
+        let mut guard = t.track_scope();
+        guard.set_value(1);
+        *guard.get_mut_name() = "guard".to_owned();
+        assert!(guard.changed(Test::value() | Test::name()));
+    }
+    assert!(!t.changed_any());
+    assert_eq!(t.value, 1);
+    assert_eq!(t.name, "guard");
+}
+
+#[test]
+fn early_return() {
+    let mut t = Test::default();
+    assert_eq!(update(&mut t, None), None);
+    assert!(!t.changed_any());
+    assert_eq!(t.name, "update");
+
+    assert_eq!(update(&mut t, Some(2)), Some(2));
+    assert!(!t.changed_any());
+    assert_eq!(t.value, 2);
+}
+
+#[test]
+fn unwinding() {
+    let mut t = Test::default();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let mut guard = t.track_scope();
+        guard.set_value(3);
+        panic!("update failed");
+    }));
+    assert!(result.is_err());
+    assert!(!t.changed_any());
+    assert_eq!(t.value, 3);
+}
@@ -0,0 +1,58 @@
+// `changed()` and `reset()` are never called on `Unused`, which must not cause warnings.
+#![deny(warnings)]
+
+use tracker::Track;
+
+#[tracker::track]
+#[derive(Default)]
+struct Unused {
+    x: u8,
+}
+
+#[tracker::track(typed_mask)]
+#[derive(Default)]
+struct Typed {
+    a: u8,
+    b: String,
+}
+
+#[tracker::track(trait_only)]
+#[derive(Default)]
+struct TraitOnly {
+    x: u8,
+}
+
+/// Reset a tracked value and report whether it had changes.
+fn take_changes<T: Track>(value: &mut T) -> bool {
+    let changed = value.changed_any();
+    value.reset();
+    changed
+}
+
+#[test]
+fn generic_helpers() {
+    let mut unused = Unused::default();
+    unused.set_x(1);
+    assert_eq!(*unused.get_x(), 1);
+
+    let mut typed = Typed::default();
+    typed.set_b("b".to_owned());
+    assert!(Track::changed(&typed, Typed::b()));
+    assert!(take_changes(&mut typed));
+    assert!(!take_changes(&mut typed));
+    typed.mark_all_changed();
+    assert!(typed.changed(<Typed as Track>::track_all()));
+    assert_eq!(*typed.get_a(), 0);
+    typed.reset();
+}
+
+#[test]
+fn trait_only() {
+    let mut t = TraitOnly::default();
+    t.set_x(1);
+    assert!(t.changed(TraitOnly::x()));
+    assert_eq!(TraitOnly::track_all(), TraitOnly::x());
+    assert_eq!(t.diff(&TraitOnly::default()), TraitOnly::x());
+    assert!(take_changes(&mut t));
+    assert_eq!(*t.get_x(), 1);
+}
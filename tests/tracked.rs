@@ -0,0 +1,78 @@
+#[derive(Clone, Default, Debug)]
+struct Bar(f32);
+
+tracker::tracked! {
+    #![track(debug)]
+
+    #[derive(Default)]
+    pub struct Foo {
+        x: u8,
+        #[no_eq]
+        y: Bar,
+    }
+
+    #[track(sql)]
+    #[derive(Default)]
+    struct Other {
+        #[tracker::column = "a_column"]
+        a: u8,
+    }
+}
+
+tracker::tracked! {
+    #[derive(PartialEq)]
+    enum Event {
+        Click,
+        Key(char),
+    }
+}
+
+// Structs defined by other macros are tracked as well.
+macro_rules! model {
+    ($name:ident { $($field:ident: $ty:ty),* }) => {
+        tracker::tracked! {
+            #![track(previous, snapshot, debug, json_patch, apply_json, changed_view, project, sql, debug_values, typed_mask, impl_eq, impl_hash, clone_resets, encapsulate)]
+            #[derive(Default)]
+            struct $name {
+                $($field: $ty),*
+            }
+        }
+    };
+}
+
+model!(Model {
+    count: u32,
+    name: String
+});
+
+#[test]
+fn structs_are_tracked() {
+    let mut foo = Foo::default();
+    foo.set_x(0);
+    foo.set_y(Bar(0.0));
+    assert!(!foo.changed_x() && foo.changed_y());
+    assert_eq!(foo.get_y().0, 0.0);
+    assert!(format!("{foo:?}").contains('y'));
+
+    let mut other = Other::default();
+    other.set_a(1);
+    assert_eq!(other.changed_columns(), vec!["a_column"]);
+
+    let mut event = EventTracker::new(Event::Click);
+    event.set(Event::Key('a'));
+    assert!(event.changed());
+}
+
+#[test]
+fn structs_from_other_macros_are_tracked() {
+    let mut model = Model::default();
+    model.set_count(1);
+    model.get_mut_name().push('a');
+    assert_eq!(Model::track_all(), Model::count() | Model::name());
+    assert!(model.changed(Model::track_all()));
+    assert_eq!(model.previous_count(), Some(&0));
+    model.update_all(|proxy| proxy.set_count(2));
+    assert_eq!(model.changed_columns(), vec!["count", "name"]);
+    model.reset();
+    assert!(!model.changed_any());
+}
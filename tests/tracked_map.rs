@@ -0,0 +1,28 @@
+use tracker::TrackedHashMap;
+
+#[tracker::track]
+#[derive(Default)]
+struct Backend {
+    #[tracker::nested]
+    users: TrackedHashMap<u32, String>,
+}
+
+#[test]
+fn changed_keys_are_synced() {
+    let mut backend = Backend::default();
+    backend.users.insert(1, "a".to_owned());
+    backend.users.insert(2, "b".to_owned());
+    assert!(backend.changed_users());
+    backend.reset_all();
+
+    backend.users.insert(1, "a".to_owned());
+    assert!(!backend.changed_any());
+
+    backend.users.entry(2).or_default().push('c');
+    backend.users.remove(&1);
+    let mut changed: Vec<_> = backend.users.changed_keys().copied().collect();
+    changed.sort_unstable();
+    assert_eq!(changed, [1, 2]);
+    assert!(backend.users.removed().contains(&1));
+    assert_eq!(backend.users.get(&2).map(String::as_str), Some("bc"));
+}
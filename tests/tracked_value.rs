@@ -0,0 +1,26 @@
+use tracker::Tracked;
+
+#[tracker::track]
+#[derive(Default)]
+struct Settings {
+    #[tracker::nested]
+    volume: Tracked<u8>,
+    name: String,
+}
+
+#[test]
+fn wrapper_is_nested_child() {
+    let mut settings = Settings::default();
+    settings.volume.set(0);
+    assert!(!settings.changed_any());
+
+    settings.volume.set(5);
+    assert_eq!(*settings.get_volume().get(), 5);
+    assert!(settings.changed_volume());
+    assert!(!settings.changed_name());
+
+    settings.reset();
+    assert!(settings.volume.changed());
+    settings.reset_all();
+    assert!(!settings.volume.changed());
+}
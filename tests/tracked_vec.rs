@@ -0,0 +1,46 @@
+use tracker::{TrackedVec, VecChange};
+
+#[tracker::track]
+#[derive(Default)]
+struct List {
+    #[tracker::no_eq]
+    rows: TrackedVec<String>,
+    #[tracker::nested]
+    selection: TrackedVec<usize>,
+}
+
+#[test]
+fn list_is_a_field() {
+    let mut list = List::default();
+    list.get_mut_rows().push("a".to_owned());
+    list.get_mut_rows().push("b".to_owned());
+    assert!(list.changed_rows());
+    assert_eq!(
+        list.rows.iter().map(String::as_str).collect::<Vec<_>>(),
+        ["a", "b"]
+    );
+
+    list.reset();
+    list.rows.reset();
+    list.update_rows(|rows| rows.set(1, "c".to_owned()));
+    assert!(list.changed_rows());
+    assert_eq!(list.rows.changed_indices().collect::<Vec<_>>(), [1]);
+    assert!(!list.rows.structurally_changed());
+}
+
+#[test]
+fn list_is_a_nested_child() {
+    let mut list = List::default();
+    list.selection.push(3);
+    assert!(list.changed_selection());
+    assert_eq!(
+        list.selection.structural_changes(),
+        [VecChange::Inserted(0)]
+    );
+
+    list.reset_all();
+    assert!(!list.changed_any());
+    for index in &list.selection {
+        assert_eq!(*index, 3);
+    }
+}
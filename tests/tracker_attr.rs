@@ -0,0 +1,43 @@
+#[tracker::track(sql, skip_methods(get_mut), ty = u16)]
+#[derive(Default)]
+struct Test {
+    #[tracker(no_eq, column = "column_a")]
+    a: u8,
+    #[tracker(do_not_track)]
+    b: u8,
+    // The previous forms keep working.
+    #[no_eq]
+    c: u8,
+    #[cfg_attr(test, tracker(no_eq))]
+    d: u8,
+    #[tracker(vis(get = "pub(crate)"))]
+    e: u8,
+}
+
+#[derive(tracker::Track, Default)]
+#[tracker(sql)]
+struct Derived {
+    #[tracker(column = "renamed")]
+    x: u8,
+    #[tracker(tracker_field)]
+    changes: u8,
+}
+
+#[test]
+fn structured_field_options() {
+    let mut t = Test::default();
+    assert_eq!(Test::track_all(), 0b1111u16);
+    t.b = 1;
+    t.set_a(0);
+    t.set_c(0);
+    t.set_d(0);
+    t.set_e(*t.get_e());
+    assert_eq!(t.changed_columns(), vec!["column_a", "c", "d"]);
+    t.reset();
+
+    let mut d = Derived::default();
+    d.set_x(1);
+    assert_eq!(d.changes, Derived::x());
+    assert_eq!(d.changed_columns(), vec!["renamed"]);
+    d.reset();
+}
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+#[tracker::track(tracker_attrs(serde(skip), doc = "Changed fields."))]
+#[derive(Default, Serialize, Deserialize)]
+struct Test {
+    name: String,
+    count: u32,
+}
+
+#[test]
+fn attributes_are_forwarded() {
+    let mut t = Test::default();
+    t.set_count(3);
+    assert_eq!(
+        serde_json::to_string(&t).unwrap(),
+        r#"{"name":"","count":3}"#
+    );
+
+    let t: Test = serde_json::from_str(r#"{"name":"tracker","count":1}"#).unwrap();
+    assert_eq!(t.get_name(), "tracker");
+    assert!(!t.changed_any());
+}
@@ -0,0 +1,54 @@
+#[tracker::track(field = "__changes", previous, encapsulate, clone_keeps)]
+struct Device {
+    tracker: String,
+    battery: u8,
+}
+
+#[test]
+fn renamed_tracker_field() {
+    let mut d = Device::new("gps".to_owned(), 100);
+    d.set_battery(90);
+    assert_eq!(d.__changes, Device::battery());
+    assert!(d.changed_battery());
+    assert!(!d.changed_tracker());
+
+    let clone = d.clone();
+    assert!(clone.changed_battery());
+
+    d.revert_battery();
+    assert_eq!(*d.get_battery(), 100);
+    assert_eq!(d.__changes, 0);
+
+    d.get_mut_tracker().push('s');
+    assert_eq!(d.get_tracker(), "gpss");
+    d.reset();
+    assert!(!d.changed_any());
+}
+
+#[tracker::track(serde)]
+#[repr(C)]
+#[derive(serde::Serialize)]
+struct Ffi {
+    a: u32,
+    #[tracker::tracker_field]
+    changes: u16,
+    b: u8,
+}
+
+#[test]
+fn declared_tracker_field() {
+    assert_eq!(std::mem::offset_of!(Ffi, changes), 4);
+
+    let mut f = Ffi {
+        a: 0,
+        changes: 0,
+        b: 0,
+    };
+    f.set_a(1);
+    f.set_b(2);
+    let mask: u16 = Ffi::track_all();
+    assert_eq!(f.changes, mask);
+    assert_eq!(serde_json::to_string(&f).unwrap(), r#"{"a":1,"b":2}"#);
+    f.reset();
+    assert_eq!(f.changes, 0);
+}
@@ -0,0 +1,39 @@
+#[tracker::track(ty = u32)]
+#[derive(Default)]
+struct Forced {
+    a: u8,
+    b: u8,
+    #[do_not_track]
+    c: u8,
+}
+
+#[tracker::track(ty = u64, typed_mask)]
+#[derive(Default)]
+struct ForcedMask {
+    a: u8,
+}
+
+#[test]
+fn forced_type() {
+    let mut f = Forced::default();
+    let mask: u32 = Forced::b();
+    assert_eq!(mask, 1 << 1);
+    assert_eq!(Forced::track_all(), 0b11);
+
+    f.set_b(1);
+    f.c = 1;
+    assert!(f.changed(mask));
+    assert_eq!(f.tracker, mask);
+    assert_eq!(std::mem::size_of_val(&f.tracker), 4);
+    f.reset();
+}
+
+#[test]
+fn forced_type_with_typed_mask() {
+    let mut f = ForcedMask::default();
+    f.set_a(1);
+    let bits: u64 = f.tracker.bits();
+    assert_eq!(bits, 1);
+    assert_eq!(ForcedMask::track_all(), ForcedMaskMask::from_bits(1));
+    f.reset();
+}
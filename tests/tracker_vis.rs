@@ -0,0 +1,34 @@
+mod model {
+    #[tracker::track(tracker_vis = "pub")]
+    pub struct Point {
+        pub x: i32,
+        pub y: i32,
+    }
+
+    #[tracker::track(previous, tracker_vis = "pub(crate)")]
+    pub struct Label {
+        pub text: String,
+    }
+}
+
+use model::{Label, Point};
+
+#[test]
+fn struct_literals_from_another_module() {
+    let mut point = Point {
+        x: 1,
+        y: 2,
+        tracker: 0,
+    };
+    point.set_x(3);
+    assert!(point.changed_x());
+    assert_eq!(point.tracker, Point::x());
+
+    let mut label = Label {
+        text: "a".to_string(),
+        tracker: 0,
+        tracker_previous: Default::default(),
+    };
+    label.set_text("b".to_string());
+    assert_eq!(label.previous_text().map(String::as_str), Some("a"));
+}
@@ -0,0 +1,61 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+
+#[tracker::track(impl_partial_eq)]
+#[derive(Default)]
+struct Test {
+    value: f32,
+    #[do_not_track]
+    untracked: u8,
+}
+
+#[tracker::track(impl_eq, impl_hash)]
+#[derive(Default, Debug)]
+struct Hashed<T: Debug + PartialEq> {
+    value: T,
+    name: String,
+}
+
+fn hash<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn partial_eq_ignores_tracker() {
+    let mut a = Test::default();
+    let b = Test::default();
+    a.set_value(1.0);
+    assert!(a != b);
+
+    a.set_value(0.0);
+    assert!(a.changed_any());
+    assert!(a == b);
+
+    a.untracked = 1;
+    assert!(a != b);
+    a.reset();
+}
+
+#[test]
+fn eq_and_hash_ignore_tracker() {
+    let mut a = Hashed::<u8>::default();
+    let b = Hashed::<u8>::default();
+    a.set_name("tracker".to_owned());
+    a.set_name(String::new());
+    assert!(a.changed_any());
+    assert!(a == b);
+    assert_eq!(hash(&a), hash(&b));
+
+    let mut set = HashSet::new();
+    set.insert(a);
+    assert!(!set.insert(b));
+
+    let mut c = Hashed::<u8>::default();
+    c.set_value(1);
+    assert_ne!(hash(&c), hash(&Hashed::<u8>::default()));
+    c.reset();
+}
@@ -0,0 +1,53 @@
+#[tracker::track(transparent)]
+#[derive(Default)]
+struct Volume {
+    value: f32,
+}
+
+#[tracker::track(transparent, previous)]
+#[derive(Default)]
+struct Labeled<T: Clone> {
+    value: Vec<T>,
+    #[do_not_track]
+    label: &'static str,
+}
+
+#[test]
+fn deref_mut_marks_the_change() {
+    let mut vol = Volume::default();
+    assert_eq!(*vol, 0.0);
+    assert!(!vol.changed(Volume::track_all()));
+
+    *vol = 0.5;
+    assert!(vol.changed(Volume::value()));
+    assert_eq!(*vol, 0.5);
+    vol.reset();
+    assert!(!vol.changed_any());
+
+    // Reading doesn't mark the change.
+    assert!(vol.is_sign_positive());
+    assert!(!vol.changed_any());
+}
+
+#[test]
+fn set_compares_the_value() {
+    let mut vol = Volume::default();
+    vol.set(0.0);
+    assert!(!vol.changed_any());
+    vol.set(1.0);
+    assert!(vol.changed_value());
+}
+
+#[test]
+fn methods_of_the_inner_value() {
+    let mut labeled = Labeled::<u8> {
+        label: "list",
+        ..Labeled::default()
+    };
+    assert!(labeled.is_empty());
+    labeled.push(1);
+    assert_eq!(labeled.len(), 1);
+    assert!(labeled.changed_value());
+    assert!(labeled.previous_value().unwrap().is_empty());
+    assert_eq!(labeled.label, "list");
+}
@@ -0,0 +1,93 @@
+#[tracker::track(typed_mask, previous, versioned)]
+#[derive(Default)]
+struct Test {
+    x: u8,
+    title: String,
+    #[no_eq]
+    list: Vec<u8>,
+}
+
+#[tracker::track(typed_mask, snapshot, apply_changes)]
+#[derive(Default)]
+struct Other {
+    x: u8,
+}
+
+#[test]
+fn masks() {
+    let mut t = Test::default();
+    assert!(!t.changed_any());
+    assert_eq!(TestMask::default(), TestMask::from_bits(0));
+    assert!(TestMask::default().is_empty());
+
+    t.set_x(1);
+    assert!(t.changed(Test::x()));
+    assert!(t.changed(Test::x() | Test::title()));
+    assert!(!t.changed(Test::title()));
+    assert!(!t.changed(Test::track_all() & !Test::x()));
+    assert_eq!((Test::x() | Test::title()).bits(), 0b11);
+
+    let mut mask = Test::x();
+    mask |= Test::list();
+    mask &= !Test::x();
+    assert_eq!(mask, Test::list());
+    assert_eq!(format!("{mask:?}"), "TestMask(0b100)");
+}
+
+#[test]
+fn methods_use_masks() {
+    let mut t = Test::default();
+    let version = t.version();
+    t.update_all(|proxy| proxy.set_title("hi".to_owned()));
+    t.get_mut_list().push(1);
+    assert_eq!(t.changed_since(version), Test::title() | Test::list());
+    assert_eq!(t.previous_title().map(String::as_str), Some(""));
+    assert_eq!(t.diff(&Test::default()), Test::title());
+    assert_eq!(Test::mask_from_paths(&["x"]), Ok(Test::x()));
+
+    t.reset();
+    assert!(!t.changed_any());
+    t.mark_all_changed();
+    assert!(t.changed(Test::x() | Test::title() | Test::list()));
+}
+
+#[test]
+fn snapshot() {
+    let mut o = Other::default();
+    let snapshot = o.snapshot();
+    o.set_x(1);
+    assert_eq!(o.changed_since(&snapshot), Other::x());
+
+    let mut copy = Other::default();
+    copy.apply_changes_from(&o);
+    assert!(copy.changed(Other::x()));
+    o.reset();
+}
+
+#[cfg(feature = "serde_json")]
+#[tracker::track(typed_mask, serde_state, json_patch, apply_json)]
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+struct Json {
+    x: u8,
+    y: u8,
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn serde() {
+    let mut j = Json::default();
+    assert_eq!(
+        j.apply_json(&serde_json::json!({ "y": 2 })).unwrap(),
+        Json::y()
+    );
+
+    let json = serde_json::to_string(&j).unwrap();
+    assert_eq!(json, r#"{"x":0,"y":2,"__changed":["y"]}"#);
+    let mut j: Json = serde_json::from_str(&json).unwrap();
+    assert!(j.changed(Json::y()) && !j.changed(Json::x()));
+    assert_eq!(
+        j.apply_json_patch(&j.to_json_patch().unwrap()).unwrap(),
+        JsonMask::default()
+    );
+    j.reset();
+}
@@ -0,0 +1,59 @@
+#[tracker::track(atomic, versioned)]
+struct Versioned {
+    x: u8,
+}
+
+#[tracker::track(atomic, ty = u128)]
+struct Wide {
+    x: u8,
+}
+
+#[tracker::track(atomic)]
+struct Declared {
+    x: u8,
+    #[tracker::tracker_field]
+    changes: u8,
+}
+
+#[tracker::track(atomic)]
+#[derive(Clone, Default)]
+struct Derived {
+    x: u8,
+}
+
+#[tracker::track(atomic, listeners)]
+struct Listened {
+    x: u8,
+}
+
+#[tracker::track(history = 4, atomic)]
+struct Recorded {
+    x: u8,
+}
+
+#[tracker::track(counters, atomic)]
+struct Counted {
+    x: u8,
+}
+
+#[tracker::track(timestamps, atomic)]
+struct Timed {
+    x: u8,
+}
+
+#[tracker::track(ordered, atomic)]
+struct Ordered {
+    x: u8,
+}
+
+#[tracker::track(persistent, atomic)]
+struct Saved {
+    x: u8,
+}
+
+#[tracker::track(atomic, consumers = 2)]
+struct Consumed {
+    x: u8,
+}
+
+fn main() {}
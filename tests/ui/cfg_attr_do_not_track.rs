@@ -0,0 +1,7 @@
+#[tracker::track]
+struct Test {
+    #[cfg_attr(test, tracker::do_not_track)]
+    x: u8,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+#[tracker::track(debug_unconsumed)]
+struct Closed {
+    x: u8,
+}
+
+impl Drop for Closed {
+    fn drop(&mut self) {}
+}
+
+#[tracker::track(data_struct, debug_unconsumed)]
+struct Converted {
+    x: u8,
+}
+
+fn main() {}
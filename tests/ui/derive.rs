@@ -0,0 +1,35 @@
+use tracker::Track;
+
+#[derive(Track)]
+struct Missing {
+    x: u8,
+}
+
+#[derive(Track)]
+struct TooNarrow {
+    #[tracker_field]
+    changes: u8,
+    a: u8,
+    b: u8,
+    c: u8,
+    d: u8,
+    e: u8,
+    f: u8,
+    g: u8,
+    h: u8,
+    i: u8,
+}
+
+#[derive(Track)]
+#[tracker(previous)]
+struct ModifiesStruct {
+    x: u8,
+    tracker: u8,
+}
+
+#[derive(Track)]
+enum Enum {
+    A,
+}
+
+fn main() {}
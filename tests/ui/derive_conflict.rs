@@ -0,0 +1,7 @@
+#[tracker::track(debug)]
+#[derive(Debug)]
+struct Test {
+    x: u8,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+#[tracker::track(dynamic, minimal, skip_methods(set))]
+struct Minimal {
+    x: u8,
+}
+
+#[tracker::track(dynamic)]
+struct Collides {
+    field: u8,
+}
+
+fn main() {}
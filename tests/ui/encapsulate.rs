@@ -0,0 +1,11 @@
+mod inner {
+    #[tracker::track(encapsulate)]
+    pub struct Test {
+        pub x: u8,
+    }
+}
+
+fn main() {
+    let mut t = inner::Test::new(0);
+    t.x = 1;
+}
@@ -0,0 +1,7 @@
+#[tracker::track(snapshot)]
+enum Test {
+    A,
+    B,
+}
+
+fn main() {}
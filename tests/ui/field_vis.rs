@@ -0,0 +1,26 @@
+mod inner {
+    #[tracker::track]
+    #[derive(Default)]
+    pub struct Test {
+        #[tracker::vis(get = "pub", set = "pub(self)")]
+        pub x: u8,
+    }
+
+    #[tracker::track]
+    pub struct Unknown {
+        #[tracker::vis(mask = "pub")]
+        pub x: u8,
+    }
+
+    #[tracker::track]
+    pub struct Twice {
+        #[tracker::vis(set = "pub", set = "")]
+        pub x: u8,
+    }
+}
+
+fn main() {
+    let mut t = inner::Test::default();
+    let _ = t.get_x();
+    t.set_x(1);
+}
@@ -0,0 +1,12 @@
+#[tracker::track]
+struct Test {
+    x: u8,
+    #[do_not_track]
+    y: u8,
+}
+
+fn main() {
+    let mut t = Test { x: 0, y: 0, tracker: 0 };
+    let (x, y) = tracker::fields_mut!(t => x, y);
+    *x = *y;
+}
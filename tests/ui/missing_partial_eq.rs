@@ -0,0 +1,17 @@
+struct NoPartialEq(u8);
+
+#[tracker::track]
+struct Test {
+    x: u8,
+    value: NoPartialEq,
+}
+
+fn main() {
+    let mut t = Test {
+        x: 0,
+        value: NoPartialEq(0),
+        tracker: 0,
+    };
+    t.set_x(1);
+    t.set_value(NoPartialEq(1));
+}
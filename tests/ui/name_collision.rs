@@ -0,0 +1,12 @@
+#[tracker::track]
+struct Changed {
+    changed: u8,
+}
+
+#[tracker::track]
+struct Accessor {
+    x: u8,
+    get_x: u8,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+struct NotTracked;
+
+#[tracker::track]
+struct Conflict {
+    #[tracker::nested]
+    #[tracker::do_not_track]
+    child: u8,
+}
+
+#[tracker::track]
+struct Untracked {
+    #[tracker::nested]
+    child: NotTracked,
+}
+
+fn main() {}
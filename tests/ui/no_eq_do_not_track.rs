@@ -0,0 +1,17 @@
+#[tracker::track]
+struct Test {
+    #[tracker::no_eq]
+    #[tracker::do_not_track]
+    a: u8,
+    #[do_not_track]
+    /// Doc comments in between don't matter.
+    #[no_eq]
+    b: u8,
+    #[tracker(do_not_track, no_eq)]
+    c: u8,
+    #[tracker::do_not_track]
+    #[cfg_attr(test, tracker::no_eq)]
+    d: u8,
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+#[tracker::track]
+struct Collision {
+    #[tracker::rename = "y"]
+    x: u8,
+    y: u8,
+}
+
+#[tracker::track]
+struct Invalid {
+    #[tracker(rename = "not valid")]
+    x: u8,
+}
+
+#[tracker::track(prefix = "t_")]
+struct Old {
+    x: u8,
+}
+
+fn main() {
+    let mut old = Old { x: 0, tracker: 0 };
+    old.set_x(1);
+}
@@ -0,0 +1,19 @@
+#[tracker::track(no_delta)]
+#[derive(Default)]
+struct Test {
+    #[tracker::no_set]
+    derived: u8,
+    #[tracker(no_mut, no_update)]
+    shared: Vec<u8>,
+    #[tracker(no_get)]
+    hidden: u8,
+}
+
+fn main() {
+    let mut t = Test::default();
+    t.set_derived(1);
+    t.get_mut_shared().push(1);
+    t.update_shared(|_| {});
+    let _ = t.get_hidden();
+    t.update_all(|proxy| proxy.set_derived(1));
+}
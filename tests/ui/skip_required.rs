@@ -0,0 +1,6 @@
+#[tracker::track(skip_methods(reset))]
+struct Test {
+    x: u8,
+}
+
+fn main() {}
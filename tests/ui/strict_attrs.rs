@@ -0,0 +1,10 @@
+#[tracker::track(strict_attrs)]
+struct Test {
+    // Bare attributes are left for other macros.
+    #[no_eq]
+    a: u8,
+    #[do_not_track]
+    b: u8,
+}
+
+fn main() {}
@@ -0,0 +1,45 @@
+#[tracker::track(debug, sql, debug)]
+struct Duplicate {
+    x: u8,
+}
+
+#[tracker::track(field = dirty)]
+struct Malformed {
+    x: u8,
+}
+
+#[tracker::track(debug = true)]
+struct Flag {
+    x: u8,
+}
+
+#[tracker::track(tracker_attrs = "serde(skip)")]
+struct AttrsValue {
+    x: u8,
+}
+
+#[tracker::track(tracker_attrs(serde(skip)))]
+struct AttrsDeclared {
+    x: u8,
+    #[tracker::tracker_field]
+    changes: u8,
+}
+
+#[tracker::track(tracker_vis = "pub")]
+struct VisDeclared {
+    x: u8,
+    #[tracker::tracker_field]
+    changes: u8,
+}
+
+#[tracker::track(typed_mask, bitflags)]
+struct Flags {
+    x: u8,
+}
+
+#[tracker::track(remote, ty = u128)]
+struct RemoteWide {
+    x: u8,
+}
+
+fn main() {}
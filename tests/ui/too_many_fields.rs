@@ -0,0 +1,134 @@
+#[tracker::track]
+struct Test {
+    field_0: u8,
+    field_1: u8,
+    field_2: u8,
+    field_3: u8,
+    field_4: u8,
+    field_5: u8,
+    field_6: u8,
+    field_7: u8,
+    field_8: u8,
+    field_9: u8,
+    field_10: u8,
+    field_11: u8,
+    field_12: u8,
+    field_13: u8,
+    field_14: u8,
+    field_15: u8,
+    field_16: u8,
+    field_17: u8,
+    field_18: u8,
+    field_19: u8,
+    field_20: u8,
+    field_21: u8,
+    field_22: u8,
+    field_23: u8,
+    field_24: u8,
+    field_25: u8,
+    field_26: u8,
+    field_27: u8,
+    field_28: u8,
+    field_29: u8,
+    field_30: u8,
+    field_31: u8,
+    field_32: u8,
+    field_33: u8,
+    field_34: u8,
+    field_35: u8,
+    field_36: u8,
+    field_37: u8,
+    field_38: u8,
+    field_39: u8,
+    field_40: u8,
+    field_41: u8,
+    field_42: u8,
+    field_43: u8,
+    field_44: u8,
+    field_45: u8,
+    field_46: u8,
+    field_47: u8,
+    field_48: u8,
+    field_49: u8,
+    field_50: u8,
+    field_51: u8,
+    field_52: u8,
+    field_53: u8,
+    field_54: u8,
+    field_55: u8,
+    field_56: u8,
+    field_57: u8,
+    field_58: u8,
+    field_59: u8,
+    field_60: u8,
+    field_61: u8,
+    field_62: u8,
+    field_63: u8,
+    field_64: u8,
+    field_65: u8,
+    field_66: u8,
+    field_67: u8,
+    field_68: u8,
+    field_69: u8,
+    field_70: u8,
+    field_71: u8,
+    field_72: u8,
+    field_73: u8,
+    field_74: u8,
+    field_75: u8,
+    field_76: u8,
+    field_77: u8,
+    field_78: u8,
+    field_79: u8,
+    field_80: u8,
+    field_81: u8,
+    field_82: u8,
+    field_83: u8,
+    field_84: u8,
+    field_85: u8,
+    field_86: u8,
+    field_87: u8,
+    field_88: u8,
+    field_89: u8,
+    field_90: u8,
+    field_91: u8,
+    field_92: u8,
+    field_93: u8,
+    field_94: u8,
+    field_95: u8,
+    field_96: u8,
+    field_97: u8,
+    field_98: u8,
+    field_99: u8,
+    field_100: u8,
+    field_101: u8,
+    field_102: u8,
+    field_103: u8,
+    field_104: u8,
+    field_105: u8,
+    field_106: u8,
+    field_107: u8,
+    field_108: u8,
+    field_109: u8,
+    field_110: u8,
+    field_111: u8,
+    field_112: u8,
+    field_113: u8,
+    field_114: u8,
+    field_115: u8,
+    field_116: u8,
+    field_117: u8,
+    field_118: u8,
+    field_119: u8,
+    field_120: u8,
+    field_121: u8,
+    field_122: u8,
+    field_123: u8,
+    field_124: u8,
+    field_125: u8,
+    field_126: u8,
+    field_127: u8,
+    field_128: u8,
+}
+
+fn main() {}
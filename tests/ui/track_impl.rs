@@ -0,0 +1,29 @@
+#[tracker::track_impl]
+struct NotAnImpl {
+    x: u8,
+}
+
+#[tracker::track]
+struct Test {
+    x: u8,
+}
+
+#[tracker::track_impl(Test)]
+impl Test {
+    fn set(&mut self) {
+        self.x = 1;
+    }
+}
+
+struct Untracked {
+    x: u8,
+}
+
+#[tracker::track_impl]
+impl Untracked {
+    fn set(&mut self) {
+        self.x = 1;
+    }
+}
+
+fn main() {}
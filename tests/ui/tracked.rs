@@ -0,0 +1,28 @@
+tracker::tracked! {
+    #![allow(dead_code)]
+
+    struct Test {
+        x: u8,
+    }
+}
+
+tracker::tracked! {
+    #![track(debug)]
+
+    struct Options {
+        x: u8,
+    }
+
+    enum Page {
+        Overview,
+    }
+}
+
+tracker::tracked! {
+    #[track = "debug"]
+    struct Value {
+        x: u8,
+    }
+}
+
+fn main() {}
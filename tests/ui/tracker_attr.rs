@@ -0,0 +1,95 @@
+#[tracker::track]
+struct Unknown {
+    #[tracker(no_equal)]
+    x: u8,
+}
+
+#[tracker::track]
+struct Duplicate {
+    #[tracker(no_eq, no_eq)]
+    x: u8,
+}
+
+#[tracker::track]
+struct Malformed {
+    #[tracker(column = column_x)]
+    x: u8,
+}
+
+#[tracker::track]
+struct Flag {
+    #[tracker(no_eq = true)]
+    x: u8,
+}
+
+#[tracker::track]
+struct CfgAttr {
+    #[cfg_attr(test, tracker(column = "x"))]
+    x: u8,
+}
+
+#[tracker::track(no_eq)]
+struct Contradiction {
+    #[tracker(eq, no_eq)]
+    x: u8,
+}
+
+#[tracker::track]
+struct CompareWithNoEq {
+    #[tracker::no_eq]
+    #[tracker::compare_with = "compare"]
+    x: u8,
+}
+
+#[tracker::track]
+struct IndexedScalar {
+    #[tracker::indexed]
+    x: u8,
+}
+
+#[tracker::track]
+struct SetCollectionVec {
+    #[tracker::set_collection]
+    x: Vec<u8>,
+}
+
+#[tracker::track]
+struct MapCollectionSet {
+    #[tracker::map_collection]
+    x: std::collections::HashSet<u8>,
+}
+
+#[tracker::track]
+struct HashEqNoEq {
+    #[tracker(hash_eq, no_eq)]
+    x: u8,
+}
+
+#[tracker::track]
+struct HashEqValue {
+    #[tracker::hash_eq = "compare"]
+    x: u8,
+}
+
+const LEN: usize = 2;
+
+#[tracker::track]
+struct ElementsLen {
+    #[tracker::elements]
+    x: [u8; LEN],
+}
+
+#[tracker::track]
+struct TooManyElements {
+    x: u8,
+    #[tracker::elements]
+    y: [u8; 128],
+}
+
+#[tracker::track(json_patch)]
+struct RedactJsonPatch {
+    #[tracker::redact]
+    x: u8,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+#[tracker::track]
+struct Device {
+    tracker: String,
+}
+
+#[tracker::track(field = "changes")]
+struct Renamed {
+    changes: u8,
+}
+
+fn main() {}
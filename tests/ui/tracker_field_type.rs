@@ -0,0 +1,23 @@
+#[tracker::track]
+struct TooNarrow {
+    #[tracker::tracker_field]
+    changes: u8,
+    a: u8,
+    b: u8,
+    c: u8,
+    d: u8,
+    e: u8,
+    f: u8,
+    g: u8,
+    h: u8,
+    i: u8,
+}
+
+#[tracker::track]
+struct NotAnInteger {
+    #[tracker::tracker_field]
+    changes: i32,
+    a: u8,
+}
+
+fn main() {}
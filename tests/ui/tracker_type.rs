@@ -0,0 +1,19 @@
+#[tracker::track(ty = u8)]
+struct TooNarrow {
+    a: u8,
+    b: u8,
+    c: u8,
+    d: u8,
+    e: u8,
+    f: u8,
+    g: u8,
+    h: u8,
+    i: u8,
+}
+
+#[tracker::track(ty = i32)]
+struct NotAnInteger {
+    a: u8,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+mod inner {
+    #[tracker::track(trait_only)]
+    #[derive(Default)]
+    pub struct Test {
+        pub x: u8,
+    }
+}
+
+fn main() {
+    let mut t = inner::Test::default();
+    t.set_x(1);
+    // The trait isn't imported here.
+    let _ = t.changed_any();
+}
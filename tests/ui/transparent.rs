@@ -0,0 +1,13 @@
+#[tracker::track(transparent)]
+struct Pair {
+    a: u8,
+    b: u8,
+}
+
+#[tracker::track(transparent)]
+struct Untracked {
+    #[do_not_track]
+    a: u8,
+}
+
+fn main() {}
@@ -0,0 +1,4 @@
+#[tracker::track]
+struct Test(u8, u16);
+
+fn main() {}
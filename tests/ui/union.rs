@@ -0,0 +1,7 @@
+#[tracker::track]
+union Test {
+    x: u8,
+    y: u16,
+}
+
+fn main() {}
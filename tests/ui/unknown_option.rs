@@ -0,0 +1,6 @@
+#[tracker::track(snapshots)]
+struct Test {
+    x: u8,
+}
+
+fn main() {}
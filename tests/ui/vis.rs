@@ -0,0 +1,20 @@
+mod inner {
+    #[tracker::track(vis = "")]
+    #[derive(Default)]
+    pub struct Private {
+        pub x: u8,
+    }
+
+    #[tracker::track(vis = "pub(in crate::inner)")]
+    #[derive(Default)]
+    pub struct InPath {
+        pub x: u8,
+    }
+}
+
+fn main() {
+    let mut private = inner::Private::default();
+    private.set_x(1);
+    let mut in_path = inner::InPath::default();
+    in_path.set_x(1);
+}
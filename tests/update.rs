@@ -0,0 +1,44 @@
+#[tracker::track]
+#[derive(Default)]
+struct Test {
+    x: u8,
+    title: String,
+    count: u32,
+    #[no_eq]
+    no_eq: u8,
+}
+
+#[test]
+fn apply_update_returns_changed_fields() {
+    let mut t = Test::default();
+    t.set_count(3);
+
+    let mask = t.apply_update(TestUpdate {
+        x: Some(1),
+        title: Some(String::new()),
+        ..Default::default()
+    });
+
+    assert_eq!(mask, Test::x());
+    assert_eq!(t.x, 1);
+    assert!(t.changed_x());
+    assert!(!t.changed_title());
+    // Changes made before the update are kept.
+    assert!(t.changed_count());
+}
+
+#[test]
+fn no_eq_fields_always_change() {
+    let mut t = Test::default();
+
+    let mask = t.apply_update(TestUpdate {
+        no_eq: Some(0),
+        ..Default::default()
+    });
+    assert_eq!(mask, Test::no_eq());
+    assert!(t.changed_no_eq());
+
+    t.reset();
+    assert_eq!(t.apply_update(TestUpdate::default()), 0);
+    assert!(!t.changed_any());
+}
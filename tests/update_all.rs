@@ -0,0 +1,73 @@
+#[tracker::track]
+#[derive(Default)]
+struct Test {
+    x: u8,
+    y: u8,
+    z: String,
+    #[do_not_track]
+    untracked: u8,
+}
+
+#[tracker::track]
+struct WithNoEq {
+    #[no_eq]
+    value: u8,
+    other: u8,
+}
+
+#[test]
+fn untouched_fields_stay_clean() {
+    let mut t = Test::default();
+
+    t.update_all(|proxy| {
+        proxy.set_x(1);
+        proxy.get_mut_z().push_str("hello");
+        let _ = proxy.get_y();
+    });
+
+    assert_eq!(t.x, 1);
+    assert_eq!(t.z, "hello");
+    assert!(t.changed_x());
+    assert!(t.changed_z());
+    assert!(!t.changed_y());
+    assert!(t.changed(Test::x() | Test::z()));
+    assert!(!t.changed(Test::y()));
+    assert_eq!(t.untracked, 0);
+}
+
+#[test]
+fn eq_check_applies_to_proxy_setters() {
+    let mut t = Test::default();
+
+    t.update_all(|proxy| {
+        proxy.set_x(0);
+        proxy.set_y(2);
+    });
+
+    assert!(!t.changed_x());
+    assert!(t.changed_y());
+
+    t.reset();
+    t.update_all(|_| {});
+    assert!(!t.changed_any());
+}
+
+#[test]
+fn no_eq_fields_are_always_recorded() {
+    let mut t = WithNoEq {
+        value: 0,
+        other: 0,
+        tracker: 0,
+    };
+
+    t.update_all(|proxy| {
+        proxy.set_value(0);
+        proxy.set_other(0);
+    });
+
+    assert!(t.changed_value());
+    assert!(!t.changed_other());
+
+    t.reset();
+    assert!(!t.changed_any());
+}
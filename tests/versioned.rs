@@ -0,0 +1,61 @@
+#[tracker::track(versioned)]
+#[derive(Default)]
+struct Test {
+    x: u8,
+    y: u8,
+    title: String,
+}
+
+#[test]
+fn observers_track_their_own_version() {
+    let mut t = Test::default();
+    let renderer = t.version();
+    let persistence = t.version();
+    assert_eq!(t.changed_since(renderer), 0);
+
+    t.set_x(1);
+    assert_eq!(t.changed_since(renderer), Test::x());
+
+    // The renderer processed the change.
+    let renderer = t.version();
+    t.get_mut_title().push('a');
+
+    assert_eq!(t.changed_since(renderer), Test::title());
+    assert_eq!(t.changed_since(persistence), Test::x() | Test::title());
+}
+
+#[test]
+fn reset_keeps_versions() {
+    let mut t = Test::default();
+    let version = t.version();
+
+    t.update_all(|proxy| {
+        proxy.set_y(1);
+        proxy.set_x(0);
+    });
+    t.reset();
+
+    assert!(!t.changed_any());
+    assert_eq!(t.changed_since(version), Test::y());
+
+    // Eq-suppressed sets don't create a new version.
+    let version = t.version();
+    t.set_y(1);
+    assert_eq!(t.version(), version);
+
+    t.mark_all_changed();
+    assert_eq!(t.changed_since(version), Test::track_all() & 0b111);
+}
+
+#[test]
+fn empty_updates_keep_the_version() {
+    let mut t = Test::default();
+    t.set_x(1);
+    let version = t.version();
+
+    t.update_all(|_| {});
+    assert_eq!(t.version(), version);
+
+    assert_eq!(t.apply_update(TestUpdate::default()), 0);
+    assert_eq!(t.version(), version);
+}
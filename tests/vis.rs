@@ -0,0 +1,51 @@
+mod inner {
+    #[tracker::track(vis = "pub(crate)")]
+    #[derive(Default)]
+    pub struct Crate {
+        pub x: u8,
+    }
+
+    #[tracker::track(vis = pub(super))]
+    #[derive(Default)]
+    pub struct Super {
+        pub x: u8,
+    }
+
+    #[tracker::track(vis = "pub(in crate::inner)")]
+    #[derive(Default)]
+    pub struct InPath {
+        x: u8,
+    }
+
+    #[tracker::track(vis = "")]
+    #[derive(Default)]
+    pub struct Private {
+        pub x: u8,
+    }
+
+    pub fn restricted() -> bool {
+        let mut in_path = InPath::default();
+        in_path.set_x(1);
+        let mut private = Private::default();
+        private.set_x(*in_path.get_x());
+        let changed = in_path.changed_x() && private.changed(Private::x());
+        in_path.reset();
+        private.reset();
+        changed
+    }
+}
+
+#[test]
+fn visibility_override() {
+    let mut c = inner::Crate::default();
+    c.set_x(1);
+    assert!(c.changed_x());
+    c.reset();
+
+    let mut s = inner::Super::default();
+    s.set_x(1);
+    assert!(s.changed(inner::Super::x()));
+    s.reset();
+
+    assert!(inner::restricted());
+}
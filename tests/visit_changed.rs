@@ -0,0 +1,69 @@
+use std::fmt::Debug;
+
+use tracker::ChangedVisitor;
+
+#[tracker::track]
+#[derive(Default)]
+struct Test {
+    title: String,
+    count: u32,
+    kind: u8,
+    #[do_not_track]
+    untracked: u8,
+}
+
+#[tracker::track]
+struct NoDebug {
+    value: std::cell::Cell<NotDebug>,
+}
+
+#[derive(PartialEq, Clone, Copy)]
+struct NotDebug;
+
+#[derive(Default)]
+struct Logger(Vec<String>);
+
+impl ChangedVisitor for Logger {
+    fn field<T: Debug + ?Sized>(&mut self, name: &'static str, value: &T) {
+        self.0.push(format!("{name} = {value:?}"));
+    }
+}
+
+struct Counter(usize);
+
+impl ChangedVisitor for Counter {
+    fn field<T: Debug + ?Sized>(&mut self, _name: &'static str, _value: &T) {
+        self.0 += 1;
+    }
+}
+
+#[test]
+fn declaration_order() {
+    let mut t = Test::default();
+    t.set_kind(2);
+    t.set_title("hi".to_owned());
+    t.untracked = 1;
+
+    let mut logger = Logger::default();
+    t.visit_changed(&mut logger);
+    assert_eq!(logger.0, vec!["title = \"hi\"", "kind = 2"]);
+
+    let mut counter = Counter(0);
+    t.reset();
+    t.visit_changed(&mut counter);
+    assert_eq!(counter.0, 0);
+    t.set_count(1);
+    t.visit_changed(&mut counter);
+    assert_eq!(counter.0, 1);
+}
+
+#[test]
+fn no_debug_compiles() {
+    let mut n = NoDebug {
+        value: std::cell::Cell::new(NotDebug),
+        tracker: 0,
+    };
+    n.mark_all_changed();
+    assert!(n.value.get() == NotDebug);
+    n.reset();
+}